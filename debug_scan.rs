@@ -1,4 +1,4 @@
-// Run with: cargo run --example debug_scan -- /path/to/scan
+// Run with: cargo run --example debug_scan -- /path/to/scan [threads]
 // Add to dux-core/Cargo.toml: [[example]] name = "debug_scan" path = "../debug_scan.rs"
 
 use dux_core::{ScanConfig, ScanMessage, Scanner};
@@ -11,9 +11,18 @@ fn main() {
         .map(PathBuf::from)
         .unwrap_or_else(|| PathBuf::from("."));
 
+    let config = ScanConfig {
+        num_threads: std::env::args()
+            .nth(2)
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0),
+        ..ScanConfig::default()
+    };
+
     println!("Scanning: {:?}", path);
+    println!("Threads: {}", config.effective_threads());
 
-    let scanner = Scanner::new(ScanConfig::default());
+    let scanner = Scanner::new(config);
     let (rx, handle) = scanner.scan(path);
 
     let start = Instant::now();
@@ -63,7 +72,7 @@ fn main() {
             ScanMessage::Completed => {
                 println!("[{:>6.1}s] COMPLETED", start.elapsed().as_secs_f64());
             }
-            ScanMessage::Cancelled => {
+            ScanMessage::Cancelled { .. } => {
                 println!("[{:>6.1}s] CANCELLED", start.elapsed().as_secs_f64());
             }
             ScanMessage::Error(e) => {
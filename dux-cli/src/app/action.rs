@@ -19,30 +19,139 @@ pub enum Action {
     Collapse,
     /// Toggle expand/collapse
     Toggle,
+    /// Expand the selected directory's whole subtree
+    ExpandAll,
+    /// Collapse every directory below the view root
+    CollapseAll,
     /// Drill down into selected directory
     DrillDown,
     /// Go back to parent
     GoBack,
+    /// Return to the view left by going back
+    GoForward,
     /// Show help overlay
     ShowHelp,
     /// Hide help overlay
     HideHelp,
-    /// Open selected item in Finder
-    OpenInFinder,
+    /// Show scan errors overlay
+    ShowErrors,
+    /// Hide scan errors overlay
+    HideErrors,
+    /// Scroll the scan errors overlay by a number of entries
+    ScrollErrors(isize),
+    /// Show details panel for the selected item
+    ShowDetails,
+    /// Hide details panel
+    HideDetails,
+    /// Show the file-type breakdown of the selected directory
+    ShowFileTypes,
+    /// Hide the file-type breakdown
+    HideFileTypes,
+    /// Open the jump-to-path prompt
+    StartGoTo,
+    /// Type a character at the jump-to-path prompt
+    GoToInput(char),
+    /// Delete the last character at the jump-to-path prompt
+    GoToBackspace,
+    /// Jump to the typed path
+    ConfirmGoTo,
+    /// Close the jump-to-path prompt
+    CancelGoTo,
+    /// Open the Large Files filter prompt
+    StartFileFilter,
+    /// Group the Large Files view by parent directory, or list it flat
+    ToggleLargeFilesGrouping,
+    /// Type a character at the Large Files filter prompt
+    FileFilterInput(char),
+    /// Delete the last character at the Large Files filter prompt
+    FileFilterBackspace,
+    /// Keep the typed Large Files filter
+    ConfirmFileFilter,
+    /// Clear the Large Files filter and show every file again
+    ClearFileFilter,
+    /// Bookmark the current view root, or remove its bookmark
+    ToggleBookmark,
+    /// Show the bookmarks overlay
+    ShowBookmarks,
+    /// Hide the bookmarks overlay
+    HideBookmarks,
+    /// Move the bookmark selection by a number of entries
+    MoveBookmark(isize),
+    /// Jump to the selected bookmark, or start a new scan there
+    OpenBookmark,
+    /// Remove the selected bookmark
+    RemoveBookmark,
+    /// Drop the selected directory from the tree and exclude it from rescans
+    ExcludeSelected,
+    /// Show the excluded names and paths
+    ShowExcludes,
+    /// Hide the excludes overlay
+    HideExcludes,
+    /// Move the excludes selection by a number of entries
+    MoveExclude(isize),
+    /// Stop excluding the selected entry and rescan
+    RemoveExclude,
+    /// Show the ancestors of the view root to jump to
+    ShowBreadcrumbs,
+    /// Hide the breadcrumbs overlay
+    HideBreadcrumbs,
+    /// Move the breadcrumbs selection by a number of entries
+    MoveBreadcrumb(isize),
+    /// Make the selected ancestor the view root
+    OpenBreadcrumb,
+    /// Show selected item in the platform file manager
+    RevealInFileManager,
+    /// Copy selected item's path to the clipboard
+    CopyPath,
+    /// Copy a text size report of the view root's children to the clipboard
+    CopyReport,
+    /// Copy the view root's children as a Markdown table to the clipboard
+    CopyMarkdown,
+    /// Export the current view to a CSV file
+    ExportCsv,
+    /// Write the selected paths to a file, or with `true` an `rm -rf` script
+    ExportSelection(bool),
     /// Request delete (show confirmation dialog)
     Delete,
     /// Confirm delete operation
     ConfirmDelete,
     /// Cancel delete operation
     CancelDelete,
+    /// Type a character into a delete confirmation that asks for a phrase
+    ConfirmInput(char),
+    /// Delete the last character of the typed confirmation
+    ConfirmBackspace,
+    /// Discard the tree and scan the root again
+    Rescan,
     /// Quit the application
     Quit,
+    /// Pause or resume the running scan
+    TogglePause,
     /// Switch to next view
     NextView,
     /// Switch to previous view
     PrevView,
     /// Cycle stale threshold (Build Artifacts view)
     CycleStaleThreshold,
+    /// Preview and confirm deleting all stale build artifacts
+    CleanStale,
+    /// Preview and confirm deleting every artifact of the selected kind
+    DeleteArtifactKind,
+    /// Open the prompt for how many of the newest artifacts of the selected
+    /// kind to keep
+    StartKeepNewest,
+    /// Type a digit at the keep-newest prompt
+    KeepNewestInput(char),
+    /// Delete the last digit at the keep-newest prompt
+    KeepNewestBackspace,
+    /// Preview deleting all but the typed number of newest artifacts
+    ConfirmKeepNewest,
+    /// Close the keep-newest prompt
+    CancelKeepNewest,
+    /// Select every item in the view (stale artifacts in Build Artifacts)
+    SelectAll,
+    /// Invert selection for every item in the view
+    InvertSelection,
     /// Extend selection upward
     SelectUp,
     /// Extend selection downward
@@ -63,6 +172,45 @@ pub enum Action {
     ConfirmMultiDelete,
     /// Cancel multi-delete operation
     CancelMultiDelete,
+    /// Switch to the next color theme
+    CycleTheme,
+    /// Mouse click on a terminal cell (double-click drills down)
+    Click { column: u16, row: u16 },
+    /// Toggle the treemap (blocks) visualization
+    ToggleTreemap,
+    /// Show or hide dotfiles
+    ToggleHidden,
+    /// Hide larger (true) or fewer (false) small entries in the Tree view
+    StepMinShare(bool),
+    /// Cycle the Tree view between all entries, directories only and files only
+    CycleKindFilter,
+    /// Toggle tree percentages between view root and parent
+    TogglePercentBase,
+    /// Switch Tree view and Overview bars between size and file count
+    ToggleBarMetric,
+    /// Step the window for marking recently changed Tree entries
+    CycleRecentWindow,
+    /// Toggle artifact ages between relative and absolute
+    ToggleAgeDisplay,
+    /// Show or hide the file-count column
+    ToggleFileCounts,
     /// No action (for tick events)
     Tick,
 }
+
+impl Action {
+    /// Whether the action starts deleting something, which a read-only
+    /// session refuses
+    pub fn is_delete(&self) -> bool {
+        matches!(
+            self,
+            Action::Delete
+                | Action::ConfirmDelete
+                | Action::ConfirmMultiDelete
+                | Action::CleanStale
+                | Action::DeleteArtifactKind
+                | Action::StartKeepNewest
+                | Action::ConfirmKeepNewest
+        )
+    }
+}
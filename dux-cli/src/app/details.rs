@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use dux_core::{DiskTree, NodeId, NodeKind};
+
+/// Snapshot of a node's metadata for the details panel.
+/// Owner and group names are resolved once when the panel opens.
+#[derive(Debug, Clone)]
+pub struct NodeDetails {
+    pub path: PathBuf,
+    pub kind: NodeKind,
+    pub size: u64,
+    pub file_count: u64,
+    pub mtime: Option<SystemTime>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub permissions: Option<String>,
+    /// Hard links to a file (Unix only)
+    pub links: Option<u64>,
+    /// Mean size of the files in a directory
+    pub avg_file_size: Option<u64>,
+    /// Biggest file in a directory, as a path relative to it, with its size
+    pub largest_file: Option<(PathBuf, u64)>,
+}
+
+impl NodeDetails {
+    pub fn from_node(tree: &DiskTree, id: NodeId) -> Option<Self> {
+        let node = tree.get(id)?;
+        // Trees from older scans kept only directory mtimes; read on demand
+        let mtime = node.mtime.or_else(|| {
+            std::fs::symlink_metadata(&node.path)
+                .and_then(|m| m.modified())
+                .ok()
+        });
+
+        let largest_file = tree
+            .largest_descendant_file(id)
+            .and_then(|file| tree.get(file))
+            .map(|file| {
+                let relative = file.path.strip_prefix(&node.path).unwrap_or(&file.path);
+                (relative.to_path_buf(), file.size)
+            });
+
+        Some(Self {
+            path: node.path.clone(),
+            kind: node.kind,
+            size: node.size,
+            file_count: node.file_count,
+            mtime,
+            owner: node.uid.map(user_label),
+            group: node.gid.map(group_label),
+            permissions: node.mode.map(|m| format_permissions(node.kind, m)),
+            // A directory's count is its subdirectories plus two, not sharing
+            links: node.nlink.filter(|_| !node.kind.is_directory()),
+            avg_file_size: tree.avg_file_size(id).filter(|_| node.kind.is_directory()),
+            largest_file,
+        })
+    }
+}
+
+/// Bytes and file count of one extension within a directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeShare {
+    /// Lowercased extension without the dot; empty for files without one
+    pub extension: String,
+    pub size: u64,
+    pub files: u64,
+}
+
+/// Breakdown of a directory's files by extension, largest first, taken
+/// when the overlay opens
+#[derive(Debug, Clone)]
+pub struct FileTypes {
+    pub path: PathBuf,
+    pub size: u64,
+    pub types: Vec<TypeShare>,
+}
+
+impl FileTypes {
+    /// Walk only the subtree under `id`; None unless it's a directory
+    pub fn from_node(tree: &DiskTree, id: NodeId) -> Option<Self> {
+        let node = tree.get(id)?;
+        if !node.kind.is_directory() {
+            return None;
+        }
+
+        let mut totals: HashMap<String, (u64, u64)> = HashMap::new();
+        let mut stack = vec![id];
+        while let Some(current) = stack.pop() {
+            let Some(node) = tree.get(current) else {
+                continue;
+            };
+            match node.kind {
+                NodeKind::Directory => stack.extend(&node.children),
+                NodeKind::File => {
+                    let extension = Path::new(&node.name)
+                        .extension()
+                        .map(|ext| ext.to_string_lossy().to_lowercase())
+                        .unwrap_or_default();
+                    let total = totals.entry(extension).or_default();
+                    total.0 += node.size;
+                    total.1 += 1;
+                }
+                NodeKind::Symlink | NodeKind::Error => {}
+            }
+        }
+
+        let mut types: Vec<TypeShare> = totals
+            .into_iter()
+            .map(|(extension, (size, files))| TypeShare {
+                extension,
+                size,
+                files,
+            })
+            .collect();
+        types.sort_by(|a, b| {
+            b.size
+                .cmp(&a.size)
+                .then_with(|| a.extension.cmp(&b.extension))
+        });
+
+        Some(Self {
+            path: node.path.clone(),
+            size: node.size,
+            types,
+        })
+    }
+}
+
+/// `name (uid)`, or just the number when the user is unknown
+#[cfg(unix)]
+fn user_label(uid: u32) -> String {
+    match users::get_user_by_uid(uid) {
+        Some(user) => format!("{} ({})", user.name().to_string_lossy(), uid),
+        None => uid.to_string(),
+    }
+}
+
+#[cfg(not(unix))]
+fn user_label(uid: u32) -> String {
+    uid.to_string()
+}
+
+/// `name (gid)`, or just the number when the group is unknown
+#[cfg(unix)]
+fn group_label(gid: u32) -> String {
+    match users::get_group_by_gid(gid) {
+        Some(group) => format!("{} ({})", group.name().to_string_lossy(), gid),
+        None => gid.to_string(),
+    }
+}
+
+#[cfg(not(unix))]
+fn group_label(gid: u32) -> String {
+    gid.to_string()
+}
+
+/// Render mode bits as `drwxr-xr-x (755)`
+pub fn format_permissions(kind: NodeKind, mode: u32) -> String {
+    let type_char = match kind {
+        NodeKind::Directory => 'd',
+        NodeKind::Symlink => 'l',
+        NodeKind::File | NodeKind::Error => '-',
+    };
+
+    let mut s = String::with_capacity(10);
+    s.push(type_char);
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        s.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        s.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        s.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+
+    format!("{} ({:03o})", s, mode & 0o7777)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_types_cover_only_the_subtree() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let photos = tree.add_sized("photos", NodeKind::Directory, 0, NodeId::ROOT);
+        let raw = tree.add_sized("raw", NodeKind::Directory, 0, photos);
+        tree.add_sized("a.JPG", NodeKind::File, 300, photos);
+        tree.add_sized("b.jpg", NodeKind::File, 200, raw);
+        tree.add_sized("notes.txt", NodeKind::File, 50, photos);
+        tree.add_sized("README", NodeKind::File, 50, raw);
+        tree.add_sized("big.iso", NodeKind::File, 9000, NodeId::ROOT);
+        tree.aggregate_sizes();
+
+        let types = FileTypes::from_node(&tree, photos).unwrap();
+        assert_eq!(types.size, 600);
+        let listed: Vec<(&str, u64, u64)> = types
+            .types
+            .iter()
+            .map(|t| (t.extension.as_str(), t.size, t.files))
+            .collect();
+        assert_eq!(listed, vec![("jpg", 500, 2), ("", 50, 1), ("txt", 50, 1)]);
+    }
+
+    #[test]
+    fn test_format_permissions() {
+        assert_eq!(
+            format_permissions(NodeKind::Directory, 0o40755),
+            "drwxr-xr-x (755)"
+        );
+        assert_eq!(
+            format_permissions(NodeKind::File, 0o100644),
+            "-rw-r--r-- (644)"
+        );
+        assert_eq!(
+            format_permissions(NodeKind::Symlink, 0o120777),
+            "lrwxrwxrwx (777)"
+        );
+        assert_eq!(
+            format_permissions(NodeKind::File, 0o600),
+            "-rw------- (600)"
+        );
+    }
+}
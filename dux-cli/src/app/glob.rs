@@ -0,0 +1,55 @@
+/// Match a Large Files filter against a path relative to the scan root,
+/// ignoring ASCII case. A pattern without `*` or `?` matches any path that
+/// contains it (`logs/`); otherwise it must match the whole path, with `?`
+/// standing for one character and `*` for any run, `/` included (`*.mov`).
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim().to_ascii_lowercase();
+    let path = path.to_ascii_lowercase();
+    if !pattern.contains(['*', '?']) {
+        return path.contains(&pattern);
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let path: Vec<char> = path.chars().collect();
+    // Greedy wildcard matching, backtracking to the last `*` on a mismatch
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < path.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == path[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.mov", "videos/2024/trip.MOV"));
+        assert!(!glob_match("*.mov", "videos/trip.mov.part"));
+        assert!(glob_match("logs/", "app/logs/today.log"));
+        assert!(glob_match("logs/*.log", "logs/a/b.log"));
+        assert!(!glob_match("logs/*.log", "app/logs/b.log"));
+        assert!(glob_match("img_???.jpg", "img_001.jpg"));
+        assert!(!glob_match("img_???.jpg", "img_01.jpg"));
+        assert!(glob_match("", "anything"));
+    }
+}
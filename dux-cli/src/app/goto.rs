@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use dux_core::{DiskTree, NodeId};
+
+/// Resolve a typed path to a node. Relative paths start at the scan root and
+/// `~` expands to the home directory. When there is no exact match, the
+/// largest sibling whose name starts with the final component is used.
+pub fn resolve_path(tree: &DiskTree, input: &str) -> Option<NodeId> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+
+    let path = expand_home(input);
+    let path = if path.is_absolute() {
+        path
+    } else {
+        tree.root_path().join(path)
+    };
+    // Drops trailing slashes and `.` components
+    let path: PathBuf = path.components().collect();
+
+    if let Some(id) = tree.find_by_path(&path) {
+        return Some(id);
+    }
+
+    // The tree holds canonical paths, so try resolving symlinks in the input
+    if let Ok(canonical) = path.canonicalize()
+        && let Some(id) = tree.find_by_path(&canonical)
+    {
+        return Some(id);
+    }
+
+    // Children are sorted by size, so the first match is the largest
+    let parent = tree.find_by_path(path.parent()?)?;
+    let prefix = path.file_name()?.to_string_lossy();
+    tree.get(parent)?
+        .children
+        .iter()
+        .copied()
+        .find(|&c| tree.get(c).is_some_and(|n| n.name.starts_with(&*prefix)))
+}
+
+fn expand_home(input: &str) -> PathBuf {
+    match input.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => match dirs::home_dir() {
+            Some(home) => home.join(rest.trim_start_matches('/')),
+            None => PathBuf::from(input),
+        },
+        _ => PathBuf::from(input),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dux_core::NodeKind;
+
+    #[test]
+    fn test_resolve_path_exact_relative_and_prefix() {
+        let mut tree = DiskTree::new(PathBuf::from("/nonexistent-dux-root"));
+        let src = tree.add_sized("src", NodeKind::Directory, 0, NodeId::ROOT);
+        let main = tree.add_sized("main.rs", NodeKind::File, 0, src);
+
+        assert_eq!(resolve_path(&tree, "/nonexistent-dux-root/src"), Some(src));
+        assert_eq!(resolve_path(&tree, "src/"), Some(src));
+        assert_eq!(resolve_path(&tree, "./src/main.rs"), Some(main));
+        assert_eq!(resolve_path(&tree, "src/ma"), Some(main));
+        assert_eq!(resolve_path(&tree, "src/lib.rs"), None);
+        assert_eq!(resolve_path(&tree, "   "), None);
+    }
+}
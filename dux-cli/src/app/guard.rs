@@ -0,0 +1,154 @@
+use std::path::{Component, Path};
+
+/// Size at or above which a deletion needs a typed confirmation
+pub const DEFAULT_TYPED_CONFIRM_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Paths this close to the filesystem root (`/usr`, `/home/alice`) always
+/// need a typed confirmation
+const SHALLOW_DEPTH: usize = 2;
+
+/// System directories that are never deleted, whatever was selected. Their
+/// contents can be, one entry at a time.
+#[cfg(not(windows))]
+const PROTECTED_PATHS: &[&str] = &[
+    "/",
+    "/Applications",
+    "/Library",
+    "/System",
+    "/Users",
+    "/Volumes",
+    "/bin",
+    "/boot",
+    "/dev",
+    "/etc",
+    "/home",
+    "/lib",
+    "/lib64",
+    "/opt",
+    "/private",
+    "/proc",
+    "/root",
+    "/sbin",
+    "/sys",
+    "/usr",
+    "/var",
+];
+
+#[cfg(windows)]
+const PROTECTED_PATHS: &[&str] = &[
+    r"C:\Program Files",
+    r"C:\Program Files (x86)",
+    r"C:\ProgramData",
+    r"C:\Users",
+    r"C:\Windows",
+];
+
+/// Whether `path` must never be deleted: a filesystem root (`/`, `C:\`),
+/// the home directory, or one of the top-level system directories. A typed
+/// confirmation is not enough.
+pub fn is_protected_path(path: &Path) -> bool {
+    if path.parent().is_none() || PROTECTED_PATHS.iter().any(|p| path == Path::new(p)) {
+        return true;
+    }
+    dirs::home_dir()
+        .is_some_and(|home| path == home || home.canonicalize().is_ok_and(|home| path == home))
+}
+
+/// Text the user must type before deleting `path`, or None when `y` is
+/// enough. The last path component is asked for so the user has to read
+/// what is about to go.
+pub fn single_phrase(path: &Path, size: u64, threshold: u64) -> Option<String> {
+    if size < threshold && !is_shallow(path) {
+        return None;
+    }
+    Some(
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| "delete".to_string()),
+    )
+}
+
+/// Text required for a multi-delete: the word `delete` when the total is
+/// above the threshold or any item is shallow
+pub fn multi_phrase<'a>(
+    items: impl IntoIterator<Item = (&'a Path, u64)>,
+    threshold: u64,
+) -> Option<String> {
+    let mut total = 0u64;
+    let mut shallow = false;
+    for (path, size) in items {
+        total = total.saturating_add(size);
+        shallow |= is_shallow(path);
+    }
+    (shallow || total >= threshold).then(|| "delete".to_string())
+}
+
+fn is_shallow(path: &Path) -> bool {
+    path.components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .count()
+        <= SHALLOW_DEPTH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIB: u64 = DEFAULT_TYPED_CONFIRM_SIZE;
+
+    #[test]
+    fn test_single_phrase() {
+        let deep = Path::new("/home/me/code/app/target");
+        assert_eq!(single_phrase(deep, 10, GIB), None);
+        assert_eq!(single_phrase(deep, 2 * GIB, GIB).as_deref(), Some("target"));
+        assert_eq!(
+            single_phrase(Path::new("/usr"), 10, GIB).as_deref(),
+            Some("usr")
+        );
+        assert_eq!(
+            single_phrase(Path::new("/home/me"), 10, GIB).as_deref(),
+            Some("me")
+        );
+    }
+
+    #[test]
+    fn test_filesystem_roots_are_protected() {
+        assert!(is_protected_path(Path::new("/")));
+        assert!(is_protected_path(Path::new("//")));
+        #[cfg(windows)]
+        {
+            assert!(is_protected_path(Path::new(r"C:\")));
+            assert!(is_protected_path(Path::new(r"D:\")));
+            assert!(is_protected_path(Path::new(r"C:\Windows")));
+            assert!(!is_protected_path(Path::new(r"C:\Users\me\target")));
+        }
+    }
+
+    #[test]
+    fn test_protected_paths() {
+        assert!(is_protected_path(Path::new("/usr/")));
+        assert!(is_protected_path(Path::new("/etc")));
+        assert!(!is_protected_path(Path::new("/usr/local/share/doc")));
+        assert!(!is_protected_path(Path::new("/etc-backup")));
+        if let Some(home) = dirs::home_dir() {
+            assert!(is_protected_path(&home));
+            assert!(!is_protected_path(&home.join("Downloads")));
+        }
+    }
+
+    #[test]
+    fn test_multi_phrase() {
+        let a = Path::new("/home/me/code/a/target");
+        let b = Path::new("/home/me/code/b/target");
+        assert_eq!(multi_phrase([(a, 10), (b, 10)], GIB), None);
+        assert_eq!(
+            multi_phrase([(a, GIB / 2), (b, GIB / 2)], GIB).as_deref(),
+            Some("delete")
+        );
+        assert_eq!(
+            multi_phrase([(a, 10), (Path::new("/opt"), 10)], GIB).as_deref(),
+            Some("delete")
+        );
+    }
+}
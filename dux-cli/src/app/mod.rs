@@ -1,6 +1,17 @@
 mod action;
+mod details;
+mod glob;
+mod goto;
+pub mod guard;
+mod reveal;
 mod state;
+mod treemap;
 pub mod views;
 
 pub use action::Action;
-pub use state::{AppMode, AppState, MultiDeleteProgress, SessionStats, ViewMode};
+pub use details::{FileTypes, NodeDetails};
+pub use state::{
+    AgeDisplay, AppMode, AppState, BarMetric, MultiDeleteProgress, PercentBase, SessionStats,
+    ViewMode,
+};
+pub use treemap::{squarify, treemap_children};
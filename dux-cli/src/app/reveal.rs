@@ -0,0 +1,114 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Show `path` in the platform file manager: a file's folder opens with the
+/// file highlighted where supported, a directory opens itself. Missing
+/// openers are ignored.
+pub fn reveal_in_file_manager(path: &Path, is_dir: bool) {
+    if is_dir {
+        open_directory(path);
+    } else {
+        reveal_file(path);
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn open_directory(path: &Path) {
+    spawn_quiet(Command::new("open").arg(path));
+}
+
+#[cfg(target_os = "macos")]
+fn reveal_file(path: &Path) {
+    spawn_quiet(Command::new("open").arg("-R").arg(path));
+}
+
+#[cfg(windows)]
+fn open_directory(path: &Path) {
+    spawn_quiet(Command::new("explorer").arg(path));
+}
+
+#[cfg(windows)]
+fn reveal_file(path: &Path) {
+    let mut arg = std::ffi::OsString::from("/select,");
+    arg.push(path);
+    spawn_quiet(Command::new("explorer").arg(arg));
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn open_directory(path: &Path) {
+    spawn_quiet(Command::new("xdg-open").arg(path));
+}
+
+/// Ask the desktop's file manager to highlight the file over D-Bus
+/// (`org.freedesktop.FileManager1`), opening the parent folder when no file
+/// manager answers. Runs on a thread since the D-Bus call waits for a reply.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn reveal_file(path: &Path) {
+    let uri = file_uri(path);
+    let parent = path.parent().map(Path::to_path_buf);
+    std::thread::spawn(move || {
+        let shown = Command::new("dbus-send")
+            .args([
+                "--session",
+                "--print-reply",
+                "--dest=org.freedesktop.FileManager1",
+                "/org/freedesktop/FileManager1",
+                "org.freedesktop.FileManager1.ShowItems",
+            ])
+            .arg(format!("array:string:{}", uri))
+            .arg("string:")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success());
+        if !shown && let Some(parent) = parent {
+            open_directory(&parent);
+        }
+    });
+}
+
+#[cfg(not(any(unix, windows)))]
+fn open_directory(_path: &Path) {}
+
+#[cfg(not(any(unix, windows)))]
+fn reveal_file(_path: &Path) {}
+
+/// Start an opener without letting its output reach the terminal
+#[cfg(any(unix, windows))]
+fn spawn_quiet(command: &mut Command) {
+    let _ = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+}
+
+/// `file://` URI with every byte outside the unreserved set percent-encoded
+#[cfg(all(unix, not(target_os = "macos")))]
+fn file_uri(path: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut uri = String::from("file://");
+    for &byte in path.as_os_str().as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+#[cfg(all(test, unix, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_uri_escapes() {
+        assert_eq!(
+            file_uri(Path::new("/home/me/My Files/a,b#1.txt")),
+            "file:///home/me/My%20Files/a%2Cb%231.txt"
+        );
+        assert_eq!(file_uri(Path::new("/tmp/å")), "file:///tmp/%C3%A5");
+    }
+}
@@ -1,10 +1,28 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
-
-use dux_core::{DiskTree, NodeId, ScanProgress};
-
-use super::views::ComputedViews;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::Local;
+use clap::ValueEnum;
+use dux_core::{DiskTree, NodeId, NodeKind, ScanProgress, SizeFormat, TreeNode};
+use ratatui::layout::Rect;
+
+use super::details::{FileTypes, NodeDetails};
+use super::goto::resolve_path;
+use super::guard;
+use super::reveal;
+use super::treemap::{squarify, treemap_children};
+use super::views::{
+    ComputedViews, FilterSummary, LargeFileRow, NodeFilter, ViewData, ViewSettings, dedup_ancestors,
+};
+use crate::audit::{self, DeletionRecord};
+use crate::bookmarks::Bookmarks;
+use crate::export::{csv, report, selection};
+use crate::watch::Watch;
 
 /// Statistics tracked during the session
 #[derive(Debug, Default, Clone)]
@@ -26,6 +44,24 @@ pub enum AppMode {
     Browsing,
     /// Showing help overlay
     Help,
+    /// Showing the scan errors overlay
+    Errors,
+    /// Showing the details panel for the selected item
+    Details,
+    /// Showing the file-type breakdown of the selected directory
+    FileTypes,
+    /// Typing a path to jump to
+    GoTo,
+    /// Typing a glob to narrow the Large Files view
+    FileFilter,
+    /// Typing how many of the newest build artifacts of a kind to keep
+    KeepNewest,
+    /// Showing the bookmarks overlay
+    Bookmarks,
+    /// Showing the excluded names and paths
+    Excludes,
+    /// Picking an ancestor of the view root to jump to
+    Breadcrumbs,
     /// Showing delete confirmation dialog (single item)
     ConfirmDelete,
     /// Showing multi-delete confirmation dialog
@@ -34,12 +70,101 @@ pub enum AppMode {
     MultiDeleting,
 }
 
-/// Which data projection is displayed
+/// What tree view percentages (and bars) are relative to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PercentBase {
+    /// Percentage of the current view root
+    ViewRoot,
+    /// Percentage of each node's own parent directory
+    Parent,
+    /// Percentage of the free space left on the scanned filesystem
+    FreeSpace,
+    /// Percentage of the amount to free, set with `--need`
+    Goal,
+}
+
+/// What the Tree view and Overview rank entries by and draw as bars
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarMetric {
+    /// Space on disk
+    Size,
+    /// Files underneath, for where the files are rather than the bytes
+    Files,
+}
+
+impl BarMetric {
+    /// The measured amount for `node`
+    pub fn of(self, node: &TreeNode) -> u64 {
+        match self {
+            BarMetric::Size => node.size,
+            BarMetric::Files => node.file_count,
+        }
+    }
+}
+
+/// How recent a change must be for the Tree view to mark its entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecentWindow {
+    Off,
+    Hour,
+    Day,
+    Week,
+}
+
+impl RecentWindow {
+    pub fn next(self) -> Self {
+        match self {
+            RecentWindow::Off => RecentWindow::Hour,
+            RecentWindow::Hour => RecentWindow::Day,
+            RecentWindow::Day => RecentWindow::Week,
+            RecentWindow::Week => RecentWindow::Off,
+        }
+    }
+
+    /// How far back a change counts as recent; None when off
+    pub fn duration(self) -> Option<Duration> {
+        let hours = match self {
+            RecentWindow::Off => return None,
+            RecentWindow::Hour => 1,
+            RecentWindow::Day => 24,
+            RecentWindow::Week => 24 * 7,
+        };
+        Some(Duration::from_secs(hours * 3600))
+    }
+
+    /// Footer label; None when off
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            RecentWindow::Off => None,
+            RecentWindow::Hour => Some("● changed < 1h"),
+            RecentWindow::Day => Some("● changed < 24h"),
+            RecentWindow::Week => Some("● changed < 7d"),
+        }
+    }
+}
+
+/// How modification times are shown in the Build Artifacts age column
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgeDisplay {
+    /// Time since the newest change, e.g. `45d`
+    Relative,
+    /// Local date of the newest change
+    Absolute,
+}
+
+/// Which data projection is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
 pub enum ViewMode {
     Tree,
     LargeFiles,
     BuildArtifacts,
+    EmptyDirs,
+    Changes,
+    /// Just the scan root's top-level entries, as a first look before
+    /// drilling in
+    Overview,
+    /// Every symlink and where it points, broken ones first
+    Symlinks,
 }
 
 /// Per-view selection state
@@ -51,19 +176,42 @@ pub struct ViewState {
 
 /// Result from a single item in a multi-delete batch
 pub enum MultiDeleteResult {
-    Success { size: u64 },
-    Failure { path: PathBuf, error: String },
+    Success {
+        path: PathBuf,
+        size: u64,
+    },
+    Failure {
+        path: PathBuf,
+        size: u64,
+        error: String,
+    },
 }
 
 /// Progress tracker for multi-delete operations
 pub struct MultiDeleteProgress {
     pub total: usize,
     pub completed: usize,
+    /// Combined size of every item in the batch
+    pub total_bytes: u64,
     pub bytes_freed: u64,
+    /// Size of the items finished so far, freed or failed, so the byte
+    /// progress still reaches the end when some deletions fail
+    pub bytes_done: u64,
     pub failures: Vec<(PathBuf, String)>,
     pub receiver: mpsc::Receiver<MultiDeleteResult>,
+    /// One thread per item, so quitting can wait for the ones still running
+    pub handles: Vec<JoinHandle<()>>,
 }
 
+/// Maximum gap between two clicks on the same row to count as a double-click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// How long status and error messages stay in the footer
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// Children listed in the text size report unless `--report-top` says otherwise
+const DEFAULT_REPORT_LIMIT: usize = 20;
+
 /// Application state
 pub struct AppState {
     /// Current mode
@@ -74,38 +222,140 @@ pub struct AppState {
     pub tree: Option<DiskTree>,
     /// Current scan progress
     pub progress: ScanProgress,
+    /// Steps done and total while the finished scan is summed and sorted
+    pub finalize_progress: Option<(usize, usize)>,
+    /// Top-level snapshot streamed by the scanner, shown until the scan ends
+    pub partial_tree: Option<DiskTree>,
     /// Currently selected node index in visible list (tree view)
     pub selected_index: usize,
     /// Current view root (for drill-down)
     pub view_root: NodeId,
     /// Navigation history (for going back)
     pub history: Vec<NodeId>,
+    /// Roots left by going back, replayed by going forward; cleared by any
+    /// new navigation
+    pub forward: Vec<NodeId>,
+    /// Selection and scroll offset last seen at each view root, restored
+    /// when going back or forward to it
+    view_positions: HashMap<NodeId, (usize, usize)>,
     /// Scroll offset for tree view
     pub scroll_offset: usize,
     /// Visible area height (set by UI)
     pub visible_height: usize,
+    /// Screen area of the main content (set by UI, for mouse hit-testing)
+    pub content_area: Rect,
+    /// Last left click (time and item index), for double-click detection
+    last_click: Option<(Instant, usize)>,
     /// Whether app should quit
     pub should_quit: bool,
     /// Spinner frame for animation
     pub spinner_frame: usize,
     /// Error message to display
     pub error_message: Option<String>,
+    /// Transient status message shown in the footer (e.g. "Copied path")
+    pub status_message: Option<String>,
+    /// When the current status/error message was set
+    message_set_at: Option<Instant>,
     /// Item pending deletion (node ID and path for confirmation dialog)
     pub pending_delete: Option<(NodeId, PathBuf)>,
     /// Session statistics (deleted items, freed space)
     pub session_stats: SessionStats,
     /// Whether tree was loaded from cache
     pub loaded_from_cache: bool,
+    /// Whether the running scan is paused
+    pub scan_paused: bool,
+    /// Whether the scan timed out, leaving the tree incomplete
+    pub partial_scan: bool,
+    /// Total and free bytes of the filesystem holding the scan root
+    pub disk_capacity: Option<(u64, u64)>,
+    /// How much space the session sets out to free (`--need`)
+    pub need: Option<u64>,
     /// Whether the tree has been modified (e.g. by deletion) and needs cache update
     pub tree_modified: bool,
     /// Receiver for async delete results
-    pub delete_receiver: Option<mpsc::Receiver<Result<(NodeId, u64), String>>>,
+    pub delete_receiver: Option<mpsc::Receiver<Result<(PathBuf, u64), String>>>,
+    /// Thread of the running single-item delete, so quitting can wait for it
+    pub delete_handle: Option<JoinHandle<()>>,
+    /// JSON Lines file each successful deletion is appended to (None with
+    /// `--no-audit`)
+    pub audit_log: Option<PathBuf>,
+    /// Filesystem watch keeping the tree current (`--watch`)
+    pub watch: Option<Watch>,
+    /// When the watch last brought the tree up to date
+    pub watch_updated: Option<chrono::DateTime<Local>>,
+    /// Rescan of the `--always-rescan` directories of a cached tree, while
+    /// it runs
+    pub refresh: Option<mpsc::Receiver<Vec<(PathBuf, DiskTree)>>>,
     /// Current view mode
     pub view_mode: ViewMode,
     /// Large files view state
     pub large_files_state: ViewState,
     /// Build artifacts view state
     pub build_artifacts_state: ViewState,
+    /// Empty directories view state
+    pub empty_dirs_state: ViewState,
+    /// Changes view state
+    pub changes_state: ViewState,
+    /// Overview state
+    pub overview_state: ViewState,
+    /// Symlinks view state
+    pub symlinks_state: ViewState,
+    /// When the scan the Changes view compares against was taken
+    pub changes_since: Option<SystemTime>,
+    /// Whether hidden (dot) files are shown in all views
+    pub show_hidden: bool,
+    /// Size threshold and kind filter of each view, kept across switches
+    view_settings: HashMap<ViewMode, ViewSettings>,
+    /// Paths the scanner could not read, with the reason
+    pub scan_errors: Vec<(PathBuf, String)>,
+    /// First entry shown in the errors overlay
+    pub errors_scroll: usize,
+    /// Entries that fit in the errors overlay (updated on render)
+    pub errors_visible: usize,
+    /// Snapshot shown in the details panel
+    pub details: Option<NodeDetails>,
+    /// Breakdown shown in the file-types overlay
+    pub file_types: Option<FileTypes>,
+    /// Path typed at the jump-to-path prompt
+    pub goto_input: String,
+    /// Count typed at the keep-newest prompt
+    pub keep_newest_input: String,
+    /// Saved directories, shown in the bookmarks overlay
+    pub bookmarks: Bookmarks,
+    /// Selected entry in the bookmarks overlay
+    pub bookmarks_selected: usize,
+    /// Names and paths left out of the scan, from `--exclude` and `X`; kept
+    /// across rescans
+    pub excludes: Vec<PathBuf>,
+    /// Selected entry in the excludes overlay
+    pub excludes_selected: usize,
+    /// Selected ancestor in the breadcrumbs overlay (0 is the scan root)
+    pub breadcrumbs_selected: usize,
+    /// Directory to scan next when a bookmark outside this tree is opened
+    pub relaunch_path: Option<PathBuf>,
+    /// Whether the relaunch should skip the cache and scan afresh
+    pub rescan: bool,
+    /// File to hand to the editor once the current frame is done
+    pub pending_open: Option<PathBuf>,
+    /// Unit base and precision for rendered sizes
+    pub size_format: SizeFormat,
+    /// What the active filters hide under the view root, with the view
+    /// root, filter and tree shape it was computed for
+    filter_summary: Option<((NodeId, NodeFilter, usize, u64), FilterSummary)>,
+    /// Whether the file-count column is shown
+    pub show_file_counts: bool,
+    /// What tree view percentages are relative to
+    pub percent_base: PercentBase,
+    /// Whether bars, percentages and order follow size or file count
+    pub bar_metric: BarMetric,
+    /// Tree entries changed within this window are marked
+    pub recent_window: RecentWindow,
+    /// Whether artifact ages are relative or dates
+    pub age_display: AgeDisplay,
+    /// Whether the Tree view shows the treemap (blocks) visualization
+    pub show_treemap: bool,
+    /// Treemap block selection state
+    pub treemap_state: ViewState,
     /// Pre-computed view data
     pub computed_views: ComputedViews,
     /// Multi-selected nodes (stable arena indices)
@@ -114,6 +364,23 @@ pub struct AppState {
     pub selecting_mode: bool,
     /// Items pending multi-delete confirmation
     pub pending_multi_delete: Option<Vec<(NodeId, PathBuf, u64)>>,
+    /// Artifact kind label when the pending multi-delete is "all of one kind"
+    pub pending_delete_kind: Option<String>,
+    /// Deletions at or above this size must be confirmed by typing
+    pub typed_confirm_size: u64,
+    /// Every delete action is refused (`--read-only`)
+    pub read_only: bool,
+    /// Children listed in the text size report before the rest are folded
+    pub report_limit: usize,
+    /// Drop the size-bar row, folding its apparent size into the header
+    pub compact: bool,
+    /// Text to print once the terminal is restored (e.g. a report the
+    /// clipboard could not take)
+    pub deferred_output: Vec<String>,
+    /// Phrase the pending deletion must be confirmed with (None = `y` is enough)
+    pub confirm_phrase: Option<String>,
+    /// Text typed into the delete confirmation so far
+    pub confirm_input: String,
     /// Multi-delete progress tracker
     pub multi_delete_progress: Option<MultiDeleteProgress>,
 }
@@ -125,39 +392,110 @@ impl AppState {
             root_path,
             tree: None,
             progress: ScanProgress::default(),
+            finalize_progress: None,
+            partial_tree: None,
             selected_index: 0,
             view_root: NodeId::ROOT,
             history: Vec::new(),
+            forward: Vec::new(),
+            view_positions: HashMap::new(),
             scroll_offset: 0,
             visible_height: 20,
+            content_area: Rect::default(),
+            last_click: None,
             should_quit: false,
             spinner_frame: 0,
             error_message: None,
+            status_message: None,
+            message_set_at: None,
             pending_delete: None,
             session_stats: SessionStats::default(),
             loaded_from_cache: false,
+            scan_paused: false,
+            partial_scan: false,
+            disk_capacity: None,
+            need: None,
             tree_modified: false,
             delete_receiver: None,
+            delete_handle: None,
+            audit_log: None,
+            watch: None,
+            watch_updated: None,
+            refresh: None,
             view_mode: ViewMode::Tree,
             large_files_state: ViewState::default(),
             build_artifacts_state: ViewState::default(),
+            empty_dirs_state: ViewState::default(),
+            changes_state: ViewState::default(),
+            overview_state: ViewState::default(),
+            symlinks_state: ViewState::default(),
+            changes_since: None,
+            show_hidden: true,
+            view_settings: HashMap::new(),
+            scan_errors: Vec::new(),
+            errors_scroll: 0,
+            errors_visible: 1,
+            details: None,
+            file_types: None,
+            goto_input: String::new(),
+            keep_newest_input: String::new(),
+            bookmarks: Bookmarks::default(),
+            bookmarks_selected: 0,
+            excludes: Vec::new(),
+            excludes_selected: 0,
+            breadcrumbs_selected: 0,
+            relaunch_path: None,
+            rescan: false,
+            pending_open: None,
+            size_format: SizeFormat::default(),
+            filter_summary: None,
+            show_file_counts: false,
+            percent_base: PercentBase::ViewRoot,
+            bar_metric: BarMetric::Size,
+            recent_window: RecentWindow::Off,
+            age_display: AgeDisplay::Relative,
+            show_treemap: false,
+            treemap_state: ViewState::default(),
             computed_views: ComputedViews::new(),
             selected_nodes: HashSet::new(),
             selecting_mode: false,
             pending_multi_delete: None,
+            pending_delete_kind: None,
+            typed_confirm_size: guard::DEFAULT_TYPED_CONFIRM_SIZE,
+            read_only: false,
+            report_limit: DEFAULT_REPORT_LIMIT,
+            compact: false,
+            deferred_output: Vec::new(),
+            confirm_phrase: None,
+            confirm_input: String::new(),
             multi_delete_progress: None,
         }
     }
 
     /// Set the tree after scanning completes
     pub fn set_tree(&mut self, tree: DiskTree) {
-        self.computed_views.rebuild(&tree);
+        self.computed_views.invalidate();
         self.tree = Some(tree);
+        self.partial_tree = None;
         self.mode = AppMode::Browsing;
         self.selected_index = 0;
         self.view_root = NodeId::ROOT;
     }
 
+    /// Compare the current tree with the one from a previous scan taken at `since`
+    pub fn set_previous_tree(&mut self, previous: &DiskTree, since: SystemTime) {
+        if let Some(tree) = &self.tree {
+            self.computed_views.change_deltas = tree.diff(previous);
+            self.changes_since = Some(since);
+            self.computed_views.invalidate_only(ViewData::Changes);
+        }
+    }
+
+    /// Replace the partial tree shown while scanning
+    pub fn set_partial_tree(&mut self, tree: DiskTree) {
+        self.partial_tree = Some(tree);
+    }
+
     /// Update scan progress
     pub fn update_progress(&mut self, progress: ScanProgress) {
         self.progress = progress;
@@ -168,51 +506,389 @@ impl AppState {
         self.mode = AppMode::Finalizing;
     }
 
-    /// Advance spinner animation
-    pub fn tick_spinner(&mut self) {
+    /// Advance the spinner and expire footer messages. Returns whether the
+    /// screen changed and needs drawing again.
+    pub fn tick(&mut self) -> bool {
         self.spinner_frame = (self.spinner_frame + 1) % 10;
+        self.expire_messages() || self.is_busy()
+    }
+
+    /// Whether a scan or deletion is running, so the screen animates and
+    /// the event loop should keep ticking
+    pub fn is_busy(&self) -> bool {
+        self.tree.is_none()
+            || self.delete_receiver.is_some()
+            || self.multi_delete_progress.is_some()
+            || self.watch.as_ref().is_some_and(Watch::is_busy)
+            || self.refresh.is_some()
+    }
+
+    /// How long until the footer message expires, if one is shown
+    pub fn message_time_left(&self) -> Option<Duration> {
+        self.message_set_at
+            .map(|at| MESSAGE_TIMEOUT.saturating_sub(at.elapsed()))
+    }
+
+    /// Clear footer messages once they have been shown long enough; true if
+    /// one was cleared
+    fn expire_messages(&mut self) -> bool {
+        if let Some(at) = self.message_set_at
+            && at.elapsed() >= MESSAGE_TIMEOUT
+        {
+            self.error_message = None;
+            self.status_message = None;
+            self.message_set_at = None;
+            return true;
+        }
+        false
+    }
+
+    /// Current view-level filter, with the Tree view's threshold and kinds
+    pub fn node_filter(&self) -> NodeFilter {
+        let tree = self.settings_of(ViewMode::Tree);
+        NodeFilter {
+            show_hidden: self.show_hidden,
+            min_share: tree.min_share,
+            kind: tree.kind,
+        }
+    }
+
+    /// Filters of the view being shown
+    pub fn view_settings(&self) -> ViewSettings {
+        self.settings_of(self.view_mode)
+    }
+
+    /// Filters last set in `mode`
+    pub fn settings_of(&self, mode: ViewMode) -> ViewSettings {
+        self.view_settings.get(&mode).copied().unwrap_or_default()
+    }
+
+    /// Toggle visibility of hidden (dot) files without rescanning
+    pub fn toggle_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        self.computed_views.invalidate();
+        self.ensure_views_computed();
+        self.adjust_selection_after_delete();
+    }
+
+    /// Raise or lower the size threshold of the current view: Tree entries
+    /// are measured against their parent, Large Files against the total.
+    /// Other views have no threshold.
+    pub fn step_min_share(&mut self, up: bool) {
+        if !matches!(self.view_mode, ViewMode::Tree | ViewMode::LargeFiles) {
+            return;
+        }
+        let settings = self.view_settings.entry(self.view_mode).or_default();
+        settings.min_share = settings.min_share.step(up);
+        if self.view_mode == ViewMode::LargeFiles {
+            let min_share = settings.min_share;
+            self.computed_views.set_large_files_min_share(min_share);
+        }
+        self.adjust_selection_after_delete();
+    }
+
+    /// Cycle the Tree view between all entries, directories only and files only
+    pub fn cycle_kind_filter(&mut self) {
+        if self.view_mode != ViewMode::Tree {
+            return;
+        }
+        let settings = self.view_settings.entry(ViewMode::Tree).or_default();
+        settings.kind = settings.kind.next();
+        self.adjust_selection_after_delete();
+    }
+
+    /// Show or hide the file-count column
+    pub fn toggle_file_counts(&mut self) {
+        self.show_file_counts = !self.show_file_counts;
+    }
+
+    /// Cycle tree percentages through view root, parent, free space (when
+    /// the filesystem could be queried and isn't full) and the `--need`
+    /// goal (when given)
+    pub fn toggle_percent_base(&mut self) {
+        self.percent_base = match self.percent_base {
+            PercentBase::ViewRoot => PercentBase::Parent,
+            PercentBase::Parent if self.free_space().is_some() => PercentBase::FreeSpace,
+            PercentBase::Parent | PercentBase::FreeSpace if self.need.is_some() => {
+                PercentBase::Goal
+            }
+            PercentBase::Parent | PercentBase::FreeSpace | PercentBase::Goal => {
+                PercentBase::ViewRoot
+            }
+        };
+    }
+
+    /// Fixed total the Tree view and Overview measure sizes against, when
+    /// percentages are of free space or of the `--need` goal. File counts
+    /// keep their own totals.
+    pub fn percent_total(&self) -> Option<u64> {
+        if self.bar_metric != BarMetric::Size {
+            return None;
+        }
+        match self.percent_base {
+            PercentBase::FreeSpace => self.free_space(),
+            PercentBase::Goal => self.need,
+            PercentBase::ViewRoot | PercentBase::Parent => None,
+        }
+    }
+
+    /// Free bytes on the scanned filesystem, unless unknown or none: a full
+    /// disk leaves nothing to measure against
+    fn free_space(&self) -> Option<u64> {
+        self.disk_capacity
+            .map(|(_, free)| free)
+            .filter(|&free| free > 0)
+    }
+
+    /// Rank and measure the Tree view and Overview by file count instead
+    /// of size, or back. The cursor stays on the same entry.
+    pub fn toggle_bar_metric(&mut self) {
+        let selected = self.selected_node();
+        self.bar_metric = match self.bar_metric {
+            BarMetric::Size => BarMetric::Files,
+            BarMetric::Files => BarMetric::Size,
+        };
+        if let Some(id) = selected
+            && let Some(idx) =
+                (0..self.current_item_count()).position(|i| self.node_at_index(i) == Some(id))
+        {
+            let height = self.visible_height;
+            let (sel, scroll) = self.active_selection_mut();
+            *sel = idx;
+            Self::ensure_visible_for(sel, scroll, height);
+        }
+    }
+
+    /// Step the Tree view's recent-activity marker through off, 1h, 24h and 7d
+    pub fn cycle_recent_window(&mut self) {
+        self.recent_window = self.recent_window.next();
+    }
+
+    /// Switch artifact ages between relative and absolute
+    pub fn toggle_age_display(&mut self) {
+        self.age_display = match self.age_display {
+            AgeDisplay::Relative => AgeDisplay::Absolute,
+            AgeDisplay::Absolute => AgeDisplay::Relative,
+        };
+    }
+
+    /// Labels for non-default view settings, shown in the footer
+    pub fn view_indicators(&self) -> Vec<String> {
+        let mut indicators: Vec<String> = Vec::new();
+        if self.read_only {
+            indicators.push("Read-only".into());
+        }
+        if !self.show_hidden {
+            indicators.push("Dotfiles hidden".into());
+        }
+        if self.percent_base == PercentBase::Parent && self.view_mode == ViewMode::Tree {
+            indicators.push("% of parent".into());
+        }
+        if self.percent_total().is_some()
+            && matches!(self.view_mode, ViewMode::Tree | ViewMode::Overview)
+        {
+            indicators.push(match self.percent_base {
+                PercentBase::Goal => format!(
+                    "% of {} to free",
+                    self.size_format.format(self.need.unwrap_or(0))
+                ),
+                _ => "% of free space".into(),
+            });
+        }
+        if self.bar_metric == BarMetric::Files
+            && matches!(self.view_mode, ViewMode::Tree | ViewMode::Overview)
+        {
+            indicators.push("by: files".into());
+        }
+        if let Some(label) = self.recent_window.label()
+            && self.view_mode == ViewMode::Tree
+        {
+            indicators.push(label.into());
+        }
+        let settings = self.view_settings();
+        if let Some(label) = settings.min_share.label() {
+            indicators.push(label.into());
+        }
+        if let Some(label) = settings.kind.label() {
+            indicators.push(label.into());
+        }
+        if !self.excludes.is_empty() {
+            indicators.push(format!("{} excluded", self.excludes.len()));
+        }
+        if self.watch.is_some() {
+            indicators.push(match self.watch_updated {
+                Some(at) => format!("Watching, updated {}", at.format("%H:%M:%S")),
+                None => "Watching".into(),
+            });
+        }
+        if self.refresh.is_some() {
+            indicators.push("Rescanning uncached paths".into());
+        }
+        let offscreen = self.selection_offscreen_count();
+        if offscreen > 0 {
+            indicators.push(format!("{} selected elsewhere", offscreen));
+        }
+        let pattern = self.computed_views.large_files_filter.trim();
+        if !pattern.is_empty() && self.view_mode == ViewMode::LargeFiles {
+            indicators.push(format!("Matching {}", pattern));
+        }
+        indicators
+    }
+
+    /// What the filters hide in the Tree view, or None when nothing is
+    /// filtered. Recomputed only when the view root, filter or tree changes.
+    pub fn filter_summary(&mut self) -> Option<FilterSummary> {
+        let filter = self.node_filter();
+        let tree = self.tree.as_ref()?;
+        if self.view_mode != ViewMode::Tree || filter == NodeFilter::default() {
+            return None;
+        }
+        let key = (self.view_root, filter, tree.live_count(), tree.total_size());
+        match &self.filter_summary {
+            Some((cached, summary)) if *cached == key => Some(*summary),
+            _ => {
+                let summary = FilterSummary::compute(tree, self.view_root, filter);
+                self.filter_summary = Some((key, summary));
+                Some(summary)
+            }
+        }
     }
 
     /// Get visible nodes in current view
     pub fn visible_nodes(&self) -> Vec<NodeId> {
+        let filter = self.node_filter();
+        let Some(tree) = &self.tree else {
+            return Vec::new();
+        };
+        let keep = |n: &TreeNode| filter.keep_in(tree, n);
+        match self.bar_metric {
+            BarMetric::Size => tree.visible_nodes_filtered(self.view_root, &keep),
+            metric => tree.visible_nodes_ranked(self.view_root, &keep, &|n| metric.of(n)),
+        }
+    }
+
+    /// Whether the treemap replaces the tree list
+    fn treemap_active(&self) -> bool {
+        self.view_mode == ViewMode::Tree && self.show_treemap
+    }
+
+    /// Nodes shown as treemap blocks, in block order
+    fn treemap_nodes(&self) -> Vec<NodeId> {
         match &self.tree {
-            Some(tree) => tree.visible_nodes(self.view_root),
+            Some(tree) => treemap_children(tree, self.view_root, self.node_filter()),
             None => Vec::new(),
         }
     }
 
+    /// Toggle the treemap visualization in the Tree view
+    pub fn toggle_treemap(&mut self) {
+        if self.view_mode == ViewMode::Tree {
+            self.show_treemap = !self.show_treemap;
+            self.treemap_state = ViewState::default();
+        }
+    }
+
     /// Get currently selected node ID (works for any view)
     pub fn selected_node(&self) -> Option<NodeId> {
         match self.view_mode {
+            ViewMode::Tree if self.show_treemap => self
+                .treemap_nodes()
+                .get(self.treemap_state.selected_index)
+                .copied(),
             ViewMode::Tree => {
                 let nodes = self.visible_nodes();
                 nodes.get(self.selected_index).copied()
             }
             ViewMode::LargeFiles => self
                 .computed_views
-                .large_files
-                .get(self.large_files_state.selected_index)
+                .large_file_at(self.large_files_state.selected_index)
                 .map(|e| e.node_id),
             ViewMode::BuildArtifacts => self
                 .computed_views
                 .build_artifacts
                 .get(self.build_artifacts_state.selected_index)
                 .map(|e| e.node_id),
+            ViewMode::EmptyDirs => self
+                .computed_views
+                .empty_dirs
+                .get(self.empty_dirs_state.selected_index)
+                .map(|e| e.node_id),
+            ViewMode::Changes => self
+                .computed_views
+                .changes
+                .get(self.changes_state.selected_index)
+                .and_then(|e| e.node_id),
+            ViewMode::Overview => self
+                .overview_nodes()
+                .get(self.overview_state.selected_index)
+                .copied(),
+            ViewMode::Symlinks => self
+                .computed_views
+                .symlinks
+                .get(self.symlinks_state.selected_index)
+                .map(|e| e.node_id),
+        }
+    }
+
+    /// Top-level entries of the scan root, largest first, for the Overview.
+    /// Only hidden files are filtered; the Tree view's threshold and kind
+    /// filter don't apply.
+    pub fn overview_nodes(&self) -> Vec<NodeId> {
+        let filter = NodeFilter {
+            show_hidden: self.show_hidden,
+            ..NodeFilter::default()
+        };
+        let Some(tree) = &self.tree else {
+            return Vec::new();
+        };
+        let metric = self.bar_metric;
+        tree.children_ranked(NodeId::ROOT, &|n| metric.of(n))
+            .into_iter()
+            .filter(|&id| tree.get(id).is_some_and(|n| filter.keep(n)))
+            .collect()
+    }
+
+    /// Leave the Overview for the Tree view: drilled into the selected
+    /// directory, or with the selected file under the cursor
+    pub fn open_overview_entry(&mut self) {
+        let Some(id) = self.selected_node() else {
+            return;
+        };
+        let is_dir = self
+            .tree
+            .as_ref()
+            .and_then(|t| t.get(id))
+            .is_some_and(|n| n.kind.is_directory() && n.has_children());
+        if is_dir && self.view_root != id {
+            self.view_mode = ViewMode::Tree;
+            self.show_treemap = false;
+            self.navigate_to(id);
+        } else {
+            self.reveal_node(id);
         }
     }
 
     /// Get total item count for current view
     fn current_item_count(&self) -> usize {
         match self.view_mode {
+            ViewMode::Tree if self.show_treemap => self.treemap_nodes().len(),
             ViewMode::Tree => self.visible_nodes().len(),
-            ViewMode::LargeFiles => self.computed_views.large_files.len(),
+            ViewMode::LargeFiles => self.computed_views.large_file_rows.len(),
             ViewMode::BuildArtifacts => self.computed_views.build_artifacts.len(),
+            ViewMode::EmptyDirs => self.computed_views.empty_dirs.len(),
+            ViewMode::Changes => self.computed_views.changes.len(),
+            ViewMode::Overview => self.overview_nodes().len(),
+            ViewMode::Symlinks => self.computed_views.symlinks.len(),
         }
     }
 
     /// Get mutable references to the active selection state
     fn active_selection_mut(&mut self) -> (&mut usize, &mut usize) {
         match self.view_mode {
+            ViewMode::Tree if self.show_treemap => (
+                &mut self.treemap_state.selected_index,
+                &mut self.treemap_state.scroll_offset,
+            ),
             ViewMode::Tree => (&mut self.selected_index, &mut self.scroll_offset),
             ViewMode::LargeFiles => (
                 &mut self.large_files_state.selected_index,
@@ -222,6 +898,22 @@ impl AppState {
                 &mut self.build_artifacts_state.selected_index,
                 &mut self.build_artifacts_state.scroll_offset,
             ),
+            ViewMode::EmptyDirs => (
+                &mut self.empty_dirs_state.selected_index,
+                &mut self.empty_dirs_state.scroll_offset,
+            ),
+            ViewMode::Changes => (
+                &mut self.changes_state.selected_index,
+                &mut self.changes_state.scroll_offset,
+            ),
+            ViewMode::Overview => (
+                &mut self.overview_state.selected_index,
+                &mut self.overview_state.scroll_offset,
+            ),
+            ViewMode::Symlinks => (
+                &mut self.symlinks_state.selected_index,
+                &mut self.symlinks_state.scroll_offset,
+            ),
         }
     }
 
@@ -291,6 +983,70 @@ impl AppState {
         Self::ensure_visible_for(sel, scroll, vh);
     }
 
+    /// Select the item under a clicked cell; a second click drills down
+    pub fn click_at(&mut self, column: u16, row: u16) {
+        if self.treemap_active() {
+            self.click_treemap(column, row);
+            return;
+        }
+
+        // Build Artifacts has a subtitle line above its list
+        let list_top = match self.view_mode {
+            ViewMode::BuildArtifacts | ViewMode::Changes => self.content_area.y + 1,
+            _ => self.content_area.y,
+        };
+        if row < list_top {
+            return;
+        }
+        let offset = (row - list_top) as usize;
+        if offset >= self.visible_height {
+            return;
+        }
+
+        let count = self.current_item_count();
+        let (sel, scroll) = self.active_selection_mut();
+        let index = *scroll + offset;
+        if index >= count {
+            return;
+        }
+        *sel = index;
+
+        let now = Instant::now();
+        let is_double = matches!(
+            self.last_click,
+            Some((at, idx)) if idx == index && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+        );
+        if is_double {
+            self.last_click = None;
+            match self.view_mode {
+                ViewMode::Tree => self.drill_down(),
+                ViewMode::Overview => self.open_overview_entry(),
+                _ => {}
+            }
+        } else {
+            self.last_click = Some((now, index));
+        }
+    }
+
+    /// Clicking a treemap block drills into it
+    fn click_treemap(&mut self, column: u16, row: u16) {
+        let nodes = self.treemap_nodes();
+        let sizes: Vec<u64> = match &self.tree {
+            Some(tree) => nodes
+                .iter()
+                .filter_map(|&id| tree.get(id).map(|n| n.size))
+                .collect(),
+            None => return,
+        };
+        let hit = squarify(&sizes, self.content_area).iter().position(|r| {
+            column >= r.x && column < r.x + r.width && row >= r.y && row < r.y + r.height
+        });
+        if let Some(index) = hit {
+            self.treemap_state.selected_index = index;
+            self.drill_down();
+        }
+    }
+
     /// Toggle expand/collapse for selected node
     pub fn toggle_selected(&mut self) {
         if let Some(node_id) = self.selected_node()
@@ -300,6 +1056,41 @@ impl AppState {
         }
     }
 
+    /// Expand the selected directory and everything below it
+    pub fn expand_all_selected(&mut self) {
+        if let Some(node_id) = self.selected_node()
+            && let Some(tree) = &mut self.tree
+        {
+            tree.set_expanded_recursive(node_id, true);
+        }
+    }
+
+    /// Collapse every directory back to the view root's children, keeping
+    /// the cursor on the top-level entry that held it
+    pub fn collapse_all(&mut self) {
+        let selected = self.selected_node();
+        let view_root = self.view_root;
+        let Some(tree) = &mut self.tree else {
+            return;
+        };
+        tree.collapse_all(view_root);
+
+        // The ancestor directly under the view root stays visible
+        let path = selected.map(|id| tree.path_to_node(id)).unwrap_or_default();
+        let top = path
+            .iter()
+            .position(|&id| id == view_root)
+            .and_then(|i| path.get(i + 1).copied());
+
+        let nodes = self.visible_nodes();
+        self.selected_index = top
+            .and_then(|id| nodes.iter().position(|&n| n == id))
+            .unwrap_or(0);
+        let scroll = &mut self.scroll_offset;
+        let sel = &mut self.selected_index;
+        Self::ensure_visible_for(sel, scroll, self.visible_height);
+    }
+
     /// Expand selected node
     pub fn expand_selected(&mut self) {
         if let Some(node_id) = self.selected_node()
@@ -311,6 +1102,7 @@ impl AppState {
 
     /// Collapse selected node
     pub fn collapse_selected(&mut self) {
+        let filter = self.node_filter();
         if let Some(node_id) = self.selected_node()
             && let Some(tree) = &mut self.tree
         {
@@ -322,7 +1114,9 @@ impl AppState {
                     // If already collapsed, go to parent
                     tree.set_expanded(parent, false);
                     // Find parent's index in visible list
-                    let nodes = tree.visible_nodes(self.view_root);
+                    let tree = &*tree;
+                    let nodes =
+                        tree.visible_nodes_filtered(self.view_root, &|n| filter.keep_in(tree, n));
                     if let Some(idx) = nodes.iter().position(|&id| id == parent) {
                         self.selected_index = idx;
                         let scroll = &mut self.scroll_offset;
@@ -342,19 +1136,134 @@ impl AppState {
             && node.kind.is_directory()
             && node.has_children()
         {
-            self.history.push(self.view_root);
-            self.view_root = node_id;
-            self.selected_index = 0;
-            self.scroll_offset = 0;
+            self.navigate_to(node_id);
         }
     }
 
     /// Go back to previous view
     pub fn go_back(&mut self) {
         if let Some(prev_root) = self.history.pop() {
-            self.view_root = prev_root;
-            self.selected_index = 0;
-            self.scroll_offset = 0;
+            self.forward.push(self.view_root);
+            self.set_view_root(prev_root, true);
+        }
+    }
+
+    /// Return to the view last left with `go_back`. Roots deleted since are
+    /// skipped.
+    pub fn go_forward(&mut self) {
+        while let Some(next_root) = self.forward.pop() {
+            if self
+                .tree
+                .as_ref()
+                .is_some_and(|t| t.get(next_root).is_some())
+            {
+                self.history.push(self.view_root);
+                self.set_view_root(next_root, true);
+                return;
+            }
+        }
+    }
+
+    /// Show the ancestors of the view root, with the parent selected
+    pub fn show_breadcrumbs(&mut self) {
+        let Some(tree) = &self.tree else {
+            return;
+        };
+        let depth = tree.path_to_node(self.view_root).len();
+        self.breadcrumbs_selected = depth.saturating_sub(2);
+        self.mode = AppMode::Breadcrumbs;
+    }
+
+    /// Hide the breadcrumbs overlay
+    pub fn hide_breadcrumbs(&mut self) {
+        self.mode = AppMode::Browsing;
+    }
+
+    /// Move the breadcrumbs selection by `delta` entries
+    pub fn move_breadcrumb(&mut self, delta: isize) {
+        let Some(tree) = &self.tree else {
+            return;
+        };
+        let max = tree.path_to_node(self.view_root).len().saturating_sub(1);
+        self.breadcrumbs_selected = self
+            .breadcrumbs_selected
+            .saturating_add_signed(delta)
+            .min(max);
+    }
+
+    /// Make the selected ancestor the view root. Going back returns to
+    /// where the jump was made from.
+    pub fn open_selected_breadcrumb(&mut self) {
+        self.mode = AppMode::Browsing;
+        let Some(target) = self.tree.as_ref().and_then(|tree| {
+            let path = tree.path_to_node(self.view_root);
+            path.get(self.breadcrumbs_selected).copied()
+        }) else {
+            return;
+        };
+        if target != self.view_root {
+            self.navigate_to(target);
+        }
+    }
+
+    /// Open `id` as a new view root, recording the current one for going
+    /// back and dropping the forward history
+    fn navigate_to(&mut self, id: NodeId) {
+        self.history.push(self.view_root);
+        self.forward.clear();
+        self.set_view_root(id, false);
+    }
+
+    /// Switch the view root, remembering where the cursor was in the old
+    /// one. With `restore`, the cursor returns to where it was in the new
+    /// root; otherwise it starts at the top.
+    fn set_view_root(&mut self, id: NodeId, restore: bool) {
+        self.view_positions
+            .insert(self.view_root, (self.selected_index, self.scroll_offset));
+        let (selected, scroll) = match self.view_positions.get(&id) {
+            Some(&position) if restore => position,
+            _ => (0, 0),
+        };
+        self.view_root = id;
+        self.treemap_state = ViewState::default();
+        // Entries may have been deleted or filtered out since
+        let last = self.visible_nodes().len().saturating_sub(1);
+        self.selected_index = selected.min(last);
+        self.scroll_offset = scroll.min(self.selected_index);
+    }
+
+    /// Group the Large Files view by parent directory, or list it flat again
+    pub fn toggle_large_files_grouping(&mut self) {
+        self.computed_views.toggle_large_files_grouping();
+        self.large_files_state = ViewState::default();
+    }
+
+    /// Fold (`Some(true)`), unfold (`Some(false)`) or toggle (`None`) the
+    /// Large Files group under the cursor. Folding moves the cursor to the
+    /// header, so folding from one of its files doesn't lose the place.
+    pub fn fold_large_files_group(&mut self, collapse: Option<bool>) {
+        let views = &self.computed_views;
+        if !views.large_files_grouped {
+            return;
+        }
+        let row = self.large_files_state.selected_index;
+        let Some(dir) = views.large_file_group_at(row).map(str::to_string) else {
+            return;
+        };
+        let collapsed = matches!(
+            views
+                .large_file_rows
+                .get(views.large_file_group_row(&dir).unwrap_or(row)),
+            Some(LargeFileRow::Group {
+                collapsed: true,
+                ..
+            })
+        );
+        let collapse = collapse.unwrap_or(!collapsed);
+        self.computed_views.set_group_collapsed(&dir, collapse);
+        if collapse && let Some(header) = self.computed_views.large_file_group_row(&dir) {
+            self.large_files_state.selected_index = header;
+            self.large_files_state.scroll_offset = self.large_files_state.scroll_offset.min(header);
         }
     }
 
@@ -363,40 +1272,68 @@ impl AppState {
         self.view_mode = match self.view_mode {
             ViewMode::Tree => ViewMode::LargeFiles,
             ViewMode::LargeFiles => ViewMode::BuildArtifacts,
-            ViewMode::BuildArtifacts => ViewMode::Tree,
+            ViewMode::BuildArtifacts => ViewMode::EmptyDirs,
+            ViewMode::EmptyDirs => ViewMode::Changes,
+            ViewMode::Changes => ViewMode::Overview,
+            ViewMode::Overview => ViewMode::Symlinks,
+            ViewMode::Symlinks => ViewMode::Tree,
         };
-        self.selected_nodes.clear();
-        self.selecting_mode = false;
         self.ensure_views_computed();
     }
 
     /// Switch to previous view mode
     pub fn prev_view(&mut self) {
         self.view_mode = match self.view_mode {
-            ViewMode::Tree => ViewMode::BuildArtifacts,
+            ViewMode::Symlinks => ViewMode::Overview,
+            ViewMode::Overview => ViewMode::Changes,
+            ViewMode::Tree => ViewMode::Symlinks,
             ViewMode::LargeFiles => ViewMode::Tree,
             ViewMode::BuildArtifacts => ViewMode::LargeFiles,
+            ViewMode::EmptyDirs => ViewMode::BuildArtifacts,
+            ViewMode::Changes => ViewMode::EmptyDirs,
         };
-        self.selected_nodes.clear();
-        self.selecting_mode = false;
         self.ensure_views_computed();
     }
 
-    /// Ensure computed views are up to date, clamp selections
+    /// Ensure the list the current view shows is up to date with the tree,
+    /// and clamp its selection if it was rebuilt. Lists of other views wait
+    /// until they're switched to.
     pub fn ensure_views_computed(&mut self) {
-        if self.computed_views.dirty {
-            if let Some(tree) = &self.tree {
-                self.computed_views.rebuild(tree);
-            }
-            // Clamp selection indices
-            let lf_count = self.computed_views.large_files.len();
-            if self.large_files_state.selected_index >= lf_count {
-                self.large_files_state.selected_index = lf_count.saturating_sub(1);
-            }
-            let ba_count = self.computed_views.build_artifacts.len();
-            if self.build_artifacts_state.selected_index >= ba_count {
-                self.build_artifacts_state.selected_index = ba_count.saturating_sub(1);
-            }
+        let data = match self.view_mode {
+            ViewMode::Tree if self.recent_window != RecentWindow::Off => ViewData::NewestMtimes,
+            ViewMode::Tree | ViewMode::Overview => return,
+            ViewMode::LargeFiles => ViewData::LargeFiles,
+            ViewMode::BuildArtifacts => ViewData::BuildArtifacts,
+            ViewMode::EmptyDirs => ViewData::EmptyDirs,
+            ViewMode::Changes => ViewData::Changes,
+            ViewMode::Symlinks => ViewData::Symlinks,
+        };
+        let filter = self.node_filter();
+        let Some(tree) = &self.tree else {
+            return;
+        };
+        if !self.computed_views.ensure(tree, filter, data) {
+            return;
+        }
+        let (count, state) = match data {
+            ViewData::LargeFiles => (
+                self.computed_views.large_file_rows.len(),
+                &mut self.large_files_state,
+            ),
+            ViewData::BuildArtifacts => (
+                self.computed_views.build_artifacts.len(),
+                &mut self.build_artifacts_state,
+            ),
+            ViewData::EmptyDirs => (
+                self.computed_views.empty_dirs.len(),
+                &mut self.empty_dirs_state,
+            ),
+            ViewData::Changes => (self.computed_views.changes.len(), &mut self.changes_state),
+            ViewData::Symlinks => (self.computed_views.symlinks.len(), &mut self.symlinks_state),
+            ViewData::NewestMtimes => return,
+        };
+        if state.selected_index >= count {
+            state.selected_index = count.saturating_sub(1);
         }
     }
 
@@ -410,64 +1347,612 @@ impl AppState {
         self.mode = AppMode::Browsing;
     }
 
-    /// Request quit
-    pub fn quit(&mut self) {
-        self.should_quit = true;
+    /// Record a path the scanner could not read
+    pub fn record_scan_error(&mut self, path: PathBuf, reason: String) {
+        self.scan_errors.push((path, reason));
     }
 
-    /// Set error message
-    pub fn set_error(&mut self, message: String) {
-        self.error_message = Some(message);
+    /// Show scan errors overlay
+    pub fn show_errors(&mut self) {
+        self.errors_scroll = 0;
+        self.mode = AppMode::Errors;
     }
 
-    /// Clear error message
-    #[allow(dead_code)]
-    pub fn clear_error(&mut self) {
-        self.error_message = None;
+    /// Hide scan errors overlay
+    pub fn hide_errors(&mut self) {
+        self.mode = AppMode::Browsing;
     }
 
-    /// Open selected item in Finder (macOS)
-    #[cfg(target_os = "macos")]
-    pub fn open_in_finder(&self) {
+    /// Scroll the errors overlay by `delta` entries
+    pub fn scroll_errors(&mut self, delta: isize) {
+        let max = self.scan_errors.len().saturating_sub(self.errors_visible);
+        self.errors_scroll = self.errors_scroll.saturating_add_signed(delta).min(max);
+    }
+
+    /// Show details panel for the selected item
+    pub fn show_details(&mut self) {
         if let Some(node_id) = self.selected_node()
             && let Some(tree) = &self.tree
-            && let Some(node) = tree.get(node_id)
+            && let Some(details) = NodeDetails::from_node(tree, node_id)
         {
-            std::process::Command::new("open")
-                .arg("-R") // Reveal in Finder
-                .arg(&node.path)
-                .spawn()
-                .ok();
+            self.details = Some(details);
+            self.mode = AppMode::Details;
         }
     }
 
-    /// Open selected item in Finder (no-op on non-macOS)
-    #[cfg(not(target_os = "macos"))]
-    pub fn open_in_finder(&self) {
-        // No-op on non-macOS platforms
+    /// Hide details panel
+    pub fn hide_details(&mut self) {
+        self.details = None;
+        self.mode = AppMode::Browsing;
     }
 
-    /// Request delete - shows confirmation dialog (single or multi)
-    pub fn request_delete(&mut self) {
-        // Guard: reject if a delete is already in progress
-        if self.delete_receiver.is_some() || self.multi_delete_progress.is_some() {
+    /// Show which file types take up the selected directory
+    pub fn show_file_types(&mut self) {
+        let Some(node_id) = self.selected_node() else {
             return;
+        };
+        match self
+            .tree
+            .as_ref()
+            .and_then(|tree| FileTypes::from_node(tree, node_id))
+        {
+            Some(types) => {
+                self.file_types = Some(types);
+                self.mode = AppMode::FileTypes;
+            }
+            None => self.set_status("File types are shown for directories".to_string()),
         }
+    }
 
-        if !self.selected_nodes.is_empty() {
+    /// Hide the file-types overlay
+    pub fn hide_file_types(&mut self) {
+        self.file_types = None;
+        self.mode = AppMode::Browsing;
+    }
+
+    /// Open the jump-to-path prompt
+    pub fn start_goto(&mut self) {
+        if self.tree.is_some() {
+            self.goto_input.clear();
+            self.mode = AppMode::GoTo;
+        }
+    }
+
+    /// Append a character to the jump-to-path prompt
+    pub fn goto_push(&mut self, c: char) {
+        self.goto_input.push(c);
+    }
+
+    /// Remove the last character from the jump-to-path prompt
+    pub fn goto_backspace(&mut self) {
+        self.goto_input.pop();
+    }
+
+    /// Close the jump-to-path prompt without moving
+    pub fn cancel_goto(&mut self) {
+        self.goto_input.clear();
+        self.mode = AppMode::Browsing;
+    }
+
+    /// Jump to the typed path, or report it in the footer if it isn't in the tree
+    pub fn confirm_goto(&mut self) {
+        self.mode = AppMode::Browsing;
+        let input = std::mem::take(&mut self.goto_input);
+        let Some(tree) = &self.tree else {
+            return;
+        };
+        match resolve_path(tree, &input) {
+            Some(id) => self.reveal_node(id),
+            None => self.set_error(format!("Not in scanned tree: {}", input.trim())),
+        }
+    }
+
+    /// Open the Large Files filter prompt, starting from the current filter
+    pub fn start_file_filter(&mut self) {
+        if self.tree.is_some() && self.view_mode == ViewMode::LargeFiles {
+            self.mode = AppMode::FileFilter;
+        }
+    }
+
+    /// Append a character to the Large Files filter, narrowing the list as
+    /// it is typed
+    pub fn file_filter_push(&mut self, c: char) {
+        let mut pattern = self.computed_views.large_files_filter.clone();
+        pattern.push(c);
+        self.set_file_filter(&pattern);
+    }
+
+    /// Remove the last character from the Large Files filter
+    pub fn file_filter_backspace(&mut self) {
+        let mut pattern = self.computed_views.large_files_filter.clone();
+        pattern.pop();
+        self.set_file_filter(&pattern);
+    }
+
+    /// Close the Large Files filter prompt, keeping the filter
+    pub fn confirm_file_filter(&mut self) {
+        self.mode = AppMode::Browsing;
+    }
+
+    /// Close the Large Files filter prompt and list every file again
+    pub fn clear_file_filter(&mut self) {
+        self.mode = AppMode::Browsing;
+        self.set_file_filter("");
+    }
+
+    fn set_file_filter(&mut self, pattern: &str) {
+        self.computed_views.set_large_files_filter(pattern);
+        self.large_files_state = ViewState::default();
+        self.selected_nodes.clear();
+    }
+
+    /// Bookmark the directory being viewed, or forget it if already bookmarked
+    pub fn toggle_bookmark(&mut self) {
+        let Some(path) = self
+            .tree
+            .as_ref()
+            .and_then(|t| t.get(self.view_root))
+            .map(|n| n.path.clone())
+        else {
+            return;
+        };
+        let message = if self.bookmarks.contains(&path) {
+            self.bookmarks.remove(&path);
+            format!("Removed bookmark {}", path.display())
+        } else {
+            let message = format!("Bookmarked {}", path.display());
+            self.bookmarks.add(path);
+            message
+        };
+        self.bookmarks.save();
+        self.set_status(message);
+    }
+
+    /// Show the bookmarks overlay, starting on the current view root if saved
+    pub fn show_bookmarks(&mut self) {
+        let current = self
+            .tree
+            .as_ref()
+            .and_then(|t| t.get(self.view_root))
+            .map(|n| n.path.as_path());
+        self.bookmarks_selected = current
+            .and_then(|p| self.bookmarks.list().iter().position(|b| b == p))
+            .unwrap_or(0);
+        self.mode = AppMode::Bookmarks;
+    }
+
+    /// Hide the bookmarks overlay
+    pub fn hide_bookmarks(&mut self) {
+        self.mode = AppMode::Browsing;
+    }
+
+    /// Move the bookmark selection by `delta` entries
+    pub fn move_bookmark(&mut self, delta: isize) {
+        let max = self.bookmarks.list().len().saturating_sub(1);
+        self.bookmarks_selected = self
+            .bookmarks_selected
+            .saturating_add_signed(delta)
+            .min(max);
+    }
+
+    /// Forget the selected bookmark
+    pub fn remove_selected_bookmark(&mut self) {
+        let Some(path) = self.bookmarks.list().get(self.bookmarks_selected).cloned() else {
+            return;
+        };
+        self.bookmarks.remove(&path);
+        self.bookmarks.save();
+        self.move_bookmark(0);
+    }
+
+    /// Drill into the selected bookmark when it is in this tree. Otherwise
+    /// quit so that `main` can start a new scan there.
+    pub fn open_selected_bookmark(&mut self) {
+        let Some(path) = self.bookmarks.list().get(self.bookmarks_selected).cloned() else {
+            return;
+        };
+        self.mode = AppMode::Browsing;
+
+        let found = self.tree.as_ref().and_then(|t| {
+            t.find_by_path(&path)
+                .filter(|&id| t.get(id).is_some_and(|n| n.kind.is_directory()))
+        });
+        if let Some(id) = found {
+            self.view_mode = ViewMode::Tree;
+            self.selected_nodes.clear();
+            self.selecting_mode = false;
+            if id != self.view_root {
+                self.navigate_to(id);
+            }
+        } else if path.is_dir() {
+            self.relaunch_path = Some(path);
+            self.quit();
+        } else {
+            self.set_error(format!("Bookmark no longer exists: {}", path.display()));
+        }
+    }
+
+    /// Drop the selected directory from the tree and add it to the
+    /// excludes, so rescans skip it too. Undone from the excludes overlay.
+    pub fn exclude_selected(&mut self) {
+        let Some((id, path)) = self.selected_node().and_then(|id| {
+            let node = self.tree.as_ref()?.get(id)?;
+            (id != NodeId::ROOT && node.kind.is_directory()).then(|| (id, node.path.clone()))
+        }) else {
+            return;
+        };
+
+        if let Some(tree) = &mut self.tree {
+            tree.remove_node(id);
+            self.tree_modified = true;
+            self.computed_views.invalidate();
+            // A Build Artifacts entry can hold the directory being viewed
+            if tree.get(self.view_root).is_none() {
+                self.history.clear();
+                self.forward.clear();
+                self.set_view_root(NodeId::ROOT, false);
+            }
+        }
+        self.selected_nodes.remove(&id);
+        self.compact_tree_if_needed();
+        self.adjust_selection_after_delete();
+        self.set_status(format!("Excluded {} (E to undo)", path.display()));
+        self.excludes.push(path);
+    }
+
+    /// Show the excludes overlay
+    pub fn show_excludes(&mut self) {
+        self.excludes_selected = self.excludes.len().saturating_sub(1);
+        self.mode = AppMode::Excludes;
+    }
+
+    /// Hide the excludes overlay
+    pub fn hide_excludes(&mut self) {
+        self.mode = AppMode::Browsing;
+    }
+
+    /// Move the excludes selection by `delta` entries
+    pub fn move_exclude(&mut self, delta: isize) {
+        let max = self.excludes.len().saturating_sub(1);
+        self.excludes_selected = self.excludes_selected.saturating_add_signed(delta).min(max);
+    }
+
+    /// Stop excluding the selected entry and rescan so it comes back
+    pub fn remove_selected_exclude(&mut self) {
+        if self.excludes_selected < self.excludes.len() {
+            self.excludes.remove(self.excludes_selected);
+            self.request_rescan();
+        }
+    }
+
+    /// Scan the same root again, ignoring the cache. The session restarts the
+    /// way opening a bookmark does; the view is restored from its snapshot.
+    pub fn request_rescan(&mut self) {
+        if self.tree.is_some() {
+            self.relaunch_path = Some(self.root_path.clone());
+            self.rescan = true;
+            self.quit();
+        }
+    }
+
+    /// Select the entry at `path` in the Tree view, expanding down to it
+    pub fn reveal_path(&mut self, path: &Path) {
+        match self.tree.as_ref().and_then(|t| t.find_by_path(path)) {
+            Some(id) => self.reveal_node(id),
+            None => self.set_error(format!("Not in scanned tree: {}", path.display())),
+        }
+    }
+
+    /// Select a node in the tree list, expanding its ancestors and leaving
+    /// the drilled-down view when the node lies outside it
+    fn reveal_node(&mut self, id: NodeId) {
+        if self.view_mode != ViewMode::Tree {
+            self.view_mode = ViewMode::Tree;
+            self.selected_nodes.clear();
+            self.selecting_mode = false;
+        }
+        self.show_treemap = false;
+
+        let Some(tree) = &mut self.tree else {
+            return;
+        };
+        if !tree.path_to_node(id).contains(&self.view_root) {
+            self.history.push(self.view_root);
+            self.forward.clear();
+            self.view_root = NodeId::ROOT;
+        }
+        if let Some(parent) = tree.get(id).and_then(|n| n.parent) {
+            tree.expand_to(parent);
+        }
+
+        match self.visible_nodes().iter().position(|&n| n == id) {
+            Some(idx) => {
+                self.selected_index = idx;
+                let scroll = &mut self.scroll_offset;
+                let sel = &mut self.selected_index;
+                Self::ensure_visible_for(sel, scroll, self.visible_height);
+            }
+            None => self.set_error("Path is hidden by the current filter".to_string()),
+        }
+    }
+
+    /// Write the active view to a timestamped CSV file in the working directory
+    pub fn export_current_view(&mut self) {
+        let Some(tree) = &self.tree else {
+            return;
+        };
+        let rows = match self.view_mode {
+            ViewMode::Tree => {
+                let nodes = if self.show_treemap {
+                    self.treemap_nodes()
+                } else {
+                    self.visible_nodes()
+                };
+                let base = tree.get(self.view_root).map_or(0, |n| n.size);
+                csv::tree_rows(tree, &nodes, base)
+            }
+            ViewMode::LargeFiles => csv::large_file_rows(tree, &self.computed_views),
+            ViewMode::BuildArtifacts => csv::artifact_rows(tree, &self.computed_views),
+            ViewMode::EmptyDirs => csv::empty_dir_rows(tree, &self.computed_views),
+            ViewMode::Changes => csv::change_rows(tree, &self.computed_views),
+            ViewMode::Symlinks => csv::symlink_rows(tree, &self.computed_views),
+            ViewMode::Overview => {
+                let base = tree.get(NodeId::ROOT).map_or(0, |n| n.size);
+                csv::tree_rows(tree, &self.overview_nodes(), base)
+            }
+        };
+
+        let file_name = format!("dux-export-{}.csv", Local::now().format("%Y%m%d-%H%M%S"));
+        let written = File::create(&file_name)
+            .and_then(|file| csv::write_csv(BufWriter::new(file), &rows, self.size_format));
+        match written {
+            Ok(()) => self.set_status(format!("Exported {} rows to {}", rows.len(), file_name)),
+            Err(e) => self.set_error(format!("Export failed: {}", e)),
+        }
+    }
+
+    /// Write the selection to a timestamped file in the working directory,
+    /// without entries already covered by a selected directory: one path
+    /// per line, or with `script` an `rm -rf` script to review and run
+    pub fn export_selection(&mut self, script: bool) {
+        let Some(tree) = &self.tree else {
+            return;
+        };
+        if self.selected_nodes.is_empty() {
+            self.set_status("Nothing selected to export".to_string());
+            return;
+        }
+        let mut nodes: Vec<&TreeNode> = dedup_ancestors(tree, &self.selected_nodes)
+            .into_iter()
+            .filter_map(|id| tree.get(id))
+            .collect();
+        nodes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let stamp = Local::now().format("%Y%m%d-%H%M%S");
+        let (file_name, contents, count) = if script {
+            let (contents, count) = selection::rm_script(
+                nodes.iter().map(|n| (n.path.as_path(), n.size)),
+                self.size_format,
+            );
+            (format!("dux-selection-{}.sh", stamp), contents, count)
+        } else {
+            (
+                format!("dux-selection-{}.txt", stamp),
+                selection::path_list(nodes.iter().map(|n| n.path.as_path())),
+                nodes.len(),
+            )
+        };
+        match std::fs::write(&file_name, contents) {
+            Ok(()) => self.set_status(format!("Wrote {} paths to {}", count, file_name)),
+            Err(e) => self.set_error(format!("Export failed: {}", e)),
+        }
+    }
+
+    /// Pause or resume the scan (only while it is walking)
+    pub fn toggle_scan_pause(&mut self) {
+        if self.mode == AppMode::Scanning {
+            self.scan_paused = !self.scan_paused;
+        }
+    }
+
+    /// Request quit
+    pub fn quit(&mut self) {
+        self.should_quit = true;
+    }
+
+    /// Set error message
+    pub fn set_error(&mut self, message: String) {
+        self.error_message = Some(message);
+        self.message_set_at = Some(Instant::now());
+    }
+
+    /// Set a transient status message
+    pub fn set_status(&mut self, message: String) {
+        self.error_message = None;
+        self.status_message = Some(message);
+        self.message_set_at = Some(Instant::now());
+    }
+
+    /// Clear error message
+    #[allow(dead_code)]
+    pub fn clear_error(&mut self) {
+        self.error_message = None;
+    }
+
+    /// Show the selected item in the platform file manager
+    pub fn reveal_in_file_manager(&self) {
+        if let Some(node_id) = self.selected_node()
+            && let Some(tree) = &self.tree
+            && let Some(node) = tree.get(node_id)
+        {
+            reveal::reveal_in_file_manager(&node.path, node.kind.is_directory());
+        }
+    }
+
+    /// Queue the selected item for the editor if it is a file; false otherwise
+    pub fn request_open_file(&mut self) -> bool {
+        let Some(node) = self
+            .selected_node()
+            .and_then(|id| self.tree.as_ref()?.get(id))
+        else {
+            return false;
+        };
+        if node.kind != NodeKind::File {
+            return false;
+        }
+        self.pending_open = Some(node.path.clone());
+        true
+    }
+
+    /// Copy the selected item's absolute path to the system clipboard
+    pub fn copy_path(&mut self) {
+        let path = match self
+            .selected_node()
+            .and_then(|id| self.tree.as_ref()?.get(id))
+        {
+            Some(node) => node.path.to_string_lossy().to_string(),
+            None => return,
+        };
+
+        let result = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(path));
+        match result {
+            Ok(()) => self.set_status("Copied path".to_string()),
+            Err(e) => self.set_error(format!("Clipboard unavailable: {}", e)),
+        }
+    }
+
+    /// Copy a text report of the view root's largest children; without a
+    /// clipboard it is printed when dux exits
+    pub fn copy_report(&mut self) {
+        let Some(tree) = &self.tree else {
+            return;
+        };
+        let text = report::size_report(
+            tree,
+            self.view_root,
+            self.node_filter(),
+            self.report_limit,
+            self.size_format,
+        );
+        let result =
+            arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text.clone()));
+        match result {
+            Ok(()) => self.set_status("Copied size report".to_string()),
+            Err(_) => {
+                self.deferred_output.push(text);
+                self.set_status(
+                    "Clipboard unavailable; report will be printed on exit".to_string(),
+                );
+            }
+        }
+    }
+
+    /// Copy the view root's children as a Markdown table; without a
+    /// clipboard it is written to a timestamped file in the working directory
+    pub fn copy_markdown(&mut self) {
+        let Some(tree) = &self.tree else {
+            return;
+        };
+        let text =
+            report::markdown_table(tree, self.view_root, self.node_filter(), self.size_format);
+        let result =
+            arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text.clone()));
+        if result.is_ok() {
+            self.set_status("Copied Markdown table".to_string());
+            return;
+        }
+        let file_name = format!("dux-listing-{}.md", Local::now().format("%Y%m%d-%H%M%S"));
+        match std::fs::write(&file_name, text) {
+            Ok(()) => self.set_status(format!("Clipboard unavailable; wrote {}", file_name)),
+            Err(e) => self.set_error(format!("Export failed: {}", e)),
+        }
+    }
+
+    /// Request delete - shows confirmation dialog (single or multi)
+    pub fn request_delete(&mut self) {
+        // Guard: reject if a delete is already in progress
+        if self.delete_receiver.is_some()
+            || self.multi_delete_progress.is_some()
+            || self.refuse_read_only()
+        {
+            return;
+        }
+
+        if !self.selected_nodes.is_empty() {
             self.request_multi_delete();
         } else if let Some(node_id) = self.selected_node()
             && let Some(tree) = &self.tree
             && let Some(node) = tree.get(node_id)
         {
-            self.pending_delete = Some((node_id, node.path.clone()));
+            let (path, size) = (node.path.clone(), node.size);
+            if self.refuse_protected([path.as_path()]) {
+                return;
+            }
+            self.confirm_phrase = guard::single_phrase(&path, size, self.typed_confirm_size);
+            self.confirm_input.clear();
+            self.pending_delete = Some((node_id, path));
             self.mode = AppMode::ConfirmDelete;
         }
     }
 
+    /// Refuse to go on in a read-only session, with a note in the footer
+    fn refuse_read_only(&mut self) -> bool {
+        if self.read_only {
+            self.set_status("Read-only session: deleting is turned off".to_string());
+        }
+        self.read_only
+    }
+
+    /// Refuse to go on when any of `paths` is the scan root or a protected
+    /// system or home directory, with an error naming it
+    fn refuse_protected<'a>(&mut self, paths: impl IntoIterator<Item = &'a Path>) -> bool {
+        let root = self.tree.as_ref().map(|t| t.root_path().to_path_buf());
+        let protected = paths
+            .into_iter()
+            .find(|path| root.as_deref() == Some(*path) || guard::is_protected_path(path));
+        match protected {
+            Some(path) => {
+                self.set_error(format!("Refusing to delete {}", path.display()));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the typed confirmation (if one is required) matches
+    pub fn confirm_input_matches(&self) -> bool {
+        self.confirm_phrase
+            .as_deref()
+            .is_none_or(|phrase| self.confirm_input == phrase)
+    }
+
+    /// Type into the delete confirmation
+    pub fn confirm_push(&mut self, c: char) {
+        self.confirm_input.push(c);
+    }
+
+    /// Remove the last typed confirmation character
+    pub fn confirm_backspace(&mut self) {
+        self.confirm_input.pop();
+    }
+
     /// Confirm and start async delete operation
     pub fn confirm_delete(&mut self) {
+        if !self.confirm_input_matches() {
+            return;
+        }
+        if self.refuse_read_only() {
+            self.pending_delete = None;
+            self.mode = AppMode::Browsing;
+            return;
+        }
+        self.confirm_phrase = None;
+        self.confirm_input.clear();
         if let Some((node_id, path)) = self.pending_delete.take() {
+            if self.refuse_protected([path.as_path()]) {
+                self.mode = AppMode::Browsing;
+                return;
+            }
             // Get size before deletion
             let size = self
                 .tree
@@ -480,10 +1965,11 @@ impl AppState {
             if let Some(tree) = &mut self.tree {
                 tree.remove_node(node_id);
                 self.tree_modified = true;
-                self.computed_views.dirty = true;
+                self.computed_views.invalidate();
             }
             // Remove from selection if present
             self.selected_nodes.remove(&node_id);
+            self.compact_tree_if_needed();
             self.adjust_selection_after_delete();
 
             // Spawn background deletion
@@ -493,7 +1979,7 @@ impl AppState {
             // Return to browsing immediately - deletion happens in background
             self.mode = AppMode::Browsing;
 
-            std::thread::spawn(move || {
+            self.delete_handle = Some(std::thread::spawn(move || {
                 let result = if path.is_dir() {
                     std::fs::remove_dir_all(&path)
                 } else {
@@ -502,39 +1988,188 @@ impl AppState {
 
                 match result {
                     Ok(()) => {
-                        let _ = tx.send(Ok((node_id, size)));
+                        let _ = tx.send(Ok((path, size)));
                     }
                     Err(e) => {
                         let _ = tx.send(Err(format!("Delete failed: {}", e)));
                     }
                 }
-            });
+            }));
         }
     }
 
-    /// Check if async delete completed and handle result
-    pub fn poll_delete(&mut self) {
-        if let Some(rx) = &self.delete_receiver
-            && let Ok(result) = rx.try_recv()
-        {
+    /// Pick up results of background work: deletions, `--always-rescan`
+    /// refreshes and watch rescans. Returns whether anything changed, so
+    /// the frame is redrawn even when the loop would otherwise sit idle.
+    pub fn poll_background(&mut self) -> bool {
+        let deleted = self.poll_delete() | self.poll_multi_delete();
+        if deleted {
+            self.refresh_disk_capacity();
+        }
+        deleted | self.poll_refresh() | self.poll_watch()
+    }
+
+    /// Query the scanned filesystem's size again, once deletions freed space
+    fn refresh_disk_capacity(&mut self) {
+        if let Some(capacity) = dux_core::fs_capacity(&self.root_path) {
+            self.disk_capacity = Some(capacity);
+        }
+    }
+
+    /// Check if async delete completed and handle result. Returns whether
+    /// it did.
+    pub fn poll_delete(&mut self) -> bool {
+        let Some(rx) = &self.delete_receiver else {
+            return false;
+        };
+        if let Ok(result) = rx.try_recv() {
             match result {
-                Ok((_node_id, size)) => {
+                Ok((path, size)) => {
                     self.session_stats.bytes_freed += size;
                     self.session_stats.items_deleted += 1;
+                    if let Some(log) = &self.audit_log {
+                        audit::append(log, &DeletionRecord::now(path, size));
+                    }
                 }
                 Err(e) => {
                     // Delete failed - we already removed from tree optimistically
                     // Could restore here but simpler to just show error
-                    self.error_message = Some(e);
+                    self.set_error(e);
                 }
             }
             self.delete_receiver = None;
+            self.delete_handle = None;
             self.mode = AppMode::Browsing;
+            return true;
+        }
+        false
+    }
+
+    /// Whether a delete confirmation is open. Its pending items hold node
+    /// IDs, so swapping subtrees in (which can reuse slots) waits until
+    /// it's answered.
+    fn confirming_delete(&self) -> bool {
+        matches!(
+            self.mode,
+            AppMode::ConfirmDelete | AppMode::ConfirmMultiDelete
+        )
+    }
+
+    /// Apply finished watch rescans, once no delete confirmation is open.
+    /// Returns whether the tree changed.
+    pub fn poll_watch(&mut self) -> bool {
+        if self.confirming_delete() {
+            return false;
+        }
+        let (Some(watch), Some(tree)) = (&mut self.watch, &self.tree) else {
+            return false;
+        };
+        if !tree.root_path().exists() {
+            self.watch = None;
+            self.set_error("Scanned directory was removed; stopped watching".to_string());
+            return true;
         }
+        let Some(results) = watch.poll(tree) else {
+            return false;
+        };
+        for (path, fresh) in results {
+            self.replace_subtree(&path, &fresh);
+        }
+        self.watch_updated = Some(Local::now());
+        true
     }
 
-    /// Adjust selection after a node is deleted
+    /// Swap in the `--always-rescan` directories once their rescan is done
+    /// and no delete confirmation is open. Returns whether the tree changed.
+    pub fn poll_refresh(&mut self) -> bool {
+        if self.confirming_delete() {
+            return false;
+        }
+        let Some(job) = &self.refresh else {
+            return false;
+        };
+        match job.try_recv() {
+            Ok(results) => {
+                self.refresh = None;
+                for (path, fresh) in results {
+                    self.replace_subtree(&path, &fresh);
+                }
+                true
+            }
+            Err(mpsc::TryRecvError::Empty) => false,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.refresh = None;
+                false
+            }
+        }
+    }
+
+    /// Swap in a fresh scan of the directory at `path`. The view root,
+    /// history and marked entries follow their paths; ones that are gone
+    /// fall back to their closest surviving ancestor, or are dropped.
+    fn replace_subtree(&mut self, path: &Path, fresh: &DiskTree) {
+        let Some(tree) = &mut self.tree else {
+            return;
+        };
+        let Some(id) = tree.find_by_path(path) else {
+            return;
+        };
+        let path_of = |id: &NodeId| tree.get(*id).map(|node| node.path.clone());
+        let view_root = path_of(&self.view_root);
+        let history: Vec<_> = self.history.iter().filter_map(path_of).collect();
+        let forward: Vec<_> = self.forward.iter().filter_map(path_of).collect();
+        let marked: Vec<_> = self.selected_nodes.iter().filter_map(path_of).collect();
+
+        tree.replace_subtree(id, fresh);
+
+        let closest = |path: &PathBuf| path.ancestors().find_map(|dir| tree.find_by_path(dir));
+        self.view_root = view_root.as_ref().and_then(closest).unwrap_or(NodeId::ROOT);
+        self.history = history.iter().filter_map(closest).collect();
+        self.forward = forward.iter().filter_map(closest).collect();
+        self.selected_nodes = marked
+            .iter()
+            .filter_map(|path| tree.find_by_path(path))
+            .collect();
+        self.tree_modified = true;
+        self.computed_views.invalidate();
+        self.compact_tree_if_needed();
+        self.adjust_selection_after_delete();
+    }
+
+    /// Reclaim tombstoned slots once deletions pile up, remapping held node IDs
+    fn compact_tree_if_needed(&mut self) {
+        let remap = match &mut self.tree {
+            Some(tree) if tree.needs_compaction() => tree.compact(),
+            _ => return,
+        };
+
+        self.view_root = remap.get(&self.view_root).copied().unwrap_or(NodeId::ROOT);
+        self.history = self
+            .history
+            .iter()
+            .filter_map(|id| remap.get(id).copied())
+            .collect();
+        self.forward = self
+            .forward
+            .iter()
+            .filter_map(|id| remap.get(id).copied())
+            .collect();
+        self.view_positions.clear();
+        self.selected_nodes = self
+            .selected_nodes
+            .iter()
+            .filter_map(|id| remap.get(id).copied())
+            .collect();
+        self.computed_views.invalidate();
+    }
+
+    /// Clamp selections after nodes are deleted or filtered out
     fn adjust_selection_after_delete(&mut self) {
+        let treemap_count = self.treemap_nodes().len();
+        if self.treemap_state.selected_index >= treemap_count {
+            self.treemap_state.selected_index = treemap_count.saturating_sub(1);
+        }
+
         match self.view_mode {
             ViewMode::Tree => {
                 let nodes = self.visible_nodes();
@@ -548,7 +2183,7 @@ impl AppState {
                 }
             }
             ViewMode::LargeFiles => {
-                let count = self.computed_views.large_files.len();
+                let count = self.computed_views.large_file_rows.len();
                 if self.large_files_state.selected_index >= count {
                     self.large_files_state.selected_index = count.saturating_sub(1);
                 }
@@ -559,12 +2194,38 @@ impl AppState {
                     self.build_artifacts_state.selected_index = count.saturating_sub(1);
                 }
             }
+            ViewMode::EmptyDirs => {
+                let count = self.computed_views.empty_dirs.len();
+                if self.empty_dirs_state.selected_index >= count {
+                    self.empty_dirs_state.selected_index = count.saturating_sub(1);
+                }
+            }
+            ViewMode::Changes => {
+                let count = self.computed_views.changes.len();
+                if self.changes_state.selected_index >= count {
+                    self.changes_state.selected_index = count.saturating_sub(1);
+                }
+            }
+            ViewMode::Overview => {
+                let count = self.overview_nodes().len();
+                if self.overview_state.selected_index >= count {
+                    self.overview_state.selected_index = count.saturating_sub(1);
+                }
+            }
+            ViewMode::Symlinks => {
+                let count = self.computed_views.symlinks.len();
+                if self.symlinks_state.selected_index >= count {
+                    self.symlinks_state.selected_index = count.saturating_sub(1);
+                }
+            }
         }
     }
 
     /// Cancel delete operation
     pub fn cancel_delete(&mut self) {
         self.pending_delete = None;
+        self.confirm_phrase = None;
+        self.confirm_input.clear();
         self.mode = AppMode::Browsing;
     }
 
@@ -584,16 +2245,21 @@ impl AppState {
     /// Get the NodeId at a given visible index for the current view
     fn node_at_index(&self, idx: usize) -> Option<NodeId> {
         match self.view_mode {
+            ViewMode::Tree if self.show_treemap => self.treemap_nodes().get(idx).copied(),
             ViewMode::Tree => {
                 let nodes = self.visible_nodes();
                 nodes.get(idx).copied()
             }
-            ViewMode::LargeFiles => self.computed_views.large_files.get(idx).map(|e| e.node_id),
+            ViewMode::LargeFiles => self.computed_views.large_file_at(idx).map(|e| e.node_id),
             ViewMode::BuildArtifacts => self
                 .computed_views
                 .build_artifacts
                 .get(idx)
                 .map(|e| e.node_id),
+            ViewMode::EmptyDirs => self.computed_views.empty_dirs.get(idx).map(|e| e.node_id),
+            ViewMode::Changes => self.computed_views.changes.get(idx).and_then(|e| e.node_id),
+            ViewMode::Overview => self.overview_nodes().get(idx).copied(),
+            ViewMode::Symlinks => self.computed_views.symlinks.get(idx).map(|e| e.node_id),
         }
     }
 
@@ -624,6 +2290,69 @@ impl AppState {
         }
     }
 
+    /// Items that bulk selection applies to in the current view: stale entries
+    /// in Build Artifacts, otherwise every visible row except the view root
+    fn bulk_selectable_nodes(&self) -> Vec<NodeId> {
+        match self.view_mode {
+            ViewMode::Tree if self.show_treemap => self.treemap_nodes(),
+            ViewMode::Tree => self
+                .visible_nodes()
+                .into_iter()
+                .filter(|&id| id != self.view_root)
+                .collect(),
+            ViewMode::LargeFiles => self
+                .computed_views
+                .shown_large_files()
+                .map(|e| e.node_id)
+                .collect(),
+            ViewMode::BuildArtifacts => self
+                .computed_views
+                .build_artifacts
+                .iter()
+                .filter(|e| e.is_stale)
+                .map(|e| e.node_id)
+                .collect(),
+            ViewMode::EmptyDirs => self
+                .computed_views
+                .empty_dirs
+                .iter()
+                .map(|e| e.node_id)
+                .collect(),
+            ViewMode::Changes => self
+                .computed_views
+                .changes
+                .iter()
+                .filter_map(|e| e.node_id)
+                .collect(),
+            ViewMode::Overview => self.overview_nodes(),
+            ViewMode::Symlinks => self
+                .computed_views
+                .symlinks
+                .iter()
+                .map(|e| e.node_id)
+                .collect(),
+        }
+    }
+
+    /// Add every item in the current view (stale ones in Build Artifacts) to the selection
+    pub fn select_all(&mut self) {
+        for node_id in self.bulk_selectable_nodes() {
+            self.add_to_selection(node_id);
+        }
+    }
+
+    /// Flip selection for every item in the current view (stale ones in Build Artifacts)
+    pub fn invert_selection(&mut self) {
+        for node_id in self.bulk_selectable_nodes() {
+            if !self.selected_nodes.remove(&node_id) {
+                self.add_to_selection(node_id);
+            }
+        }
+        if self.selected_nodes.is_empty() {
+            self.selecting_mode = false;
+        }
+    }
+
     /// Clear the multi-selection and exit selecting mode
     pub fn clear_selection(&mut self) {
         self.selected_nodes.clear();
@@ -635,6 +2364,52 @@ impl AppState {
         self.selected_nodes.len()
     }
 
+    /// Selected nodes without a row in the current view. Selections are kept
+    /// across view switches, so these still count toward batch actions but
+    /// have no marker on screen.
+    pub fn selection_offscreen_count(&self) -> usize {
+        if self.selected_nodes.is_empty() {
+            return 0;
+        }
+        let shown: HashSet<NodeId> = match self.view_mode {
+            ViewMode::Tree if self.show_treemap => self.treemap_nodes().into_iter().collect(),
+            ViewMode::Tree => self.visible_nodes().into_iter().collect(),
+            ViewMode::LargeFiles => (0..self.computed_views.large_file_rows.len())
+                .filter_map(|row| self.computed_views.large_file_at(row))
+                .map(|e| e.node_id)
+                .collect(),
+            ViewMode::BuildArtifacts => self
+                .computed_views
+                .build_artifacts
+                .iter()
+                .map(|e| e.node_id)
+                .collect(),
+            ViewMode::EmptyDirs => self
+                .computed_views
+                .empty_dirs
+                .iter()
+                .map(|e| e.node_id)
+                .collect(),
+            ViewMode::Changes => self
+                .computed_views
+                .changes
+                .iter()
+                .filter_map(|e| e.node_id)
+                .collect(),
+            ViewMode::Overview => self.overview_nodes().into_iter().collect(),
+            ViewMode::Symlinks => self
+                .computed_views
+                .symlinks
+                .iter()
+                .map(|e| e.node_id)
+                .collect(),
+        };
+        self.selected_nodes
+            .iter()
+            .filter(|id| !shown.contains(id))
+            .count()
+    }
+
     /// Add current node to selection, move up, add new node
     pub fn select_move_up(&mut self) {
         if let Some(node_id) = self.node_at_index(self.current_selected_index()) {
@@ -707,52 +2482,148 @@ impl AppState {
     /// Get current selected index for the active view
     fn current_selected_index(&self) -> usize {
         match self.view_mode {
+            ViewMode::Tree if self.show_treemap => self.treemap_state.selected_index,
             ViewMode::Tree => self.selected_index,
             ViewMode::LargeFiles => self.large_files_state.selected_index,
             ViewMode::BuildArtifacts => self.build_artifacts_state.selected_index,
+            ViewMode::EmptyDirs => self.empty_dirs_state.selected_index,
+            ViewMode::Changes => self.changes_state.selected_index,
+            ViewMode::Overview => self.overview_state.selected_index,
+            ViewMode::Symlinks => self.symlinks_state.selected_index,
         }
     }
 
     // --- Multi-delete methods ---
 
-    /// Remove children whose ancestor is also selected
-    fn dedup_selected_nodes(&self) -> Vec<NodeId> {
-        let tree = match &self.tree {
-            Some(t) => t,
-            None => return Vec::new(),
-        };
-
-        let mut result: Vec<NodeId> = Vec::new();
-        for &node_id in &self.selected_nodes {
-            // Walk up to check if any ancestor is also in the set
-            let mut ancestor_selected = false;
-            let mut current = node_id;
-            while let Some(node) = tree.get(current) {
-                if let Some(parent) = node.parent {
-                    if self.selected_nodes.contains(&parent) {
-                        ancestor_selected = true;
-                        break;
-                    }
-                    current = parent;
-                } else {
-                    break;
-                }
-            }
-            if !ancestor_selected {
-                result.push(node_id);
-            }
+    /// Prepare multi-delete: dedup, build item list, show confirm dialog
+    fn request_multi_delete(&mut self) {
+        let nodes = self.selected_nodes.clone();
+        self.request_delete_of(&nodes);
+    }
+
+    /// Preview deleting every stale build artifact. Nothing is removed until
+    /// the multi-delete confirmation is accepted.
+    pub fn request_clean_stale(&mut self) {
+        if self.view_mode != ViewMode::BuildArtifacts
+            || self.delete_receiver.is_some()
+            || self.multi_delete_progress.is_some()
+        {
+            return;
+        }
+
+        self.ensure_views_computed();
+        let stale = self.computed_views.stale_artifacts();
+        if stale.is_empty() {
+            self.set_status("No stale artifacts".to_string());
+            return;
         }
-        result
+        self.request_delete_of(&stale);
     }
 
-    /// Prepare multi-delete: dedup, build item list, show confirm dialog
-    fn request_multi_delete(&mut self) {
+    /// Preview deleting every build artifact of the selected entry's kind
+    /// (e.g. all `node_modules`). Nested matches are deduplicated like any
+    /// multi-delete.
+    pub fn request_delete_kind(&mut self) {
+        if self.view_mode != ViewMode::BuildArtifacts
+            || self.delete_receiver.is_some()
+            || self.multi_delete_progress.is_some()
+        {
+            return;
+        }
+
+        self.ensure_views_computed();
+        let artifacts = &self.computed_views.build_artifacts;
+        let Some(kind) = artifacts
+            .get(self.build_artifacts_state.selected_index)
+            .map(|e| e.kind.clone())
+        else {
+            return;
+        };
+        let nodes: HashSet<NodeId> = artifacts
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| e.node_id)
+            .collect();
+        self.request_delete_of(&nodes);
+        if self.mode == AppMode::ConfirmMultiDelete {
+            self.pending_delete_kind = Some(kind.label().to_string());
+        }
+    }
+
+    /// Open the prompt for how many artifacts of the selected entry's kind
+    /// to keep, newest first
+    pub fn start_keep_newest(&mut self) {
+        if self.refuse_read_only() {
+            return;
+        }
+        if self.view_mode == ViewMode::BuildArtifacts
+            && self.delete_receiver.is_none()
+            && self.multi_delete_progress.is_none()
+            && !self.computed_views.build_artifacts.is_empty()
+        {
+            self.keep_newest_input.clear();
+            self.mode = AppMode::KeepNewest;
+        }
+    }
+
+    /// Type a digit at the keep-newest prompt; anything else is ignored
+    pub fn keep_newest_push(&mut self, c: char) {
+        if c.is_ascii_digit() && self.keep_newest_input.len() < 4 {
+            self.keep_newest_input.push(c);
+        }
+    }
+
+    /// Remove the last digit from the keep-newest prompt
+    pub fn keep_newest_backspace(&mut self) {
+        self.keep_newest_input.pop();
+    }
+
+    /// Close the keep-newest prompt without selecting anything
+    pub fn cancel_keep_newest(&mut self) {
+        self.keep_newest_input.clear();
+        self.mode = AppMode::Browsing;
+    }
+
+    /// Preview deleting every artifact of the selected entry's kind except
+    /// the typed number of most recently built ones
+    pub fn confirm_keep_newest(&mut self) {
+        self.mode = AppMode::Browsing;
+        let input = std::mem::take(&mut self.keep_newest_input);
+        let Ok(keep) = input.parse::<usize>() else {
+            return;
+        };
+        let Some(kind) = self
+            .computed_views
+            .build_artifacts
+            .get(self.build_artifacts_state.selected_index)
+            .map(|e| e.kind.clone())
+        else {
+            return;
+        };
+        let nodes = self.computed_views.all_but_newest(&kind, keep);
+        if nodes.is_empty() {
+            self.set_status(format!(
+                "No more than {} {} artifacts; nothing to delete",
+                keep,
+                kind.label()
+            ));
+            return;
+        }
+        self.request_delete_of(&nodes);
+    }
+
+    /// Build the multi-delete item list for `nodes` and show the confirm dialog
+    fn request_delete_of(&mut self, nodes: &HashSet<NodeId>) {
+        self.pending_delete_kind = None;
+        if self.refuse_read_only() {
+            return;
+        }
         let tree = match &self.tree {
             Some(t) => t,
             None => return,
         };
 
-        let deduped = self.dedup_selected_nodes();
+        let deduped = dedup_ancestors(tree, nodes);
         if deduped.is_empty() {
             return;
         }
@@ -769,22 +2640,45 @@ impl AppState {
             })
             .collect();
 
-        if items.is_empty() {
+        if items.is_empty()
+            || self.refuse_protected(items.iter().map(|(_, path, _)| path.as_path()))
+        {
             return;
         }
 
+        self.confirm_phrase = guard::multi_phrase(
+            items.iter().map(|(_, path, size)| (path.as_path(), *size)),
+            self.typed_confirm_size,
+        );
+        self.confirm_input.clear();
         self.pending_multi_delete = Some(items);
         self.mode = AppMode::ConfirmMultiDelete;
     }
 
     /// Confirm multi-delete: optimistic tree removal + spawn concurrent threads
     pub fn confirm_multi_delete(&mut self) {
+        if !self.confirm_input_matches() {
+            return;
+        }
+        if self.refuse_read_only() {
+            self.pending_multi_delete = None;
+            self.mode = AppMode::Browsing;
+            return;
+        }
+        self.confirm_phrase = None;
+        self.confirm_input.clear();
+        self.pending_delete_kind = None;
         let items = match self.pending_multi_delete.take() {
             Some(items) => items,
             None => return,
         };
+        if self.refuse_protected(items.iter().map(|(_, path, _)| path.as_path())) {
+            self.mode = AppMode::Browsing;
+            return;
+        }
 
         let total = items.len();
+        let total_bytes = items.iter().map(|&(_, _, size)| size).sum();
 
         // Optimistic tree removal
         if let Some(tree) = &mut self.tree {
@@ -792,61 +2686,86 @@ impl AppState {
                 tree.remove_node(node_id);
             }
             self.tree_modified = true;
-            self.computed_views.dirty = true;
+            self.computed_views.invalidate();
         }
         self.selected_nodes.clear();
         self.selecting_mode = false;
+        self.compact_tree_if_needed();
         self.adjust_selection_after_delete();
 
         // Shared channel for all delete threads
         let (tx, rx) = mpsc::channel();
 
-        self.multi_delete_progress = Some(MultiDeleteProgress {
-            total,
-            completed: 0,
-            bytes_freed: 0,
-            failures: Vec::new(),
-            receiver: rx,
-        });
-        self.mode = AppMode::MultiDeleting;
-
         // Spawn one thread per item (concurrent deletion)
+        let mut handles = Vec::with_capacity(total);
         for (_node_id, path, size) in items {
             let tx = tx.clone();
-            std::thread::spawn(move || {
+            handles.push(std::thread::spawn(move || {
                 let result = if path.is_dir() {
                     std::fs::remove_dir_all(&path)
                 } else {
                     std::fs::remove_file(&path)
                 };
                 let msg = match result {
-                    Ok(()) => MultiDeleteResult::Success { size },
+                    Ok(()) => MultiDeleteResult::Success { path, size },
                     Err(e) => MultiDeleteResult::Failure {
                         path,
+                        size,
                         error: format!("{}", e),
                     },
                 };
                 let _ = tx.send(msg);
-            });
+            }));
         }
+
+        self.multi_delete_progress = Some(MultiDeleteProgress {
+            total,
+            completed: 0,
+            total_bytes,
+            bytes_freed: 0,
+            bytes_done: 0,
+            failures: Vec::new(),
+            receiver: rx,
+            handles,
+        });
+        self.mode = AppMode::MultiDeleting;
+    }
+
+    /// Whether a delete thread, single-item or of the running
+    /// multi-delete, is still going
+    pub fn deletions_running(&self) -> bool {
+        let running = |handle: &JoinHandle<()>| !handle.is_finished();
+        self.delete_handle.as_ref().is_some_and(running)
+            || self
+                .multi_delete_progress
+                .as_ref()
+                .is_some_and(|p| p.handles.iter().any(running))
     }
 
-    /// Poll multi-delete channel, update progress, transition when done
-    pub fn poll_multi_delete(&mut self) {
+    /// Poll multi-delete channel, update progress, transition when done.
+    /// Returns whether any deletion finished.
+    pub fn poll_multi_delete(&mut self) -> bool {
         let progress = match &mut self.multi_delete_progress {
             Some(p) => p,
-            None => return,
+            None => return false,
         };
 
+        let mut finished = false;
         while let Ok(result) = progress.receiver.try_recv() {
+            finished = true;
             progress.completed += 1;
             match result {
-                MultiDeleteResult::Success { size, .. } => {
+                MultiDeleteResult::Success { path, size } => {
                     progress.bytes_freed += size;
+                    progress.bytes_done += size;
                     self.session_stats.bytes_freed += size;
                     self.session_stats.items_deleted += 1;
+                    if let Some(log) = &self.audit_log {
+                        audit::append(log, &DeletionRecord::now(path, size));
+                    }
                 }
-                MultiDeleteResult::Failure { path, error } => {
+                MultiDeleteResult::Failure { path, size, error } => {
+                    progress.bytes_done += size;
                     progress.failures.push((path, error));
                 }
             }
@@ -874,16 +2793,421 @@ impl AppState {
                         failures[0].1
                     )
                 };
-                self.error_message = Some(msg);
+                self.set_error(msg);
             }
             self.multi_delete_progress = None;
             self.mode = AppMode::Browsing;
         }
+        finished
     }
 
     /// Cancel multi-delete confirmation
     pub fn cancel_multi_delete(&mut self) {
         self.pending_multi_delete = None;
+        self.pending_delete_kind = None;
+        self.confirm_phrase = None;
+        self.confirm_input.clear();
         self.mode = AppMode::Browsing;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::views::{KindFilter, MinShare};
+    use super::*;
+
+    #[test]
+    fn test_selection_survives_view_round_trip() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let src = tree.add_sized("src", NodeKind::Directory, 0, NodeId::ROOT);
+        let video = tree.add_sized("video.mp4", NodeKind::File, 8 << 20, NodeId::ROOT);
+        tree.add_sized("main.rs", NodeKind::File, 4096, src);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+
+        let mut state = AppState::new(PathBuf::from("/p"));
+        state.set_tree(tree);
+        state.add_to_selection(src);
+        state.add_to_selection(video);
+        assert_eq!(state.selection_offscreen_count(), 0);
+
+        // The directory has no row among the large files, the video does
+        state.next_view();
+        assert_eq!(state.view_mode, ViewMode::LargeFiles);
+        assert_eq!(state.selection_count(), 2);
+        assert_eq!(state.selection_offscreen_count(), 1);
+
+        while state.view_mode != ViewMode::Tree {
+            state.next_view();
+        }
+        assert_eq!(state.selected_nodes, HashSet::from([src, video]));
+        assert_eq!(state.selection_offscreen_count(), 0);
+
+        state.prev_view();
+        state.next_view();
+        assert_eq!(state.selection_count(), 2);
+    }
+
+    #[test]
+    fn test_filters_are_kept_per_view() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        tree.add_sized("video.mp4", NodeKind::File, 8 << 20, NodeId::ROOT);
+        tree.add_sized("notes.txt", NodeKind::File, 4096, NodeId::ROOT);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+
+        let mut state = AppState::new(PathBuf::from("/p"));
+        state.set_tree(tree);
+        state.cycle_kind_filter();
+        assert_eq!(state.view_settings().kind, KindFilter::DirectoriesOnly);
+
+        // Large Files starts unfiltered and gets a threshold of its own
+        state.next_view();
+        assert_eq!(state.view_mode, ViewMode::LargeFiles);
+        assert_eq!(state.view_settings(), ViewSettings::default());
+        assert_eq!(state.computed_views.large_file_rows.len(), 2);
+        state.step_min_share(true);
+        state.cycle_kind_filter();
+        assert_eq!(state.computed_views.large_file_rows.len(), 1);
+        assert_eq!(state.view_settings().kind, KindFilter::All);
+
+        while state.view_mode != ViewMode::Tree {
+            state.next_view();
+        }
+        assert_eq!(state.node_filter().kind, KindFilter::DirectoriesOnly);
+        assert_eq!(state.node_filter().min_share, MinShare::default());
+        state.prev_view();
+        while state.view_mode != ViewMode::LargeFiles {
+            state.prev_view();
+        }
+        assert!(state.view_settings().min_share.label().is_some());
+    }
+
+    #[test]
+    fn test_overview_opens_entries_in_tree() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let src = tree.add_sized("src", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("main.rs", NodeKind::File, 4096, src);
+        let notes = tree.add_sized("notes.txt", NodeKind::File, 1024, NodeId::ROOT);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+
+        let mut state = AppState::new(PathBuf::from("/p"));
+        state.set_tree(tree);
+        state.view_mode = ViewMode::Overview;
+        assert_eq!(state.overview_nodes(), vec![src, notes]);
+
+        // A directory is drilled into
+        state.open_overview_entry();
+        assert_eq!(state.view_mode, ViewMode::Tree);
+        assert_eq!(state.view_root, src);
+
+        // A file is shown selected in the scan root
+        state.view_mode = ViewMode::Overview;
+        state.overview_state.selected_index = 1;
+        state.open_overview_entry();
+        assert_eq!(state.view_mode, ViewMode::Tree);
+        assert_eq!(state.view_root, NodeId::ROOT);
+        assert_eq!(state.selected_node(), Some(notes));
+    }
+
+    #[test]
+    fn test_finished_deletes_ask_for_redraw() {
+        let mut state = AppState::new(PathBuf::from("/p"));
+        state.set_tree(DiskTree::new(PathBuf::from("/p")));
+        assert!(!state.poll_background());
+
+        let (tx, rx) = mpsc::channel();
+        state.delete_receiver = Some(rx);
+        state.mode = AppMode::ConfirmDelete;
+        assert!(!state.poll_background());
+        tx.send(Err("permission denied".to_string())).unwrap();
+        assert!(state.poll_background());
+        assert_eq!(state.mode, AppMode::Browsing);
+        assert!(state.error_message.is_some());
+        assert!(!state.poll_background());
+
+        let (tx, rx) = mpsc::channel();
+        state.multi_delete_progress = Some(MultiDeleteProgress {
+            total: 1,
+            completed: 0,
+            total_bytes: 10,
+            bytes_freed: 0,
+            bytes_done: 0,
+            failures: Vec::new(),
+            receiver: rx,
+            handles: Vec::new(),
+        });
+        state.mode = AppMode::MultiDeleting;
+        assert!(!state.poll_background());
+        tx.send(MultiDeleteResult::Success {
+            path: PathBuf::from("/p/a"),
+            size: 10,
+        })
+        .unwrap();
+        assert!(state.poll_background());
+        assert_eq!(state.mode, AppMode::Browsing);
+        assert!(state.multi_delete_progress.is_none());
+    }
+
+    #[test]
+    fn test_refresh_waits_for_delete_confirmation() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let cache = tree.add_sized("cache", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("a", NodeKind::File, 0, cache);
+        tree.aggregate_sizes();
+        let mut fresh = DiskTree::new(PathBuf::from("/p/cache"));
+        fresh.add_sized("b", NodeKind::File, 0, NodeId::ROOT);
+        fresh.aggregate_sizes();
+
+        let mut state = AppState::new(PathBuf::from("/p"));
+        state.set_tree(tree);
+        state.view_root = cache;
+        state.request_delete();
+        assert_eq!(state.mode, AppMode::ConfirmDelete);
+
+        let (tx, rx) = mpsc::channel();
+        state.refresh = Some(rx);
+        tx.send(vec![(PathBuf::from("/p/cache"), fresh)]).unwrap();
+        // The pending node ID must keep naming the file being confirmed
+        assert!(!state.poll_refresh());
+        let (id, path) = state.pending_delete.clone().unwrap();
+        let tree = state.tree.as_ref().unwrap();
+        assert_eq!(tree.get(id).map(|n| n.path.as_path()), Some(path.as_path()));
+
+        state.cancel_delete();
+        assert!(state.poll_refresh());
+        let tree = state.tree.as_ref().unwrap();
+        assert!(tree.find_by_path(Path::new("/p/cache/a")).is_none());
+        assert!(tree.find_by_path(Path::new("/p/cache/b")).is_some());
+    }
+
+    #[test]
+    fn test_single_delete_is_waited_for() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("old.log");
+        std::fs::write(&file, b"log").unwrap();
+        let mut tree = DiskTree::new(dir.path().to_path_buf());
+        let id = tree.add_sized("old.log", NodeKind::File, 3, NodeId::ROOT);
+        tree.aggregate_sizes();
+
+        let mut state = AppState::new(dir.path().to_path_buf());
+        state.set_tree(tree);
+        state.selected_index = state.visible_nodes().iter().position(|&n| n == id).unwrap();
+        state.request_delete();
+        // Temp dirs sit near `/`, where the name has to be typed
+        if let Some(phrase) = state.confirm_phrase.clone() {
+            state.confirm_input = phrase;
+        }
+        state.confirm_delete();
+        assert!(state.delete_handle.is_some());
+        while state.deletions_running() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        // Once the thread is done its result is already waiting
+        assert!(state.poll_delete());
+        assert!(state.delete_handle.is_none());
+        assert_eq!(state.session_stats.items_deleted, 1);
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn test_read_only_refuses_deletes() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let target = tree.add_sized("target", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.aggregate_sizes();
+
+        let mut state = AppState::new(PathBuf::from("/p"));
+        state.set_tree(tree);
+        state.read_only = true;
+        state.request_delete();
+        assert_eq!(state.mode, AppMode::Browsing);
+        assert!(state.pending_delete.is_none());
+        assert!(state.status_message.is_some());
+
+        state.selected_nodes.insert(target);
+        state.request_delete();
+        assert_eq!(state.mode, AppMode::Browsing);
+        assert!(state.pending_multi_delete.is_none());
+    }
+
+    #[test]
+    fn test_percent_base_cycles_through_available_totals() {
+        let mut state = AppState::new(PathBuf::from("/p"));
+        state.toggle_percent_base();
+        assert_eq!(state.percent_base, PercentBase::Parent);
+        // Neither free space nor a goal is known
+        state.toggle_percent_base();
+        assert_eq!(state.percent_base, PercentBase::ViewRoot);
+
+        // A full disk gives no free-space base
+        state.disk_capacity = Some((100 << 30, 0));
+        state.toggle_percent_base();
+        state.toggle_percent_base();
+        assert_eq!(state.percent_base, PercentBase::ViewRoot);
+
+        state.disk_capacity = Some((100 << 30, 8 << 30));
+        state.need = Some(20 << 30);
+        state.toggle_percent_base();
+        state.toggle_percent_base();
+        assert_eq!(state.percent_base, PercentBase::FreeSpace);
+        assert_eq!(state.percent_total(), Some(8 << 30));
+        state.toggle_percent_base();
+        assert_eq!(state.percent_base, PercentBase::Goal);
+        assert_eq!(state.percent_total(), Some(20 << 30));
+        state.bar_metric = BarMetric::Files;
+        assert_eq!(state.percent_total(), None);
+        state.toggle_percent_base();
+        assert_eq!(state.percent_base, PercentBase::ViewRoot);
+    }
+
+    #[test]
+    fn test_back_and_forward_restore_views() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let a = tree.add_sized("a", NodeKind::Directory, 0, NodeId::ROOT);
+        let b = tree.add_sized("b", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("big", NodeKind::File, 300, a);
+        tree.add_sized("small", NodeKind::File, 100, a);
+        tree.add_sized("c", NodeKind::File, 50, b);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+        tree.set_expanded(a, true);
+
+        let mut state = AppState::new(PathBuf::from("/p"));
+        state.set_tree(tree);
+        let row = |state: &AppState, id| state.visible_nodes().iter().position(|&n| n == id);
+        state.selected_index = row(&state, a).unwrap();
+        state.drill_down();
+        assert_eq!(state.view_root, a);
+        assert_eq!(state.history, vec![NodeId::ROOT]);
+        state.move_down();
+        let inner_row = state.selected_index;
+        assert_ne!(inner_row, 0);
+
+        state.go_back();
+        assert_eq!(state.view_root, NodeId::ROOT);
+        assert_eq!(state.selected_node(), Some(a));
+        state.go_forward();
+        assert_eq!(state.view_root, a);
+        assert_eq!(state.selected_index, inner_row);
+        // Nothing left to go forward to
+        state.go_forward();
+        assert_eq!(state.view_root, a);
+
+        // Opening a new view drops the forward history
+        state.go_back();
+        state.selected_index = row(&state, b).unwrap();
+        state.drill_down();
+        assert_eq!(state.view_root, b);
+        assert!(state.forward.is_empty());
+        state.go_forward();
+        assert_eq!(state.view_root, b);
+        state.go_back();
+        assert_eq!(state.view_root, NodeId::ROOT);
+        assert_eq!(state.selected_node(), Some(b));
+    }
+
+    #[test]
+    fn test_delete_kind_selects_every_artifact_of_that_kind() {
+        let mut tree = DiskTree::new(PathBuf::from("/w"));
+        let a = tree.add_sized("a", NodeKind::Directory, 0, NodeId::ROOT);
+        let b = tree.add_sized("b", NodeKind::Directory, 0, NodeId::ROOT);
+        let c = tree.add_sized("c", NodeKind::Directory, 0, NodeId::ROOT);
+        let a_modules = tree.add_sized("node_modules", NodeKind::Directory, 0, a);
+        tree.add_sized("lib.js", NodeKind::File, 300, a_modules);
+        let b_modules = tree.add_sized("node_modules", NodeKind::Directory, 0, b);
+        let nested = tree.add_sized("node_modules", NodeKind::Directory, 0, b_modules);
+        tree.add_sized("dep.js", NodeKind::File, 200, nested);
+        let target = tree.add_sized("target", NodeKind::Directory, 0, c);
+        tree.add_sized("app", NodeKind::File, 500, target);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+
+        let mut state = AppState::new(PathBuf::from("/w"));
+        state.set_tree(tree);
+        while state.view_mode != ViewMode::BuildArtifacts {
+            state.next_view();
+        }
+        state.ensure_views_computed();
+        state.build_artifacts_state.selected_index = state
+            .computed_views
+            .build_artifacts
+            .iter()
+            .position(|e| e.node_id == b_modules)
+            .unwrap();
+        state.request_delete_kind();
+
+        assert_eq!(state.mode, AppMode::ConfirmMultiDelete);
+        assert_eq!(state.pending_delete_kind.as_deref(), Some("Node"));
+        // The nested node_modules goes with its parent; target stays
+        let ids: HashSet<NodeId> = state
+            .pending_multi_delete
+            .iter()
+            .flatten()
+            .map(|(id, _, _)| *id)
+            .collect();
+        assert_eq!(ids, HashSet::from([a_modules, b_modules]));
+
+        state.cancel_multi_delete();
+        assert!(state.pending_delete_kind.is_none());
+    }
+
+    #[test]
+    fn test_reveal_path_selects_file_in_collapsed_tree() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let docs = tree.add_sized("docs", NodeKind::Directory, 0, NodeId::ROOT);
+        let old = tree.add_sized("old", NodeKind::Directory, 0, docs);
+        let report = tree.add_sized("report.pdf", NodeKind::File, 100, old);
+        let music = tree.add_sized("music", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("song.mp3", NodeKind::File, 500, music);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+
+        let mut state = AppState::new(PathBuf::from("/p"));
+        state.set_tree(tree);
+        state.selected_index = state
+            .visible_nodes()
+            .iter()
+            .position(|&n| n == music)
+            .unwrap();
+        state.drill_down();
+        assert_eq!(state.view_root, music);
+
+        // Outside the drilled-down view: back to the root, expanded down to it
+        state.reveal_path(Path::new("/p/docs/old/report.pdf"));
+        assert_eq!(state.view_root, NodeId::ROOT);
+        assert_eq!(state.selected_node(), Some(report));
+        assert_eq!(state.history, vec![NodeId::ROOT, music]);
+        assert!(state.error_message.is_none());
+
+        state.reveal_path(Path::new("/p/docs/new.pdf"));
+        assert_eq!(state.selected_node(), Some(report));
+        assert!(state.error_message.is_some());
+    }
+
+    #[test]
+    fn test_deletes_refresh_disk_capacity() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("old.log");
+        std::fs::write(&file, b"log").unwrap();
+        let mut tree = DiskTree::new(dir.path().to_path_buf());
+        let id = tree.add_sized("old.log", NodeKind::File, 3, NodeId::ROOT);
+        tree.aggregate_sizes();
+
+        let mut state = AppState::new(dir.path().to_path_buf());
+        state.set_tree(tree);
+        // As if the disk was full when the scan started
+        state.disk_capacity = Some((1, 0));
+        state.selected_index = state.visible_nodes().iter().position(|&n| n == id).unwrap();
+        state.request_delete();
+        if let Some(phrase) = state.confirm_phrase.clone() {
+            state.confirm_input = phrase;
+        }
+        state.confirm_delete();
+        while state.deletions_running() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert!(state.poll_background());
+        assert!(state.disk_capacity.is_some_and(|(total, _)| total > 1));
+    }
+}
@@ -0,0 +1,212 @@
+use dux_core::{DiskTree, NodeId};
+use ratatui::layout::Rect;
+
+use super::views::NodeFilter;
+
+/// Terminal cells are roughly twice as tall as they are wide
+const CELL_ASPECT: f64 = 2.0;
+
+/// Children of `root` that get a block (zero-sized entries have no area)
+pub fn treemap_children(tree: &DiskTree, root: NodeId, filter: NodeFilter) -> Vec<NodeId> {
+    tree.get(root)
+        .map(|node| {
+            node.children
+                .iter()
+                .copied()
+                .filter(|&id| {
+                    tree.get(id)
+                        .is_some_and(|n| n.size > 0 && filter.keep_in(tree, n))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Floating-point rectangle used while laying out
+#[derive(Debug, Clone, Copy)]
+struct RectF {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+/// Squarified treemap layout (Bruls, Huizing & van Wijk).
+///
+/// `sizes` must be sorted descending. Returns one cell-aligned rectangle per
+/// size, in input order. Blocks are laid out so they look roughly square on a
+/// terminal, accounting for the tall aspect ratio of character cells.
+pub fn squarify(sizes: &[u64], area: Rect) -> Vec<Rect> {
+    let total: u64 = sizes.iter().sum();
+    if sizes.is_empty() || total == 0 || area.width == 0 || area.height == 0 {
+        return vec![Rect::new(area.x, area.y, 0, 0); sizes.len()];
+    }
+
+    // Work in a space where one unit is equally wide and tall
+    let width = area.width as f64;
+    let height = area.height as f64 * CELL_ASPECT;
+    let scale = width * height / total as f64;
+    let values: Vec<f64> = sizes.iter().map(|&s| s as f64 * scale).collect();
+
+    let mut free = RectF {
+        x: 0.0,
+        y: 0.0,
+        w: width,
+        h: height,
+    };
+    let mut laid_out = Vec::with_capacity(values.len());
+    let mut start = 0;
+
+    while start < values.len() {
+        let short_side = free.w.min(free.h);
+        let mut end = start + 1;
+        while end < values.len()
+            && worst_ratio(&values[start..=end], short_side)
+                <= worst_ratio(&values[start..end], short_side)
+        {
+            end += 1;
+        }
+        free = layout_row(&values[start..end], free, &mut laid_out);
+        start = end;
+    }
+
+    laid_out.into_iter().map(|r| to_cells(r, area)).collect()
+}
+
+/// Worst aspect ratio of a row of values laid along a side of length `side`
+fn worst_ratio(row: &[f64], side: f64) -> f64 {
+    let sum: f64 = row.iter().sum();
+    if sum <= 0.0 || side <= 0.0 {
+        return f64::INFINITY;
+    }
+    let max = row.iter().cloned().fold(f64::MIN, f64::max);
+    let min = row.iter().cloned().fold(f64::MAX, f64::min);
+    let side2 = side * side;
+    let sum2 = sum * sum;
+    (side2 * max / sum2).max(sum2 / (side2 * min))
+}
+
+/// Place a row along the shorter side of `free`, returning the remaining space
+fn layout_row(row: &[f64], free: RectF, out: &mut Vec<RectF>) -> RectF {
+    let sum: f64 = row.iter().sum();
+
+    if free.w >= free.h {
+        // Vertical strip on the left
+        let strip_w = if free.h > 0.0 { sum / free.h } else { 0.0 };
+        let mut y = free.y;
+        for &v in row {
+            let h = if strip_w > 0.0 { v / strip_w } else { 0.0 };
+            out.push(RectF {
+                x: free.x,
+                y,
+                w: strip_w,
+                h,
+            });
+            y += h;
+        }
+        RectF {
+            x: free.x + strip_w,
+            y: free.y,
+            w: (free.w - strip_w).max(0.0),
+            h: free.h,
+        }
+    } else {
+        // Horizontal strip on top
+        let strip_h = if free.w > 0.0 { sum / free.w } else { 0.0 };
+        let mut x = free.x;
+        for &v in row {
+            let w = if strip_h > 0.0 { v / strip_h } else { 0.0 };
+            out.push(RectF {
+                x,
+                y: free.y,
+                w,
+                h: strip_h,
+            });
+            x += w;
+        }
+        RectF {
+            x: free.x,
+            y: free.y + strip_h,
+            w: free.w,
+            h: (free.h - strip_h).max(0.0),
+        }
+    }
+}
+
+/// Snap a layout rectangle to terminal cells. Edges are rounded independently
+/// so neighbouring blocks share borders without gaps or overlap.
+fn to_cells(r: RectF, area: Rect) -> Rect {
+    let x0 = r.x.round() as u16;
+    let x1 = (r.x + r.w).round() as u16;
+    let y0 = (r.y / CELL_ASPECT).round() as u16;
+    let y1 = ((r.y + r.h) / CELL_ASPECT).round() as u16;
+    let x1 = x1.min(area.width);
+    let y1 = y1.min(area.height);
+    Rect::new(
+        area.x + x0.min(x1),
+        area.y + y0.min(y1),
+        x1.saturating_sub(x0),
+        y1.saturating_sub(y0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(r: &Rect) -> u32 {
+        r.width as u32 * r.height as u32
+    }
+
+    fn overlaps(a: &Rect, b: &Rect) -> bool {
+        a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+    }
+
+    #[test]
+    fn test_single_block_fills_area() {
+        let rect = Rect::new(2, 3, 40, 10);
+        let rects = squarify(&[100], rect);
+        assert_eq!(rects, vec![rect]);
+    }
+
+    #[test]
+    fn test_blocks_tile_area_without_overlap() {
+        let rect = Rect::new(0, 0, 80, 24);
+        let sizes = [600, 300, 200, 100, 50, 25, 25];
+        let rects = squarify(&sizes, rect);
+
+        assert_eq!(rects.len(), sizes.len());
+        let covered: u32 = rects.iter().map(area).sum();
+        assert_eq!(covered, area(&rect));
+
+        for (i, a) in rects.iter().enumerate() {
+            for b in rects.iter().skip(i + 1) {
+                assert!(!overlaps(a, b), "{:?} overlaps {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_area_is_proportional() {
+        let rect = Rect::new(0, 0, 100, 50);
+        let rects = squarify(&[3, 1], rect);
+        let big = area(&rects[0]) as f64;
+        let small = area(&rects[1]) as f64;
+        assert!((big / small - 3.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_worst_ratio_prefers_squares() {
+        // A single square-ish item has ratio 1
+        assert!((worst_ratio(&[4.0], 2.0) - 1.0).abs() < f64::EPSILON);
+        // Adding a tiny item to the row makes it worse
+        assert!(worst_ratio(&[4.0, 0.1], 2.0) > worst_ratio(&[4.0], 2.0));
+    }
+
+    #[test]
+    fn test_empty_and_zero_inputs() {
+        assert!(squarify(&[], Rect::new(0, 0, 10, 10)).is_empty());
+        let rects = squarify(&[0, 0], Rect::new(0, 0, 10, 10));
+        assert!(rects.iter().all(|r| area(r) == 0));
+    }
+}
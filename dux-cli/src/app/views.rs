@@ -1,6 +1,207 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
-use dux_core::{DiskTree, NodeId, NodeKind, size_percentage};
+use color_eyre::{Result, eyre::eyre};
+use dux_core::{DiskTree, LinkStatus, NodeId, NodeKind, TreeNode, size_percentage};
+
+use super::glob::glob_match;
+
+/// View-level filter applied on top of the scanned tree (no rescan needed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeFilter {
+    /// Whether dotfiles and dot-directories are shown
+    pub show_hidden: bool,
+    /// Tree view entries smaller than this share of their parent are hidden
+    pub min_share: MinShare,
+    /// Which kinds of entries the Tree view lists
+    pub kind: KindFilter,
+}
+
+impl Default for NodeFilter {
+    fn default() -> Self {
+        Self {
+            show_hidden: true,
+            min_share: MinShare::default(),
+            kind: KindFilter::default(),
+        }
+    }
+}
+
+impl NodeFilter {
+    /// Whether a node passes the filter (its ancestors are checked separately)
+    pub fn keep(&self, node: &TreeNode) -> bool {
+        self.show_hidden || !node.name.starts_with('.')
+    }
+
+    /// Like `keep`, but also applies the `kind` filter and hides nodes below
+    /// the `min_share` of their parent. Only the tree list and treemap use
+    /// this; the other views list entries from the whole tree, where a parent
+    /// share means little.
+    pub fn keep_in(&self, tree: &DiskTree, node: &TreeNode) -> bool {
+        self.keep(node)
+            && self.kind.admits(node)
+            && node
+                .parent
+                .and_then(|p| tree.get(p))
+                .is_none_or(|parent| self.min_share.admits(node.size, parent.size))
+    }
+}
+
+/// Steps for `MinShare`, in tenths of a percent, with their footer labels
+const MIN_SHARE_STEPS: [(u64, &str); 7] = [
+    (0, ""),
+    (1, "Hiding <0.1%"),
+    (5, "Hiding <0.5%"),
+    (10, "Hiding <1%"),
+    (20, "Hiding <2%"),
+    (50, "Hiding <5%"),
+    (100, "Hiding <10%"),
+];
+
+/// Live size threshold for the Tree view, relative to each entry's parent.
+/// Unlike the scanner's minimum file size, nothing is dropped from the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MinShare(usize);
+
+impl MinShare {
+    /// Next larger (`up`) or smaller threshold, stopping at the ends
+    pub fn step(self, up: bool) -> Self {
+        if up {
+            Self((self.0 + 1).min(MIN_SHARE_STEPS.len() - 1))
+        } else {
+            Self(self.0.saturating_sub(1))
+        }
+    }
+
+    /// Footer label, or None when nothing is hidden
+    pub fn label(self) -> Option<&'static str> {
+        (self.0 > 0).then(|| MIN_SHARE_STEPS[self.0].1)
+    }
+
+    /// Whether an entry of `size` is at least the threshold share of `parent_size`
+    pub fn admits(self, size: u64, parent_size: u64) -> bool {
+        let permille = MIN_SHARE_STEPS[self.0].0 as u128;
+        size as u128 * 1000 >= parent_size as u128 * permille
+    }
+
+    /// Like `admits`, for an entry already given as a percentage
+    pub fn admits_percentage(self, percentage: f64) -> bool {
+        percentage * 10.0 >= MIN_SHARE_STEPS[self.0].0 as f64
+    }
+}
+
+/// Filters each view keeps for itself, so switching views brings back the
+/// ones set there. The Tree view uses both; Large Files has its own size
+/// threshold, relative to the scanned total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ViewSettings {
+    pub min_share: MinShare,
+    pub kind: KindFilter,
+}
+
+/// Entry kinds listed in the Tree view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KindFilter {
+    #[default]
+    All,
+    DirectoriesOnly,
+    /// Files, plus the directories that lead to them
+    FilesOnly,
+}
+
+impl KindFilter {
+    /// Next mode in the All → DirectoriesOnly → FilesOnly cycle
+    pub fn next(self) -> Self {
+        match self {
+            KindFilter::All => KindFilter::DirectoriesOnly,
+            KindFilter::DirectoriesOnly => KindFilter::FilesOnly,
+            KindFilter::FilesOnly => KindFilter::All,
+        }
+    }
+
+    /// Footer label, or None when everything is listed
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            KindFilter::All => None,
+            KindFilter::DirectoriesOnly => Some("Directories only"),
+            KindFilter::FilesOnly => Some("Files only"),
+        }
+    }
+
+    pub fn admits(self, node: &TreeNode) -> bool {
+        match self {
+            KindFilter::All => true,
+            KindFilter::DirectoriesOnly => node.kind.is_directory(),
+            KindFilter::FilesOnly => match node.kind {
+                NodeKind::File => true,
+                NodeKind::Directory => node.file_count > 0,
+                NodeKind::Symlink | NodeKind::Error => false,
+            },
+        }
+    }
+}
+
+/// How much of a subtree the active filters leave out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FilterSummary {
+    pub shown_files: u64,
+    pub total_files: u64,
+    pub hidden_size: u64,
+}
+
+impl FilterSummary {
+    /// Count what `filter` hides below `root`. Hidden subtrees are counted
+    /// from their totals without being walked.
+    pub fn compute(tree: &DiskTree, root: NodeId, filter: NodeFilter) -> Self {
+        let Some(root_node) = tree.get(root) else {
+            return Self::default();
+        };
+        let mut hidden_files = 0;
+        let mut hidden_size = 0;
+        let mut stack = vec![root_node];
+        while let Some(node) = stack.pop() {
+            for child in node.children.iter().filter_map(|&c| tree.get(c)) {
+                if filter.keep_in(tree, child) {
+                    stack.push(child);
+                } else {
+                    hidden_files += child.file_count;
+                    hidden_size += child.size;
+                }
+            }
+        }
+        Self {
+            shown_files: root_node.file_count.saturating_sub(hidden_files),
+            total_files: root_node.file_count,
+            hidden_size,
+        }
+    }
+}
+
+/// Drop nodes whose ancestor is also in `nodes` (deleting the ancestor covers them)
+pub fn dedup_ancestors(tree: &DiskTree, nodes: &HashSet<NodeId>) -> Vec<NodeId> {
+    let mut result: Vec<NodeId> = Vec::new();
+    for &node_id in nodes {
+        // Walk up to check if any ancestor is also in the set
+        let mut ancestor_selected = false;
+        let mut current = node_id;
+        while let Some(node) = tree.get(current) {
+            if let Some(parent) = node.parent {
+                if nodes.contains(&parent) {
+                    ancestor_selected = true;
+                    break;
+                }
+                current = parent;
+            } else {
+                break;
+            }
+        }
+        if !ancestor_selected {
+            result.push(node_id);
+        }
+    }
+    result
+}
 
 #[derive(Debug, Clone)]
 pub struct LargeFileEntry {
@@ -10,7 +211,68 @@ pub struct LargeFileEntry {
     pub percentage: f64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// One row of the Large Files view
+#[derive(Debug, Clone, PartialEq)]
+pub enum LargeFileRow {
+    /// Header for the files sharing a parent directory, with their total
+    Group {
+        /// Parent directory relative to the scan root ("" for the root)
+        dir: String,
+        size: u64,
+        percentage: f64,
+        count: usize,
+        collapsed: bool,
+    },
+    /// Index into `ComputedViews::large_files`
+    File(usize),
+}
+
+impl LargeFileEntry {
+    /// Parent directory relative to the scan root, the key files are
+    /// grouped by
+    pub fn parent_dir(&self) -> &str {
+        Path::new(&self.relative_path)
+            .parent()
+            .and_then(|dir| dir.to_str())
+            .unwrap_or("")
+    }
+}
+
+/// A directory with no files anywhere below it. Only the outermost one of a
+/// nested chain is listed; its empty subdirectories are counted in `nested`.
+#[derive(Debug, Clone)]
+pub struct EmptyDirEntry {
+    pub node_id: NodeId,
+    pub relative_path: String,
+    pub size: u64,
+    /// Empty subdirectories removed along with this one
+    pub nested: u64,
+}
+
+/// A symlink in the tree and where it points
+#[derive(Debug, Clone)]
+pub struct SymlinkEntry {
+    pub node_id: NodeId,
+    pub relative_path: String,
+    /// Target as written in the link; None when it couldn't be read
+    pub target: Option<PathBuf>,
+    pub status: LinkStatus,
+}
+
+/// A path whose size changed since the previous scan
+#[derive(Debug, Clone)]
+pub struct ChangeEntry {
+    /// None when the path no longer exists
+    pub node_id: Option<NodeId>,
+    pub path: PathBuf,
+    pub relative_path: String,
+    /// Bytes gained (positive) or lost (negative)
+    pub delta: i64,
+    /// Current size (0 when removed)
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ArtifactKind {
     Rust,
     Xcode,
@@ -22,10 +284,26 @@ pub enum ArtifactKind {
     NextNuxt,
     Vendor,
     Cache,
+    /// User-defined label from `artifacts.toml`
+    Custom(String),
 }
 
 impl ArtifactKind {
-    pub fn label(&self) -> &'static str {
+    /// Kinds with a built-in label, used to resolve labels from the config file
+    const BUILTIN: [ArtifactKind; 10] = [
+        ArtifactKind::Rust,
+        ArtifactKind::Xcode,
+        ArtifactKind::Node,
+        ArtifactKind::Generic,
+        ArtifactKind::Gradle,
+        ArtifactKind::Python,
+        ArtifactKind::CocoaPods,
+        ArtifactKind::NextNuxt,
+        ArtifactKind::Vendor,
+        ArtifactKind::Cache,
+    ];
+
+    pub fn label(&self) -> &str {
         match self {
             ArtifactKind::Rust => "Rust",
             ArtifactKind::Xcode => "Xcode",
@@ -37,23 +315,75 @@ impl ArtifactKind {
             ArtifactKind::NextNuxt => "Next/Nuxt",
             ArtifactKind::Vendor => "Vendor",
             ArtifactKind::Cache => "Cache",
+            ArtifactKind::Custom(label) => label,
         }
     }
+
+    /// Built-in kind whose label matches (case-insensitive), else a custom kind
+    pub fn from_label(label: &str) -> Self {
+        Self::BUILTIN
+            .into_iter()
+            .find(|k| k.label().eq_ignore_ascii_case(label))
+            .unwrap_or_else(|| ArtifactKind::Custom(label.to_string()))
+    }
+}
+
+/// Directory-name → artifact kind table: built-ins merged with user rules
+#[derive(Debug, Clone)]
+pub struct ArtifactRules {
+    by_name: HashMap<String, ArtifactKind>,
 }
 
-pub fn classify_artifact(name: &str) -> Option<ArtifactKind> {
-    match name {
-        "target" => Some(ArtifactKind::Rust),
-        "DerivedData" | "Build" => Some(ArtifactKind::Xcode),
-        "node_modules" => Some(ArtifactKind::Node),
-        "build" | "dist" => Some(ArtifactKind::Generic),
-        ".gradle" => Some(ArtifactKind::Gradle),
-        "__pycache__" | ".tox" | ".venv" | "venv" => Some(ArtifactKind::Python),
-        "Pods" => Some(ArtifactKind::CocoaPods),
-        ".next" | ".nuxt" => Some(ArtifactKind::NextNuxt),
-        "vendor" => Some(ArtifactKind::Vendor),
-        ".cache" => Some(ArtifactKind::Cache),
-        _ => None,
+impl Default for ArtifactRules {
+    fn default() -> Self {
+        let builtin: [(&str, ArtifactKind); 16] = [
+            ("target", ArtifactKind::Rust),
+            ("DerivedData", ArtifactKind::Xcode),
+            ("Build", ArtifactKind::Xcode),
+            ("node_modules", ArtifactKind::Node),
+            ("build", ArtifactKind::Generic),
+            ("dist", ArtifactKind::Generic),
+            (".gradle", ArtifactKind::Gradle),
+            ("__pycache__", ArtifactKind::Python),
+            (".tox", ArtifactKind::Python),
+            (".venv", ArtifactKind::Python),
+            ("venv", ArtifactKind::Python),
+            ("Pods", ArtifactKind::CocoaPods),
+            (".next", ArtifactKind::NextNuxt),
+            (".nuxt", ArtifactKind::NextNuxt),
+            ("vendor", ArtifactKind::Vendor),
+            (".cache", ArtifactKind::Cache),
+        ];
+        Self {
+            by_name: builtin
+                .into_iter()
+                .map(|(name, kind)| (name.to_string(), kind))
+                .collect(),
+        }
+    }
+}
+
+impl ArtifactRules {
+    /// Built-in rules extended by a TOML file mapping directory names to labels,
+    /// e.g. `"bazel-out" = "Bazel"`. User rules override built-ins.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let entries: HashMap<String, String> = toml::from_str(&contents)
+            .map_err(|e| eyre!("Invalid artifacts file {}: {}", path.display(), e))?;
+        Ok(Self::default().with_rules(entries))
+    }
+
+    /// Add or override rules mapping directory names to labels
+    pub fn with_rules(mut self, rules: HashMap<String, String>) -> Self {
+        for (name, label) in rules {
+            self.by_name.insert(name, ArtifactKind::from_label(&label));
+        }
+        self
+    }
+
+    /// Artifact kind for a directory name, if it is a known build artifact
+    pub fn classify(&self, name: &str) -> Option<&ArtifactKind> {
+        self.by_name.get(name)
     }
 }
 
@@ -64,6 +394,8 @@ pub struct BuildArtifactEntry {
     pub size: u64,
     pub percentage: f64,
     pub kind: ArtifactKind,
+    /// Number of files inside the artifact directory
+    pub file_count: u64,
     pub is_stale: bool,
     /// Most recent mtime of any descendant directory
     pub newest_mtime: Option<SystemTime>,
@@ -110,27 +442,281 @@ impl StaleThreshold {
     }
 }
 
+/// One of the lists `ComputedViews` derives from the tree, each rebuilt on
+/// its own when first needed after the tree changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ViewData {
+    LargeFiles,
+    BuildArtifacts,
+    EmptyDirs,
+    Symlinks,
+    Changes,
+    NewestMtimes,
+}
+
+impl ViewData {
+    const ALL: [ViewData; 6] = [
+        ViewData::LargeFiles,
+        ViewData::BuildArtifacts,
+        ViewData::EmptyDirs,
+        ViewData::Symlinks,
+        ViewData::Changes,
+        ViewData::NewestMtimes,
+    ];
+}
+
 pub struct ComputedViews {
+    /// Every large file, largest first, so the filter can change without a
+    /// rebuild
     pub large_files: Vec<LargeFileEntry>,
+    /// Indices into `large_files` of the entries matching the filters
+    large_files_shown: Vec<usize>,
+    /// Glob the Large Files view is narrowed by (empty = no filter)
+    pub large_files_filter: String,
+    /// Large Files entries below this share of the scanned total are hidden
+    pub large_files_min_share: MinShare,
+    /// Whether the Large Files view groups files under their parent directory
+    pub large_files_grouped: bool,
+    /// Groups folded to just their header
+    collapsed_groups: HashSet<String>,
+    /// What the Large Files view lists, in order: plain files, or group
+    /// headers each followed by their files unless folded
+    pub large_file_rows: Vec<LargeFileRow>,
     pub build_artifacts: Vec<BuildArtifactEntry>,
-    pub dirty: bool,
+    pub empty_dirs: Vec<EmptyDirEntry>,
+    /// Every symlink, broken ones first
+    pub symlinks: Vec<SymlinkEntry>,
+    /// Newest mtime at or below each node, for the recent-activity marker
+    pub newest_mtimes: Vec<Option<SystemTime>>,
+    /// Size changes against the previous scan, from `DiskTree::diff`
+    pub change_deltas: Vec<(PathBuf, i64)>,
+    pub changes: Vec<ChangeEntry>,
+    /// Lists out of date with the tree; see [`ComputedViews::ensure`]
+    stale: HashSet<ViewData>,
     pub stale_threshold: StaleThreshold,
+    pub artifact_rules: ArtifactRules,
 }
 
 impl ComputedViews {
     pub fn new() -> Self {
         Self {
             large_files: Vec::new(),
+            large_files_shown: Vec::new(),
+            large_files_filter: String::new(),
+            large_files_min_share: MinShare::default(),
+            large_files_grouped: false,
+            collapsed_groups: HashSet::new(),
+            large_file_rows: Vec::new(),
             build_artifacts: Vec::new(),
-            dirty: true,
+            empty_dirs: Vec::new(),
+            symlinks: Vec::new(),
+            newest_mtimes: Vec::new(),
+            change_deltas: Vec::new(),
+            changes: Vec::new(),
+            stale: ViewData::ALL.into_iter().collect(),
             stale_threshold: StaleThreshold::SevenDays,
+            artifact_rules: ArtifactRules::default(),
         }
     }
 
-    pub fn rebuild(&mut self, tree: &DiskTree) {
-        self.large_files = Self::rebuild_large_files(tree);
-        self.build_artifacts = Self::rebuild_build_artifacts(tree, self.stale_threshold);
-        self.dirty = false;
+    /// Mark every list out of date, after the tree or the view filter changed
+    pub fn invalidate(&mut self) {
+        self.stale.extend(ViewData::ALL);
+    }
+
+    /// Mark just `data` out of date, when nothing else depends on what changed
+    pub fn invalidate_only(&mut self, data: ViewData) {
+        self.stale.insert(data);
+    }
+
+    /// Rebuild every list now, e.g. for an export without the TUI
+    pub fn rebuild(&mut self, tree: &DiskTree, filter: NodeFilter) {
+        self.invalidate();
+        for data in ViewData::ALL {
+            self.ensure(tree, filter, data);
+        }
+    }
+
+    /// Bring `data` up to date if the tree changed since it was built.
+    /// Returns whether it was rebuilt. Lists never asked for (say, Large
+    /// Files while browsing the tree) are never computed.
+    pub fn ensure(&mut self, tree: &DiskTree, filter: NodeFilter, data: ViewData) -> bool {
+        if !self.stale.remove(&data) {
+            return false;
+        }
+        if data == ViewData::NewestMtimes {
+            self.newest_mtimes = tree.newest_mtimes();
+            return true;
+        }
+        let excluded = tree.excluded_mask(|n| filter.keep(n));
+        match data {
+            ViewData::LargeFiles => {
+                self.large_files = Self::rebuild_large_files(tree, &excluded);
+                self.apply_large_files_filter();
+            }
+            ViewData::BuildArtifacts => {
+                self.build_artifacts = Self::rebuild_build_artifacts(
+                    tree,
+                    &excluded,
+                    self.stale_threshold,
+                    &self.artifact_rules,
+                );
+            }
+            ViewData::EmptyDirs => self.empty_dirs = Self::rebuild_empty_dirs(tree, &excluded),
+            ViewData::Symlinks => {
+                self.symlinks = Self::rebuild_symlinks(tree, &excluded);
+            }
+            ViewData::Changes => {
+                self.changes = Self::rebuild_changes(tree, &excluded, &self.change_deltas);
+            }
+            ViewData::NewestMtimes => {}
+        }
+        true
+    }
+
+    /// Narrow the Large Files view to paths matching `pattern` (see
+    /// `glob_match`); an empty pattern lists everything again
+    pub fn set_large_files_filter(&mut self, pattern: &str) {
+        self.large_files_filter = pattern.to_string();
+        self.apply_large_files_filter();
+    }
+
+    /// Hide Large Files entries below `min_share` of the scanned total
+    pub fn set_large_files_min_share(&mut self, min_share: MinShare) {
+        self.large_files_min_share = min_share;
+        self.apply_large_files_filter();
+    }
+
+    fn apply_large_files_filter(&mut self) {
+        let pattern = self.large_files_filter.trim();
+        let min_share = self.large_files_min_share;
+        self.large_files_shown = self
+            .large_files
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| pattern.is_empty() || glob_match(pattern, &entry.relative_path))
+            .filter(|(_, entry)| min_share.admits_percentage(entry.percentage))
+            .map(|(index, _)| index)
+            .collect();
+        self.rebuild_large_file_rows();
+    }
+
+    /// Large files matching the filters, largest first
+    pub fn shown_large_files(&self) -> impl Iterator<Item = &LargeFileEntry> {
+        self.large_files_shown
+            .iter()
+            .map(|&index| &self.large_files[index])
+    }
+
+    /// Switch the Large Files view between a flat list and groups by parent
+    /// directory
+    pub fn toggle_large_files_grouping(&mut self) {
+        self.large_files_grouped = !self.large_files_grouped;
+        self.rebuild_large_file_rows();
+    }
+
+    /// Fold or unfold the Large Files group for `dir`
+    pub fn set_group_collapsed(&mut self, dir: &str, collapsed: bool) {
+        if collapsed {
+            self.collapsed_groups.insert(dir.to_string());
+        } else {
+            self.collapsed_groups.remove(dir);
+        }
+        self.rebuild_large_file_rows();
+    }
+
+    /// The file on a Large Files row; None for group headers
+    pub fn large_file_at(&self, row: usize) -> Option<&LargeFileEntry> {
+        match self.large_file_rows.get(row)? {
+            LargeFileRow::File(index) => self.large_files.get(*index),
+            LargeFileRow::Group { .. } => None,
+        }
+    }
+
+    /// Parent directory of the group a Large Files row belongs to
+    pub fn large_file_group_at(&self, row: usize) -> Option<&str> {
+        match self.large_file_rows.get(row)? {
+            LargeFileRow::Group { dir, .. } => Some(dir),
+            LargeFileRow::File(index) => self.large_files.get(*index).map(|e| e.parent_dir()),
+        }
+    }
+
+    /// Row of the header for the group of `dir`
+    pub fn large_file_group_row(&self, dir: &str) -> Option<usize> {
+        self.large_file_rows
+            .iter()
+            .position(|row| matches!(row, LargeFileRow::Group { dir: d, .. } if d == dir))
+    }
+
+    /// Groups are ordered by their total size, largest first; files keep
+    /// their size order within a group
+    fn rebuild_large_file_rows(&mut self) {
+        if !self.large_files_grouped {
+            self.large_file_rows = self
+                .large_files_shown
+                .iter()
+                .map(|&index| LargeFileRow::File(index))
+                .collect();
+            return;
+        }
+
+        let mut groups: Vec<(&str, u64, f64, Vec<usize>)> = Vec::new();
+        let mut group_of: HashMap<&str, usize> = HashMap::new();
+        for &index in &self.large_files_shown {
+            let entry = &self.large_files[index];
+            let dir = entry.parent_dir();
+            let group = *group_of.entry(dir).or_insert_with(|| {
+                groups.push((dir, 0, 0.0, Vec::new()));
+                groups.len() - 1
+            });
+            let (_, size, percentage, files) = &mut groups[group];
+            *size += entry.size;
+            *percentage += entry.percentage;
+            files.push(index);
+        }
+        groups.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut rows = Vec::with_capacity(groups.len() + self.large_files_shown.len());
+        for (dir, size, percentage, files) in groups {
+            let collapsed = self.collapsed_groups.contains(dir);
+            rows.push(LargeFileRow::Group {
+                dir: dir.to_string(),
+                size,
+                percentage,
+                count: files.len(),
+                collapsed,
+            });
+            if !collapsed {
+                rows.extend(files.into_iter().map(LargeFileRow::File));
+            }
+        }
+        self.large_file_rows = rows;
+    }
+
+    /// Node IDs of every artifact of `kind` except the `keep` most recently
+    /// built, for pruning old copies across many projects. Artifacts with
+    /// no known mtime count as the oldest.
+    pub fn all_but_newest(&self, kind: &ArtifactKind, keep: usize) -> HashSet<NodeId> {
+        let mut artifacts: Vec<&BuildArtifactEntry> = self
+            .build_artifacts
+            .iter()
+            .filter(|e| &e.kind == kind)
+            .collect();
+        artifacts.sort_by(|a, b| {
+            b.newest_mtime
+                .cmp(&a.newest_mtime)
+                .then_with(|| a.relative_path.cmp(&b.relative_path))
+        });
+        artifacts.iter().skip(keep).map(|e| e.node_id).collect()
+    }
+
+    /// Node IDs of every artifact currently flagged stale
+    pub fn stale_artifacts(&self) -> HashSet<NodeId> {
+        self.build_artifacts
+            .iter()
+            .filter(|e| e.is_stale)
+            .map(|e| e.node_id)
+            .collect()
     }
 
     pub fn cycle_stale_threshold(&mut self) {
@@ -150,13 +736,13 @@ impl ComputedViews {
         }
     }
 
-    fn rebuild_large_files(tree: &DiskTree) -> Vec<LargeFileEntry> {
+    fn rebuild_large_files(tree: &DiskTree, excluded: &[bool]) -> Vec<LargeFileEntry> {
         let total_size = tree.total_size();
         let root_path = tree.root_path();
 
         let mut entries: Vec<LargeFileEntry> = tree
             .iter()
-            .filter(|node| node.kind == NodeKind::File)
+            .filter(|node| node.kind == NodeKind::File && !excluded[node.id.index()])
             .map(|node| {
                 let relative_path = node
                     .path
@@ -173,13 +759,21 @@ impl ComputedViews {
             })
             .collect();
 
-        entries.sort_by(|a, b| b.size.cmp(&a.size));
+        // Ties go to the path; entries come in NodeId order and the sort is
+        // stable, so the order is deterministic either way
+        entries.sort_by(|a, b| {
+            b.size
+                .cmp(&a.size)
+                .then_with(|| a.relative_path.cmp(&b.relative_path))
+        });
         entries
     }
 
     fn rebuild_build_artifacts(
         tree: &DiskTree,
+        excluded: &[bool],
         threshold: StaleThreshold,
+        rules: &ArtifactRules,
     ) -> Vec<BuildArtifactEntry> {
         let total_size = tree.total_size();
         let root_path = tree.root_path();
@@ -188,15 +782,15 @@ impl ComputedViews {
         let mut entries: Vec<BuildArtifactEntry> = tree
             .iter()
             .filter_map(|node| {
-                if !node.kind.is_directory() {
+                if !node.kind.is_directory() || excluded[node.id.index()] {
                     return None;
                 }
-                let kind = classify_artifact(&node.name)?;
+                let kind = rules.classify(&node.name)?.clone();
                 // Skip if any ancestor is also a build artifact (e.g. target/debug/build)
                 let mut parent_id = node.parent;
                 while let Some(pid) = parent_id {
                     if let Some(parent) = tree.get(pid) {
-                        if classify_artifact(&parent.name).is_some() {
+                        if rules.classify(&parent.name).is_some() {
                             return None;
                         }
                         parent_id = parent.parent;
@@ -225,17 +819,131 @@ impl ComputedViews {
                     size: node.size,
                     percentage: size_percentage(node.size, total_size),
                     kind,
+                    file_count: node.file_count,
                     is_stale,
                     newest_mtime,
                 })
             })
             .collect();
 
-        entries.sort_by(|a, b| b.size.cmp(&a.size));
+        // Same tie-breaking as Large Files
+        entries.sort_by(|a, b| {
+            b.size
+                .cmp(&a.size)
+                .then_with(|| a.relative_path.cmp(&b.relative_path))
+        });
         entries
     }
 
-    /// Walk all descendant directories and return the most recent mtime
+    /// Directories containing nothing but other empty directories, sorted by
+    /// path. Symlinks and unreadable entries count as content, so a listed
+    /// directory is always safe to remove as a whole.
+    fn rebuild_empty_dirs(tree: &DiskTree, excluded: &[bool]) -> Vec<EmptyDirEntry> {
+        let mut empty = vec![false; tree.len()];
+        let mut nested = vec![0u64; tree.len()];
+        // Parents precede children in the arena, so walking backwards sees
+        // every child before its parent
+        let nodes: Vec<&TreeNode> = tree.iter().collect();
+        for node in nodes.iter().rev() {
+            if node.kind != NodeKind::Directory {
+                continue;
+            }
+            let i = node.id.index();
+            empty[i] = node.children.iter().all(|c| empty[c.index()]);
+            nested[i] = node.children.iter().map(|c| nested[c.index()] + 1).sum();
+        }
+
+        let root_path = tree.root_path();
+        let mut entries: Vec<EmptyDirEntry> = nodes
+            .iter()
+            .filter(|node| {
+                let i = node.id.index();
+                empty[i]
+                    && !excluded[i]
+                    && node
+                        .parent
+                        .is_some_and(|p| p == NodeId::ROOT || !empty[p.index()])
+            })
+            .map(|node| EmptyDirEntry {
+                node_id: node.id,
+                relative_path: node
+                    .path
+                    .strip_prefix(root_path)
+                    .unwrap_or(&node.path)
+                    .to_string_lossy()
+                    .to_string(),
+                size: node.size,
+                nested: nested[node.id.index()],
+            })
+            .collect();
+
+        entries.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        entries
+    }
+
+    /// Every symlink with where it points: broken links first, then links
+    /// leaving the tree, then links within it, each group sorted by path.
+    /// Where each leads was resolved by the scan.
+    fn rebuild_symlinks(tree: &DiskTree, excluded: &[bool]) -> Vec<SymlinkEntry> {
+        let root_path = tree.root_path();
+        let mut entries: Vec<SymlinkEntry> = tree
+            .iter()
+            .filter(|node| node.kind == NodeKind::Symlink && !excluded[node.id.index()])
+            .map(|node| SymlinkEntry {
+                node_id: node.id,
+                relative_path: node
+                    .path
+                    .strip_prefix(root_path)
+                    .unwrap_or(&node.path)
+                    .to_string_lossy()
+                    .to_string(),
+                target: node.link_target.clone(),
+                status: node.link_status.unwrap_or(LinkStatus::Broken),
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            a.status
+                .cmp(&b.status)
+                .then_with(|| a.relative_path.cmp(&b.relative_path))
+        });
+        entries
+    }
+
+    /// Changed paths, largest change (either way) first. Removed paths and
+    /// paths deleted during the session have no node.
+    fn rebuild_changes(
+        tree: &DiskTree,
+        excluded: &[bool],
+        deltas: &[(PathBuf, i64)],
+    ) -> Vec<ChangeEntry> {
+        let root_path = tree.root_path();
+        let mut entries: Vec<ChangeEntry> = deltas
+            .iter()
+            .filter_map(|(path, delta)| {
+                let node_id = tree.find_by_path(path);
+                if node_id.is_some_and(|id| excluded[id.index()]) {
+                    return None;
+                }
+                Some(ChangeEntry {
+                    node_id,
+                    path: path.clone(),
+                    relative_path: path
+                        .strip_prefix(root_path)
+                        .unwrap_or(path)
+                        .to_string_lossy()
+                        .to_string(),
+                    delta: *delta,
+                    size: node_id.and_then(|id| tree.get(id)).map_or(0, |n| n.size),
+                })
+            })
+            .collect();
+
+        entries.sort_by_key(|e| std::cmp::Reverse(e.delta.unsigned_abs()));
+        entries
+    }
+
+    /// Walk all descendants and return the most recent mtime
     fn newest_descendant_mtime(tree: &DiskTree, root: NodeId) -> Option<SystemTime> {
         let mut newest: Option<SystemTime> = None;
         let mut stack = vec![root];
@@ -255,3 +963,350 @@ impl ComputedViews {
         newest
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_min_share_hides_small_children() {
+        let mut tree = DiskTree::new(PathBuf::from("/r"));
+        let big = tree.add_sized("big", NodeKind::File, 0, NodeId::ROOT);
+        let small = tree.add_sized("small", NodeKind::File, 0, NodeId::ROOT);
+        tree.set_size(big, 995);
+        tree.set_size(small, 5);
+        tree.aggregate_sizes();
+
+        let mut filter = NodeFilter::default();
+        assert_eq!(filter.min_share.label(), None);
+        assert_eq!(filter.min_share.step(false), MinShare::default());
+        filter.min_share = filter.min_share.step(true).step(true);
+        assert_eq!(filter.min_share.label(), Some("Hiding <0.5%"));
+        let keep = |id| filter.keep_in(&tree, tree.get(id).unwrap());
+        assert!(keep(NodeId::ROOT));
+        assert!(keep(big));
+        assert!(keep(small)); // exactly 0.5%
+
+        filter.min_share = filter.min_share.step(true);
+        let keep = |id| filter.keep_in(&tree, tree.get(id).unwrap());
+        assert!(keep(big));
+        assert!(!keep(small));
+        assert_eq!(
+            tree.visible_nodes_filtered(NodeId::ROOT, &|n| filter.keep_in(&tree, n)),
+            vec![NodeId::ROOT, big]
+        );
+    }
+
+    #[test]
+    fn test_filter_summary_counts_hidden_subtrees() {
+        let mut tree = DiskTree::new(PathBuf::from("/r"));
+        let src = tree.add_sized("src", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("main.rs", NodeKind::File, 900, src);
+        let git = tree.add_sized(".git", NodeKind::Directory, 0, NodeId::ROOT);
+        let objects = tree.add_sized("objects", NodeKind::Directory, 0, git);
+        tree.add_sized("pack", NodeKind::File, 80, objects);
+        tree.add_sized("HEAD", NodeKind::File, 20, git);
+        tree.add_sized("tiny", NodeKind::File, 1, NodeId::ROOT);
+        tree.aggregate_sizes();
+
+        let mut filter = NodeFilter::default();
+        assert_eq!(
+            FilterSummary::compute(&tree, NodeId::ROOT, filter),
+            FilterSummary {
+                shown_files: 4,
+                total_files: 4,
+                hidden_size: 0,
+            }
+        );
+
+        // A hidden directory counts with everything below it
+        filter.show_hidden = false;
+        filter.min_share = filter.min_share.step(true).step(true).step(true);
+        assert_eq!(
+            FilterSummary::compute(&tree, NodeId::ROOT, filter),
+            FilterSummary {
+                shown_files: 1,
+                total_files: 4,
+                hidden_size: 101,
+            }
+        );
+        assert_eq!(
+            FilterSummary::compute(&tree, src, filter),
+            FilterSummary {
+                shown_files: 1,
+                total_files: 1,
+                hidden_size: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_artifact_rules_builtin_and_custom() {
+        let rules = ArtifactRules::default();
+        assert_eq!(rules.classify("target"), Some(&ArtifactKind::Rust));
+        assert_eq!(rules.classify("src"), None);
+
+        let custom: HashMap<String, String> = [
+            ("bazel-out".to_string(), "Bazel".to_string()),
+            (".build-cache".to_string(), "cache".to_string()),
+            ("target".to_string(), "Maven".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let rules = rules.with_rules(custom);
+        assert_eq!(
+            rules.classify("bazel-out"),
+            Some(&ArtifactKind::Custom("Bazel".to_string()))
+        );
+        // Labels matching a built-in reuse that kind
+        assert_eq!(rules.classify(".build-cache"), Some(&ArtifactKind::Cache));
+        // User rules override built-ins
+        assert_eq!(rules.classify("target").map(|k| k.label()), Some("Maven"));
+        assert_eq!(rules.classify("node_modules"), Some(&ArtifactKind::Node));
+    }
+
+    #[test]
+    fn test_artifact_rules_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("artifacts.toml");
+        std::fs::write(&path, "\"bazel-out\" = \"Bazel\"\n").unwrap();
+        let rules = ArtifactRules::from_file(&path).unwrap();
+        assert_eq!(
+            rules.classify("bazel-out").map(|k| k.label()),
+            Some("Bazel")
+        );
+
+        std::fs::write(&path, "bazel-out = 3\n").unwrap();
+        assert!(ArtifactRules::from_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_kind_filter_keeps_directories_leading_to_files() {
+        let mut tree = DiskTree::new(PathBuf::from("/k"));
+        let src = tree.add_sized("src", NodeKind::Directory, 0, NodeId::ROOT);
+        let main = tree.add_sized("main.rs", NodeKind::File, 0, src);
+        let empty = tree.add_sized("empty", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("link", NodeKind::Symlink, 0, NodeId::ROOT);
+        tree.aggregate_sizes();
+        tree.set_expanded(src, true);
+
+        let visible = |kind| {
+            let filter = NodeFilter {
+                kind,
+                ..NodeFilter::default()
+            };
+            let mut nodes =
+                tree.visible_nodes_filtered(NodeId::ROOT, &|n| filter.keep_in(&tree, n));
+            nodes.sort_by_key(|id| id.index());
+            nodes
+        };
+        assert_eq!(visible(KindFilter::All).len(), 5);
+        assert_eq!(
+            visible(KindFilter::DirectoriesOnly),
+            vec![NodeId::ROOT, src, empty]
+        );
+        assert_eq!(
+            visible(KindFilter::FilesOnly),
+            vec![NodeId::ROOT, src, main]
+        );
+        assert_eq!(KindFilter::FilesOnly.next(), KindFilter::All);
+        assert_eq!(KindFilter::All.label(), None);
+    }
+
+    #[test]
+    fn test_empty_dirs_lists_outermost() {
+        let mut tree = DiskTree::new(PathBuf::from("/w"));
+        let scaffold = tree.add_sized("scaffold", NodeKind::Directory, 0, NodeId::ROOT);
+        let inner = tree.add_sized("inner", NodeKind::Directory, 0, scaffold);
+        tree.add_sized("deeper", NodeKind::Directory, 0, inner);
+        let src = tree.add_sized("src", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("main.rs", NodeKind::File, 0, src);
+        let gen_dir = tree.add_sized("gen", NodeKind::Directory, 0, src);
+        let links = tree.add_sized("links", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("l", NodeKind::Symlink, 0, links);
+        tree.aggregate_sizes();
+
+        let entries = ComputedViews::rebuild_empty_dirs(&tree, &vec![false; tree.len()]);
+        let listed: Vec<(NodeId, u64)> = entries.iter().map(|e| (e.node_id, e.nested)).collect();
+        assert_eq!(listed, vec![(scaffold, 2), (gen_dir, 0)]);
+    }
+
+    #[test]
+    fn test_views_rebuild_only_when_asked_for() {
+        let mut tree = DiskTree::new(PathBuf::from("/v"));
+        let file = tree.add_sized("a.bin", NodeKind::File, 0, NodeId::ROOT);
+        tree.add_sized("empty", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.set_size(file, 10);
+        tree.aggregate_sizes();
+
+        let mut views = ComputedViews::new();
+        let filter = NodeFilter::default();
+        assert!(views.ensure(&tree, filter, ViewData::LargeFiles));
+        assert!(!views.ensure(&tree, filter, ViewData::LargeFiles));
+        assert_eq!(views.large_files.len(), 1);
+        // Never asked for, so never built
+        assert!(views.empty_dirs.is_empty());
+
+        views.invalidate();
+        assert!(views.ensure(&tree, filter, ViewData::EmptyDirs));
+        assert_eq!(views.empty_dirs.len(), 1);
+        views.invalidate_only(ViewData::Changes);
+        assert!(!views.ensure(&tree, filter, ViewData::EmptyDirs));
+        assert!(views.ensure(&tree, filter, ViewData::LargeFiles));
+    }
+
+    #[test]
+    fn test_symlinks_sorted_broken_first() {
+        let mut tree = DiskTree::new(PathBuf::from("/w"));
+        let mut link = |name: &str, target: &str, status: LinkStatus| {
+            let id = tree.add_sized(name, NodeKind::Symlink, 0, NodeId::ROOT);
+            let node = tree.get_mut(id).unwrap();
+            node.link_target = Some(PathBuf::from(target));
+            node.link_status = Some(status);
+            id
+        };
+        let lib = link("lib", "usr/lib", LinkStatus::Inside);
+        let brew = link("brew", "/opt/brew", LinkStatus::Outside);
+        let gone = link("gone", "missing", LinkStatus::Broken);
+
+        let entries = ComputedViews::rebuild_symlinks(&tree, &vec![false; tree.len()]);
+        let listed: Vec<(NodeId, LinkStatus)> =
+            entries.iter().map(|e| (e.node_id, e.status)).collect();
+        assert_eq!(
+            listed,
+            vec![
+                (gone, LinkStatus::Broken),
+                (brew, LinkStatus::Outside),
+                (lib, LinkStatus::Inside),
+            ]
+        );
+        assert_eq!(entries[2].target, Some(PathBuf::from("usr/lib")));
+    }
+
+    #[test]
+    fn test_large_files_filter_picks_rows_without_copying() {
+        let mut views = ComputedViews::new();
+        views.large_files = ["a.mov", "disk.img", "b.mov"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, path)| LargeFileEntry {
+                node_id: NodeId(i + 1),
+                relative_path: path.to_string(),
+                size: 100 - i as u64,
+                percentage: 30.0,
+            })
+            .collect();
+        views.set_large_files_filter("*.mov");
+        assert_eq!(
+            views.large_file_rows,
+            [LargeFileRow::File(0), LargeFileRow::File(2)]
+        );
+        assert_eq!(views.large_file_at(1).unwrap().relative_path, "b.mov");
+        assert_eq!(views.shown_large_files().count(), 2);
+        assert_eq!(views.large_files.len(), 3);
+
+        views.set_large_files_filter("");
+        assert_eq!(views.shown_large_files().count(), 3);
+    }
+
+    #[test]
+    fn test_large_files_group_by_parent() {
+        let mut views = ComputedViews::new();
+        views.large_files = [
+            ("Downloads/clips/a.mov", 500),
+            ("disk.img", 400),
+            ("Downloads/clips/b.mov", 300),
+            ("Downloads/setup.dmg", 200),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (path, size))| LargeFileEntry {
+            node_id: NodeId(i + 1),
+            relative_path: path.to_string(),
+            size,
+            percentage: size as f64 / 14.0,
+        })
+        .collect();
+        views.apply_large_files_filter();
+        assert_eq!(views.large_file_rows.len(), 4);
+        assert_eq!(views.large_file_at(1).unwrap().size, 400);
+
+        views.toggle_large_files_grouping();
+        let headers: Vec<_> = views
+            .large_file_rows
+            .iter()
+            .filter_map(|row| match row {
+                LargeFileRow::Group {
+                    dir, size, count, ..
+                } => Some((dir.as_str(), *size, *count)),
+                LargeFileRow::File(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            headers,
+            [
+                ("Downloads/clips", 800, 2),
+                ("", 400, 1),
+                ("Downloads", 200, 1),
+            ]
+        );
+        assert_eq!(views.large_file_at(0).map(|e| e.size), None);
+        assert_eq!(views.large_file_at(2).unwrap().size, 300);
+        assert_eq!(views.large_file_group_at(2), Some("Downloads/clips"));
+
+        views.set_group_collapsed("Downloads/clips", true);
+        assert_eq!(views.large_file_rows.len(), 5);
+        assert_eq!(views.large_file_group_row(""), Some(1));
+        assert_eq!(views.large_file_at(2).unwrap().size, 400);
+    }
+
+    #[test]
+    fn test_dedup_ancestors_keeps_topmost() {
+        let mut tree = DiskTree::new(PathBuf::from("/w"));
+        let repo = tree.add_sized("repo", NodeKind::Directory, 0, NodeId::ROOT);
+        let target = tree.add_sized("target", NodeKind::Directory, 0, repo);
+        let other = tree.add_sized("node_modules", NodeKind::Directory, 0, NodeId::ROOT);
+
+        let set: HashSet<NodeId> = [repo, target, other].into_iter().collect();
+        let mut deduped = dedup_ancestors(&tree, &set);
+        deduped.sort_by_key(|id| id.index());
+        assert_eq!(deduped, vec![repo, other]);
+    }
+
+    #[test]
+    fn test_all_but_newest_keeps_most_recent_of_kind() {
+        let day = Duration::from_secs(86_400);
+        let mut views = ComputedViews::new();
+        views.build_artifacts = [
+            ("a/target", ArtifactKind::Rust, Some(3)),
+            ("b/target", ArtifactKind::Rust, Some(9)),
+            ("c/node_modules", ArtifactKind::Node, Some(1)),
+            ("d/target", ArtifactKind::Rust, None),
+            ("e/target", ArtifactKind::Rust, Some(6)),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(i, (path, kind, days))| BuildArtifactEntry {
+            node_id: NodeId(i + 1),
+            relative_path: path.to_string(),
+            size: 100,
+            percentage: 20.0,
+            kind,
+            file_count: 1,
+            is_stale: false,
+            newest_mtime: days.map(|d| SystemTime::UNIX_EPOCH + day * d),
+        })
+        .collect();
+
+        // b and e are the newest targets; d has no mtime so goes first
+        assert_eq!(
+            views.all_but_newest(&ArtifactKind::Rust, 2),
+            HashSet::from([NodeId(1), NodeId(4)])
+        );
+        assert_eq!(views.all_but_newest(&ArtifactKind::Rust, 0).len(), 4);
+        assert!(views.all_but_newest(&ArtifactKind::Rust, 4).is_empty());
+        assert!(views.all_but_newest(&ArtifactKind::Node, 1).is_empty());
+    }
+}
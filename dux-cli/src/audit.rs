@@ -0,0 +1,86 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+/// How a deleted item was removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeleteMethod {
+    /// Removed outright, not moved to a trash
+    Permanent,
+}
+
+/// One line of the deletion audit log (`deletions.jsonl`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeletionRecord {
+    /// Local time of the deletion, RFC 3339
+    pub time: String,
+    pub path: PathBuf,
+    /// Disk usage freed, in bytes
+    pub size: u64,
+    pub method: DeleteMethod,
+}
+
+impl DeletionRecord {
+    pub fn now(path: PathBuf, size: u64) -> Self {
+        Self {
+            time: Local::now().to_rfc3339(),
+            path,
+            size,
+            method: DeleteMethod::Permanent,
+        }
+    }
+}
+
+/// Append a record to the log at `log` (best effort: the item is already
+/// gone, so a failed write must not get in the way)
+pub fn append(log: &Path, record: &DeletionRecord) {
+    let Ok(line) = serde_json::to_string(record) else {
+        return;
+    };
+    if let Some(parent) = log.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(log) {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+/// The last `count` records in the log, oldest first. Lines that don't
+/// parse are skipped.
+pub fn recent(log: &Path, count: usize) -> Vec<DeletionRecord> {
+    let contents = fs::read_to_string(log).unwrap_or_default();
+    let records: Vec<DeletionRecord> = contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    records[records.len().saturating_sub(count)..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_and_read_recent() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("nested/deletions.jsonl");
+        for (name, size) in [("a", 1), ("b", 2), ("c", 3)] {
+            append(&log, &DeletionRecord::now(PathBuf::from(name), size));
+        }
+        let mut contents = fs::read_to_string(&log).unwrap();
+        contents.push_str("not json\n");
+        fs::write(&log, contents).unwrap();
+
+        let records = recent(&log, 2);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].path, PathBuf::from("b"));
+        assert_eq!(records[1].size, 3);
+        assert_eq!(records[1].method, DeleteMethod::Permanent);
+        assert_eq!(recent(&log, 10).len(), 3);
+        assert!(recent(&dir.path().join("missing.jsonl"), 5).is_empty());
+    }
+}
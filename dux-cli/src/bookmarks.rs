@@ -0,0 +1,105 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+/// Directories saved with `b`, persisted in `bookmarks.toml`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Bookmarks {
+    #[serde(default)]
+    paths: Vec<PathBuf>,
+}
+
+impl Bookmarks {
+    /// Load bookmarks from the config directory; a missing or unreadable
+    /// file means no bookmarks
+    pub fn load() -> Self {
+        config::bookmarks_file()
+            .map(|path| Self::load_from(&path))
+            .unwrap_or_default()
+    }
+
+    fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save bookmarks to the config directory (best effort)
+    pub fn save(&self) {
+        if let Some(path) = config::bookmarks_file() {
+            self.save_to(&path);
+        }
+    }
+
+    fn save_to(&self, path: &Path) {
+        if let Some(parent) = path.parent()
+            && fs::create_dir_all(parent).is_ok()
+            && let Ok(contents) = toml::to_string(self)
+        {
+            let _ = fs::write(path, contents);
+        }
+    }
+
+    /// Add a path, keeping the list sorted; false if it was already there
+    pub fn add(&mut self, path: PathBuf) -> bool {
+        match self.paths.binary_search(&path) {
+            Ok(_) => false,
+            Err(index) => {
+                self.paths.insert(index, path);
+                true
+            }
+        }
+    }
+
+    /// Remove a path; false if it wasn't bookmarked
+    pub fn remove(&mut self, path: &Path) -> bool {
+        let before = self.paths.len();
+        self.paths.retain(|p| p != path);
+        self.paths.len() != before
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.paths.iter().any(|p| p == path)
+    }
+
+    pub fn list(&self) -> &[PathBuf] {
+        &self.paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_remove_and_round_trip() {
+        let mut bookmarks = Bookmarks::default();
+        assert!(bookmarks.add(PathBuf::from("/work/b")));
+        assert!(bookmarks.add(PathBuf::from("/work/a")));
+        assert!(!bookmarks.add(PathBuf::from("/work/a")));
+        assert_eq!(
+            bookmarks.list(),
+            [PathBuf::from("/work/a"), PathBuf::from("/work/b")]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("nested/bookmarks.toml");
+        bookmarks.save_to(&file);
+        let mut loaded = Bookmarks::load_from(&file);
+        assert_eq!(loaded.list(), bookmarks.list());
+
+        assert!(loaded.remove(Path::new("/work/a")));
+        assert!(!loaded.remove(Path::new("/work/a")));
+        assert!(loaded.contains(Path::new("/work/b")));
+        assert_eq!(loaded.list(), [PathBuf::from("/work/b")]);
+        assert!(
+            Bookmarks::load_from(&dir.path().join("missing.toml"))
+                .list()
+                .is_empty()
+        );
+    }
+}
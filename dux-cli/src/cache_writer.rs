@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+
+use dux_core::{CacheMetadata, DiskTree, save_cache};
+
+/// Sole writer of one cache file. Saves run one at a time on a background
+/// thread; when several queue up behind a slow write only the latest is
+/// written, so an older tree can never land on top of a newer one.
+pub struct CacheWriter {
+    path: PathBuf,
+    jobs: Option<mpsc::Sender<(DiskTree, CacheMetadata)>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl CacheWriter {
+    pub fn new(path: PathBuf) -> Self {
+        let (tx, rx) = mpsc::channel::<(DiskTree, CacheMetadata)>();
+        let thread_path = path.clone();
+        let handle = std::thread::spawn(move || {
+            while let Ok(mut job) = rx.recv() {
+                // Anything queued meanwhile supersedes this one
+                while let Ok(newer) = rx.try_recv() {
+                    job = newer;
+                }
+                let (tree, meta) = job;
+                match save_cache(&thread_path, &tree, &meta) {
+                    Ok(()) => log::info!("cache written to {}", thread_path.display()),
+                    Err(e) => log::warn!("cache not written to {}: {}", thread_path.display(), e),
+                }
+            }
+        });
+        Self {
+            path,
+            jobs: Some(tx),
+            handle: Some(handle),
+        }
+    }
+
+    /// Queue `tree` to be written; returns at once
+    pub fn save(&self, tree: DiskTree, meta: CacheMetadata) {
+        if let Some(jobs) = &self.jobs {
+            let _ = jobs.send((tree, meta));
+        }
+    }
+
+    /// Wait for queued saves to be written, so exiting never cuts one short
+    pub fn finish(mut self) {
+        self.jobs.take();
+        if let Some(handle) = self.handle.take()
+            && handle.join().is_err()
+        {
+            log::warn!("cache writer for {} panicked", self.path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use dux_core::{CachedScanConfig, NodeKind, load_cache};
+
+    use super::*;
+
+    #[test]
+    fn test_cache_writer_keeps_latest_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tree.cache");
+        let meta = |node_count| CacheMetadata {
+            version: dux_core::CACHE_VERSION,
+            root_path: PathBuf::from("/r"),
+            scan_time: SystemTime::now(),
+            root_mtime: SystemTime::UNIX_EPOCH,
+            total_size: 0,
+            node_count,
+            config: CachedScanConfig {
+                follow_symlinks: false,
+                size_symlinks: false,
+                same_filesystem: true,
+                max_depth: None,
+                track_apparent: false,
+                extra_filesystems: Vec::new(),
+                sample_depth: None,
+                exclude: Vec::new(),
+                count_dir_overhead: false,
+            },
+            partial: false,
+        };
+
+        let writer = CacheWriter::new(path.clone());
+        let mut tree = DiskTree::new(PathBuf::from("/r"));
+        writer.save(tree.clone(), meta(1));
+        tree.add_sized("a", NodeKind::File, 0, dux_core::NodeId::ROOT);
+        writer.save(tree, meta(2));
+        writer.finish();
+
+        let (loaded, tree) = load_cache(&path).unwrap();
+        assert_eq!(loaded.node_count, 2);
+        assert_eq!(tree.len(), 2);
+        assert!(!path.with_extension("tmp").exists());
+    }
+}
@@ -0,0 +1,177 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use color_eyre::Result;
+use color_eyre::eyre::{bail, eyre};
+use serde::{Deserialize, Deserializer};
+
+use crate::ui::ThemeName;
+
+/// Default flags from `config.toml`. Keys are the long flag names
+/// (`cross-filesystems = true`, `theme = "light"`); flags given on the
+/// command line win, and a missing key keeps the built-in default.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub max_depth: Option<usize>,
+    pub follow_symlinks: Option<bool>,
+    pub size_symlinks: Option<bool>,
+    pub cross_filesystems: Option<bool>,
+    pub threads: Option<u16>,
+    pub also_scan: Option<Vec<PathBuf>>,
+    #[serde(deserialize_with = "duration")]
+    pub timeout: Option<Duration>,
+    pub sample: Option<usize>,
+    pub exclude: Option<Vec<PathBuf>>,
+    pub no_cache: Option<bool>,
+    pub cache_dir: Option<PathBuf>,
+    pub verify_cache: Option<bool>,
+    pub cache_spot_checks: Option<usize>,
+    pub always_rescan: Option<Vec<PathBuf>>,
+    pub apparent: Option<bool>,
+    pub dir_overhead: Option<bool>,
+    pub progress_bar: Option<bool>,
+    pub tick_rate: Option<u64>,
+    pub si: Option<bool>,
+    pub precision: Option<u8>,
+    #[serde(deserialize_with = "size")]
+    pub confirm_above: Option<u64>,
+    pub read_only: Option<bool>,
+    #[serde(deserialize_with = "size")]
+    pub need: Option<u64>,
+    pub report_top: Option<usize>,
+    #[serde(deserialize_with = "theme_name")]
+    pub theme: Option<ThemeName>,
+    pub overview: Option<bool>,
+    pub compact: Option<bool>,
+    pub ascii: Option<bool>,
+    pub no_audit: Option<bool>,
+}
+
+impl Config {
+    /// Read `config.toml`; no file means built-in defaults, and an invalid
+    /// one is reported and ignored
+    pub fn load() -> Self {
+        let Some(path) = config_file().filter(|path| path.exists()) else {
+            return Self::default();
+        };
+        Self::from_file(&path).unwrap_or_else(|e| {
+            eprintln!("Warning: {} (using built-in defaults)", e);
+            Self::default()
+        })
+    }
+
+    fn from_file(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Self::parse(&contents).map_err(|e| eyre!("Invalid config file {}: {}", path.display(), e))
+    }
+
+    /// Parse and range-check the same limits the command line enforces
+    fn parse(contents: &str) -> Result<Self> {
+        let config: Self = toml::from_str(contents)?;
+        if config.threads.is_some_and(|threads| threads > 256) {
+            bail!("`threads` must be 0-256");
+        }
+        if config
+            .tick_rate
+            .is_some_and(|ms| !(10..=1000).contains(&ms))
+        {
+            bail!("`tick-rate` must be 10-1000");
+        }
+        if config.precision.is_some_and(|precision| precision > 3) {
+            bail!("`precision` must be 0-3");
+        }
+        Ok(config)
+    }
+}
+
+fn duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    crate::parse_duration(&text)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+fn size<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    crate::parse_size(&text)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+fn theme_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<ThemeName>, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    ThemeName::from_name(&text)
+        .map(Some)
+        .ok_or_else(|| serde::de::Error::custom(format!("unknown theme `{}`", text)))
+}
+
+/// Directory holding persisted user preferences (`~/.config/dux/` on Linux)
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("dux"))
+}
+
+/// Path of the optional default flags (`config.toml`)
+pub fn config_file() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("config.toml"))
+}
+
+/// Path of the optional custom color theme (`theme.toml`)
+pub fn theme_file() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("theme.toml"))
+}
+
+/// Path of the optional custom build-artifact rules (`artifacts.toml`)
+pub fn artifacts_file() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("artifacts.toml"))
+}
+
+/// Path of the deletion audit log (`deletions.jsonl`)
+pub fn audit_file() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("deletions.jsonl"))
+}
+
+/// Path of the saved bookmarks (`bookmarks.toml`)
+pub fn bookmarks_file() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("bookmarks.toml"))
+}
+
+/// Load the theme chosen in a previous session
+pub fn load_theme_name() -> Option<ThemeName> {
+    let path = config_dir()?.join("theme");
+    let contents = fs::read_to_string(path).ok()?;
+    ThemeName::from_name(contents.trim())
+}
+
+/// Remember the theme for the next session (best effort)
+pub fn save_theme_name(name: ThemeName) {
+    if let Some(dir) = config_dir()
+        && fs::create_dir_all(&dir).is_ok()
+    {
+        let _ = fs::write(dir.join("theme"), name.as_str());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse(
+            "cross-filesystems = true\ntheme = \"light\"\ntimeout = \"5m\"\nconfirm-above = \"500M\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.cross_filesystems, Some(true));
+        assert_eq!(config.theme, Some(ThemeName::Light));
+        assert_eq!(config.timeout, Some(Duration::from_secs(300)));
+        assert_eq!(config.confirm_above, Some(500 * 1024 * 1024));
+        assert_eq!(config.max_depth, None);
+
+        assert!(Config::parse("").unwrap().theme.is_none());
+        assert!(Config::parse("cross_filesystems = true").is_err());
+        assert!(Config::parse("theme = \"neon\"").is_err());
+        assert!(Config::parse("tick-rate = 5").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use dux_core::{DiskTree, NodeId, SizeFormat};
+
+use crate::app::views::ComputedViews;
+
+/// One exported line
+pub struct ExportRow {
+    pub path: PathBuf,
+    pub size: u64,
+    pub percentage: f64,
+    pub kind: String,
+}
+
+/// Every node under `root` in tree order (children largest first),
+/// regardless of expansion state
+pub fn flatten_tree(tree: &DiskTree, root: NodeId) -> Vec<NodeId> {
+    let mut nodes = Vec::new();
+    let mut stack = vec![root];
+    while let Some(id) = stack.pop() {
+        nodes.push(id);
+        if let Some(node) = tree.get(id) {
+            stack.extend(node.children.iter().rev());
+        }
+    }
+    nodes
+}
+
+/// Rows for Tree view nodes, as percentages of `base`
+pub fn tree_rows(tree: &DiskTree, nodes: &[NodeId], base: u64) -> Vec<ExportRow> {
+    nodes
+        .iter()
+        .filter_map(|&id| tree.get(id))
+        .map(|node| ExportRow {
+            path: node.path.clone(),
+            size: node.size,
+            percentage: percent_of(node.size, base),
+            kind: node.kind.label().to_string(),
+        })
+        .collect()
+}
+
+/// Rows for the Large Files view
+pub fn large_file_rows(tree: &DiskTree, views: &ComputedViews) -> Vec<ExportRow> {
+    views
+        .shown_large_files()
+        .filter_map(|entry| {
+            tree.get(entry.node_id).map(|node| ExportRow {
+                path: node.path.clone(),
+                size: entry.size,
+                percentage: entry.percentage,
+                kind: node.kind.label().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Rows for the Build Artifacts view; `kind` is the artifact label
+pub fn artifact_rows(tree: &DiskTree, views: &ComputedViews) -> Vec<ExportRow> {
+    views
+        .build_artifacts
+        .iter()
+        .filter_map(|entry| {
+            tree.get(entry.node_id).map(|node| ExportRow {
+                path: node.path.clone(),
+                size: entry.size,
+                percentage: entry.percentage,
+                kind: entry.kind.label().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Rows for the Empty Directories view
+pub fn empty_dir_rows(tree: &DiskTree, views: &ComputedViews) -> Vec<ExportRow> {
+    views
+        .empty_dirs
+        .iter()
+        .filter_map(|entry| {
+            tree.get(entry.node_id).map(|node| ExportRow {
+                path: node.path.clone(),
+                size: entry.size,
+                percentage: percent_of(entry.size, tree.total_size()),
+                kind: node.kind.label().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Rows for the Symlinks view; `kind` says where the link leads (`broken`,
+/// `outside` or `inside` the scanned tree)
+pub fn symlink_rows(tree: &DiskTree, views: &ComputedViews) -> Vec<ExportRow> {
+    views
+        .symlinks
+        .iter()
+        .filter_map(|entry| {
+            tree.get(entry.node_id).map(|node| ExportRow {
+                path: node.path.clone(),
+                size: node.size,
+                percentage: percent_of(node.size, tree.total_size()),
+                kind: entry.status.label().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Rows for the Changes view. `size` is the size of the change and `kind`
+/// says which way it went (`grew`, `shrank` or `removed`)
+pub fn change_rows(tree: &DiskTree, views: &ComputedViews) -> Vec<ExportRow> {
+    views
+        .changes
+        .iter()
+        .map(|entry| {
+            let size = entry.delta.unsigned_abs();
+            let kind = match (entry.node_id, entry.delta > 0) {
+                (None, _) => "removed",
+                (Some(_), true) => "grew",
+                (Some(_), false) => "shrank",
+            };
+            ExportRow {
+                path: entry.path.clone(),
+                size,
+                percentage: percent_of(size, tree.total_size()),
+                kind: kind.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Write rows with a header line: `path,size_bytes,size_human,percentage,kind`
+pub fn write_csv<W: Write>(mut out: W, rows: &[ExportRow], format: SizeFormat) -> io::Result<()> {
+    writeln!(out, "path,size_bytes,size_human,percentage,kind")?;
+    for row in rows {
+        writeln!(
+            out,
+            "{},{},{},{:.2},{}",
+            quote(&row.path.to_string_lossy()),
+            row.size,
+            quote(&format.format(row.size)),
+            row.percentage,
+            quote(&row.kind),
+        )?;
+    }
+    out.flush()
+}
+
+fn percent_of(size: u64, base: u64) -> f64 {
+    if base == 0 {
+        0.0
+    } else {
+        size as f64 / base as f64 * 100.0
+    }
+}
+
+/// Quote a field when it contains a delimiter, quote or line break,
+/// doubling embedded quotes (RFC 4180)
+fn quote(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("/plain/path"), "/plain/path");
+        assert_eq!(quote("/a,b"), "\"/a,b\"");
+        assert_eq!(quote("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(quote("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let rows = vec![ExportRow {
+            path: PathBuf::from("/data/a, b.bin"),
+            size: 2048,
+            percentage: 12.345,
+            kind: "file".to_string(),
+        }];
+        let mut out = Vec::new();
+        write_csv(&mut out, &rows, SizeFormat::default()).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "path,size_bytes,size_human,percentage,kind\n\"/data/a, b.bin\",2048,2.0 KiB,12.35,file\n"
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod csv;
+pub mod report;
+pub mod selection;
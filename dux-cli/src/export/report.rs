@@ -0,0 +1,357 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use dux_core::{DiskTree, NodeId, SizeFormat, size_percentage};
+
+use crate::app::views::NodeFilter;
+
+/// Plain-text summary of `root`'s largest children for pasting into chat or
+/// a ticket: a header with the directory's size, then one aligned row per
+/// child (size, share, name) and a final row folding in the rest.
+pub fn size_report(
+    tree: &DiskTree,
+    root: NodeId,
+    filter: NodeFilter,
+    limit: usize,
+    format: SizeFormat,
+) -> String {
+    let Some(root_node) = tree.get(root) else {
+        return String::new();
+    };
+    let children: Vec<_> = root_node
+        .children
+        .iter()
+        .filter_map(|&id| tree.get(id))
+        .filter(|node| filter.keep_in(tree, node))
+        .collect();
+    let (top, rest) = children.split_at(limit.min(children.len()));
+
+    let mut rows: Vec<(String, f64, String)> = top
+        .iter()
+        .map(|node| {
+            let suffix = if node.kind.is_directory() { "/" } else { "" };
+            (
+                format.format(node.size),
+                size_percentage(node.size, root_node.size),
+                format!("{}{}", node.name, suffix),
+            )
+        })
+        .collect();
+    if !rest.is_empty() {
+        let size = rest.iter().map(|node| node.size).sum();
+        rows.push((
+            format.format(size),
+            size_percentage(size, root_node.size),
+            format!("({} more)", rest.len()),
+        ));
+    }
+
+    let width = rows.iter().map(|(size, ..)| size.len()).max().unwrap_or(0);
+    let mut report = format!(
+        "{}  {}\n",
+        root_node.path.display(),
+        format.format(root_node.size)
+    );
+    for (size, percent, name) in rows {
+        let _ = writeln!(report, "{:>width$}  {:>5.1}%  {}", size, percent, name);
+    }
+    report
+}
+
+/// Markdown table (`| Name | Size | % |`) of `root`'s children as the tree
+/// view shows them, for pasting into docs or a pull request. Shares are of
+/// `root`'s size; pipes in names are escaped so rows stay intact.
+pub fn markdown_table(
+    tree: &DiskTree,
+    root: NodeId,
+    filter: NodeFilter,
+    format: SizeFormat,
+) -> String {
+    let Some(root_node) = tree.get(root) else {
+        return String::new();
+    };
+    let mut table = String::from("| Name | Size | % |\n|------|-----:|--:|\n");
+    for node in root_node
+        .children
+        .iter()
+        .filter_map(|&id| tree.get(id))
+        .filter(|node| filter.keep_in(tree, node))
+    {
+        let suffix = if node.kind.is_directory() { "/" } else { "" };
+        let _ = writeln!(
+            table,
+            "| {}{} | {} | {:.1}% |",
+            node.name.replace('|', "\\|"),
+            suffix,
+            format.format(node.size),
+            size_percentage(node.size, root_node.size)
+        );
+    }
+    table
+}
+
+/// Files under this apparent size count as small in the slack report: the
+/// ones worth packing into an archive
+const SMALL_FILE_CUTOFF: u64 = 64 * 1024;
+
+/// Space lost to block rounding under `root`: the total, what archiving
+/// small files could win back, and the children holding the most of it
+pub fn slack_report(tree: &DiskTree, root: NodeId, limit: usize, format: SizeFormat) -> String {
+    let Some(root_node) = tree.get(root) else {
+        return String::new();
+    };
+    let slack = tree.slack_sizes();
+    let total = slack[root.index()];
+
+    let mut report = format!(
+        "{}  slack {} of {} allocated ({:.1}%)\n",
+        root_node.path.display(),
+        format.format(total),
+        format.format(root_node.size),
+        size_percentage(total, root_node.size)
+    );
+    let _ = writeln!(
+        report,
+        "Reclaimable by archiving small files (under {}): ~{}",
+        format.format(SMALL_FILE_CUTOFF),
+        format.format(tree.small_file_slack(root, SMALL_FILE_CUTOFF))
+    );
+
+    let mut children: Vec<_> = root_node
+        .children
+        .iter()
+        .filter_map(|&id| tree.get(id))
+        .map(|node| (slack[node.id.index()], node))
+        .filter(|(slack, _)| *slack > 0)
+        .collect();
+    children
+        .sort_by(|(a, a_node), (b, b_node)| b.cmp(a).then_with(|| a_node.name.cmp(&b_node.name)));
+    children.truncate(limit);
+
+    let width = children
+        .iter()
+        .map(|(slack, _)| format.format(*slack).len())
+        .max()
+        .unwrap_or(0);
+    for (slack, node) in children {
+        let suffix = if node.kind.is_directory() { "/" } else { "" };
+        let _ = writeln!(
+            report,
+            "{:>width$}  {:>5.1}%  {}{}",
+            format.format(slack),
+            size_percentage(slack, total),
+            node.name,
+            suffix
+        );
+    }
+    report
+}
+
+/// Directories that grew or shrank the most from `old` to `new`, matched
+/// by path: a header with both totals, then up to `limit` rows each of
+/// growth and shrinkage, largest change first. Directories only in one tree
+/// count in full.
+pub fn diff_report(old: &DiskTree, new: &DiskTree, limit: usize, format: SizeFormat) -> String {
+    let root = new.root_path();
+    let size_of = |tree: &DiskTree| tree.get(NodeId::ROOT).map_or(0, |node| node.size);
+    let (before, after) = (size_of(old), size_of(new));
+    let mut report = format!(
+        "{}  {} -> {} ({})\n",
+        root.display(),
+        format.format(before),
+        format.format(after),
+        signed(after as i64 - before as i64, format)
+    );
+
+    let is_dir = |path: &Path| {
+        [new, old].iter().any(|tree| {
+            tree.find_by_path(path)
+                .and_then(|id| tree.get(id))
+                .is_some_and(|node| node.kind.is_directory())
+        })
+    };
+    let mut changes: Vec<(PathBuf, i64)> = new
+        .diff(old)
+        .into_iter()
+        .filter(|(path, _)| path != root && is_dir(path))
+        .collect();
+    changes.sort_by(|(a, a_delta), (b, b_delta)| b_delta.cmp(a_delta).then_with(|| a.cmp(b)));
+    let grew: Vec<_> = changes.iter().filter(|(_, d)| *d > 0).take(limit).collect();
+    let shrank: Vec<_> = changes
+        .iter()
+        .rev()
+        .filter(|(_, d)| *d < 0)
+        .take(limit)
+        .collect();
+    if grew.is_empty() && shrank.is_empty() {
+        report.push_str("No directory changed size\n");
+        return report;
+    }
+
+    let width = grew
+        .iter()
+        .chain(&shrank)
+        .map(|(_, delta)| signed(*delta, format).len())
+        .max()
+        .unwrap_or(0);
+    for (title, rows) in [("Grew", grew), ("Shrank", shrank)] {
+        if rows.is_empty() {
+            continue;
+        }
+        let _ = writeln!(report, "{}:", title);
+        for (path, delta) in rows {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            let _ = writeln!(
+                report,
+                "  {:>width$}  {}/",
+                signed(*delta, format),
+                relative.display()
+            );
+        }
+    }
+    report
+}
+
+/// `delta` bytes with an explicit sign, e.g. `+1.0 MiB`
+fn signed(delta: i64, format: SizeFormat) -> String {
+    let sign = if delta < 0 { '-' } else { '+' };
+    format!("{}{}", sign, format.format(delta.unsigned_abs()))
+}
+
+#[cfg(test)]
+mod tests {
+    use dux_core::NodeKind;
+
+    use super::*;
+
+    #[test]
+    fn test_size_report_aligns_and_caps_rows() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let target = tree.add_sized("target", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("app", NodeKind::File, 6 << 20, target);
+        tree.add_sized("Cargo.lock", NodeKind::File, 3 << 10, NodeId::ROOT);
+        tree.add_sized("a", NodeKind::File, 1 << 10, NodeId::ROOT);
+        tree.add_sized("b", NodeKind::File, 1 << 10, NodeId::ROOT);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+
+        let report = size_report(
+            &tree,
+            NodeId::ROOT,
+            NodeFilter::default(),
+            2,
+            SizeFormat::default(),
+        );
+        assert_eq!(
+            report,
+            "/p  6.0 MiB\n\
+             6.0 MiB   99.9%  target/\n\
+             3.0 KiB    0.0%  Cargo.lock\n\
+             2.0 KiB    0.0%  (2 more)\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_table_escapes_pipes() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let target = tree.add_sized("target", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("app", NodeKind::File, 3 << 20, target);
+        tree.add_sized("a|b.txt", NodeKind::File, 1 << 20, NodeId::ROOT);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+
+        let table = markdown_table(
+            &tree,
+            NodeId::ROOT,
+            NodeFilter::default(),
+            SizeFormat::default(),
+        );
+        assert_eq!(
+            table,
+            "| Name | Size | % |\n\
+             |------|-----:|--:|\n\
+             | target/ | 3.0 MiB | 75.0% |\n\
+             | a\\|b.txt | 1.0 MiB | 25.0% |\n"
+        );
+    }
+
+    #[test]
+    fn test_slack_report_ranks_children_by_slack() {
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let mut add = |name: &str, kind, size, apparent, parent| {
+            let id = tree.add_sized(name, kind, size, parent);
+            tree.get_mut(id).unwrap().apparent_size = apparent;
+            id
+        };
+        let modules = add("node_modules", NodeKind::Directory, 0, None, NodeId::ROOT);
+        for name in ["a.js", "b.js", "c.js"] {
+            add(name, NodeKind::File, 4096, Some(96), modules);
+        }
+        add("video.mp4", NodeKind::File, 8192, Some(8000), NodeId::ROOT);
+        add(
+            "sparse.img",
+            NodeKind::File,
+            4096,
+            Some(1 << 20),
+            NodeId::ROOT,
+        );
+        // Slack, but too large to be worth archiving
+        add(
+            "disk.iso",
+            NodeKind::File,
+            200 * 1024,
+            Some(199 * 1024),
+            NodeId::ROOT,
+        );
+        tree.aggregate_sizes();
+
+        let report = slack_report(&tree, NodeId::ROOT, 10, SizeFormat::default());
+        assert_eq!(
+            report,
+            [
+                "/p  slack 12.9 KiB of 224.0 KiB allocated (5.8%)",
+                "Reclaimable by archiving small files (under 64.0 KiB): ~11.9 KiB",
+                "11.7 KiB   90.8%  node_modules/",
+                " 1.0 KiB    7.7%  disk.iso",
+                "   192 B    1.5%  video.mp4",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_diff_report_lists_directory_growth_and_shrinkage() {
+        let build = |dirs: &[(&str, u64)]| {
+            let mut tree = DiskTree::new(PathBuf::from("/p"));
+            for &(name, size) in dirs {
+                let dir = tree.add_sized(name, NodeKind::Directory, 0, NodeId::ROOT);
+                tree.add_sized("data", NodeKind::File, size, dir);
+            }
+            tree.aggregate_sizes();
+            tree
+        };
+        let old = build(&[("docs", 4 << 20), ("src", 1 << 20), ("old", 1 << 10)]);
+        let new = build(&[("docs", 1 << 20), ("src", 2 << 20), ("target", 8 << 20)]);
+
+        let report = diff_report(&old, &new, 10, SizeFormat::default());
+        assert_eq!(
+            report,
+            [
+                "/p  5.0 MiB -> 11.0 MiB (+6.0 MiB)",
+                "Grew:",
+                "  +8.0 MiB  target/",
+                "  +1.0 MiB  src/",
+                "Shrank:",
+                "  -3.0 MiB  docs/",
+                "  -1.0 KiB  old/",
+                "",
+            ]
+            .join("\n")
+        );
+        assert!(
+            diff_report(&new, &new, 10, SizeFormat::default())
+                .ends_with("No directory changed size\n")
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use dux_core::SizeFormat;
+
+use crate::app::guard::is_protected_path;
+
+/// Selected paths, one absolute path per line
+pub fn path_list<'a>(paths: impl IntoIterator<Item = &'a Path>) -> String {
+    paths
+        .into_iter()
+        .map(|path| format!("{}\n", path.display()))
+        .collect()
+}
+
+/// A shell script removing each of `items` (path, size) with `rm -rf`, for
+/// review before running it elsewhere. A comment header says what it
+/// deletes and how much; paths dux would never delete and paths that are
+/// not valid UTF-8 are left in as comments instead. Returns the script and
+/// how many paths it removes.
+pub fn rm_script<'a>(
+    items: impl IntoIterator<Item = (&'a Path, u64)>,
+    format: SizeFormat,
+) -> (String, usize) {
+    let mut commands = String::new();
+    let mut count = 0;
+    let mut total = 0u64;
+    for (path, size) in items {
+        match path.to_str() {
+            _ if is_protected_path(path) => {
+                let _ = writeln!(commands, "# skipped, protected: {}", path.display());
+            }
+            None => {
+                let _ = writeln!(commands, "# skipped, not UTF-8: {}", path.display());
+            }
+            Some(text) => {
+                let _ = writeln!(commands, "rm -rf -- {}", shell_quote(text));
+                count += 1;
+                total = total.saturating_add(size);
+            }
+        }
+    }
+
+    let mut script = String::from("#!/bin/sh\n");
+    script
+        .push_str("# Written by dux. Read every line before running: nothing here is undoable.\n");
+    let _ = writeln!(
+        script,
+        "# Removes {} path{}, {} in total as scanned.\n",
+        count,
+        if count == 1 { "" } else { "s" },
+        format.format(total)
+    );
+    script.push_str(&commands);
+    (script, count)
+}
+
+/// Single-quote `text` for a POSIX shell
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rm_script_quotes_and_skips_protected() {
+        let (script, count) = rm_script(
+            [
+                (Path::new("/p/target"), 3 << 20),
+                (Path::new("/p/it's here"), 1 << 20),
+                (Path::new("/usr"), 1 << 30),
+            ],
+            SizeFormat::default(),
+        );
+        assert_eq!(
+            script,
+            "#!/bin/sh\n\
+             # Written by dux. Read every line before running: nothing here is undoable.\n\
+             # Removes 2 paths, 4.0 MiB in total as scanned.\n\
+             \n\
+             rm -rf -- '/p/target'\n\
+             rm -rf -- '/p/it'\\''s here'\n\
+             # skipped, protected: /usr\n"
+        );
+        assert_eq!(count, 2);
+        assert_eq!(
+            path_list([Path::new("/p/a"), Path::new("/p/b c")]),
+            "/p/a\n/p/b c\n"
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Appends `log` records from dux itself to a file, stamped with the time
+/// since startup. Dependencies' records are dropped.
+struct FileLogger {
+    file: Mutex<LineWriter<File>>,
+    started: Instant,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target().starts_with("dux")
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(
+                file,
+                "{:>9.3}s {:<5} {}: {}",
+                self.started.elapsed().as_secs_f64(),
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Send log records at `level` or above to `path`, replacing the file. The
+/// TUI owns the terminal, so logging never goes to stdout or stderr.
+pub fn init(path: &Path, level: LevelFilter) -> io::Result<()> {
+    let logger = FileLogger {
+        file: Mutex::new(LineWriter::new(File::create(path)?)),
+        started: Instant::now(),
+    };
+    log::set_boxed_logger(Box::new(logger)).map_err(io::Error::other)?;
+    log::set_max_level(level);
+    log::info!("dux {} logging at {}", env!("CARGO_PKG_VERSION"), level);
+    Ok(())
+}
@@ -1,30 +1,57 @@
 mod app;
+mod audit;
+mod bookmarks;
+mod cache_writer;
+mod config;
+mod export;
+mod logging;
+mod session;
 mod tui;
 mod ui;
+mod watch;
 
-use std::io::{self, stdout};
-use std::path::PathBuf;
+use std::io::{self, Write, stdout};
+use std::path::{Path, PathBuf};
 use std::thread::JoinHandle;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
-use clap::Parser;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser};
 use color_eyre::Result;
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use dux_core::{
-    CacheMetadata, CachedScanConfig, CancellationToken, DiskTree, ScanConfig, ScanMessage, Scanner,
-    cache_path_for, get_mtime, is_cache_valid, load_cache, save_cache, spot_check_mtimes,
+    CacheMetadata, CachedScanConfig, CancellationToken, DiskTree, NodeId, ScanConfig, ScanMessage,
+    Scanner, SizeFormat, SizeUnits, cache_path_for, get_mtime, is_cache_valid, load_cache,
+    spot_check_mtimes,
+};
+use ratatui::{
+    Terminal,
+    backend::CrosstermBackend,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    widgets::Widget,
 };
-use ratatui::{Terminal, backend::CrosstermBackend, style::Style, widgets::Widget};
 
+use app::views::{ArtifactRules, ComputedViews, FilterSummary, NodeFilter, dedup_ancestors};
 use app::{Action, AppMode, AppState, ViewMode};
-use tui::{AppEvent, EventHandler, handle_key};
+use bookmarks::Bookmarks;
+use cache_writer::CacheWriter;
+use config::Config;
+use export::{csv, report};
+use session::{UiSnapshot, snapshot_path};
+use tui::{AppEvent, EventHandler, handle_key, handle_mouse, open_in_editor};
 use ui::{
-    AppLayout, BuildArtifactsView, ConfirmDeleteView, ConfirmMultiDeleteView, Footer, Header,
-    HelpView, LargeFilesView, MultiDeleteProgressView, ProgressView, Theme, TreeView,
+    AppLayout, BookmarksView, BreadcrumbsView, BuildArtifactsView, ChangesView, ConfirmDeleteView,
+    ConfirmMultiDeleteView, DetailsView, EmptyDirsView, ErrorsView, ExcludesView, FileTypesView,
+    Footer, Header, HelpView, Icons, LargeFilesView, MultiDeleteProgressView, OverviewView,
+    ProgressView, SymlinksView, Theme, ThemeName, ThemeOverrides, TooSmallView, TreeView,
+    TreemapView,
 };
+use watch::Watch;
 
 /// DUX - Interactive Terminal Disk Usage Analyzer
 #[derive(Parser, Debug)]
@@ -32,7 +59,8 @@ use ui::{
 #[command(about = "An interactive, DaisyDisk-like terminal disk usage analyzer")]
 #[command(version)]
 struct Args {
-    /// Path to analyze (defaults to current directory)
+    /// Path to analyze (defaults to current directory); a file scans its
+    /// directory and selects it
     #[arg(default_value = ".")]
     path: PathBuf,
 
@@ -44,19 +72,229 @@ struct Args {
     #[arg(short, long)]
     follow_symlinks: bool,
 
+    /// Size symlinks by the file they point to, without descending. Targets
+    /// inside PATH and repeated targets are only counted once
+    #[arg(long)]
+    size_symlinks: bool,
+
     /// Cross filesystem boundaries
     #[arg(short = 'x', long)]
     cross_filesystems: bool,
 
+    /// Number of scan threads, 1-256 (0 = auto, Rayon's default of one per core).
+    /// Fewer threads can be faster on spinning disks
+    #[arg(short = 'j', long, default_value_t = 0, value_parser = clap::value_parser!(u16).range(0..=256))]
+    threads: u16,
+
+    /// Also enter the filesystem holding PATH when staying on one filesystem
+    /// (repeatable; ignored with -x). Unreadable paths are listed as scan errors
+    #[arg(long, value_name = "PATH")]
+    also_scan: Vec<PathBuf>,
+
+    /// Stop scanning after this long and browse what was found (e.g. 30s, 5m);
+    /// printing and exporting commands fail instead of reporting partial results
+    #[arg(long, value_parser = parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Sample for a quick first look: from this depth down (default 3), walk
+    /// only a few subdirectories per directory and estimate the rest. Sizes
+    /// marked ~ are extrapolated
+    #[arg(long, value_name = "DEPTH", num_args = 0..=1, default_missing_value = "3")]
+    sample: Option<usize>,
+
+    /// Skip entries matching PATH (repeatable): a bare name such as `.git`
+    /// anywhere, or exactly that path. `X` adds more while browsing
+    #[arg(long, value_name = "PATH")]
+    exclude: Vec<PathBuf>,
+
     /// Disable cache (always perform fresh scan)
     #[arg(long)]
     no_cache: bool,
+
+    /// Directory for scan caches; the flag wins over `DUX_CACHE_DIR`, which
+    /// wins over the platform cache directory. `/dev/null` turns caching off
+    /// like `--no-cache`
+    #[arg(long, env = "DUX_CACHE_DIR", value_name = "PATH")]
+    cache_dir: Option<PathBuf>,
+
+    /// Before trusting a cache, check every directory's mtime and listing
+    /// and every file's size and mtime against it, for filesystems whose
+    /// directory mtimes can't be relied on (some network mounts). Slower to
+    /// start
+    #[arg(long)]
+    verify_cache: bool,
+
+    /// How many of the largest directories have their mtimes checked
+    /// before a cache is used
+    #[arg(long, value_name = "N", default_value_t = 32)]
+    cache_spot_checks: usize,
+
+    /// Rescan PATH (repeatable) whenever the cache is used, for directories
+    /// that change too often to cache (e.g. Downloads); the rest of the
+    /// tree still loads from the cache
+    #[arg(long, value_name = "PATH")]
+    always_rescan: Vec<PathBuf>,
+
+    /// Also record apparent file sizes and show block-rounding slack
+    #[arg(long)]
+    apparent: bool,
+
+    /// Count the space directories themselves take, as `du` does
+    #[arg(long)]
+    dir_overhead: bool,
+
+    /// Keep watching the directory after the scan and rescan the parts
+    /// that change, updating the view in place
+    #[arg(long)]
+    watch: bool,
+
+    /// Count entries first so the scan shows a percent-complete bar
+    /// (roughly doubles directory traversal time)
+    #[arg(long)]
+    progress_bar: bool,
+
+    /// Milliseconds between redraws while scanning or deleting; when idle
+    /// the screen is only redrawn on input
+    #[arg(long, value_name = "MS", default_value_t = 50, value_parser = clap::value_parser!(u64).range(10..=1000))]
+    tick_rate: u64,
+
+    /// Show sizes in SI units (powers of 1000: kB, MB, GB) instead of KiB, MiB, GiB
+    #[arg(long)]
+    si: bool,
+
+    /// Decimal places shown in sizes (0-3)
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(0..=3))]
+    precision: u8,
+
+    /// Deletions at least this large (or within two levels of `/`) must be
+    /// confirmed by typing the name instead of pressing y (e.g. 500M, 2G; 0 = always)
+    #[arg(long, value_name = "SIZE", default_value = "1G", value_parser = parse_size)]
+    confirm_above: u64,
+
+    /// Turn off every delete action, for demos and shared sessions; the
+    /// cache is left as it was on exit
+    #[arg(long)]
+    read_only: bool,
+
+    /// How much space you set out to free (e.g. 20G); `p` can then show
+    /// sizes as a share of it, besides a share of the free space left
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    need: Option<u64>,
+
+    /// Rows in the text size report copied with Y (the rest are folded into
+    /// one line), in the --slack breakdown and per direction in --diff
+    #[arg(long, value_name = "N", default_value_t = 20)]
+    report_top: usize,
+
+    /// Print the stale build artifacts that would be cleaned, then exit (no TUI)
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Write a view to a CSV file, then exit (no TUI)
+    #[arg(long, value_name = "FILE")]
+    export_csv: Option<PathBuf>,
+
+    /// Stream every node to stdout as JSON Lines (path, size, kind, depth),
+    /// then exit (no TUI)
+    #[arg(long, conflicts_with_all = ["dry_run", "export_csv"])]
+    jsonl: bool,
+
+    /// Print the N largest files, then exit (no TUI). Faster and far lighter
+    /// on memory than a full scan, since no tree is built
+    #[arg(long, value_name = "N", conflicts_with_all = ["dry_run", "export_csv", "jsonl"])]
+    top_files: Option<usize>,
+
+    /// Print how much space block rounding wastes (allocated minus apparent
+    /// size) and which directories hold it, then exit (no TUI)
+    #[arg(long, conflicts_with_all = ["dry_run", "export_csv", "jsonl", "top_files"])]
+    slack: bool,
+
+    /// View written by --export-csv (the tree is flattened in full)
+    #[arg(long, value_enum, default_value = "tree")]
+    export_view: ViewMode,
+
+    /// Write a diagnostic log of the scan (skipped paths, errors, timings,
+    /// cache hits) to FILE; nothing is logged to the terminal
+    #[arg(long, env = "DUX_LOG", value_name = "FILE")]
+    log: Option<PathBuf>,
+
+    /// Detail written to the --log file: error, warn, info, debug or trace
+    /// (trace adds a line per directory)
+    #[arg(long, value_name = "LEVEL", default_value = "debug")]
+    log_level: log::LevelFilter,
+
+    /// Color theme (defaults to the last theme chosen with `t`)
+    #[arg(long, value_enum)]
+    theme: Option<ThemeName>,
+
+    /// Open on the Overview of the top-level entries, sized against the
+    /// whole scan, instead of the tree
+    #[arg(long)]
+    overview: bool,
+
+    /// Drop the size-bar row to give the list one more line; the header
+    /// still shows the total
+    #[arg(long)]
+    compact: bool,
+
+    /// Draw icons as ASCII tags ([D] [F] [L]) instead of emoji; on by
+    /// default when TERM names a terminal without emoji (e.g. `linux`)
+    #[arg(long, alias = "no-emoji")]
+    ascii: bool,
+
+    /// Don't record deletions in the audit log (`deletions.jsonl` in the
+    /// config directory)
+    #[arg(long)]
+    no_audit: bool,
+
+    /// Print the last N deletions (default 20) from the audit log and exit
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "20")]
+    audit_log: Option<usize>,
+
+    /// Print the build, platform size logic and effective defaults, to see
+    /// why two machines count differently, and exit
+    #[arg(long)]
+    info: bool,
+
+    /// Print the directories that grew or shrank the most between two saved
+    /// caches (OLD NEW), or between a directory's cache and a fresh scan of
+    /// it (DIR), and exit
+    #[arg(long, value_name = "OLD", num_args = 1..=2)]
+    diff: Option<Vec<PathBuf>>,
 }
 
 fn main() -> Result<()> {
     color_eyre::install()?;
 
-    let args = Args::parse();
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    apply_config(&mut args, &matches, Config::load());
+    if let Some(file) = &args.log
+        && let Err(e) = logging::init(file, args.log_level)
+    {
+        eprintln!("Warning: cannot write log to {}: {}", file.display(), e);
+    }
+    args.exclude = args
+        .exclude
+        .iter()
+        .map(|path| match path.components().count() {
+            1 => path.clone(),
+            _ => path.canonicalize().unwrap_or_else(|_| path.clone()),
+        })
+        .collect();
+
+    if args.info {
+        run_info(&args, &mut stdout().lock())?;
+        return Ok(());
+    }
+    if let Some(count) = args.audit_log {
+        run_audit_log(count, &args);
+        return Ok(());
+    }
+
+    if let Some(paths) = &args.diff {
+        return run_diff(&paths[0], paths.get(1).map(PathBuf::as_path), &args);
+    }
 
     // Resolve path
     let path = args
@@ -70,73 +308,678 @@ fn main() -> Result<()> {
         eprintln!("Error: Path does not exist: {}", path.display());
         std::process::exit(1);
     }
-    if !path.is_dir() {
+    // A file is shown selected in its directory
+    let (path, mut reveal) = if path.is_dir() {
+        (path, None)
+    } else if let Some(parent) = path.parent() {
+        (parent.to_path_buf(), Some(path.clone()))
+    } else {
         eprintln!("Error: Path is not a directory: {}", path.display());
         std::process::exit(1);
+    };
+
+    if args.dry_run {
+        return run_dry_run(path, &args);
+    }
+    if let Some(file) = &args.export_csv {
+        return run_export_csv(path, file, &args);
+    }
+    if args.jsonl {
+        return run_export_jsonl(path, &args);
     }
+    if args.slack {
+        return run_slack(path, &args);
+    }
+    if let Some(n) = args.top_files {
+        return run_top_files(&path, n, &args);
+    }
+
+    // Resolve config before entering the alternate screen so load errors stay visible
+    let mut theme = load_theme(&args);
+    theme.icons = Icons::detect(args.ascii, std::env::var("TERM").ok().as_deref());
+    let artifact_rules = load_artifact_rules();
 
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
-    // Run app
-    let result = run_app(&mut terminal, path, &args);
+    // Run app; opening a bookmark outside the scanned tree starts over there
+    let mut path = path;
+    let mut rescan = false;
+    let mut deferred_output = Vec::new();
+    let result = loop {
+        match run_app(
+            &mut terminal,
+            path,
+            &args,
+            &mut theme,
+            artifact_rules.clone(),
+            rescan,
+            reveal.take(),
+        ) {
+            Ok(exit) => {
+                deferred_output.extend(exit.deferred_output);
+                let Some(next) = exit.relaunch_path else {
+                    break Ok(());
+                };
+                path = next;
+                rescan = exit.rescan;
+                args.exclude = exit.excludes;
+                terminal.clear()?;
+            }
+            Err(e) => break Err(e),
+        }
+    };
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(io::stdout(), LeaveAlternateScreen)?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+
+    for text in deferred_output {
+        print!("{}", text);
+    }
 
     result
 }
 
-fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    path: PathBuf,
-    args: &Args,
-) -> Result<()> {
-    let theme = Theme::default();
-    let mut state = AppState::new(path.clone());
-    let event_handler = EventHandler::new(50); // 50ms tick rate
+/// What `run_app` hands back once its session ends
+struct AppExit {
+    /// Directory to scan next (a bookmark outside the scanned tree, or the
+    /// same root when rescanning)
+    relaunch_path: Option<PathBuf>,
+    /// Whether the next scan must skip the cache
+    rescan: bool,
+    /// Text to print after the terminal is restored
+    deferred_output: Vec<String>,
+    /// Excludes the next scan starts with, including any added with `X`
+    excludes: Vec<PathBuf>,
+}
 
-    // Scan configuration
-    let scan_config = ScanConfig {
+/// Fill in every flag not given on the command line (or through its
+/// environment variable) from `config.toml`
+fn apply_config(args: &mut Args, matches: &ArgMatches, config: Config) {
+    let given = |id: &str| {
+        matches!(
+            matches.value_source(id),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        )
+    };
+    macro_rules! merge {
+        ($($field:ident),* $(,)?) => {$(
+            if !given(stringify!($field))
+                && let Some(value) = config.$field
+            {
+                args.$field = value.into();
+            }
+        )*};
+    }
+    merge!(
+        max_depth,
+        follow_symlinks,
+        size_symlinks,
+        cross_filesystems,
+        threads,
+        also_scan,
+        timeout,
+        sample,
+        exclude,
+        no_cache,
+        cache_dir,
+        verify_cache,
+        cache_spot_checks,
+        always_rescan,
+        apparent,
+        dir_overhead,
+        progress_bar,
+        tick_rate,
+        si,
+        precision,
+        confirm_above,
+        read_only,
+        need,
+        report_top,
+        theme,
+        overview,
+        compact,
+        ascii,
+        no_audit,
+    );
+}
+
+/// Parse a duration such as `30s`, `5m`, `1h` or a plain number of seconds
+fn parse_duration(s: &str) -> std::result::Result<Duration, String> {
+    let s = s.trim();
+    let (number, scale) = match s.char_indices().last() {
+        Some((i, 's')) => (&s[..i], 1),
+        Some((i, 'm')) => (&s[..i], 60),
+        Some((i, 'h')) => (&s[..i], 3600),
+        _ => (s, 1),
+    };
+    let value: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration '{}' (expected e.g. 30s, 5m, 1h)", s))?;
+    if value == 0 {
+        return Err("duration must be greater than zero".to_string());
+    }
+    let secs = value
+        .checked_mul(scale)
+        .ok_or_else(|| format!("duration too large: {}", s))?;
+    Ok(Duration::from_secs(secs))
+}
+
+/// Clap value parser for human-readable sizes (`500K`, `1.5M`, `2GiB`)
+fn parse_size(s: &str) -> std::result::Result<u64, String> {
+    dux_core::parse_human_size(s).map_err(|e| e.to_string())
+}
+
+/// Size rendering chosen on the command line
+fn size_format(args: &Args) -> SizeFormat {
+    SizeFormat {
+        units: if args.si {
+            SizeUnits::Si
+        } else {
+            SizeUnits::Binary
+        },
+        precision: args.precision as usize,
+    }
+}
+
+/// Scanner settings chosen on the command line
+fn scan_config(args: &Args) -> ScanConfig {
+    ScanConfig {
         follow_symlinks: args.follow_symlinks,
+        size_symlinks: args.size_symlinks,
         max_depth: args.max_depth,
         same_filesystem: !args.cross_filesystems,
-        num_threads: 0,
-    };
+        extra_filesystems: args.also_scan.clone(),
+        num_threads: args.threads as usize,
+        track_apparent: args.apparent || args.slack,
+        scan_timeout: args.timeout,
+        sample_depth: args.sample,
+        exclude: args.exclude.clone(),
+        count_dir_overhead: args.dir_overhead,
+    }
+}
 
-    // Cache configuration (for validation)
-    let cache_config = CachedScanConfig {
+/// Settings a cached tree must have been scanned with to be reused or compared
+fn cache_config(args: &Args) -> CachedScanConfig {
+    CachedScanConfig {
         follow_symlinks: args.follow_symlinks,
+        size_symlinks: args.size_symlinks,
         same_filesystem: !args.cross_filesystems,
+        extra_filesystems: args.also_scan.clone(),
         max_depth: args.max_depth,
+        track_apparent: args.apparent,
+        sample_depth: args.sample,
+        exclude: args.exclude.clone(),
+        count_dir_overhead: args.dir_overhead,
+    }
+}
+
+/// The cached tree of an earlier scan of `path` with the same settings, even
+/// if it is out of date, and when that scan was taken
+fn previous_scan(path: &Path, args: &Args) -> Option<(SystemTime, DiskTree)> {
+    if args.no_cache {
+        return None;
+    }
+    let (meta, tree) = load_cache(&cache_path_for(path, &cache_dir(args)?)).ok()?;
+    (!meta.partial && meta.root_path == path && meta.config == cache_config(args))
+        .then_some((meta.scan_time, tree))
+}
+
+/// Cache directory that means "never persist"
+const NULL_CACHE_DIR: &str = if cfg!(windows) { "NUL" } else { "/dev/null" };
+
+/// Where scan caches live: `--cache-dir`, then `DUX_CACHE_DIR`, then
+/// `dux/` in the platform cache directory. None when caching is pointed at
+/// the null device.
+fn cache_dir(args: &Args) -> Option<PathBuf> {
+    args.cache_dir
+        .clone()
+        .or_else(|| dirs::cache_dir().map(|d| d.join("dux")))
+        .filter(|dir| dir != Path::new(NULL_CACHE_DIR))
+}
+
+/// Scan to completion on the current thread's behalf, for the non-interactive modes
+fn scan_blocking(path: &Path, args: &Args) -> Result<DiskTree> {
+    let (rx, handle) = Scanner::new(scan_config(args)).scan(path.to_path_buf());
+    finish_scan(rx, handle)
+}
+
+/// Drain a scan's messages until the scanner hangs up and take its tree.
+/// A scan cut short by `--timeout` is an error: everything built on this
+/// prints or exports the tree as if it were complete.
+fn finish_scan(
+    messages: impl IntoIterator<Item = ScanMessage>,
+    handle: JoinHandle<DiskTree>,
+) -> Result<DiskTree> {
+    let mut timed_out = false;
+    for msg in messages {
+        timed_out |= matches!(msg, ScanMessage::Cancelled { timed_out: true });
+    }
+    let tree = handle
+        .join()
+        .map_err(|_| color_eyre::eyre::eyre!("Scanner thread panicked"))?;
+    if timed_out {
+        return Err(color_eyre::eyre::eyre!(
+            "Scan timed out after {} entries; the results would be partial (raise --timeout)",
+            dux_core::format_count(tree.live_count() as u64)
+        ));
+    }
+    Ok(tree)
+}
+
+/// Scan without the TUI and write one view to a CSV file
+fn run_export_csv(path: PathBuf, file: &Path, args: &Args) -> Result<()> {
+    let previous = match args.export_view {
+        ViewMode::Changes => previous_scan(&path, args),
+        _ => None,
+    };
+    let tree = scan_blocking(&path, args)?;
+
+    let mut views = ComputedViews::new();
+    views.artifact_rules = load_artifact_rules();
+    if let Some((_, old)) = &previous {
+        views.change_deltas = tree.diff(old);
+    }
+    views.rebuild(&tree, NodeFilter::default());
+
+    let rows = match args.export_view {
+        ViewMode::Tree => {
+            let nodes = csv::flatten_tree(&tree, NodeId::ROOT);
+            csv::tree_rows(&tree, &nodes, tree.total_size())
+        }
+        ViewMode::LargeFiles => csv::large_file_rows(&tree, &views),
+        ViewMode::BuildArtifacts => csv::artifact_rows(&tree, &views),
+        ViewMode::EmptyDirs => csv::empty_dir_rows(&tree, &views),
+        ViewMode::Changes => csv::change_rows(&tree, &views),
+        ViewMode::Symlinks => csv::symlink_rows(&tree, &views),
+        ViewMode::Overview => {
+            let nodes = tree
+                .get(NodeId::ROOT)
+                .map_or(Vec::new(), |n| n.children.clone());
+            csv::tree_rows(&tree, &nodes, tree.total_size())
+        }
     };
+    let out = std::fs::File::create(file)?;
+    csv::write_csv(io::BufWriter::new(out), &rows, size_format(args))?;
+    println!("Exported {} rows to {}", rows.len(), file.display());
+
+    Ok(())
+}
+
+/// Scan without the TUI and stream the tree to stdout as JSON Lines. A reader
+/// that stops early (e.g. `| head`) closes the pipe, which ends the export
+/// quietly.
+fn run_export_jsonl(path: PathBuf, args: &Args) -> Result<()> {
+    let tree = scan_blocking(&path, args)?;
+
+    let stdout = io::stdout().lock();
+    match dux_core::write_jsonl(io::BufWriter::new(stdout), &tree) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Scan without the TUI, tracking apparent sizes, and print the slack report
+fn run_slack(path: PathBuf, args: &Args) -> Result<()> {
+    let tree = scan_blocking(&path, args)?;
+    print!(
+        "{}",
+        report::slack_report(&tree, NodeId::ROOT, args.report_top, size_format(args))
+    );
+    Ok(())
+}
+
+/// Print the size changes between two cache files, or, given only a
+/// directory, between its cache and a fresh scan
+fn run_diff(old: &Path, new: Option<&Path>, args: &Args) -> Result<()> {
+    let load = |file: &Path| {
+        load_cache(file).map_err(|e| color_eyre::eyre::eyre!("{}: {}", file.display(), e))
+    };
+    let (before, after) = match new {
+        Some(new) => {
+            let ((old_meta, before), (new_meta, after)) = (load(old)?, load(new)?);
+            if old_meta.root_path != new_meta.root_path {
+                return Err(color_eyre::eyre::eyre!(
+                    "{} is a cache of {} but {} is a cache of {}; only caches of the same directory can be compared",
+                    old.display(),
+                    old_meta.root_path.display(),
+                    new.display(),
+                    new_meta.root_path.display()
+                ));
+            }
+            (before, after)
+        }
+        None => {
+            let path = old.canonicalize()?;
+            let Some((_, before)) = previous_scan(&path, args) else {
+                return Err(color_eyre::eyre::eyre!(
+                    "No cache of {} with the current settings to compare against",
+                    path.display()
+                ));
+            };
+            (before, scan_blocking(&path, args)?)
+        }
+    };
+    print!(
+        "{}",
+        report::diff_report(&before, &after, args.report_top, size_format(args))
+    );
+    Ok(())
+}
+
+/// Print the last `count` deletions from the audit log, oldest first
+fn run_audit_log(count: usize, args: &Args) {
+    let Some(log) = config::audit_file() else {
+        return;
+    };
+    let records = audit::recent(&log, count);
+    if records.is_empty() {
+        println!("No deletions recorded in {}", log.display());
+        return;
+    }
+    let format = size_format(args);
+    for record in records {
+        let time = chrono::DateTime::parse_from_rfc3339(&record.time)
+            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or(record.time);
+        println!(
+            "{}  {:>12}  {}",
+            time,
+            format.format(record.size),
+            record.path.display()
+        );
+    }
+}
+
+/// Print what this build and platform do, and the defaults in effect after
+/// `config.toml` and the environment
+fn run_info(args: &Args, out: &mut impl Write) -> io::Result<()> {
+    let yes_no = |on: bool| if on { "yes" } else { "no" };
+    let path_or_none = |path: Option<PathBuf>| {
+        path.map(|p| p.display().to_string())
+            .unwrap_or_else(|| "(none)".to_string())
+    };
+    let config = scan_config(args);
+
+    writeln!(out, "dux {}", env!("CARGO_PKG_VERSION"))?;
+    writeln!(
+        out,
+        "Platform:          {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )?;
+    writeln!(out, "Size measurement:  {}", dux_core::SIZE_STRATEGY)?;
+    writeln!(out, "Owners and modes:  {}", yes_no(cfg!(unix)))?;
+    writeln!(out, "Trash:             no (deletions are permanent)")?;
+    writeln!(
+        out,
+        "Cache format:      v{}, uncompressed",
+        dux_core::CACHE_VERSION
+    )?;
+    writeln!(out)?;
+    let config_file = config::config_file();
+    let missing = config_file.as_ref().is_some_and(|file| !file.exists());
+    writeln!(
+        out,
+        "Config file:       {}{}",
+        path_or_none(config_file),
+        if missing { " (not present)" } else { "" }
+    )?;
+    writeln!(
+        out,
+        "Cache directory:   {}",
+        path_or_none(cache_dir(args).filter(|_| !args.no_cache))
+    )?;
+    writeln!(
+        out,
+        "Audit log:         {}",
+        path_or_none(config::audit_file().filter(|_| !args.no_audit))
+    )?;
+    writeln!(out)?;
+    writeln!(out, "Threads:           {}", config.effective_threads())?;
+    writeln!(out, "Same filesystem:   {}", yes_no(config.same_filesystem))?;
+    writeln!(
+        out,
+        "Filesystem type:   {}",
+        dux_core::fs_type(&args.path).unwrap_or_else(|| "(unknown)".to_string())
+    )?;
+    writeln!(out, "Follow symlinks:   {}", yes_no(config.follow_symlinks))?;
+    writeln!(out, "Size symlinks:     {}", yes_no(config.size_symlinks))?;
+    writeln!(out, "Apparent sizes:    {}", yes_no(config.track_apparent))?;
+    writeln!(
+        out,
+        "Dir overhead:      {}",
+        yes_no(config.count_dir_overhead)
+    )?;
+    let exclude: Vec<_> = config
+        .exclude
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect();
+    writeln!(
+        out,
+        "Excludes:          {}",
+        if exclude.is_empty() {
+            "(none)".to_string()
+        } else {
+            exclude.join(", ")
+        }
+    )?;
+    writeln!(
+        out,
+        "Cache validation:  {} largest directories{}",
+        args.cache_spot_checks,
+        if args.verify_cache {
+            ", then every directory"
+        } else {
+            ""
+        }
+    )?;
+    if !args.always_rescan.is_empty() {
+        let paths: Vec<_> = args
+            .always_rescan
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect();
+        writeln!(out, "Always rescanned:  {}", paths.join(", "))?;
+    }
+    writeln!(
+        out,
+        "Size units:        {}, {} decimals",
+        if args.si {
+            "SI (1000)"
+        } else {
+            "binary (1024)"
+        },
+        args.precision
+    )?;
+    Ok(())
+}
+
+/// Print the `n` largest files under `path` without building a tree
+fn run_top_files(path: &Path, n: usize, args: &Args) -> Result<()> {
+    let format = size_format(args);
+    for (file, size) in Scanner::top_files(path, n, &scan_config(args)) {
+        println!("{:>12}  {}", format.format(size), file.display());
+    }
+    Ok(())
+}
+
+/// Scan without the TUI and list the stale build artifacts a cleanup would delete
+fn run_dry_run(path: PathBuf, args: &Args) -> Result<()> {
+    let tree = scan_blocking(&path, args)?;
+
+    let mut views = ComputedViews::new();
+    views.artifact_rules = load_artifact_rules();
+    views.rebuild(&tree, NodeFilter::default());
+    let stale = views.stale_artifacts();
+
+    let format = size_format(args);
+    let mut items: Vec<(u64, PathBuf)> = dedup_ancestors(&tree, &stale)
+        .into_iter()
+        .filter_map(|id| tree.get(id).map(|n| (n.size, n.path.clone())))
+        .collect();
+    items.sort_by_key(|(size, _)| std::cmp::Reverse(*size));
+
+    for (size, item_path) in &items {
+        println!("{:>12}  {}", format.format(*size), item_path.display());
+    }
+    let total: u64 = items.iter().map(|(size, _)| size).sum();
+    println!(
+        "{} stale artifact{} (older than {}), {} would be freed",
+        items.len(),
+        if items.len() == 1 { "" } else { "s" },
+        views.stale_threshold.label(),
+        format.format(total)
+    );
+
+    Ok(())
+}
+
+/// Longest the event loop sleeps while idle. Input wakes it immediately;
+/// this only bounds how stale an otherwise unchanged screen can get.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    path: PathBuf,
+    args: &Args,
+    theme: &mut Theme,
+    artifact_rules: ArtifactRules,
+    rescan: bool,
+    mut reveal: Option<PathBuf>,
+) -> Result<AppExit> {
+    let mut state = AppState::new(path.clone());
+    state.bookmarks = Bookmarks::load();
+    state.computed_views.artifact_rules = artifact_rules;
+    state.size_format = size_format(args);
+    state.typed_confirm_size = args.confirm_above;
+    state.read_only = args.read_only;
+    state.need = args.need;
+    state.report_limit = args.report_top;
+    state.compact = args.compact;
+    if args.overview {
+        state.view_mode = ViewMode::Overview;
+    }
+    state.disk_capacity = dux_core::fs_capacity(&path);
+    state.excludes = args.exclude.clone();
+    if !args.no_audit {
+        state.audit_log = config::audit_file();
+    }
+    let event_handler = EventHandler::new(args.tick_rate);
+
+    // Scan configuration
+    let scan_config = scan_config(args);
+
+    // Changes during the first scan are queued and rescanned once it's done
+    if args.watch {
+        match Watch::start(&path, scan_config.clone()) {
+            Ok(watch) => state.watch = Some(watch),
+            Err(e) => state.set_error(format!("Cannot watch {}: {}", path.display(), e)),
+        }
+    }
+
+    // Cache configuration (for validation)
+    let cache_config = cache_config(args);
 
     // Try to load from cache
-    let cache_dir = dirs::cache_dir().map(|d| d.join("dux"));
+    let cache_dir = cache_dir(args);
+    if !args.no_cache
+        && let Some(dir) = &cache_dir
+    {
+        let _ = std::fs::create_dir_all(dir);
+    }
     let cache_path = cache_dir.as_ref().map(|d| cache_path_for(&path, d));
     let mut loaded_from_cache = false;
+    // Directories taken fresh from disk even when the cache is used
+    let always_rescan: Vec<PathBuf> = args
+        .always_rescan
+        .iter()
+        .map(|dir| dir.canonicalize().unwrap_or_else(|_| dir.clone()))
+        .collect();
+    // An out-of-date cache of the same root is kept to diff the new scan against
+    let mut previous_scan = None;
+
+    // A rescan restores the view it was started from once the scan is done
+    let mut rescan_snapshot = cache_path
+        .as_ref()
+        .filter(|_| rescan)
+        .and_then(|cp| UiSnapshot::load(&snapshot_path(cp)));
 
     if !args.no_cache
         && let Some(ref cp) = cache_path
-        && let Ok((meta, tree)) = load_cache(cp)
-        && is_cache_valid(&meta, &path, &cache_config)
-        && spot_check_mtimes(&tree, 32)
     {
-        state.set_tree(tree);
-        state.loaded_from_cache = true;
-        loaded_from_cache = true;
+        match load_cache(cp) {
+            Ok((meta, tree)) if rescan => {
+                log::info!("cache skipped for {}: rescan requested", path.display());
+                if !meta.partial && meta.root_path == path && meta.config == cache_config {
+                    previous_scan = Some((meta.scan_time, tree));
+                }
+            }
+            Ok((meta, tree))
+                if is_cache_valid(&meta, &path, &cache_config)
+                    && spot_check_mtimes(&tree, args.cache_spot_checks, &always_rescan)
+                    && (!args.verify_cache
+                        || Scanner::verify_tree(&tree, &scan_config, &always_rescan)) =>
+            {
+                log::info!(
+                    "cache hit for {}: {} nodes scanned {:?}",
+                    path.display(),
+                    meta.node_count,
+                    meta.scan_time
+                );
+                state.set_tree(tree);
+                if let Some(snapshot) = UiSnapshot::load(&snapshot_path(cp)) {
+                    snapshot.apply(&mut state);
+                }
+                state.loaded_from_cache = true;
+                loaded_from_cache = true;
+                let stale: Vec<PathBuf> = always_rescan
+                    .iter()
+                    .filter(|dir| {
+                        state
+                            .tree
+                            .as_ref()
+                            .is_some_and(|t| t.find_by_path(dir).is_some())
+                    })
+                    .cloned()
+                    .collect();
+                if !stale.is_empty() {
+                    log::info!("rescanning {} always-rescan directories", stale.len());
+                    state.refresh = Some(watch::rescan(stale, scan_config.clone()));
+                }
+            }
+            Ok((meta, tree)) => {
+                log::info!(
+                    "cache miss for {}: partial, out of date or scanned with other settings",
+                    path.display()
+                );
+                if meta.root_path == path && meta.config == cache_config {
+                    if meta.partial {
+                        // What the unfinished scan found stays on screen until the new one
+                        // has something to show
+                        state.set_partial_tree(tree);
+                    } else {
+                        previous_scan = Some((meta.scan_time, tree));
+                    }
+                }
+            }
+            Err(e) => log::info!("cache miss for {}: {}", path.display(), e),
+        }
     }
 
     // Start scanner only if not loaded from cache
     let cancel_token = CancellationToken::new();
     let (progress_rx, scan_handle) = if !loaded_from_cache {
-        let scanner = Scanner::new(scan_config.clone()).with_cancellation(cancel_token.clone());
+        let scanner = Scanner::new(scan_config.clone())
+            .with_cancellation(cancel_token.clone())
+            .with_pre_count(args.progress_bar);
         let (rx, handle) = scanner.scan(path.clone());
         (Some(rx), Some(handle))
     } else {
@@ -146,15 +989,18 @@ fn run_app(
     // Store the join handle in an Option so we can take it once
     let mut scan_handle: Option<JoinHandle<DiskTree>> = scan_handle;
 
-    // For cache saving after scan
+    // For cache saving after scan; every write goes through the one writer
     let cache_path_for_save = cache_path.clone();
+    let cache_writer = cache_path.clone().map(CacheWriter::new);
     let cache_config_for_save = cache_config.clone();
     let root_path_for_save = path.clone();
 
+    let mut redraw = true;
     loop {
         // Check for scan progress/completion (only if scanning)
         if let Some(ref rx) = progress_rx {
             while let Ok(msg) = rx.try_recv() {
+                redraw = true;
                 match msg {
                     ScanMessage::Progress(progress) => {
                         state.update_progress(progress);
@@ -162,221 +1008,592 @@ fn run_app(
                     ScanMessage::Finalizing => {
                         state.set_finalizing();
                     }
+                    ScanMessage::FinalizingProgress { done, total } => {
+                        state.finalize_progress = Some((done, total));
+                    }
+                    ScanMessage::PartialTree(tree) => {
+                        state.set_partial_tree(tree);
+                    }
                     ScanMessage::Completed => {
                         // Scanner completed, get the tree
                         if let Some(handle) = scan_handle.take()
                             && let Ok(tree) = handle.join()
                         {
                             // Save to cache in background
-                            if let Some(ref cp) = cache_path_for_save {
-                                let tree_for_cache = tree.clone();
-                                let cache_path = cp.clone();
-                                let config = cache_config_for_save.clone();
-                                let root = root_path_for_save.clone();
-                                let root_mtime = get_mtime(&root).unwrap_or(SystemTime::UNIX_EPOCH);
-                                std::thread::spawn(move || {
-                                    let meta = CacheMetadata {
-                                        version: dux_core::CACHE_VERSION,
-                                        root_path: root,
-                                        scan_time: SystemTime::now(),
-                                        root_mtime,
-                                        total_size: tree_for_cache.total_size(),
-                                        node_count: tree_for_cache.live_count(),
-                                        config,
-                                    };
-                                    let _ = save_cache(&cache_path, &tree_for_cache, &meta);
-                                });
+                            if let Some(ref writer) = cache_writer {
+                                write_cache(
+                                    writer,
+                                    tree.clone(),
+                                    &root_path_for_save,
+                                    cache_config_for_save.clone(),
+                                    false,
+                                );
+                            }
+                            state.set_tree(tree);
+                            if let Some((since, old)) = previous_scan.take() {
+                                state.set_previous_tree(&old, since);
+                            }
+                            if let Some(snapshot) = rescan_snapshot.take() {
+                                snapshot.apply(&mut state);
+                            }
+                        }
+                        break;
+                    }
+                    ScanMessage::Cancelled { timed_out: true } => {
+                        // Keep the partial tree, cached but marked partial
+                        if let Some(handle) = scan_handle.take()
+                            && let Ok(tree) = handle.join()
+                        {
+                            if let Some(ref writer) = cache_writer {
+                                write_cache(
+                                    writer,
+                                    tree.clone(),
+                                    &root_path_for_save,
+                                    cache_config_for_save.clone(),
+                                    true,
+                                );
                             }
                             state.set_tree(tree);
+                            state.partial_scan = true;
+                            state.set_error("Scan timed out; showing partial results".to_string());
                         }
                         break;
                     }
-                    ScanMessage::Cancelled => {
+                    ScanMessage::Cancelled { timed_out: false } => {
                         state.quit();
                     }
                     ScanMessage::Error(e) => {
                         state.set_error(e);
                     }
+                    ScanMessage::PathError { path, reason } => {
+                        state.record_scan_error(path, reason);
+                    }
                     _ => {}
                 }
             }
         }
 
-        // Draw UI
-        terminal.draw(|frame| {
-            let area = frame.area();
-            let layout = AppLayout::new(area);
-
-            // Background
-            frame
-                .buffer_mut()
-                .set_style(area, Style::default().bg(theme.bg));
-
-            // Update visible height for scrolling
-            state.visible_height = layout.tree.height as usize;
-
-            // Header
-            Header::new(&state, &theme).render(layout.header, frame.buffer_mut());
-
-            // Size bar (total)
-            render_size_bar(&state, &theme, layout.size_bar, frame.buffer_mut());
-
-            // Main content
-            match state.mode {
-                AppMode::Scanning | AppMode::Finalizing => {
-                    ProgressView::new(
-                        &state.progress,
-                        state.spinner_frame,
-                        state.mode == AppMode::Finalizing,
-                        &theme,
-                    )
-                    .render(layout.tree, frame.buffer_mut());
-                }
-                AppMode::Browsing
-                | AppMode::Help
-                | AppMode::ConfirmDelete
-                | AppMode::ConfirmMultiDelete
-                | AppMode::MultiDeleting => {
-                    state.ensure_views_computed();
-
-                    match state.view_mode {
-                        ViewMode::Tree => {
-                            if let Some(tree) = &state.tree {
-                                TreeView::new(
-                                    tree,
-                                    state.view_root,
-                                    state.selected_index,
-                                    state.scroll_offset,
-                                    &state.selected_nodes,
-                                    &theme,
-                                )
-                                .render(layout.tree, frame.buffer_mut());
-                            }
-                        }
-                        ViewMode::LargeFiles => {
-                            LargeFilesView::new(
-                                &state.computed_views.large_files,
-                                state.large_files_state.selected_index,
-                                state.large_files_state.scroll_offset,
-                                &state.selected_nodes,
-                                &theme,
-                            )
-                            .render(layout.tree, frame.buffer_mut());
-                        }
-                        ViewMode::BuildArtifacts => {
-                            BuildArtifactsView::new(
-                                &state.computed_views.build_artifacts,
-                                state.build_artifacts_state.selected_index,
-                                state.build_artifacts_state.scroll_offset,
-                                state.computed_views.stale_threshold,
-                                &state.selected_nodes,
-                                &theme,
-                            )
-                            .render(layout.tree, frame.buffer_mut());
-                        }
-                    }
-
-                    // Help overlay
-                    if state.mode == AppMode::Help {
-                        HelpView::new(&theme).render(area, frame.buffer_mut());
-                    }
-
-                    // Multi-delete confirmation dialog (check before single)
-                    if state.mode == AppMode::ConfirmMultiDelete
-                        && let Some(ref items) = state.pending_multi_delete
-                    {
-                        ConfirmMultiDeleteView::new(items, &theme).render(area, frame.buffer_mut());
-                    }
-
-                    // Single delete confirmation dialog
-                    if state.mode == AppMode::ConfirmDelete
-                        && let Some(path) = state.pending_delete_path()
-                    {
-                        let size = state.pending_delete_size();
-                        ConfirmDeleteView::new(path, size, &theme).render(area, frame.buffer_mut());
-                    }
-
-                    // Multi-delete progress overlay
-                    if state.mode == AppMode::MultiDeleting
-                        && let Some(ref progress) = state.multi_delete_progress
-                    {
-                        MultiDeleteProgressView::new(progress, &theme)
-                            .render(area, frame.buffer_mut());
-                    }
-                }
-            }
-
-            // Compute selection size for footer
-            let selection_size = selection_total_size(&state);
+        // Select the file dux was started on once its directory is loaded
+        if state.tree.is_some()
+            && let Some(file) = reveal.take()
+        {
+            state.reveal_path(&file);
+            redraw = true;
+        }
 
-            // Footer
-            Footer::new(state.mode, state.view_mode, &theme, &state.session_stats)
-                .with_stale_threshold(state.computed_views.stale_threshold)
-                .with_selection(
-                    state.selection_count(),
-                    selection_size,
-                    state.selecting_mode,
-                )
-                .render(layout.footer, frame.buffer_mut());
-        })?;
+        // Finished deletions and rescans show in this frame, not after the
+        // next key press
+        redraw |= state.poll_background();
 
-        // Poll for async delete completion
-        state.poll_delete();
-        state.poll_multi_delete();
+        // Draw UI, unless nothing changed since the last frame
+        if redraw || state.is_busy() {
+            draw(terminal, &mut state, theme)?;
+        }
 
-        // Handle events
-        match event_handler.next()? {
+        // Handle events. Between scans and deletions there is nothing to
+        // animate, so wait for input (or the footer message to expire)
+        let timeout = if state.is_busy() {
+            event_handler.tick_rate()
+        } else {
+            state.message_time_left().unwrap_or(IDLE_TIMEOUT)
+        };
+        match event_handler.next_within(timeout)? {
             AppEvent::Key(key) => {
                 let action = handle_key(
                     key,
                     state.mode,
                     state.selection_count() > 0,
                     state.selecting_mode,
+                    state.confirm_phrase.is_some(),
                 );
-                handle_action(&mut state, action);
+                handle_action(&mut state, theme, action);
+                redraw = true;
+            }
+            AppEvent::Mouse(mouse) => {
+                let action = handle_mouse(mouse, state.mode);
+                handle_action(&mut state, theme, action);
+                redraw = true;
             }
             AppEvent::Resize(_, _) => {
-                // Terminal will redraw on next loop
+                redraw = true;
             }
             AppEvent::Tick => {
-                state.tick_spinner();
+                redraw = state.tick();
             }
-            _ => {}
         }
 
+        if let Some(file) = state.pending_open.take()
+            && let Err(e) = open_in_editor(terminal, &file)
+        {
+            state.set_error(e.to_string());
+        }
+
+        cancel_token.set_paused(state.scan_paused);
         if state.should_quit {
             cancel_token.cancel();
             break;
         }
     }
 
-    // Save cache if tree was modified (e.g. deletions)
+    // Deletions cut off by exiting would leave items half-removed, missing
+    // from the audit log, and cached as gone; let them finish first
+    if state.deletions_running() {
+        state.set_status("Finishing deletions…".to_string());
+        while state.deletions_running() {
+            state.poll_multi_delete();
+            draw(terminal, &mut state, theme)?;
+            std::thread::sleep(event_handler.tick_rate());
+        }
+    }
+    state.poll_delete();
+    state.poll_multi_delete();
+
+    // A scan quit before it finished still leaves what it found, cached as partial
+    if let Some(handle) = scan_handle.take()
+        && let Ok(tree) = handle.join()
+        && let Some(ref writer) = cache_writer
+        && tree.live_count() > 1
+    {
+        write_cache(
+            writer,
+            tree,
+            &root_path_for_save,
+            cache_config_for_save.clone(),
+            true,
+        );
+    }
+
+    // Remember where the view was left for the next cache load
+    if let Some(ref cp) = cache_path_for_save
+        && let Some(snapshot) = UiSnapshot::capture(&state)
+    {
+        snapshot.save(&snapshot_path(cp));
+    }
+
+    // Save cache if tree was modified (e.g. deletions or excludes)
     if state.tree_modified
-        && let Some(ref tree) = state.tree
-        && let Some(ref cp) = cache_path_for_save
+        && !state.read_only
+        && let Some(ref writer) = cache_writer
+        && let Some(tree) = state.tree.take()
     {
-        let root_mtime = get_mtime(&root_path_for_save).unwrap_or(SystemTime::UNIX_EPOCH);
-        let meta = CacheMetadata {
-            version: dux_core::CACHE_VERSION,
-            root_path: root_path_for_save.clone(),
-            scan_time: SystemTime::now(),
-            root_mtime,
-            total_size: tree.total_size(),
-            node_count: tree.live_count(),
-            config: cache_config_for_save.clone(),
+        let config = CachedScanConfig {
+            exclude: state.excludes.clone(),
+            ..cache_config_for_save.clone()
         };
-        let _ = save_cache(cp, tree, &meta);
+        write_cache(
+            writer,
+            tree,
+            &root_path_for_save,
+            config,
+            state.partial_scan,
+        );
     }
 
     // Drop tree in background to avoid blocking on deallocation
     if let Some(tree) = state.tree.take() {
         std::thread::spawn(move || drop(tree));
     }
+    if let Some(writer) = cache_writer {
+        writer.finish();
+    }
+
+    Ok(AppExit {
+        relaunch_path: state.relaunch_path,
+        rescan: state.rescan,
+        deferred_output: state.deferred_output,
+        excludes: state.excludes,
+    })
+}
+
+/// Render one frame of the whole UI
+fn draw(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    state: &mut AppState,
+    theme: &Theme,
+) -> Result<()> {
+    let filter_summary = state.filter_summary();
+    terminal.draw(|frame| {
+        let area = frame.area();
+        let layout = AppLayout::new(area, filter_summary.is_some(), !state.compact);
+
+        // Background
+        frame
+            .buffer_mut()
+            .set_style(area, Style::default().bg(theme.bg));
+
+        // Explain a blank screen instead of drawing clipped views; the next
+        // resize redraws normally
+        if !AppLayout::fits(area) {
+            state.content_area = Rect::default();
+            TooSmallView::new(theme).render(area, frame.buffer_mut());
+            return;
+        }
+
+        // Update visible height for scrolling
+        state.visible_height = layout.tree.height as usize;
+        state.content_area = layout.tree;
+
+        // Header
+        Header::new(state, theme).render(layout.header, frame.buffer_mut());
+
+        // Size bar (total)
+        if layout.size_bar.height > 0 {
+            render_size_bar(state, theme, layout.size_bar, frame.buffer_mut());
+        }
+
+        // Main content
+        match state.mode {
+            AppMode::Scanning | AppMode::Finalizing => {
+                let progress_view = ProgressView::new(
+                    &state.progress,
+                    state.spinner_frame,
+                    state.mode == AppMode::Finalizing,
+                    theme,
+                )
+                .with_finalize_progress(state.finalize_progress)
+                .with_size_format(state.size_format);
+
+                // Progress box on top, partial tree below once one arrives
+                let progress_height = progress_view.height();
+                let (progress_area, partial_area) = match &state.partial_tree {
+                    Some(_) if layout.tree.height > progress_height + 3 => {
+                        let [top, rest] = Layout::vertical([
+                            Constraint::Length(progress_height),
+                            Constraint::Min(0),
+                        ])
+                        .areas(layout.tree);
+                        (top, Some(rest))
+                    }
+                    _ => (layout.tree, None),
+                };
+                progress_view.render(progress_area, frame.buffer_mut());
+
+                if let (Some(tree), Some(area)) = (&state.partial_tree, partial_area) {
+                    // No row is selected while the scan is running
+                    TreeView::new(
+                        tree,
+                        NodeId::ROOT,
+                        usize::MAX,
+                        0,
+                        &state.selected_nodes,
+                        theme,
+                    )
+                    .with_file_counts(state.show_file_counts)
+                    .with_size_format(state.size_format)
+                    .render(area, frame.buffer_mut());
+                }
+            }
+            AppMode::Browsing
+            | AppMode::Help
+            | AppMode::Errors
+            | AppMode::Details
+            | AppMode::FileTypes
+            | AppMode::GoTo
+            | AppMode::FileFilter
+            | AppMode::KeepNewest
+            | AppMode::Bookmarks
+            | AppMode::Excludes
+            | AppMode::Breadcrumbs
+            | AppMode::ConfirmDelete
+            | AppMode::ConfirmMultiDelete
+            | AppMode::MultiDeleting => {
+                state.ensure_views_computed();
+                // One `now` per frame, so every row is judged alike
+                let recent_cutoff = state
+                    .recent_window
+                    .duration()
+                    .and_then(|window| SystemTime::now().checked_sub(window));
+
+                match state.view_mode {
+                    ViewMode::Tree => {
+                        if let Some(tree) = &state.tree
+                            && state.show_treemap
+                        {
+                            TreemapView::new(
+                                tree,
+                                state.view_root,
+                                state.treemap_state.selected_index,
+                                theme,
+                            )
+                            .with_filter(state.node_filter())
+                            .with_size_format(state.size_format)
+                            .render(layout.tree, frame.buffer_mut());
+                        } else if let Some(tree) = &state.tree {
+                            TreeView::new(
+                                tree,
+                                state.view_root,
+                                state.selected_index,
+                                state.scroll_offset,
+                                &state.selected_nodes,
+                                theme,
+                            )
+                            .with_filter(state.node_filter())
+                            .with_percent_base(state.percent_base)
+                            .with_percent_total(state.percent_total())
+                            .with_metric(state.bar_metric)
+                            .with_recent(&state.computed_views.newest_mtimes, recent_cutoff)
+                            .with_file_counts(state.show_file_counts)
+                            .with_size_format(state.size_format)
+                            .render(layout.tree, frame.buffer_mut());
+                        }
+                    }
+                    ViewMode::LargeFiles => {
+                        LargeFilesView::new(
+                            &state.computed_views.large_files,
+                            &state.computed_views.large_file_rows,
+                            state.large_files_state.selected_index,
+                            state.large_files_state.scroll_offset,
+                            &state.selected_nodes,
+                            theme,
+                        )
+                        .with_size_format(state.size_format)
+                        .with_filter(&state.computed_views.large_files_filter)
+                        .render(layout.tree, frame.buffer_mut());
+                    }
+                    ViewMode::BuildArtifacts => {
+                        BuildArtifactsView::new(
+                            &state.computed_views.build_artifacts,
+                            state.build_artifacts_state.selected_index,
+                            state.build_artifacts_state.scroll_offset,
+                            state.computed_views.stale_threshold,
+                            &state.selected_nodes,
+                            theme,
+                        )
+                        .with_file_counts(state.show_file_counts)
+                        .with_age_display(state.age_display)
+                        .with_size_format(state.size_format)
+                        .render(layout.tree, frame.buffer_mut());
+                    }
+                    ViewMode::Changes => {
+                        ChangesView::new(
+                            &state.computed_views.changes,
+                            state.changes_state.selected_index,
+                            state.changes_state.scroll_offset,
+                            state.changes_since,
+                            &state.selected_nodes,
+                            theme,
+                        )
+                        .with_size_format(state.size_format)
+                        .render(layout.tree, frame.buffer_mut());
+                    }
+                    ViewMode::EmptyDirs => {
+                        EmptyDirsView::new(
+                            &state.computed_views.empty_dirs,
+                            state.empty_dirs_state.selected_index,
+                            state.empty_dirs_state.scroll_offset,
+                            &state.selected_nodes,
+                            theme,
+                        )
+                        .with_size_format(state.size_format)
+                        .render(layout.tree, frame.buffer_mut());
+                    }
+                    ViewMode::Symlinks => {
+                        SymlinksView::new(
+                            &state.computed_views.symlinks,
+                            state.symlinks_state.selected_index,
+                            state.symlinks_state.scroll_offset,
+                            &state.selected_nodes,
+                            theme,
+                        )
+                        .render(layout.tree, frame.buffer_mut());
+                    }
+                    ViewMode::Overview => {
+                        if let Some(tree) = &state.tree {
+                            let nodes = state.overview_nodes();
+                            OverviewView::new(
+                                tree,
+                                &nodes,
+                                state.overview_state.selected_index,
+                                state.overview_state.scroll_offset,
+                                &state.selected_nodes,
+                                theme,
+                            )
+                            .with_metric(state.bar_metric)
+                            .with_percent_total(state.percent_total())
+                            .with_size_format(state.size_format)
+                            .render(layout.tree, frame.buffer_mut());
+                        }
+                    }
+                }
+
+                // Help overlay
+                if state.mode == AppMode::Help {
+                    HelpView::new(theme)
+                        .with_read_only(state.read_only)
+                        .render(area, frame.buffer_mut());
+                }
 
+                // Scan errors overlay
+                if state.mode == AppMode::Errors {
+                    state.errors_visible = ErrorsView::visible_entries(area);
+                    ErrorsView::new(&state.scan_errors, state.errors_scroll, theme)
+                        .render(area, frame.buffer_mut());
+                }
+
+                // Bookmarks overlay
+                if state.mode == AppMode::Bookmarks {
+                    BookmarksView::new(
+                        state.bookmarks.list(),
+                        state.bookmarks_selected,
+                        &state.root_path,
+                        theme,
+                    )
+                    .render(area, frame.buffer_mut());
+                }
+
+                // Excludes overlay
+                if state.mode == AppMode::Excludes {
+                    ExcludesView::new(&state.excludes, state.excludes_selected, theme)
+                        .render(area, frame.buffer_mut());
+                }
+
+                // Breadcrumbs overlay
+                if state.mode == AppMode::Breadcrumbs
+                    && let Some(ref tree) = state.tree
+                {
+                    BreadcrumbsView::new(tree, state.view_root, state.breadcrumbs_selected, theme)
+                        .with_size_format(state.size_format)
+                        .render(area, frame.buffer_mut());
+                }
+
+                // Details panel
+                if state.mode == AppMode::Details
+                    && let Some(ref details) = state.details
+                {
+                    DetailsView::new(details, theme)
+                        .with_size_format(state.size_format)
+                        .render(area, frame.buffer_mut());
+                }
+
+                // File-type breakdown
+                if state.mode == AppMode::FileTypes
+                    && let Some(ref types) = state.file_types
+                {
+                    FileTypesView::new(types, theme)
+                        .with_size_format(state.size_format)
+                        .render(area, frame.buffer_mut());
+                }
+
+                // Multi-delete confirmation dialog (check before single)
+                if state.mode == AppMode::ConfirmMultiDelete
+                    && let Some(ref items) = state.pending_multi_delete
+                {
+                    ConfirmMultiDeleteView::new(items, theme)
+                        .with_artifact_kind(state.pending_delete_kind.as_deref())
+                        .with_size_format(state.size_format)
+                        .with_typed_confirm(state.confirm_phrase.as_deref(), &state.confirm_input)
+                        .render(area, frame.buffer_mut());
+                }
+
+                // Single delete confirmation dialog
+                if state.mode == AppMode::ConfirmDelete
+                    && let Some(path) = state.pending_delete_path()
+                {
+                    let size = state.pending_delete_size();
+                    ConfirmDeleteView::new(path, size, theme)
+                        .with_size_format(state.size_format)
+                        .with_typed_confirm(state.confirm_phrase.as_deref(), &state.confirm_input)
+                        .render(area, frame.buffer_mut());
+                }
+
+                // Multi-delete progress overlay
+                if state.mode == AppMode::MultiDeleting
+                    && let Some(ref progress) = state.multi_delete_progress
+                {
+                    MultiDeleteProgressView::new(progress, theme)
+                        .with_size_format(state.size_format)
+                        .render(area, frame.buffer_mut());
+                }
+            }
+        }
+
+        if let Some(summary) = filter_summary {
+            render_filter_summary(&summary, state, theme, layout.summary, frame.buffer_mut());
+        }
+
+        // Compute selection size for footer
+        let selection_size = selection_total_size(state);
+
+        // Footer
+        Footer::new(state.mode, state.view_mode, theme, &state.session_stats)
+            .with_stale_threshold(state.computed_views.stale_threshold)
+            .with_selection(
+                state.selection_count(),
+                selection_size,
+                state.selecting_mode,
+            )
+            .with_tree_total(state.tree.as_ref().map_or(0, |t| t.total_size()))
+            .with_indicators(state.view_indicators())
+            .with_typed_confirm(state.confirm_phrase.is_some())
+            .with_read_only(state.read_only)
+            .with_size_format(state.size_format)
+            .with_messages(
+                state.status_message.as_deref(),
+                state.error_message.as_deref(),
+            )
+            .with_goto_input(&state.goto_input)
+            .with_keep_newest_input(&state.keep_newest_input)
+            .with_file_filter(&state.computed_views.large_files_filter)
+            .render(layout.footer, frame.buffer_mut());
+    })?;
     Ok(())
 }
 
-fn handle_action(state: &mut AppState, action: Action) {
+/// Queue `tree` to be saved as the cache for `root` (best effort). `partial`
+/// marks a scan that was cancelled or timed out before it finished.
+fn write_cache(
+    writer: &CacheWriter,
+    tree: DiskTree,
+    root: &Path,
+    config: CachedScanConfig,
+    partial: bool,
+) {
+    let meta = CacheMetadata {
+        version: dux_core::CACHE_VERSION,
+        root_path: root.to_path_buf(),
+        scan_time: SystemTime::now(),
+        root_mtime: get_mtime(root).unwrap_or(SystemTime::UNIX_EPOCH),
+        total_size: tree.total_size(),
+        node_count: tree.live_count(),
+        config,
+        partial,
+    };
+    writer.save(tree, meta);
+}
+
+/// Built-in artifact rules merged with `artifacts.toml`, if present
+fn load_artifact_rules() -> ArtifactRules {
+    if let Some(path) = config::artifacts_file()
+        && path.exists()
+    {
+        match ArtifactRules::from_file(&path) {
+            Ok(rules) => return rules,
+            Err(e) => eprintln!("Warning: {} (using built-in artifact rules)", e),
+        }
+    }
+    ArtifactRules::default()
+}
+
+/// Pick the palette (`--theme` flag, else the last cycled theme), then lay
+/// the colors from `theme.toml` over it
+fn load_theme(args: &Args) -> Theme {
+    let theme = Theme::from_name(
+        args.theme
+            .or_else(config::load_theme_name)
+            .unwrap_or_default(),
+    );
+    if let Some(path) = config::theme_file()
+        && path.exists()
+    {
+        match ThemeOverrides::from_file(&path) {
+            Ok(overrides) => return theme.with_overrides(&overrides),
+            Err(e) => eprintln!("Warning: {} (ignoring custom colors)", e),
+        }
+    }
+    theme
+}
+
+fn handle_action(state: &mut AppState, theme: &mut Theme, action: Action) {
     match action {
         Action::MoveUp => state.move_up(),
         Action::MoveDown => state.move_down(),
@@ -394,24 +1611,39 @@ fn handle_action(state: &mut AppState, action: Action) {
         Action::ToggleSelect => state.toggle_select(),
         Action::ClearSelection => state.clear_selection(),
         // Tree-specific actions: only apply in Tree view
-        Action::Expand => {
-            if state.view_mode == ViewMode::Tree {
-                state.expand_selected();
-            }
-        }
-        Action::Collapse => {
+        Action::Expand => match state.view_mode {
+            ViewMode::Tree => state.expand_selected(),
+            ViewMode::LargeFiles => state.fold_large_files_group(Some(false)),
+            ViewMode::Overview => state.open_overview_entry(),
+            _ => {}
+        },
+        Action::Collapse => match state.view_mode {
+            ViewMode::Tree => state.collapse_selected(),
+            ViewMode::LargeFiles => state.fold_large_files_group(Some(true)),
+            _ => {}
+        },
+        Action::Toggle => match state.view_mode {
+            ViewMode::Tree => state.toggle_selected(),
+            ViewMode::LargeFiles => state.fold_large_files_group(None),
+            _ => {}
+        },
+        Action::ExpandAll => {
             if state.view_mode == ViewMode::Tree {
-                state.collapse_selected();
+                state.expand_all_selected();
             }
         }
-        Action::Toggle => {
+        Action::CollapseAll => {
             if state.view_mode == ViewMode::Tree {
-                state.toggle_selected();
+                state.collapse_all();
             }
         }
         Action::DrillDown => {
-            if state.view_mode == ViewMode::Tree {
-                state.drill_down();
+            if !state.request_open_file() {
+                match state.view_mode {
+                    ViewMode::Tree => state.drill_down(),
+                    ViewMode::Overview => state.open_overview_entry(),
+                    _ => {}
+                }
             }
         }
         Action::GoBack => {
@@ -419,6 +1651,11 @@ fn handle_action(state: &mut AppState, action: Action) {
                 state.go_back();
             }
         }
+        Action::GoForward => {
+            if state.view_mode == ViewMode::Tree {
+                state.go_forward();
+            }
+        }
         Action::NextView => state.next_view(),
         Action::PrevView => state.prev_view(),
         Action::CycleStaleThreshold => {
@@ -426,20 +1663,92 @@ fn handle_action(state: &mut AppState, action: Action) {
                 state.computed_views.cycle_stale_threshold();
             }
         }
+        Action::CleanStale => state.request_clean_stale(),
+        Action::DeleteArtifactKind => state.request_delete_kind(),
+        Action::StartKeepNewest => state.start_keep_newest(),
+        Action::KeepNewestInput(c) => state.keep_newest_push(c),
+        Action::KeepNewestBackspace => state.keep_newest_backspace(),
+        Action::ConfirmKeepNewest => state.confirm_keep_newest(),
+        Action::CancelKeepNewest => state.cancel_keep_newest(),
+        Action::SelectAll => state.select_all(),
+        Action::InvertSelection => state.invert_selection(),
+        Action::CycleTheme => {
+            *theme = Theme {
+                icons: theme.icons,
+                ..Theme::from_name(theme.name.next()).with_overrides(&theme.overrides)
+            };
+            config::save_theme_name(theme.name);
+        }
         Action::ShowHelp => state.show_help(),
         Action::HideHelp => state.hide_help(),
-        Action::OpenInFinder => state.open_in_finder(),
+        Action::ShowErrors => state.show_errors(),
+        Action::HideErrors => state.hide_errors(),
+        Action::ScrollErrors(delta) => state.scroll_errors(delta),
+        Action::ShowDetails => state.show_details(),
+        Action::HideDetails => state.hide_details(),
+        Action::ShowFileTypes => state.show_file_types(),
+        Action::HideFileTypes => state.hide_file_types(),
+        Action::StartGoTo => state.start_goto(),
+        Action::GoToInput(c) => state.goto_push(c),
+        Action::GoToBackspace => state.goto_backspace(),
+        Action::ConfirmGoTo => state.confirm_goto(),
+        Action::CancelGoTo => state.cancel_goto(),
+        Action::StartFileFilter => state.start_file_filter(),
+        Action::ToggleLargeFilesGrouping => {
+            if state.view_mode == ViewMode::LargeFiles {
+                state.toggle_large_files_grouping();
+            }
+        }
+        Action::FileFilterInput(c) => state.file_filter_push(c),
+        Action::FileFilterBackspace => state.file_filter_backspace(),
+        Action::ConfirmFileFilter => state.confirm_file_filter(),
+        Action::ClearFileFilter => state.clear_file_filter(),
+        Action::ToggleBookmark => state.toggle_bookmark(),
+        Action::ShowBookmarks => state.show_bookmarks(),
+        Action::HideBookmarks => state.hide_bookmarks(),
+        Action::MoveBookmark(delta) => state.move_bookmark(delta),
+        Action::OpenBookmark => state.open_selected_bookmark(),
+        Action::RemoveBookmark => state.remove_selected_bookmark(),
+        Action::ExcludeSelected => state.exclude_selected(),
+        Action::ShowExcludes => state.show_excludes(),
+        Action::HideExcludes => state.hide_excludes(),
+        Action::MoveExclude(delta) => state.move_exclude(delta),
+        Action::RemoveExclude => state.remove_selected_exclude(),
+        Action::ShowBreadcrumbs => state.show_breadcrumbs(),
+        Action::HideBreadcrumbs => state.hide_breadcrumbs(),
+        Action::MoveBreadcrumb(delta) => state.move_breadcrumb(delta),
+        Action::OpenBreadcrumb => state.open_selected_breadcrumb(),
+        Action::RevealInFileManager => state.reveal_in_file_manager(),
+        Action::CopyPath => state.copy_path(),
+        Action::CopyReport => state.copy_report(),
+        Action::CopyMarkdown => state.copy_markdown(),
+        Action::ExportCsv => state.export_current_view(),
+        Action::ExportSelection(script) => state.export_selection(script),
         Action::Delete => state.request_delete(),
         Action::ConfirmDelete => state.confirm_delete(),
         Action::CancelDelete => state.cancel_delete(),
+        Action::ConfirmInput(c) => state.confirm_push(c),
+        Action::ConfirmBackspace => state.confirm_backspace(),
         Action::ConfirmMultiDelete => state.confirm_multi_delete(),
         Action::CancelMultiDelete => state.cancel_multi_delete(),
+        Action::Click { column, row } => state.click_at(column, row),
+        Action::ToggleTreemap => state.toggle_treemap(),
+        Action::ToggleHidden => state.toggle_hidden(),
+        Action::CycleKindFilter => state.cycle_kind_filter(),
+        Action::StepMinShare(up) => state.step_min_share(up),
+        Action::TogglePercentBase => state.toggle_percent_base(),
+        Action::ToggleBarMetric => state.toggle_bar_metric(),
+        Action::CycleRecentWindow => state.cycle_recent_window(),
+        Action::ToggleAgeDisplay => state.toggle_age_display(),
+        Action::ToggleFileCounts => state.toggle_file_counts(),
+        Action::Rescan => state.request_rescan(),
         Action::Quit => state.quit(),
+        Action::TogglePause => state.toggle_scan_pause(),
         Action::Tick => {}
     }
 }
 
-/// Compute total size of selected nodes for footer display
+/// Size of the selection, counting nested picks once (as deleting it would)
 fn selection_total_size(state: &AppState) -> u64 {
     if state.selected_nodes.is_empty() {
         return 0;
@@ -448,10 +1757,9 @@ fn selection_total_size(state: &AppState) -> u64 {
         Some(t) => t,
         None => return 0,
     };
-    state
-        .selected_nodes
-        .iter()
-        .filter_map(|id| tree.get(*id))
+    dedup_ancestors(tree, &state.selected_nodes)
+        .into_iter()
+        .filter_map(|id| tree.get(id))
         .map(|n| n.size)
         .sum()
 }
@@ -475,24 +1783,189 @@ fn render_size_bar(
         .unwrap_or(state.progress.bytes_scanned);
 
     let is_scanning = state.tree.is_none();
-    let bar_width = area.width.saturating_sub(20) as usize;
+    let apparent = state.tree.as_ref().and_then(|t| t.total_apparent_size());
+
+    // Label - don't show "100%" during scanning since we don't know the final total
+    let label = match apparent {
+        _ if is_scanning => format!("{} scanned", state.size_format.format(total_size)),
+        Some(apparent) => format!(
+            "allocated {} (apparent {}, {} slack)",
+            state.size_format.format(total_size),
+            state.size_format.format(apparent),
+            state
+                .size_format
+                .format(total_size.saturating_sub(apparent)),
+        ),
+        None => format!("{} total", state.size_format.format(total_size)),
+    };
+
+    // Leave room for the label; the longer apparent-size label shrinks the bar
+    let label_width = label.chars().count() as u16;
+    let bar_width = area.width.saturating_sub(label_width.max(18) + 2) as usize;
 
     // During scanning, show a pulsing/growing bar; after complete, show full bar
     let (bar, _) = ui::bar_chart::render_bar(100.0, bar_width, theme.green);
 
     // Bar
     buf.set_string(area.x + 1, area.y, &bar, Style::default().fg(theme.green));
-
-    // Label - don't show "100%" during scanning since we don't know the final total
-    let label = if is_scanning {
-        format!("{} scanned", dux_core::format_size(total_size))
-    } else {
-        format!("{} total", dux_core::format_size(total_size))
-    };
     buf.set_string(
-        area.x + area.width - label.len() as u16 - 1,
+        area.x + area.width.saturating_sub(label_width + 1),
         area.y,
         &label,
         Style::default().fg(theme.fg_dim),
     );
 }
+
+/// One line above the footer saying how much the active filters leave out
+fn render_filter_summary(
+    summary: &FilterSummary,
+    state: &AppState,
+    theme: &Theme,
+    area: ratatui::layout::Rect,
+    buf: &mut ratatui::buffer::Buffer,
+) {
+    let text = format!(
+        "Showing {} of {} files (hidden: {})",
+        dux_core::format_count(summary.shown_files),
+        dux_core::format_count(summary.total_files),
+        state.size_format.format(summary.hidden_size),
+    );
+    let text: String = text
+        .chars()
+        .take(area.width.saturating_sub(2) as usize)
+        .collect();
+    buf.set_string(
+        area.x + 1,
+        area.y,
+        &text,
+        Style::default().fg(theme.fg_muted),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("45"), Ok(Duration::from_secs(45)));
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("999999999999999999h").is_err());
+        assert_eq!(
+            parse_duration(&format!("{}s", u64::MAX)),
+            Ok(Duration::from_secs(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn test_finish_scan_rejects_timed_out_scan() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        tx.send(ScanMessage::Cancelled { timed_out: true }).unwrap();
+        drop(tx);
+        let handle = std::thread::spawn(|| DiskTree::new(PathBuf::from("/p")));
+        let err = finish_scan(rx, handle).unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        tx.send(ScanMessage::Completed).unwrap();
+        drop(tx);
+        let handle = std::thread::spawn(|| DiskTree::new(PathBuf::from("/p")));
+        assert!(finish_scan(rx, handle).is_ok());
+    }
+
+    #[test]
+    fn test_also_scan_reaches_scan_and_cache_config() {
+        let args = Args::parse_from(["dux", "--also-scan", "/data", "--also-scan", "/media/usb"]);
+        let paths = vec![PathBuf::from("/data"), PathBuf::from("/media/usb")];
+        assert_eq!(scan_config(&args).extra_filesystems, paths);
+        assert_eq!(cache_config(&args).extra_filesystems, paths);
+    }
+
+    #[test]
+    fn test_cache_dir_flag_and_null_device() {
+        let args = Args::parse_from(["dux", "--cache-dir", "/tmp/dux-cache"]);
+        assert_eq!(cache_dir(&args), Some(PathBuf::from("/tmp/dux-cache")));
+
+        let args = Args::parse_from(["dux", "--cache-dir", NULL_CACHE_DIR]);
+        assert_eq!(cache_dir(&args), None);
+        assert!(previous_scan(Path::new("/p"), &args).is_none());
+    }
+
+    #[test]
+    fn test_run_info_reports_build_and_settings() {
+        let args = Args::parse_from(["dux", "--info", "--apparent", "--exclude", ".git"]);
+        let mut out = Vec::new();
+        run_info(&args, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.starts_with(&format!("dux {}\n", env!("CARGO_PKG_VERSION"))));
+        assert!(out.contains(dux_core::SIZE_STRATEGY));
+        assert!(out.contains(&format!("v{}", dux_core::CACHE_VERSION)));
+        assert!(out.contains("Apparent sizes:    yes"));
+        assert!(out.contains("Excludes:          .git"));
+    }
+
+    #[test]
+    fn test_run_diff_rejects_caches_of_different_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let save = |name: &str, root: &str| {
+            let file = dir.path().join(name);
+            let tree = DiskTree::new(PathBuf::from(root));
+            let meta = dux_core::CacheMetadata {
+                version: dux_core::CACHE_VERSION,
+                root_path: PathBuf::from(root),
+                scan_time: std::time::SystemTime::now(),
+                root_mtime: std::time::SystemTime::UNIX_EPOCH,
+                total_size: 0,
+                node_count: 1,
+                config: cache_config(&Args::parse_from(["dux"])),
+                partial: false,
+            };
+            dux_core::save_cache(&file, &tree, &meta).unwrap();
+            file
+        };
+        let home = save("home.dux", "/home");
+        let home_later = save("home-later.dux", "/home");
+        let var = save("var.dux", "/var");
+        let args = Args::parse_from(["dux"]);
+
+        assert!(run_diff(&home, Some(&home_later), &args).is_ok());
+        let err = run_diff(&home, Some(&var), &args).unwrap_err();
+        assert!(err.to_string().contains("same directory"));
+    }
+
+    #[test]
+    fn test_selection_total_counts_nested_picks_once() {
+        use dux_core::NodeKind;
+
+        let mut tree = DiskTree::new(PathBuf::from("/p"));
+        let src = tree.add_sized("src", NodeKind::Directory, 0, NodeId::ROOT);
+        let main = tree.add_sized("main.rs", NodeKind::File, 4096, src);
+        let notes = tree.add_sized("notes.txt", NodeKind::File, 1024, NodeId::ROOT);
+        tree.aggregate_sizes();
+
+        let mut state = AppState::new(PathBuf::from("/p"));
+        state.set_tree(tree);
+        assert_eq!(selection_total_size(&state), 0);
+        state.add_to_selection(src);
+        state.add_to_selection(main);
+        assert_eq!(selection_total_size(&state), 4096);
+        state.add_to_selection(notes);
+        assert_eq!(selection_total_size(&state), 5120);
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("2G"), Ok(2 << 30));
+        assert!(
+            parse_size("lots")
+                .unwrap_err()
+                .contains("Invalid size 'lots'")
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use dux_core::NodeId;
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppState;
+
+/// Where the tree view was left, saved next to the scan cache so that
+/// reopening the same directory from cache picks up in the same place
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UiSnapshot {
+    /// Live node count and total size when saved; expanded IDs are only
+    /// trusted when the loaded tree still matches
+    node_count: usize,
+    total_size: u64,
+    expanded: Vec<usize>,
+    view_root: PathBuf,
+    selected: Option<PathBuf>,
+    scroll_offset: usize,
+}
+
+/// Sidecar file for a cache file (`<hash>.ui` next to `<hash>.dux`)
+pub fn snapshot_path(cache_path: &Path) -> PathBuf {
+    cache_path.with_extension("ui")
+}
+
+impl UiSnapshot {
+    /// Record expansion, view root and tree selection (None before a tree exists)
+    pub fn capture(state: &AppState) -> Option<Self> {
+        let tree = state.tree.as_ref()?;
+        let selected = state
+            .visible_nodes()
+            .get(state.selected_index)
+            .and_then(|&id| tree.get(id))
+            .map(|n| n.path.clone());
+
+        Some(Self {
+            node_count: tree.live_count(),
+            total_size: tree.total_size(),
+            expanded: tree
+                .iter()
+                .filter(|n| n.is_expanded)
+                .map(|n| n.id.index())
+                .collect(),
+            view_root: tree.get(state.view_root)?.path.clone(),
+            selected,
+            scroll_offset: state.scroll_offset,
+        })
+    }
+
+    /// Load a snapshot; a missing or unreadable file is treated as no snapshot
+    pub fn load(path: &Path) -> Option<Self> {
+        let contents = fs::read_to_string(path).ok()?;
+        toml::from_str(&contents).ok()
+    }
+
+    /// Save the snapshot (best effort)
+    pub fn save(&self, path: &Path) {
+        if let Some(parent) = path.parent()
+            && fs::create_dir_all(parent).is_ok()
+            && let Ok(contents) = toml::to_string(self)
+        {
+            let _ = fs::write(path, contents);
+        }
+    }
+
+    /// Restore onto a freshly loaded tree. View root and selection are found
+    /// by path, so they survive changes that make the stored IDs stale.
+    pub fn apply(&self, state: &mut AppState) {
+        let Some(tree) = &mut state.tree else {
+            return;
+        };
+
+        if tree.live_count() == self.node_count && tree.total_size() == self.total_size {
+            for &index in &self.expanded {
+                tree.set_expanded(NodeId(index), true);
+            }
+        }
+
+        if let Some(root) = tree.find_by_path(&self.view_root)
+            && root != NodeId::ROOT
+            && tree.get(root).is_some_and(|n| n.kind.is_directory())
+        {
+            state.history.push(NodeId::ROOT);
+            state.view_root = root;
+        }
+
+        let Some(selected) = self.selected.as_deref().and_then(|p| tree.find_by_path(p)) else {
+            return;
+        };
+        if !tree.path_to_node(selected).contains(&state.view_root) {
+            return;
+        }
+        if let Some(parent) = tree.get(selected).and_then(|n| n.parent) {
+            tree.expand_to(parent);
+        }
+        if let Some(index) = state.visible_nodes().iter().position(|&id| id == selected) {
+            state.selected_index = index;
+            state.scroll_offset = self.scroll_offset.min(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dux_core::{DiskTree, NodeKind};
+
+    fn sample_tree() -> DiskTree {
+        let mut tree = DiskTree::new(PathBuf::from("/r"));
+        let a = tree.add_sized("a", NodeKind::Directory, 0, NodeId::ROOT);
+        let b = tree.add_sized("b", NodeKind::Directory, 0, a);
+        tree.add_sized("f", NodeKind::File, 0, b);
+        tree.add_sized("g", NodeKind::File, 0, b);
+        tree
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut state = AppState::new(PathBuf::from("/r"));
+        state.set_tree(sample_tree());
+        let a = NodeId(1);
+        state.view_root = a;
+        let tree = state.tree.as_mut().unwrap();
+        tree.set_expanded(a, true);
+        tree.set_expanded(NodeId(2), true);
+        state.selected_index = 3; // a, b, f, g
+        state.scroll_offset = 1;
+
+        let snapshot = UiSnapshot::capture(&state).unwrap();
+        let snapshot: UiSnapshot = toml::from_str(&toml::to_string(&snapshot).unwrap()).unwrap();
+
+        let mut restored = AppState::new(PathBuf::from("/r"));
+        restored.set_tree(sample_tree());
+        snapshot.apply(&mut restored);
+
+        assert_eq!(restored.view_root, a);
+        assert_eq!(restored.history, vec![NodeId::ROOT]);
+        assert_eq!(restored.selected_index, 3);
+        assert_eq!(restored.scroll_offset, 1);
+        assert_eq!(restored.visible_nodes(), state.visible_nodes());
+    }
+}
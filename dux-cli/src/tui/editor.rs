@@ -0,0 +1,74 @@
+use std::io::{self, Stdout};
+use std::path::Path;
+use std::process::Command;
+
+use color_eyre::{Result, eyre::eyre};
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{Terminal, backend::CrosstermBackend};
+
+/// Used when neither `$EDITOR` nor `$PAGER` is set
+const FALLBACK_PAGER: &str = "less";
+
+/// Open a file in `$EDITOR` (or `$PAGER`, then `less`), handing the terminal
+/// over until it exits. The TUI is restored even if the program fails.
+pub fn open_in_editor(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    path: &Path,
+) -> Result<()> {
+    let [program, args @ ..] = &viewer_command(env_var("EDITOR"), env_var("PAGER"))[..] else {
+        unreachable!("viewer command always has a program");
+    };
+
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+
+    let status = Command::new(program).args(args).arg(path).status();
+
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(eyre!("{} exited with {}", program, status)),
+        Err(e) => Err(eyre!("Could not run {}: {}", program, e)),
+    }
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Program and arguments to open a file with. Values may carry arguments
+/// (`EDITOR="code -w"`); empty values are treated as unset.
+fn viewer_command(editor: Option<String>, pager: Option<String>) -> Vec<String> {
+    [editor, pager]
+        .into_iter()
+        .flatten()
+        .map(|value| {
+            value
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .find(|words| !words.is_empty())
+        .unwrap_or_else(|| vec![FALLBACK_PAGER.to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewer_command_precedence() {
+        let some = |s: &str| Some(s.to_string());
+        assert_eq!(viewer_command(some("vim"), some("more")), ["vim"]);
+        assert_eq!(viewer_command(some("code -w"), None), ["code", "-w"]);
+        assert_eq!(viewer_command(some("  "), some("more")), ["more"]);
+        assert_eq!(viewer_command(None, None), ["less"]);
+    }
+}
@@ -28,9 +28,15 @@ impl EventHandler {
         }
     }
 
-    /// Poll for the next event
-    pub fn next(&self) -> color_eyre::Result<AppEvent> {
-        if event::poll(self.tick_rate)? {
+    /// How long to wait for input between animation frames
+    pub fn tick_rate(&self) -> Duration {
+        self.tick_rate
+    }
+
+    /// Wait up to `timeout` for the next event; a `Tick` if none arrives.
+    /// Idle callers pass a long timeout so the loop sleeps instead of spinning.
+    pub fn next_within(&self, timeout: Duration) -> color_eyre::Result<AppEvent> {
+        if event::poll(timeout)? {
             match event::read()? {
                 CrosstermEvent::Key(key) => Ok(AppEvent::Key(key)),
                 CrosstermEvent::Mouse(mouse) => Ok(AppEvent::Mouse(mouse)),
@@ -0,0 +1,520 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::app::{Action, AppMode};
+
+/// Help screen group a binding is listed under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Views,
+    Navigation,
+    Tree,
+    Actions,
+    Other,
+}
+
+impl Section {
+    /// Sections in help screen order
+    pub const ALL: [Section; 5] = [
+        Section::Views,
+        Section::Navigation,
+        Section::Tree,
+        Section::Actions,
+        Section::Other,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Section::Views => "Views",
+            Section::Navigation => "Navigation",
+            Section::Tree => "Tree",
+            Section::Actions => "Actions",
+            Section::Other => "Other",
+        }
+    }
+}
+
+/// A key, optionally with Ctrl held. Shift is not matched: terminals report
+/// shifted letters as their uppercase character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    code: KeyCode,
+    ctrl: bool,
+}
+
+impl Key {
+    const fn code(code: KeyCode) -> Self {
+        Self { code, ctrl: false }
+    }
+
+    const fn char(c: char) -> Self {
+        Self::code(KeyCode::Char(c))
+    }
+
+    const fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            ctrl: true,
+        }
+    }
+
+    fn matches(self, event: &KeyEvent) -> bool {
+        event.code == self.code && event.modifiers.contains(KeyModifiers::CONTROL) == self.ctrl
+    }
+
+    /// How the key is written on the help screen
+    pub fn label(self) -> String {
+        let name = match self.code {
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "S-Tab".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if self.ctrl => c.to_ascii_uppercase().to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            other => format!("{:?}", other),
+        };
+        if self.ctrl {
+            format!("Ctrl+{}", name)
+        } else {
+            name
+        }
+    }
+}
+
+/// When a binding applies, on top of the mode it is listed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum When {
+    Always,
+    /// Shift is held or select mode is on (arrows extend the selection)
+    Extending,
+    /// Select mode is on (vi keys extend the selection)
+    Selecting,
+    /// Something is selected, or select mode is on
+    HasSelection,
+}
+
+/// State the conditional bindings depend on
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KeyContext {
+    pub shift: bool,
+    pub selecting: bool,
+    pub has_selection: bool,
+}
+
+impl When {
+    fn holds(self, context: KeyContext) -> bool {
+        match self {
+            When::Always => true,
+            When::Extending => context.shift || context.selecting,
+            When::Selecting => context.selecting,
+            When::HasSelection => context.has_selection || context.selecting,
+        }
+    }
+}
+
+/// Keys that trigger an action, and how the help screen lists them
+#[derive(Debug, Clone, Copy)]
+pub struct Binding {
+    pub keys: &'static [Key],
+    pub action: Action,
+    pub when: When,
+    /// Empty for bindings the help screen leaves out (variants of a listed one)
+    pub description: &'static str,
+    pub section: Section,
+}
+
+impl Binding {
+    const fn new(keys: &'static [Key], action: Action) -> Self {
+        Self {
+            keys,
+            action,
+            when: When::Always,
+            description: "",
+            section: Section::Other,
+        }
+    }
+
+    const fn when(self, when: When) -> Self {
+        Self { when, ..self }
+    }
+
+    const fn help(self, section: Section, description: &'static str) -> Self {
+        Self {
+            section,
+            description,
+            ..self
+        }
+    }
+
+    /// Keys as written on the help screen, e.g. `↑ k`
+    pub fn keys_label(&self) -> String {
+        self.keys
+            .iter()
+            .map(|key| key.label())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+const UP: Key = Key::code(KeyCode::Up);
+const DOWN: Key = Key::code(KeyCode::Down);
+const LEFT: Key = Key::code(KeyCode::Left);
+const RIGHT: Key = Key::code(KeyCode::Right);
+const PAGE_UP: Key = Key::code(KeyCode::PageUp);
+const PAGE_DOWN: Key = Key::code(KeyCode::PageDown);
+const HOME: Key = Key::code(KeyCode::Home);
+const END: Key = Key::code(KeyCode::End);
+const ENTER: Key = Key::code(KeyCode::Enter);
+const ESC: Key = Key::code(KeyCode::Esc);
+const BACKSPACE: Key = Key::code(KeyCode::Backspace);
+const DELETE: Key = Key::code(KeyCode::Delete);
+const CTRL_C: Key = Key::ctrl('c');
+
+use Section::{Actions, Navigation, Other, Tree, Views};
+
+/// Main keymap: everything the help screen lists
+pub const BROWSING: &[Binding] = &[
+    // Views
+    Binding::new(&[Key::code(KeyCode::Tab)], Action::NextView).help(Views, "Next view"),
+    Binding::new(&[Key::code(KeyCode::BackTab)], Action::PrevView).help(Views, "Previous view"),
+    Binding::new(&[Key::char('s')], Action::CycleStaleThreshold)
+        .help(Views, "Cycle stale threshold (Build Artifacts)"),
+    Binding::new(&[Key::char('C')], Action::CleanStale)
+        .help(Views, "Clean all stale artifacts (Build Artifacts)"),
+    Binding::new(&[Key::char('D')], Action::DeleteArtifactKind)
+        .help(Views, "Delete all of selected kind (Build Artifacts)"),
+    Binding::new(&[Key::char('N')], Action::StartKeepNewest).help(
+        Views,
+        "Delete all but the newest N of kind (Build Artifacts)",
+    ),
+    Binding::new(&[Key::char('T')], Action::ToggleAgeDisplay)
+        .help(Views, "Relative age / date (Build Artifacts)"),
+    Binding::new(&[Key::char('.')], Action::ToggleHidden).help(Views, "Show/hide hidden files"),
+    Binding::new(&[Key::char('c')], Action::ToggleFileCounts)
+        .help(Views, "Show/hide file-count column"),
+    Binding::new(&[Key::char('e')], Action::ShowErrors).help(Views, "Show scan errors"),
+    Binding::new(&[Key::char('i')], Action::ShowDetails)
+        .help(Views, "Show details (owner, permissions)"),
+    Binding::new(&[Key::char('I')], Action::ShowFileTypes)
+        .help(Views, "File types in selected directory"),
+    Binding::new(&[Key::char(':')], Action::StartGoTo).help(Views, "Jump to path"),
+    Binding::new(&[Key::char('/')], Action::StartFileFilter)
+        .help(Views, "Filter by glob, e.g. *.mov (Large Files)"),
+    Binding::new(&[Key::char('F')], Action::ToggleLargeFilesGrouping)
+        .help(Views, "Group by folder; Space folds (Large Files)"),
+    Binding::new(&[Key::char('b')], Action::ToggleBookmark)
+        .help(Views, "Bookmark/unbookmark current directory"),
+    Binding::new(&[Key::char('B')], Action::ShowBookmarks).help(Views, "Bookmarks (jump or scan)"),
+    Binding::new(&[Key::char('X')], Action::ExcludeSelected)
+        .help(Views, "Exclude selected directory from this session"),
+    Binding::new(&[Key::char('E')], Action::ShowExcludes).help(Views, "Excluded paths (undo)"),
+    Binding::new(&[Key::char('x')], Action::ExportCsv).help(Views, "Export current view to CSV"),
+    // Navigation
+    Binding::new(&[UP, Key::char('k')], Action::MoveUp).help(Navigation, "Move up"),
+    Binding::new(&[DOWN, Key::char('j')], Action::MoveDown).help(Navigation, "Move down"),
+    Binding::new(&[PAGE_UP], Action::PageUp).help(Navigation, "Page up"),
+    Binding::new(&[PAGE_DOWN], Action::PageDown).help(Navigation, "Page down"),
+    Binding::new(&[HOME, Key::char('g')], Action::GoToFirst).help(Navigation, "Go to first"),
+    Binding::new(&[END, Key::char('G')], Action::GoToLast).help(Navigation, "Go to last"),
+    Binding::new(&[Key::char('v')], Action::ToggleSelect)
+        .help(Navigation, "Enter/exit select mode"),
+    Binding::new(&[Key::char('a')], Action::SelectAll)
+        .help(Navigation, "Select all (stale in Build Artifacts)"),
+    Binding::new(&[Key::char('A')], Action::InvertSelection).help(Navigation, "Invert selection"),
+    Binding::new(&[Key::char('K')], Action::SelectUp)
+        .help(Navigation, "Select up (or S-↑, ↑ in select mode)"),
+    Binding::new(&[Key::char('J')], Action::SelectDown)
+        .help(Navigation, "Select down (or S-↓, ↓ in select mode)"),
+    Binding::new(&[UP], Action::SelectUp).when(When::Extending),
+    Binding::new(&[DOWN], Action::SelectDown).when(When::Extending),
+    Binding::new(&[PAGE_UP], Action::SelectPageUp).when(When::Extending),
+    Binding::new(&[PAGE_DOWN], Action::SelectPageDown).when(When::Extending),
+    Binding::new(&[HOME], Action::SelectToFirst).when(When::Extending),
+    Binding::new(&[END], Action::SelectToLast).when(When::Extending),
+    Binding::new(&[Key::char('k')], Action::SelectUp).when(When::Selecting),
+    Binding::new(&[Key::char('j')], Action::SelectDown).when(When::Selecting),
+    Binding::new(&[Key::char('g')], Action::SelectToFirst).when(When::Selecting),
+    Binding::new(&[Key::char('G')], Action::SelectToLast).when(When::Selecting),
+    Binding::new(&[ESC], Action::ClearSelection)
+        .when(When::HasSelection)
+        .help(Navigation, "Clear selection"),
+    Binding::new(&[BACKSPACE, ESC, Key::char('[')], Action::GoBack).help(Navigation, "Go back"),
+    Binding::new(&[Key::char(']')], Action::GoForward).help(Navigation, "Go forward"),
+    Binding::new(&[Key::char('u')], Action::ShowBreadcrumbs)
+        .help(Navigation, "Jump up to an ancestor"),
+    // Tree
+    Binding::new(&[RIGHT, Key::char('l')], Action::Expand).help(Tree, "Expand directory"),
+    Binding::new(&[LEFT, Key::char('h')], Action::Collapse).help(Tree, "Collapse directory"),
+    Binding::new(&[Key::char(' ')], Action::Toggle).help(Tree, "Toggle expand/collapse"),
+    Binding::new(&[Key::char('*')], Action::ExpandAll).help(Tree, "Expand all under cursor"),
+    Binding::new(&[Key::char('-')], Action::CollapseAll).help(Tree, "Collapse all"),
+    Binding::new(&[Key::char('m')], Action::ToggleTreemap).help(Tree, "Toggle treemap blocks"),
+    Binding::new(&[Key::char('p')], Action::TogglePercentBase)
+        .help(Tree, "Percent of view root / parent / free space / --need"),
+    Binding::new(&[Key::char('M')], Action::ToggleBarMetric)
+        .help(Tree, "Bars and order by size / file count"),
+    Binding::new(&[Key::char('H')], Action::CycleRecentWindow)
+        .help(Tree, "Mark changes within off / 1h / 24h / 7d"),
+    // `-` already collapses, so its shifted key lowers the threshold
+    Binding::new(&[Key::char('+')], Action::StepMinShare(true))
+        .help(Tree, "Hide more small entries (also Large Files)"),
+    Binding::new(&[Key::char('_')], Action::StepMinShare(false))
+        .help(Tree, "Hide fewer small entries"),
+    Binding::new(&[Key::char('f')], Action::CycleKindFilter)
+        .help(Tree, "All / directories / files only"),
+    Binding::new(&[ENTER], Action::DrillDown).help(Tree, "Drill down / open file in $EDITOR"),
+    // Actions
+    Binding::new(&[Key::char('o')], Action::RevealInFileManager)
+        .help(Actions, "Show in file manager"),
+    Binding::new(&[Key::char('y')], Action::CopyPath).help(Actions, "Copy path to clipboard"),
+    Binding::new(&[Key::char('Y')], Action::CopyReport)
+        .help(Actions, "Copy size report of this directory"),
+    Binding::new(&[Key::char('L')], Action::CopyMarkdown)
+        .help(Actions, "Copy this directory as a Markdown table"),
+    Binding::new(&[Key::char('w')], Action::ExportSelection(false))
+        .help(Actions, "Write selected paths to a file"),
+    Binding::new(&[Key::char('W')], Action::ExportSelection(true))
+        .help(Actions, "Write an rm script for the selection"),
+    Binding::new(&[Key::char('d')], Action::Delete).help(Actions, "Delete selected item(s)"),
+    // Other
+    Binding::new(&[Key::char('t')], Action::CycleTheme).help(Other, "Cycle color theme"),
+    Binding::new(&[Key::char('R'), Key::code(KeyCode::F(5))], Action::Rescan)
+        .help(Other, "Rescan (keeps the current view)"),
+    Binding::new(&[Key::char('?')], Action::ShowHelp).help(Other, "Toggle this help"),
+    Binding::new(&[Key::char('q'), CTRL_C], Action::Quit).help(Other, "Quit"),
+];
+
+/// Mouse input handled by `handle_mouse`, listed under Navigation
+pub const MOUSE_HELP: &[(&str, &str)] = &[
+    ("Wheel", "Move up/down"),
+    ("Click", "Select row (double-click drills down)"),
+];
+
+const SCANNING: &[Binding] = &[
+    Binding::new(&[Key::char('q'), CTRL_C], Action::Quit),
+    Binding::new(&[Key::char('p')], Action::TogglePause),
+];
+
+const HELP: &[Binding] = &[Binding::new(
+    &[ESC, Key::char('q'), Key::char('?')],
+    Action::HideHelp,
+)];
+
+const ERRORS: &[Binding] = &[
+    Binding::new(&[ESC, Key::char('q'), Key::char('e')], Action::HideErrors),
+    Binding::new(&[UP, Key::char('k')], Action::ScrollErrors(-1)),
+    Binding::new(&[DOWN, Key::char('j')], Action::ScrollErrors(1)),
+    Binding::new(&[PAGE_UP], Action::ScrollErrors(-10)),
+    Binding::new(&[PAGE_DOWN], Action::ScrollErrors(10)),
+    Binding::new(&[HOME, Key::char('g')], Action::ScrollErrors(isize::MIN)),
+    Binding::new(&[END, Key::char('G')], Action::ScrollErrors(isize::MAX)),
+];
+
+const DETAILS: &[Binding] = &[Binding::new(
+    &[ESC, Key::char('q'), Key::char('i')],
+    Action::HideDetails,
+)];
+
+const FILE_TYPES: &[Binding] = &[Binding::new(
+    &[ESC, Key::char('q'), Key::char('I')],
+    Action::HideFileTypes,
+)];
+
+/// Other characters are typed into the prompt
+const GOTO: &[Binding] = &[
+    Binding::new(&[ESC, CTRL_C], Action::CancelGoTo),
+    Binding::new(&[ENTER], Action::ConfirmGoTo),
+    Binding::new(&[BACKSPACE], Action::GoToBackspace),
+];
+
+/// Other characters are typed into the filter
+const FILE_FILTER: &[Binding] = &[
+    Binding::new(&[ESC, CTRL_C], Action::ClearFileFilter),
+    Binding::new(&[ENTER], Action::ConfirmFileFilter),
+    Binding::new(&[BACKSPACE], Action::FileFilterBackspace),
+];
+
+/// Other digits are typed into the prompt
+const KEEP_NEWEST: &[Binding] = &[
+    Binding::new(&[ESC, CTRL_C], Action::CancelKeepNewest),
+    Binding::new(&[ENTER], Action::ConfirmKeepNewest),
+    Binding::new(&[BACKSPACE], Action::KeepNewestBackspace),
+];
+
+const BOOKMARKS: &[Binding] = &[
+    Binding::new(
+        &[ESC, Key::char('q'), Key::char('B')],
+        Action::HideBookmarks,
+    ),
+    Binding::new(&[UP, Key::char('k')], Action::MoveBookmark(-1)),
+    Binding::new(&[DOWN, Key::char('j')], Action::MoveBookmark(1)),
+    Binding::new(&[HOME, Key::char('g')], Action::MoveBookmark(isize::MIN)),
+    Binding::new(&[END, Key::char('G')], Action::MoveBookmark(isize::MAX)),
+    Binding::new(&[ENTER], Action::OpenBookmark),
+    Binding::new(&[Key::char('d'), DELETE], Action::RemoveBookmark),
+];
+
+const EXCLUDES: &[Binding] = &[
+    Binding::new(&[ESC, Key::char('q'), Key::char('E')], Action::HideExcludes),
+    Binding::new(&[UP, Key::char('k')], Action::MoveExclude(-1)),
+    Binding::new(&[DOWN, Key::char('j')], Action::MoveExclude(1)),
+    Binding::new(&[HOME, Key::char('g')], Action::MoveExclude(isize::MIN)),
+    Binding::new(&[END, Key::char('G')], Action::MoveExclude(isize::MAX)),
+    Binding::new(&[Key::char('d'), DELETE], Action::RemoveExclude),
+];
+
+const BREADCRUMBS: &[Binding] = &[
+    Binding::new(
+        &[ESC, Key::char('q'), Key::char('u')],
+        Action::HideBreadcrumbs,
+    ),
+    Binding::new(&[UP, Key::char('k')], Action::MoveBreadcrumb(-1)),
+    Binding::new(&[DOWN, Key::char('j')], Action::MoveBreadcrumb(1)),
+    Binding::new(&[HOME, Key::char('g')], Action::MoveBreadcrumb(isize::MIN)),
+    Binding::new(&[END, Key::char('G')], Action::MoveBreadcrumb(isize::MAX)),
+    Binding::new(&[ENTER, Key::char('l')], Action::OpenBreadcrumb),
+];
+
+const CONFIRM_DELETE: &[Binding] = &[
+    Binding::new(&[Key::char('y'), ENTER], Action::ConfirmDelete),
+    Binding::new(&[Key::char('n'), ESC], Action::CancelDelete),
+];
+
+const CONFIRM_MULTI_DELETE: &[Binding] = &[
+    Binding::new(&[Key::char('y'), ENTER], Action::ConfirmMultiDelete),
+    Binding::new(&[Key::char('n'), ESC], Action::CancelMultiDelete),
+];
+
+/// Typed confirmation: other characters go into the phrase, so only Enter confirms
+const TYPED_CONFIRM_DELETE: &[Binding] = &[
+    Binding::new(&[ENTER], Action::ConfirmDelete),
+    Binding::new(&[ESC, CTRL_C], Action::CancelDelete),
+    Binding::new(&[BACKSPACE], Action::ConfirmBackspace),
+];
+
+const TYPED_CONFIRM_MULTI_DELETE: &[Binding] = &[
+    Binding::new(&[ENTER], Action::ConfirmMultiDelete),
+    Binding::new(&[ESC, CTRL_C], Action::CancelMultiDelete),
+    Binding::new(&[BACKSPACE], Action::ConfirmBackspace),
+];
+
+const MULTI_DELETING: &[Binding] = &[Binding::new(&[Key::char('q'), CTRL_C], Action::Quit)];
+
+/// Bindings active in `mode`. `typed_confirm` is set when the pending
+/// deletion must be confirmed by typing a phrase rather than pressing `y`.
+pub fn bindings(mode: AppMode, typed_confirm: bool) -> &'static [Binding] {
+    match mode {
+        AppMode::Browsing => BROWSING,
+        AppMode::Scanning | AppMode::Finalizing => SCANNING,
+        AppMode::Help => HELP,
+        AppMode::Errors => ERRORS,
+        AppMode::Details => DETAILS,
+        AppMode::FileTypes => FILE_TYPES,
+        AppMode::GoTo => GOTO,
+        AppMode::FileFilter => FILE_FILTER,
+        AppMode::KeepNewest => KEEP_NEWEST,
+        AppMode::Bookmarks => BOOKMARKS,
+        AppMode::Excludes => EXCLUDES,
+        AppMode::Breadcrumbs => BREADCRUMBS,
+        AppMode::ConfirmDelete if typed_confirm => TYPED_CONFIRM_DELETE,
+        AppMode::ConfirmMultiDelete if typed_confirm => TYPED_CONFIRM_MULTI_DELETE,
+        AppMode::ConfirmDelete => CONFIRM_DELETE,
+        AppMode::ConfirmMultiDelete => CONFIRM_MULTI_DELETE,
+        AppMode::MultiDeleting => MULTI_DELETING,
+    }
+}
+
+/// Action bound to `key`. Conditional bindings that hold win over the
+/// unconditional binding of the same key.
+pub fn lookup(bindings: &[Binding], key: &KeyEvent, context: KeyContext) -> Option<Action> {
+    let matching = || {
+        bindings
+            .iter()
+            .filter(|binding| binding.keys.iter().any(|k| k.matches(key)))
+    };
+    matching()
+        .find(|binding| binding.when != When::Always && binding.when.holds(context))
+        .or_else(|| matching().find(|binding| binding.when == When::Always))
+        .map(|binding| binding.action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_TABLES: [&[Binding]; 17] = [
+        BROWSING,
+        SCANNING,
+        HELP,
+        ERRORS,
+        DETAILS,
+        FILE_TYPES,
+        GOTO,
+        FILE_FILTER,
+        KEEP_NEWEST,
+        BOOKMARKS,
+        EXCLUDES,
+        BREADCRUMBS,
+        CONFIRM_DELETE,
+        CONFIRM_MULTI_DELETE,
+        TYPED_CONFIRM_DELETE,
+        TYPED_CONFIRM_MULTI_DELETE,
+        MULTI_DELETING,
+    ];
+
+    #[test]
+    fn test_no_key_bound_twice_under_same_condition() {
+        for table in ALL_TABLES {
+            for (i, a) in table.iter().enumerate() {
+                for b in &table[i + 1..] {
+                    let shared = a.keys.iter().find(|k| b.keys.contains(k));
+                    assert!(
+                        a.when != b.when || shared.is_none(),
+                        "{:?} bound to both {:?} and {:?}",
+                        shared,
+                        a.action,
+                        b.action
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup_prefers_conditional_bindings() {
+        let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let idle = KeyContext::default();
+        let selecting = KeyContext {
+            selecting: true,
+            ..KeyContext::default()
+        };
+
+        let up = press(KeyCode::Up);
+        assert_eq!(lookup(BROWSING, &up, idle), Some(Action::MoveUp));
+        assert_eq!(lookup(BROWSING, &up, selecting), Some(Action::SelectUp));
+        let esc = press(KeyCode::Esc);
+        assert_eq!(lookup(BROWSING, &esc, idle), Some(Action::GoBack));
+        assert_eq!(
+            lookup(BROWSING, &esc, selecting),
+            Some(Action::ClearSelection)
+        );
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(lookup(BROWSING, &ctrl_c, idle), Some(Action::Quit));
+        assert_eq!(
+            lookup(BROWSING, &press(KeyCode::Char('c')), idle),
+            Some(Action::ToggleFileCounts)
+        );
+        assert_eq!(lookup(BROWSING, &press(KeyCode::Char('Z')), idle), None);
+    }
+}
@@ -1,5 +1,8 @@
+mod editor;
 mod event;
 mod handler;
+pub mod keymap;
 
+pub use editor::open_in_editor;
 pub use event::{AppEvent, EventHandler};
-pub use handler::handle_key;
+pub use handler::{handle_key, handle_mouse};
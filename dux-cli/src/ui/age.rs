@@ -0,0 +1,63 @@
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local};
+
+use crate::app::AgeDisplay;
+
+/// Width of the age column (fits `2024-03-05`)
+pub const AGE_WIDTH: usize = 10;
+
+/// Render a modification time as an age (`45d`) or a local date, with `—`
+/// when unknown. Times in the future count as `now`.
+pub fn format_age(mtime: Option<SystemTime>, now: SystemTime, display: AgeDisplay) -> String {
+    let Some(mtime) = mtime else {
+        return "—".to_string();
+    };
+    match display {
+        AgeDisplay::Absolute => DateTime::<Local>::from(mtime)
+            .format("%Y-%m-%d")
+            .to_string(),
+        AgeDisplay::Relative => {
+            let secs = now.duration_since(mtime).map_or(0, |d| d.as_secs());
+            match secs {
+                0..60 => "now".to_string(),
+                60..3600 => format!("{}m", secs / 60),
+                3600..86400 => format!("{}h", secs / 3600),
+                86400..31_536_000 => format!("{}d", secs / 86400),
+                _ => format!("{}y", secs / 31_536_000),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_format_age_relative() {
+        let now = SystemTime::now();
+        let ago = |secs| Some(now - Duration::from_secs(secs));
+        let relative = |mtime| format_age(mtime, now, AgeDisplay::Relative);
+        assert_eq!(relative(None), "—");
+        assert_eq!(relative(ago(5)), "now");
+        assert_eq!(relative(ago(150)), "2m");
+        assert_eq!(relative(ago(5 * 3600)), "5h");
+        assert_eq!(relative(ago(45 * 86400)), "45d");
+        assert_eq!(relative(ago(800 * 86400)), "2y");
+        assert_eq!(relative(Some(now + Duration::from_secs(60))), "now");
+    }
+
+    #[test]
+    fn test_format_age_absolute() {
+        let date = format_age(
+            Some(SystemTime::now()),
+            SystemTime::now(),
+            AgeDisplay::Absolute,
+        );
+        assert_eq!(date, Local::now().format("%Y-%m-%d").to_string());
+        assert_eq!(date.chars().count(), AGE_WIDTH);
+    }
+}
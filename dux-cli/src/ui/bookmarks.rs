@@ -0,0 +1,114 @@
+use std::path::{Path, PathBuf};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Padding, Widget},
+};
+
+use super::text::truncate_left;
+use super::theme::Theme;
+
+/// Label for bookmarks that open a new scan rather than jumping in this tree
+const NEW_SCAN_LABEL: &str = "new scan";
+
+/// Overlay listing saved directories. Entries outside the scanned root are
+/// marked, since opening them starts dux over on that path.
+pub struct BookmarksView<'a> {
+    bookmarks: &'a [PathBuf],
+    selected_index: usize,
+    root_path: &'a Path,
+    theme: &'a Theme,
+}
+
+impl<'a> BookmarksView<'a> {
+    pub fn new(
+        bookmarks: &'a [PathBuf],
+        selected_index: usize,
+        root_path: &'a Path,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            bookmarks,
+            selected_index,
+            root_path,
+            theme,
+        }
+    }
+}
+
+impl Widget for BookmarksView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = 70.min(area.width.saturating_sub(4));
+        let wanted = self.bookmarks.len().max(1) as u16 + 4;
+        let height = wanted.min(area.height.saturating_sub(4));
+        if width < 20 || height < 4 {
+            return;
+        }
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + (area.height - height) / 2;
+        let dialog_area = Rect::new(x, y, width, height);
+
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .title(" Bookmarks ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.blue))
+            .style(Style::default().bg(self.theme.bg_surface))
+            .padding(Padding::horizontal(1));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        let dim_style = Style::default().fg(self.theme.fg_dim);
+        let hint_y = inner.y + inner.height - 1;
+
+        if self.bookmarks.is_empty() {
+            buf.set_string(
+                inner.x,
+                inner.y,
+                "No bookmarks yet (b bookmarks the current directory)",
+                dim_style,
+            );
+            buf.set_string(inner.x, hint_y, "Esc close", dim_style);
+            return;
+        }
+
+        // Last row is reserved for key hints; scroll to keep the selection visible
+        let visible = inner.height.saturating_sub(2).max(1) as usize;
+        let selected = self.selected_index.min(self.bookmarks.len() - 1);
+        let offset = (selected + 1).saturating_sub(visible);
+        let label_width = NEW_SCAN_LABEL.len() + 1;
+        let max_len = (inner.width as usize).saturating_sub(label_width + 2);
+
+        for (i, path) in self.bookmarks.iter().enumerate().skip(offset).take(visible) {
+            let row = inner.y + (i - offset) as u16;
+            let style = if i == selected {
+                Style::default()
+                    .fg(self.theme.fg)
+                    .bg(self.theme.bg_highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.fg)
+            };
+            if i == selected {
+                buf.set_style(Rect::new(inner.x, row, inner.width, 1), style);
+            }
+            let marker = if i == selected { "▸ " } else { "  " };
+            let text = truncate_left(&path.to_string_lossy(), max_len);
+            buf.set_string(inner.x, row, format!("{}{}", marker, text), style);
+
+            if !path.starts_with(self.root_path) {
+                let label_x = inner.x + inner.width.saturating_sub(NEW_SCAN_LABEL.len() as u16);
+                buf.set_string(label_x, row, NEW_SCAN_LABEL, style.fg(self.theme.yellow));
+            }
+        }
+
+        let hints = "Enter open  ·  d remove  ·  Esc close";
+        let hints: String = hints.chars().take(inner.width as usize).collect();
+        buf.set_string(inner.x, hint_y, &hints, dim_style);
+    }
+}
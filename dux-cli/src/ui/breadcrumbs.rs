@@ -0,0 +1,116 @@
+use dux_core::{DiskTree, NodeId, SizeFormat};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Padding, Widget},
+};
+
+use super::text::truncate_left;
+use super::theme::Theme;
+
+/// Overlay listing the ancestors of the current view root, outermost first,
+/// so the view can jump up several levels at once
+pub struct BreadcrumbsView<'a> {
+    tree: &'a DiskTree,
+    view_root: NodeId,
+    selected_index: usize,
+    size_format: SizeFormat,
+    theme: &'a Theme,
+}
+
+impl<'a> BreadcrumbsView<'a> {
+    pub fn new(
+        tree: &'a DiskTree,
+        view_root: NodeId,
+        selected_index: usize,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            tree,
+            view_root,
+            selected_index,
+            size_format: SizeFormat::default(),
+            theme,
+        }
+    }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+}
+
+impl Widget for BreadcrumbsView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let path = self.tree.path_to_node(self.view_root);
+        let width = 70.min(area.width.saturating_sub(4));
+        let wanted = path.len() as u16 + 4;
+        let height = wanted.min(area.height.saturating_sub(4));
+        if width < 20 || height < 4 || path.is_empty() {
+            return;
+        }
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + (area.height - height) / 2;
+        let dialog_area = Rect::new(x, y, width, height);
+
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .title(" Jump to ancestor ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.blue))
+            .style(Style::default().bg(self.theme.bg_surface))
+            .padding(Padding::horizontal(1));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        let dim_style = Style::default().fg(self.theme.fg_dim);
+        let hint_y = inner.y + inner.height - 1;
+
+        // Last row is reserved for key hints; scroll to keep the selection visible
+        let visible = inner.height.saturating_sub(2).max(1) as usize;
+        let selected = self.selected_index.min(path.len() - 1);
+        let offset = (selected + 1).saturating_sub(visible);
+
+        for (i, &id) in path.iter().enumerate().skip(offset).take(visible) {
+            let Some(node) = self.tree.get(id) else {
+                continue;
+            };
+            let row = inner.y + (i - offset) as u16;
+            let style = if i == selected {
+                Style::default()
+                    .fg(self.theme.fg)
+                    .bg(self.theme.bg_highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.fg)
+            };
+            if i == selected {
+                buf.set_style(Rect::new(inner.x, row, inner.width, 1), style);
+            }
+
+            let size = self.size_format.format(node.size);
+            let size_x = inner.x + inner.width.saturating_sub(size.len() as u16);
+            buf.set_string(size_x, row, &size, style.fg(self.theme.fg_dim));
+
+            // The scan root shows its full path; ancestors below it are
+            // indented by depth under it
+            let marker = if i == selected { "▸ " } else { "  " };
+            let name = if id == NodeId::ROOT {
+                node.path.to_string_lossy().into_owned()
+            } else {
+                format!("{}{}", " ".repeat(i.min(8)), node.name)
+            };
+            let max_len = (inner.width as usize).saturating_sub(size.len() + 4);
+            let text = truncate_left(&name, max_len);
+            buf.set_string(inner.x, row, format!("{}{}", marker, text), style);
+        }
+
+        let hints = "Enter open  ·  Esc close";
+        let hints: String = hints.chars().take(inner.width as usize).collect();
+        buf.set_string(inner.x, hint_y, &hints, dim_style);
+    }
+}
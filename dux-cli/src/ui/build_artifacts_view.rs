@@ -1,6 +1,7 @@
 use std::collections::HashSet;
+use std::time::SystemTime;
 
-use dux_core::{NodeId, format_size};
+use dux_core::{NodeId, NodeKind, SizeFormat, format_count};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -8,10 +9,17 @@ use ratatui::{
     widgets::Widget,
 };
 
+use crate::app::AgeDisplay;
 use crate::app::views::{BuildArtifactEntry, StaleThreshold};
 
+use super::age::{AGE_WIDTH, format_age};
+
 use super::bar_chart::render_bar;
+use super::layout::SizeColumns;
+use super::scrollbar::{render_scrollbar, split_scrollbar};
+use super::text::{display_width, truncate_left};
 use super::theme::Theme;
+use super::tree_view::{FILE_COUNT_WIDTH, MIN_NAME_WIDTH};
 
 /// Build artifacts flat list view
 pub struct BuildArtifactsView<'a> {
@@ -20,6 +28,9 @@ pub struct BuildArtifactsView<'a> {
     scroll_offset: usize,
     stale_threshold: StaleThreshold,
     selected_nodes: &'a HashSet<NodeId>,
+    show_file_counts: bool,
+    age_display: AgeDisplay,
+    size_format: SizeFormat,
     theme: &'a Theme,
 }
 
@@ -38,9 +49,27 @@ impl<'a> BuildArtifactsView<'a> {
             scroll_offset,
             stale_threshold,
             selected_nodes,
+            show_file_counts: false,
+            age_display: AgeDisplay::Relative,
+            size_format: SizeFormat::default(),
             theme,
         }
     }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    pub fn with_file_counts(mut self, show: bool) -> Self {
+        self.show_file_counts = show;
+        self
+    }
+
+    pub fn with_age_display(mut self, display: AgeDisplay) -> Self {
+        self.age_display = display;
+        self
+    }
 }
 
 impl Widget for BuildArtifactsView<'_> {
@@ -50,7 +79,14 @@ impl Widget for BuildArtifactsView<'_> {
         }
 
         // Subtitle row showing stale threshold
-        let subtitle = format!("Stale: >{} (s to change)", self.stale_threshold.label());
+        let subtitle = format!(
+            "Stale: >{} (s to change) · Age: {} (T to change)",
+            self.stale_threshold.label(),
+            match self.age_display {
+                AgeDisplay::Relative => "relative",
+                AgeDisplay::Absolute => "date",
+            }
+        );
         buf.set_string(
             area.x + 1,
             area.y,
@@ -73,20 +109,37 @@ impl Widget for BuildArtifactsView<'_> {
             return;
         }
 
-        // Column widths (same as TreeView)
-        let bar_width: usize = 24;
-        let pct_width: usize = 6;
-        let size_width: usize = 10;
+        let (list_area, scrollbar_area) = split_scrollbar(list_area, self.entries.len());
+        if let Some(bar_area) = scrollbar_area {
+            render_scrollbar(
+                bar_area,
+                self.entries.len(),
+                self.scroll_offset,
+                self.theme,
+                buf,
+            );
+        }
+
+        let columns = SizeColumns::new(list_area);
         // Reserve space for kind label + stale indicator
         let kind_width: usize = 12; // "[CocoaPods] " max
         let stale_width: usize = 6; // "stale " or "      "
-        let path_width = list_area.width as usize
-            - bar_width
-            - pct_width
-            - size_width
-            - kind_width
-            - stale_width
-            - 4;
+        let fixed_path_width = columns.name_width.saturating_sub(kind_width + stale_width);
+        // The age column is dropped before the path gets too short
+        let age_width = if fixed_path_width >= AGE_WIDTH + 1 + MIN_NAME_WIDTH {
+            AGE_WIDTH + 1
+        } else {
+            0
+        };
+        let base_path_width = fixed_path_width - age_width;
+        let count_width =
+            if self.show_file_counts && base_path_width >= FILE_COUNT_WIDTH + MIN_NAME_WIDTH {
+                FILE_COUNT_WIDTH
+            } else {
+                0
+            };
+        let path_width = base_path_width - count_width;
+        let now = SystemTime::now();
 
         for (i, entry) in self
             .entries
@@ -145,18 +198,15 @@ impl Widget for BuildArtifactsView<'_> {
                         self.theme.bg
                     })
             };
-            buf.set_string(x, y, "📁", icon_style);
-            x += 2;
+            let icons = self.theme.icons;
+            buf.set_string(x, y, icons.for_kind(NodeKind::Directory, false), icon_style);
+            x += icons.width();
 
             // Path
             let marker_offset = if is_multi_selected { 2 } else { 0 };
-            let max_path_len = path_width.saturating_sub(3 + marker_offset);
-            let display_path = if entry.relative_path.len() > max_path_len {
-                let start = entry.relative_path.len() - max_path_len + 3;
-                format!("...{}", &entry.relative_path[start..])
-            } else {
-                entry.relative_path.clone()
-            };
+            let max_path_len =
+                path_width.saturating_sub(icons.width() as usize + 1 + marker_offset);
+            let display_path = truncate_left(&entry.relative_path, max_path_len);
 
             let path_style = if is_cursor {
                 row_style.add_modifier(Modifier::BOLD)
@@ -171,10 +221,12 @@ impl Widget for BuildArtifactsView<'_> {
                     .add_modifier(Modifier::BOLD)
             };
             buf.set_string(x, y, &display_path, path_style);
-            x += display_path.len() as u16 + 1;
+            x += display_width(&display_path) as u16 + 1;
 
             // Kind label
-            let kind_label = format!("[{}]", entry.kind.label());
+            // Custom labels are clipped to the reserved column
+            let label: String = entry.kind.label().chars().take(kind_width - 3).collect();
+            let kind_label = format!("[{}]", label);
             let kind_style = if is_cursor {
                 row_style
             } else {
@@ -187,7 +239,7 @@ impl Widget for BuildArtifactsView<'_> {
                     })
             };
             buf.set_string(x, y, &kind_label, kind_style);
-            x += kind_label.len() as u16 + 1;
+            x += kind_label.chars().count() as u16 + 1;
 
             // Stale indicator
             if entry.is_stale {
@@ -205,12 +257,57 @@ impl Widget for BuildArtifactsView<'_> {
                 buf.set_string(x, y, "stale", stale_style);
             }
 
-            // Right-aligned section
-            let right_x = list_area.x + list_area.width
-                - bar_width as u16
-                - pct_width as u16
-                - size_width as u16
-                - 2;
+            // Age of the newest change
+            if age_width > 0 {
+                let age_str = format!(
+                    "{:>width$}",
+                    format_age(entry.newest_mtime, now, self.age_display),
+                    width = AGE_WIDTH
+                );
+                let age_style = if is_cursor {
+                    row_style
+                } else {
+                    Style::default()
+                        .fg(if entry.is_stale {
+                            self.theme.yellow
+                        } else {
+                            self.theme.fg_dim
+                        })
+                        .bg(if is_multi_selected {
+                            self.theme.bg_highlight
+                        } else {
+                            self.theme.bg
+                        })
+                };
+                buf.set_string(
+                    columns.right_x - count_width as u16 - age_width as u16,
+                    y,
+                    &age_str,
+                    age_style,
+                );
+            }
+
+            // File count
+            if count_width > 0 {
+                let count_str = format!("{:>9}", format_count(entry.file_count));
+                let count_style = if is_cursor {
+                    row_style
+                } else {
+                    Style::default()
+                        .fg(self.theme.fg_muted)
+                        .bg(if is_multi_selected {
+                            self.theme.bg_highlight
+                        } else {
+                            self.theme.bg
+                        })
+                };
+                buf.set_string(
+                    columns.right_x - count_width as u16,
+                    y,
+                    &count_str,
+                    count_style,
+                );
+            }
 
             // Size bar
             let bar_color = if is_cursor {
@@ -218,9 +315,9 @@ impl Widget for BuildArtifactsView<'_> {
             } else {
                 self.theme.size_color(entry.percentage)
             };
-            let (bar, _) = render_bar(entry.percentage, bar_width.saturating_sub(2), bar_color);
+            let (bar, _) = render_bar(entry.percentage, columns.bar_width, bar_color);
             buf.set_string(
-                right_x,
+                columns.right_x,
                 y,
                 &bar,
                 if is_cursor {
@@ -247,10 +344,10 @@ impl Widget for BuildArtifactsView<'_> {
                         self.theme.bg
                     })
             };
-            buf.set_string(right_x + bar_width as u16 - 1, y, &pct_str, pct_style);
+            buf.set_string(columns.pct_x, y, &pct_str, pct_style);
 
             // Size
-            let size_str = format!("{:>9}", format_size(entry.size));
+            let size_str = format!("{:>9}", self.size_format.format(entry.size));
             let size_style = if is_cursor {
                 row_style
             } else {
@@ -262,12 +359,7 @@ impl Widget for BuildArtifactsView<'_> {
                         self.theme.bg
                     })
             };
-            buf.set_string(
-                right_x + bar_width as u16 + pct_width as u16 - 1,
-                y,
-                &size_str,
-                size_style,
-            );
+            buf.set_string(columns.size_x, y, &size_str, size_style);
         }
     }
 }
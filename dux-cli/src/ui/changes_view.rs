@@ -0,0 +1,207 @@
+use std::collections::HashSet;
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local};
+use dux_core::{NodeId, SizeFormat};
+use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+use crate::app::views::ChangeEntry;
+
+use super::scrollbar::{render_scrollbar, split_scrollbar};
+use super::text::{display_width, truncate_left};
+use super::theme::Theme;
+
+/// Paths that grew or shrank since the previous scan, largest change first
+pub struct ChangesView<'a> {
+    entries: &'a [ChangeEntry],
+    selected_index: usize,
+    scroll_offset: usize,
+    since: Option<SystemTime>,
+    selected_nodes: &'a HashSet<NodeId>,
+    size_format: SizeFormat,
+    theme: &'a Theme,
+}
+
+impl<'a> ChangesView<'a> {
+    pub fn new(
+        entries: &'a [ChangeEntry],
+        selected_index: usize,
+        scroll_offset: usize,
+        since: Option<SystemTime>,
+        selected_nodes: &'a HashSet<NodeId>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            entries,
+            selected_index,
+            scroll_offset,
+            since,
+            selected_nodes,
+            size_format: SizeFormat::default(),
+            theme,
+        }
+    }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+}
+
+impl Widget for ChangesView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 2 || area.width < 40 {
+            return;
+        }
+
+        let Some(since) = self.since else {
+            let msg = "No earlier scan of this directory to compare with";
+            let x = area.x + (area.width.saturating_sub(msg.len() as u16)) / 2;
+            let y = area.y + area.height / 2;
+            buf.set_string(x, y, msg, Style::default().fg(self.theme.fg_dim));
+            return;
+        };
+
+        // Subtitle row naming the scan being compared with
+        let subtitle = format!(
+            "Since the scan of {}",
+            DateTime::<Local>::from(since).format("%Y-%m-%d %H:%M")
+        );
+        buf.set_string(
+            area.x + 1,
+            area.y,
+            &subtitle,
+            Style::default().fg(self.theme.fg_dim),
+        );
+
+        let list_area = Rect::new(
+            area.x,
+            area.y + 1,
+            area.width,
+            area.height.saturating_sub(1),
+        );
+
+        if self.entries.is_empty() {
+            let msg = "Nothing changed";
+            let x = list_area.x + (list_area.width.saturating_sub(msg.len() as u16)) / 2;
+            let y = list_area.y + list_area.height / 2;
+            buf.set_string(x, y, msg, Style::default().fg(self.theme.fg_dim));
+            return;
+        }
+
+        let (list_area, scrollbar_area) = split_scrollbar(list_area, self.entries.len());
+        if let Some(bar_area) = scrollbar_area {
+            render_scrollbar(
+                bar_area,
+                self.entries.len(),
+                self.scroll_offset,
+                self.theme,
+                buf,
+            );
+        }
+
+        let delta_width: usize = 12;
+        let size_width: usize = 10;
+        let path_width = (list_area.width as usize).saturating_sub(delta_width + size_width + 2);
+
+        for (i, entry) in self
+            .entries
+            .iter()
+            .skip(self.scroll_offset)
+            .take(list_area.height as usize)
+            .enumerate()
+        {
+            let y = list_area.y + i as u16;
+            let is_cursor = i + self.scroll_offset == self.selected_index;
+            let is_multi_selected = entry
+                .node_id
+                .is_some_and(|id| self.selected_nodes.contains(&id));
+
+            let row_bg = if is_cursor {
+                self.theme.selection_bg
+            } else if is_multi_selected {
+                self.theme.bg_highlight
+            } else {
+                self.theme.bg
+            };
+            let style_for = |fg| {
+                if is_cursor {
+                    Style::default().bg(row_bg).fg(self.theme.selection_fg)
+                } else {
+                    Style::default().bg(row_bg).fg(fg)
+                }
+            };
+
+            // Clear the row
+            for x in 0..list_area.width {
+                buf.set_string(list_area.x + x, y, " ", style_for(self.theme.fg));
+            }
+
+            let mut x = list_area.x;
+
+            // Selection marker
+            if is_multi_selected {
+                buf.set_string(
+                    x,
+                    y,
+                    "▪ ",
+                    Style::default().bg(row_bg).fg(self.theme.purple),
+                );
+                x += 2;
+            }
+
+            // Signed change: growth is what ate the disk
+            let sign = if entry.delta > 0 { '+' } else { '-' };
+            let delta_str = format!(
+                "{:>11}",
+                format!(
+                    "{}{}",
+                    sign,
+                    self.size_format.format(entry.delta.unsigned_abs())
+                )
+            );
+            let delta_color = if entry.delta > 0 {
+                self.theme.red
+            } else {
+                self.theme.green
+            };
+            buf.set_string(x, y, &delta_str, style_for(delta_color));
+            x += delta_width as u16;
+
+            // Path (truncated with leading ... if too long)
+            let suffix = if entry.node_id.is_none() {
+                " (removed)"
+            } else {
+                ""
+            };
+            let path = if entry.relative_path.is_empty() {
+                "."
+            } else {
+                entry.relative_path.as_str()
+            };
+            let marker_offset = if is_multi_selected { 2 } else { 0 };
+            let max_path_len = path_width.saturating_sub(marker_offset + suffix.len());
+            let display_path = truncate_left(path, max_path_len);
+            buf.set_string(x, y, &display_path, style_for(self.theme.fg));
+            if !suffix.is_empty() {
+                buf.set_string(
+                    x + display_width(&display_path) as u16,
+                    y,
+                    suffix,
+                    style_for(self.theme.fg_dim),
+                );
+            }
+
+            // Current size
+            if entry.node_id.is_some() {
+                let size_str = format!("{:>9}", self.size_format.format(entry.size));
+                buf.set_string(
+                    list_area.x + list_area.width - size_width as u16,
+                    y,
+                    &size_str,
+                    style_for(self.theme.fg_muted),
+                );
+            }
+        }
+    }
+}
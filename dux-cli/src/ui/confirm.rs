@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use dux_core::SizeFormat;
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Rect},
@@ -7,18 +8,41 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Padding, Widget},
 };
 
+use super::text::truncate_left;
 use super::theme::Theme;
 
 /// Delete confirmation dialog widget
 pub struct ConfirmDeleteView<'a> {
     path: &'a Path,
     size: Option<u64>,
+    size_format: SizeFormat,
+    phrase: Option<&'a str>,
+    input: &'a str,
     theme: &'a Theme,
 }
 
 impl<'a> ConfirmDeleteView<'a> {
     pub fn new(path: &'a Path, size: Option<u64>, theme: &'a Theme) -> Self {
-        Self { path, size, theme }
+        Self {
+            path,
+            size,
+            size_format: SizeFormat::default(),
+            phrase: None,
+            input: "",
+            theme,
+        }
+    }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    /// Ask for `phrase` to be typed instead of `y` (input typed so far)
+    pub fn with_typed_confirm(mut self, phrase: Option<&'a str>, input: &'a str) -> Self {
+        self.phrase = phrase;
+        self.input = input;
+        self
     }
 }
 
@@ -26,7 +50,8 @@ impl Widget for ConfirmDeleteView<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         // Center the dialog box
         let width = 50.min(area.width.saturating_sub(4));
-        let height = 9.min(area.height.saturating_sub(4));
+        let base_height = if self.phrase.is_some() { 12 } else { 9 };
+        let height = base_height.min(area.height.saturating_sub(4));
         let x = area.x + (area.width - width) / 2;
         let y = area.y + (area.height - height) / 2;
         let dialog_area = Rect::new(x, y, width, height);
@@ -57,26 +82,70 @@ impl Widget for ConfirmDeleteView<'_> {
         // Path to delete (truncated if needed)
         let path_str = self.path.to_string_lossy();
         let max_path_len = (inner.width as usize).saturating_sub(2);
-        let display_path = if path_str.len() > max_path_len {
-            format!("...{}", &path_str[path_str.len() - max_path_len + 3..])
-        } else {
-            path_str.to_string()
-        };
+        let display_path = truncate_left(&path_str, max_path_len);
 
         buf.set_string(inner.x, inner.y, "Delete:", text_style);
         buf.set_string(inner.x, inner.y + 1, &display_path, path_style);
 
         // Size info
         if let Some(size) = self.size {
-            let size_str = format!("Size: {}", dux_core::format_size(size));
+            let size_str = format!("Size: {}", self.size_format.format(size));
             buf.set_string(inner.x, inner.y + 3, &size_str, text_style);
         }
 
         // Action hints
         let hints_y = inner.y + inner.height.saturating_sub(1);
+        if let Some(phrase) = self.phrase {
+            render_typed_prompt(
+                phrase,
+                self.input,
+                inner.x,
+                hints_y.saturating_sub(3),
+                self.theme,
+                buf,
+            );
+            buf.set_string(inner.x, hints_y, "[Enter]", key_style);
+            buf.set_string(inner.x + 8, hints_y, "Delete", text_style);
+            buf.set_string(inner.x + 18, hints_y, "[Esc]", key_style);
+            buf.set_string(inner.x + 24, hints_y, "Cancel", text_style);
+            return;
+        }
         buf.set_string(inner.x, hints_y, "[y]", key_style);
         buf.set_string(inner.x + 4, hints_y, "Yes, delete", text_style);
         buf.set_string(inner.x + 18, hints_y, "[n]", key_style);
         buf.set_string(inner.x + 22, hints_y, "Cancel", text_style);
     }
 }
+
+/// Two lines asking for `phrase`, with the typed input below turning green
+/// once it matches
+pub(super) fn render_typed_prompt(
+    phrase: &str,
+    input: &str,
+    x: u16,
+    y: u16,
+    theme: &Theme,
+    buf: &mut Buffer,
+) {
+    let text_style = Style::default().fg(theme.fg);
+    let phrase_style = Style::default()
+        .fg(theme.yellow)
+        .add_modifier(Modifier::BOLD);
+    buf.set_string(x, y, "Type ", text_style);
+    buf.set_string(x + 5, y, phrase, phrase_style);
+    buf.set_string(
+        x + 5 + phrase.chars().count() as u16,
+        y,
+        " to confirm:",
+        text_style,
+    );
+
+    let input_style = if input == phrase {
+        Style::default()
+            .fg(theme.green)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        text_style
+    };
+    buf.set_string(x, y + 1, format!("> {}▏", input), input_style);
+}
@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 
+use dux_core::SizeFormat;
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Rect},
@@ -7,17 +8,48 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Padding, Widget},
 };
 
+use super::confirm::render_typed_prompt;
+use super::text::{display_width, truncate_left};
 use super::theme::Theme;
 
 /// Multi-delete confirmation dialog widget
 pub struct ConfirmMultiDeleteView<'a> {
     items: &'a [(dux_core::NodeId, PathBuf, u64)],
+    artifact_kind: Option<&'a str>,
+    size_format: SizeFormat,
+    phrase: Option<&'a str>,
+    input: &'a str,
     theme: &'a Theme,
 }
 
 impl<'a> ConfirmMultiDeleteView<'a> {
     pub fn new(items: &'a [(dux_core::NodeId, PathBuf, u64)], theme: &'a Theme) -> Self {
-        Self { items, theme }
+        Self {
+            items,
+            artifact_kind: None,
+            size_format: SizeFormat::default(),
+            phrase: None,
+            input: "",
+            theme,
+        }
+    }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    /// Word the heading as deleting every artifact of this kind
+    pub fn with_artifact_kind(mut self, kind: Option<&'a str>) -> Self {
+        self.artifact_kind = kind;
+        self
+    }
+
+    /// Ask for `phrase` to be typed instead of `y` (input typed so far)
+    pub fn with_typed_confirm(mut self, phrase: Option<&'a str>, input: &'a str) -> Self {
+        self.phrase = phrase;
+        self.input = input;
+        self
     }
 }
 
@@ -30,7 +62,10 @@ impl Widget for ConfirmMultiDeleteView<'_> {
 
         // Dynamic height: title(1) + padding(2) + "Delete N items:"(1) + paths(show_count)
         // + "...and N more"(if has_more) + blank(1) + total_size(1) + blank(1) + hints(1) + border(2) + padding(2)
-        let content_lines = 1 + show_count + if has_more { 1 } else { 0 } + 1 + 1 + 1 + 1;
+        // A typed confirmation adds its prompt, input and a blank line
+        let prompt_lines = if self.phrase.is_some() { 3 } else { 0 };
+        let content_lines =
+            1 + show_count + if has_more { 1 } else { 0 } + 1 + 1 + 1 + 1 + prompt_lines;
         let height = (content_lines as u16 + 4).min(area.height.saturating_sub(4)); // +4 for borders+padding
         let width = 60.min(area.width.saturating_sub(4));
 
@@ -64,29 +99,35 @@ impl Widget for ConfirmMultiDeleteView<'_> {
         let max_w = inner.width as usize - 2;
 
         // Header line
-        let header = format!(
-            "Delete {} item{}:",
-            count,
-            if count == 1 { "" } else { "s" }
-        );
+        let header = match self.artifact_kind {
+            Some(kind) => format!(
+                "Delete all {} {} artifact{} ({})?",
+                count,
+                kind,
+                if count == 1 { "" } else { "s" },
+                self.size_format.format(total_size)
+            ),
+            None => format!(
+                "Delete {} item{}:",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+        };
+        let header: String = header.chars().take(inner.width as usize).collect();
         buf.set_string(inner.x, row, &header, text_style);
         row += 1;
 
         // List up to 5 paths with sizes
         for (_, path, size) in self.items.iter().take(5) {
-            let size_str = dux_core::format_size(*size);
+            let size_str = self.size_format.format(*size);
             let path_str = path.to_string_lossy();
             // Reserve space for "  path  (size)"
             let size_part = format!("  ({})", size_str);
             let avail = max_w.saturating_sub(size_part.len() + 2);
-            let display_path = if path_str.len() > avail {
-                format!("...{}", &path_str[path_str.len() - avail + 3..])
-            } else {
-                path_str.to_string()
-            };
+            let display_path = truncate_left(&path_str, avail);
             buf.set_string(inner.x + 1, row, &display_path, path_style);
             buf.set_string(
-                inner.x + 1 + display_path.len() as u16,
+                inner.x + 1 + display_width(&display_path) as u16,
                 row,
                 &size_part,
                 dim_style,
@@ -104,12 +145,25 @@ impl Widget for ConfirmMultiDeleteView<'_> {
         row += 1; // blank line
 
         // Total size
-        let total_str = format!("Total: {}", dux_core::format_size(total_size));
+        let total_str = format!("Total: {}", self.size_format.format(total_size));
         buf.set_string(inner.x, row, &total_str, text_style);
         row += 1;
 
+        if let Some(phrase) = self.phrase {
+            row += 1; // blank line
+            render_typed_prompt(phrase, self.input, inner.x, row, self.theme, buf);
+            row += 2;
+        }
+
         // Action hints at bottom
         let hints_y = row.max(inner.y + inner.height.saturating_sub(1));
+        if self.phrase.is_some() {
+            buf.set_string(inner.x, hints_y, "[Enter]", key_style);
+            buf.set_string(inner.x + 8, hints_y, "Delete all", text_style);
+            buf.set_string(inner.x + 22, hints_y, "[Esc]", key_style);
+            buf.set_string(inner.x + 28, hints_y, "Cancel", text_style);
+            return;
+        }
         buf.set_string(inner.x, hints_y, "[y]", key_style);
         buf.set_string(inner.x + 4, hints_y, "Yes, delete all", text_style);
         buf.set_string(inner.x + 22, hints_y, "[n]", key_style);
@@ -0,0 +1,137 @@
+use chrono::{DateTime, Local};
+use dux_core::{SizeFormat, format_count};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Padding, Widget},
+};
+
+use crate::app::NodeDetails;
+
+use super::text::truncate_left;
+use super::theme::Theme;
+
+/// Read-only overlay with metadata for the selected item
+pub struct DetailsView<'a> {
+    details: &'a NodeDetails,
+    size_format: SizeFormat,
+    theme: &'a Theme,
+}
+
+impl<'a> DetailsView<'a> {
+    pub fn new(details: &'a NodeDetails, theme: &'a Theme) -> Self {
+        Self {
+            details,
+            size_format: SizeFormat::default(),
+            theme,
+        }
+    }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+}
+
+impl Widget for DetailsView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = 70.min(area.width.saturating_sub(4));
+        let height = 16.min(area.height.saturating_sub(4));
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + (area.height - height) / 2;
+        let dialog_area = Rect::new(x, y, width, height);
+
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .title(" Details ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.blue))
+            .style(Style::default().bg(self.theme.bg_surface))
+            .padding(Padding::uniform(1));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        let label_style = Style::default()
+            .fg(self.theme.fg_dim)
+            .add_modifier(Modifier::BOLD);
+        let value_style = Style::default().fg(self.theme.fg);
+        let unknown = "—".to_string();
+
+        let d = self.details;
+        let modified = d
+            .mtime
+            .map(|t| {
+                DateTime::<Local>::from(t)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_else(|| unknown.clone());
+        let files = if d.kind.is_directory() {
+            format_count(d.file_count)
+        } else {
+            unknown.clone()
+        };
+        let average = d
+            .avg_file_size
+            .map(|size| self.size_format.format(size))
+            .unwrap_or_else(|| unknown.clone());
+        let largest = d
+            .largest_file
+            .as_ref()
+            .map(|(path, size)| format!("{}  {}", self.size_format.format(*size), path.display()))
+            .unwrap_or_else(|| unknown.clone());
+
+        let links = match d.links {
+            Some(1) => "1".to_string(),
+            Some(n) => format!(
+                "{} (data shared with {} other path{})",
+                n,
+                n - 1,
+                if n == 2 { "" } else { "s" }
+            ),
+            None => unknown.clone(),
+        };
+
+        let rows = [
+            ("Path", d.path.to_string_lossy().to_string()),
+            ("Size", self.size_format.format(d.size)),
+            ("Files", files),
+            ("Avg file", average),
+            ("Largest file", largest),
+            ("Modified", modified),
+            ("Owner", d.owner.clone().unwrap_or_else(|| unknown.clone())),
+            ("Group", d.group.clone().unwrap_or_else(|| unknown.clone())),
+            (
+                "Permissions",
+                d.permissions.clone().unwrap_or_else(|| unknown.clone()),
+            ),
+            ("Links", links),
+        ];
+
+        let label_width = 13u16;
+        let value_width = inner.width.saturating_sub(label_width) as usize;
+        for (i, (label, value)) in rows.iter().enumerate() {
+            let row = inner.y + i as u16;
+            if row >= inner.y + inner.height {
+                break;
+            }
+            buf.set_string(inner.x, row, label, label_style);
+
+            // Long paths keep their tail, which is the informative part
+            let value = truncate_left(value, value_width);
+            buf.set_string(inner.x + label_width, row, &value, value_style);
+        }
+
+        let hint_y = inner.y + inner.height.saturating_sub(1);
+        buf.set_string(
+            inner.x,
+            hint_y,
+            "Press Esc to close",
+            Style::default().fg(self.theme.fg_dim),
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashSet;
+
+use dux_core::{NodeId, NodeKind, SizeFormat, format_count};
+use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+use crate::app::views::EmptyDirEntry;
+
+use super::scrollbar::{render_scrollbar, split_scrollbar};
+use super::text::truncate_left;
+use super::theme::Theme;
+
+/// Flat list of directories that hold no files
+pub struct EmptyDirsView<'a> {
+    entries: &'a [EmptyDirEntry],
+    selected_index: usize,
+    scroll_offset: usize,
+    selected_nodes: &'a HashSet<NodeId>,
+    size_format: SizeFormat,
+    theme: &'a Theme,
+}
+
+impl<'a> EmptyDirsView<'a> {
+    pub fn new(
+        entries: &'a [EmptyDirEntry],
+        selected_index: usize,
+        scroll_offset: usize,
+        selected_nodes: &'a HashSet<NodeId>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            entries,
+            selected_index,
+            scroll_offset,
+            selected_nodes,
+            size_format: SizeFormat::default(),
+            theme,
+        }
+    }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+}
+
+impl Widget for EmptyDirsView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 1 || area.width < 40 {
+            return;
+        }
+
+        if self.entries.is_empty() {
+            let msg = "No empty directories found";
+            let x = area.x + (area.width.saturating_sub(msg.len() as u16)) / 2;
+            let y = area.y + area.height / 2;
+            buf.set_string(x, y, msg, Style::default().fg(self.theme.fg_dim));
+            return;
+        }
+
+        let (area, scrollbar_area) = split_scrollbar(area, self.entries.len());
+        if let Some(bar_area) = scrollbar_area {
+            render_scrollbar(
+                bar_area,
+                self.entries.len(),
+                self.scroll_offset,
+                self.theme,
+                buf,
+            );
+        }
+
+        let nested_width: usize = 14;
+        let size_width: usize = 10;
+        let path_width = (area.width as usize).saturating_sub(nested_width + size_width + 2);
+
+        for (i, entry) in self
+            .entries
+            .iter()
+            .skip(self.scroll_offset)
+            .take(area.height as usize)
+            .enumerate()
+        {
+            let y = area.y + i as u16;
+            let is_cursor = i + self.scroll_offset == self.selected_index;
+            let is_multi_selected = self.selected_nodes.contains(&entry.node_id);
+
+            let row_bg = if is_cursor {
+                self.theme.selection_bg
+            } else if is_multi_selected {
+                self.theme.bg_highlight
+            } else {
+                self.theme.bg
+            };
+            let style_for = |fg| {
+                if is_cursor {
+                    Style::default().bg(row_bg).fg(self.theme.selection_fg)
+                } else {
+                    Style::default().bg(row_bg).fg(fg)
+                }
+            };
+
+            // Clear the row
+            for x in 0..area.width {
+                buf.set_string(area.x + x, y, " ", style_for(self.theme.fg));
+            }
+
+            let mut x = area.x;
+
+            // Selection marker
+            if is_multi_selected {
+                buf.set_string(
+                    x,
+                    y,
+                    "▪ ",
+                    Style::default().bg(row_bg).fg(self.theme.purple),
+                );
+                x += 2;
+            }
+
+            let icons = self.theme.icons;
+            buf.set_string(
+                x,
+                y,
+                icons.for_kind(NodeKind::Directory, false),
+                style_for(self.theme.fg_dim),
+            );
+            x += icons.width();
+
+            // Path (truncated with leading ... if too long)
+            let marker_offset = if is_multi_selected { 2 } else { 0 };
+            let max_path_len =
+                path_width.saturating_sub(icons.width() as usize + 1 + marker_offset);
+            let display_path = truncate_left(&entry.relative_path, max_path_len);
+            buf.set_string(x, y, &display_path, style_for(self.theme.fg));
+
+            // Right-aligned section
+            let right_x = area.x + area.width - nested_width as u16 - size_width as u16 - 1;
+
+            if entry.nested > 0 {
+                let nested_str =
+                    format!("{:>12}", format!("+{} nested", format_count(entry.nested)));
+                buf.set_string(right_x, y, &nested_str, style_for(self.theme.fg_dim));
+            }
+
+            let size_str = format!("{:>9}", self.size_format.format(entry.size));
+            buf.set_string(
+                right_x + nested_width as u16,
+                y,
+                &size_str,
+                style_for(self.theme.fg_muted),
+            );
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Padding, Widget},
+};
+
+use super::text::truncate_left;
+use super::theme::Theme;
+
+/// Rows used by each error entry (path, then reason)
+const ROWS_PER_ENTRY: u16 = 2;
+
+/// Scrollable overlay listing paths the scanner could not read
+pub struct ErrorsView<'a> {
+    errors: &'a [(PathBuf, String)],
+    scroll_offset: usize,
+    theme: &'a Theme,
+}
+
+/// Overlay box for a given screen area
+fn dialog_area(area: Rect) -> Rect {
+    let width = 80.min(area.width.saturating_sub(4));
+    let height = area.height.saturating_sub(4);
+    let x = area.x + (area.width - width) / 2;
+    let y = area.y + (area.height - height) / 2;
+    Rect::new(x, y, width, height)
+}
+
+/// Number of entries that fit in a box of `height` rows (borders and the
+/// position row excluded)
+fn entries_for_height(height: u16) -> usize {
+    (height.saturating_sub(3) / ROWS_PER_ENTRY).max(1) as usize
+}
+
+impl<'a> ErrorsView<'a> {
+    pub fn new(errors: &'a [(PathBuf, String)], scroll_offset: usize, theme: &'a Theme) -> Self {
+        Self {
+            errors,
+            scroll_offset,
+            theme,
+        }
+    }
+
+    /// How many entries the overlay shows at once when rendered into `area`
+    pub fn visible_entries(area: Rect) -> usize {
+        entries_for_height(dialog_area(area).height)
+    }
+}
+
+impl Widget for ErrorsView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_area = dialog_area(area);
+        if dialog_area.width < 20 || dialog_area.height < 5 {
+            return;
+        }
+
+        Clear.render(dialog_area, buf);
+
+        let title = format!(
+            " Scan errors ({}) ",
+            dux_core::format_count(self.errors.len() as u64)
+        );
+        let block = Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.yellow))
+            .style(Style::default().bg(self.theme.bg_surface))
+            .padding(Padding::horizontal(1));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        let dim_style = Style::default().fg(self.theme.fg_dim);
+
+        if self.errors.is_empty() {
+            buf.set_string(inner.x, inner.y, "No errors during scan", dim_style);
+            return;
+        }
+
+        // Last row is reserved for the position indicator
+        let visible = entries_for_height(dialog_area.height);
+        let offset = self
+            .scroll_offset
+            .min(self.errors.len().saturating_sub(visible));
+        let max_len = inner.width as usize;
+
+        let path_style = Style::default().fg(self.theme.fg);
+        let reason_style = Style::default().fg(self.theme.red);
+
+        for (i, (path, reason)) in self.errors.iter().skip(offset).take(visible).enumerate() {
+            let row = inner.y + i as u16 * ROWS_PER_ENTRY;
+            let path_str = if path.as_os_str().is_empty() {
+                "(unknown path)".to_string()
+            } else {
+                path.to_string_lossy().to_string()
+            };
+            buf.set_string(inner.x, row, truncate_left(&path_str, max_len), path_style);
+            let reason: String = format!("  {}", reason).chars().take(max_len).collect();
+            buf.set_string(inner.x, row + 1, &reason, reason_style);
+        }
+
+        let last = (offset + visible).min(self.errors.len());
+        let position = format!(
+            "{}-{} of {}  ·  ↑↓ scroll  ·  Esc close",
+            offset + 1,
+            last,
+            self.errors.len()
+        );
+        let position: String = position.chars().take(max_len).collect();
+        buf.set_string(inner.x, inner.y + inner.height - 1, &position, dim_style);
+    }
+}
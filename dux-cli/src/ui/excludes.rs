@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Padding, Widget},
+};
+
+use super::text::truncate_left;
+use super::theme::Theme;
+
+/// Overlay listing the names and paths left out of the scan, so an
+/// exclusion can be undone
+pub struct ExcludesView<'a> {
+    excludes: &'a [PathBuf],
+    selected_index: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> ExcludesView<'a> {
+    pub fn new(excludes: &'a [PathBuf], selected_index: usize, theme: &'a Theme) -> Self {
+        Self {
+            excludes,
+            selected_index,
+            theme,
+        }
+    }
+}
+
+impl Widget for ExcludesView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = 70.min(area.width.saturating_sub(4));
+        let wanted = self.excludes.len().max(1) as u16 + 4;
+        let height = wanted.min(area.height.saturating_sub(4));
+        if width < 20 || height < 4 {
+            return;
+        }
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + (area.height - height) / 2;
+        let dialog_area = Rect::new(x, y, width, height);
+
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .title(" Excluded ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.blue))
+            .style(Style::default().bg(self.theme.bg_surface))
+            .padding(Padding::horizontal(1));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+
+        let dim_style = Style::default().fg(self.theme.fg_dim);
+        let hint_y = inner.y + inner.height - 1;
+
+        if self.excludes.is_empty() {
+            buf.set_string(
+                inner.x,
+                inner.y,
+                "Nothing excluded (X excludes the selected directory)",
+                dim_style,
+            );
+            buf.set_string(inner.x, hint_y, "Esc close", dim_style);
+            return;
+        }
+
+        // Last row is reserved for key hints; scroll to keep the selection visible
+        let visible = inner.height.saturating_sub(2).max(1) as usize;
+        let selected = self.selected_index.min(self.excludes.len() - 1);
+        let offset = (selected + 1).saturating_sub(visible);
+        let max_len = (inner.width as usize).saturating_sub(2);
+
+        for (i, path) in self.excludes.iter().enumerate().skip(offset).take(visible) {
+            let row = inner.y + (i - offset) as u16;
+            let style = if i == selected {
+                Style::default()
+                    .fg(self.theme.fg)
+                    .bg(self.theme.bg_highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.fg)
+            };
+            if i == selected {
+                buf.set_style(Rect::new(inner.x, row, inner.width, 1), style);
+            }
+            let marker = if i == selected { "▸ " } else { "  " };
+            let text = truncate_left(&path.to_string_lossy(), max_len);
+            buf.set_string(inner.x, row, format!("{}{}", marker, text), style);
+        }
+
+        let hints = "d include again and rescan  ·  Esc close";
+        let hints: String = hints.chars().take(inner.width as usize).collect();
+        buf.set_string(inner.x, hint_y, &hints, dim_style);
+    }
+}
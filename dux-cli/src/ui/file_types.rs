@@ -0,0 +1,176 @@
+use dux_core::{SizeFormat, format_count, size_percentage};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Clear, Padding, Widget},
+};
+
+use crate::app::FileTypes;
+
+use super::bar_chart::render_bar;
+use super::text::{truncate_left, truncate_right};
+use super::theme::Theme;
+
+/// Overlay with the selected directory's size split by file extension.
+/// Types that don't fit are folded into a last row.
+pub struct FileTypesView<'a> {
+    types: &'a FileTypes,
+    size_format: SizeFormat,
+    theme: &'a Theme,
+}
+
+impl<'a> FileTypesView<'a> {
+    pub fn new(types: &'a FileTypes, theme: &'a Theme) -> Self {
+        Self {
+            types,
+            size_format: SizeFormat::default(),
+            theme,
+        }
+    }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+}
+
+impl Widget for FileTypesView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = 70.min(area.width.saturating_sub(4));
+        let height = 20.min(area.height.saturating_sub(4));
+        let x = area.x + (area.width - width) / 2;
+        let y = area.y + (area.height - height) / 2;
+        let dialog_area = Rect::new(x, y, width, height);
+
+        Clear.render(dialog_area, buf);
+
+        let block = Block::default()
+            .title(" File types ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.blue))
+            .style(Style::default().bg(self.theme.bg_surface))
+            .padding(Padding::uniform(1));
+
+        let inner = block.inner(dialog_area);
+        block.render(dialog_area, buf);
+        if inner.height < 4 {
+            return;
+        }
+
+        let header = format!(
+            "{}  {}",
+            self.size_format.format(self.types.size),
+            self.types.path.display()
+        );
+        buf.set_string(
+            inner.x,
+            inner.y,
+            truncate_left(&header, inner.width as usize),
+            Style::default()
+                .fg(self.theme.fg_dim)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        // Header, a blank line, and the hint at the bottom
+        let rows_available = inner.height.saturating_sub(3) as usize;
+        let all = &self.types.types;
+        let (shown, rest) = if all.len() > rows_available {
+            all.split_at(rows_available.saturating_sub(1))
+        } else {
+            (&all[..], &[][..])
+        };
+
+        let mut rows: Vec<(String, u64, u64)> = shown
+            .iter()
+            .map(|share| {
+                let label = if share.extension.is_empty() {
+                    "(no extension)".to_string()
+                } else {
+                    format!(".{}", share.extension)
+                };
+                (label, share.size, share.files)
+            })
+            .collect();
+        if !rest.is_empty() {
+            rows.push((
+                format!("({} more)", rest.len()),
+                rest.iter().map(|share| share.size).sum(),
+                rest.iter().map(|share| share.files).sum(),
+            ));
+        }
+        if rows.is_empty() {
+            buf.set_string(
+                inner.x,
+                inner.y + 2,
+                "No files",
+                Style::default().fg(self.theme.fg_dim),
+            );
+        }
+
+        let label_width: usize = 16;
+        let pct_width: usize = 7;
+        let size_width: usize = 10;
+        let count_width: usize = 12;
+        let bar_width = (inner.width as usize)
+            .saturating_sub(label_width + pct_width + size_width + count_width + 2);
+        for (i, (label, size, files)) in rows.iter().enumerate() {
+            let row = inner.y + 2 + i as u16;
+            let percentage = size_percentage(*size, self.types.size);
+            let mut x = inner.x;
+
+            let color = label
+                .strip_prefix('.')
+                .map_or(self.theme.fg, |ext| self.theme.extension_color(ext));
+            buf.set_string(
+                x,
+                row,
+                truncate_right(label, label_width - 1),
+                Style::default().fg(color),
+            );
+            x += label_width as u16;
+
+            let (bar, bar_color) =
+                render_bar(percentage, bar_width, self.theme.size_color(percentage));
+            buf.set_string(x, row, &bar, Style::default().fg(bar_color));
+            x += bar_width as u16 + 1;
+
+            buf.set_string(
+                x,
+                row,
+                format!("{:>5.1}%", percentage),
+                Style::default().fg(self.theme.fg_dim),
+            );
+            x += pct_width as u16;
+
+            buf.set_string(
+                x,
+                row,
+                format!("{:>9}", self.size_format.format(*size)),
+                Style::default().fg(self.theme.fg),
+            );
+            x += size_width as u16;
+
+            let count = format!(
+                "{} file{}",
+                format_count(*files),
+                if *files == 1 { "" } else { "s" }
+            );
+            buf.set_string(
+                x,
+                row,
+                format!("{:>11}", count),
+                Style::default().fg(self.theme.fg_muted),
+            );
+        }
+
+        let hint_y = inner.y + inner.height.saturating_sub(1);
+        buf.set_string(
+            inner.x,
+            hint_y,
+            "Press Esc to close",
+            Style::default().fg(self.theme.fg_dim),
+        );
+    }
+}
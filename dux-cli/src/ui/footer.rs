@@ -1,3 +1,4 @@
+use dux_core::SizeFormat;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -19,7 +20,18 @@ pub struct Footer<'a> {
     stale_threshold: Option<StaleThreshold>,
     selection_count: usize,
     selection_size: u64,
+    /// Scanned total, for the projected size after deleting the selection
+    tree_total: u64,
     selecting_mode: bool,
+    status_message: Option<&'a str>,
+    error_message: Option<&'a str>,
+    goto_input: &'a str,
+    file_filter: &'a str,
+    keep_newest_input: &'a str,
+    typed_confirm: bool,
+    read_only: bool,
+    indicators: Vec<String>,
+    size_format: SizeFormat,
 }
 
 impl<'a> Footer<'a> {
@@ -37,10 +49,25 @@ impl<'a> Footer<'a> {
             stale_threshold: None,
             selection_count: 0,
             selection_size: 0,
+            tree_total: 0,
             selecting_mode: false,
+            status_message: None,
+            error_message: None,
+            goto_input: "",
+            file_filter: "",
+            keep_newest_input: "",
+            typed_confirm: false,
+            read_only: false,
+            indicators: Vec::new(),
+            size_format: SizeFormat::default(),
         }
     }
 
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
     pub fn with_stale_threshold(mut self, threshold: StaleThreshold) -> Self {
         self.stale_threshold = Some(threshold);
         self
@@ -52,6 +79,55 @@ impl<'a> Footer<'a> {
         self.selecting_mode = selecting;
         self
     }
+
+    /// Scanned total; with a selection the footer projects the total after
+    /// deleting it
+    pub fn with_tree_total(mut self, total: u64) -> Self {
+        self.tree_total = total;
+        self
+    }
+
+    /// Short labels describing non-default view settings (e.g. "Dotfiles hidden")
+    pub fn with_indicators(mut self, indicators: Vec<String>) -> Self {
+        self.indicators = indicators;
+        self
+    }
+
+    /// Text typed at the jump-to-path prompt (shown in `AppMode::GoTo`)
+    pub fn with_goto_input(mut self, input: &'a str) -> Self {
+        self.goto_input = input;
+        self
+    }
+
+    /// Glob typed at the Large Files filter prompt (shown in `AppMode::FileFilter`)
+    pub fn with_file_filter(mut self, pattern: &'a str) -> Self {
+        self.file_filter = pattern;
+        self
+    }
+
+    /// Count typed at the keep-newest prompt (shown in `AppMode::KeepNewest`)
+    pub fn with_keep_newest_input(mut self, input: &'a str) -> Self {
+        self.keep_newest_input = input;
+        self
+    }
+
+    /// Whether the pending deletion is confirmed by typing a phrase
+    pub fn with_typed_confirm(mut self, typed: bool) -> Self {
+        self.typed_confirm = typed;
+        self
+    }
+
+    /// Leave out the delete hints, which a read-only session refuses
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    pub fn with_messages(mut self, status: Option<&'a str>, error: Option<&'a str>) -> Self {
+        self.status_message = status;
+        self.error_message = error;
+        self
+    }
 }
 
 impl Widget for Footer<'_> {
@@ -66,8 +142,9 @@ impl Widget for Footer<'_> {
             ("v", "Select".to_string())
         };
 
-        let hints: Vec<(&str, String)> = match self.mode {
-            AppMode::Scanning | AppMode::Finalizing => vec![("q", "Quit".to_string())],
+        let mut hints: Vec<(&str, String)> = match self.mode {
+            AppMode::Scanning => vec![("p", "Pause/Resume".to_string()), ("q", "Quit".to_string())],
+            AppMode::Finalizing => vec![("q", "Quit".to_string())],
             AppMode::Browsing => match self.view_mode {
                 ViewMode::Tree => vec![
                     ("Tab", "Views".to_string()),
@@ -79,6 +156,25 @@ impl Widget for Footer<'_> {
                     ("q", "Quit".to_string()),
                 ],
                 ViewMode::LargeFiles => vec![
+                    ("Tab", "Views".to_string()),
+                    ("↑↓", "Navigate".to_string()),
+                    select_hint.clone(),
+                    ("/", "Filter".to_string()),
+                    ("F", "Group".to_string()),
+                    ("d", "Delete".to_string()),
+                    ("?", "Help".to_string()),
+                    ("q", "Quit".to_string()),
+                ],
+                ViewMode::Overview => vec![
+                    ("Tab", "Views".to_string()),
+                    ("↑↓", "Navigate".to_string()),
+                    select_hint.clone(),
+                    ("Enter", "Open in tree".to_string()),
+                    ("d", "Delete".to_string()),
+                    ("?", "Help".to_string()),
+                    ("q", "Quit".to_string()),
+                ],
+                ViewMode::EmptyDirs | ViewMode::Changes | ViewMode::Symlinks => vec![
                     ("Tab", "Views".to_string()),
                     ("↑↓", "Navigate".to_string()),
                     select_hint.clone(),
@@ -96,6 +192,9 @@ impl Widget for Footer<'_> {
                         ("↑↓", "Navigate".to_string()),
                         select_hint.clone(),
                         ("s", stale_label),
+                        ("C", "Clean stale".to_string()),
+                        ("D", "Delete kind".to_string()),
+                        ("N", "Keep newest".to_string()),
                         ("d", "Delete".to_string()),
                         ("?", "Help".to_string()),
                         ("q", "Quit".to_string()),
@@ -103,11 +202,48 @@ impl Widget for Footer<'_> {
                 }
             },
             AppMode::Help => vec![("Esc", "Close help".to_string()), ("q", "Quit".to_string())],
+            AppMode::Details => vec![("Esc", "Close details".to_string())],
+            AppMode::FileTypes => vec![("Esc", "Close file types".to_string())],
+            AppMode::GoTo => vec![("Enter", "Go".to_string()), ("Esc", "Cancel".to_string())],
+            AppMode::FileFilter => {
+                vec![("Enter", "Keep".to_string()), ("Esc", "Clear".to_string())]
+            }
+            AppMode::KeepNewest => vec![
+                ("Enter", "Preview delete".to_string()),
+                ("Esc", "Cancel".to_string()),
+            ],
+            AppMode::Bookmarks => vec![
+                ("Enter", "Open".to_string()),
+                ("d", "Remove".to_string()),
+                ("Esc", "Close".to_string()),
+            ],
+            AppMode::Breadcrumbs => vec![
+                ("↑↓", "Select".to_string()),
+                ("Enter", "Open".to_string()),
+                ("Esc", "Close".to_string()),
+            ],
+            AppMode::Excludes => vec![
+                ("d", "Include & rescan".to_string()),
+                ("Esc", "Close".to_string()),
+            ],
+            AppMode::Errors => vec![
+                ("↑↓", "Scroll".to_string()),
+                ("Esc", "Close errors".to_string()),
+            ],
+            AppMode::ConfirmDelete | AppMode::ConfirmMultiDelete if self.typed_confirm => {
+                vec![
+                    ("Enter", "Delete".to_string()),
+                    ("Esc", "Cancel".to_string()),
+                ]
+            }
             AppMode::ConfirmDelete | AppMode::ConfirmMultiDelete => {
                 vec![("y", "Yes".to_string()), ("n", "Cancel".to_string())]
             }
-            AppMode::MultiDeleting => vec![("q", "Quit (deletions continue)".to_string())],
+            AppMode::MultiDeleting => vec![("q", "Quit when done".to_string())],
         };
+        if self.read_only && self.mode == AppMode::Browsing {
+            hints.retain(|(key, _)| !matches!(*key, "d" | "C" | "D" | "N"));
+        }
 
         let key_style = Style::default()
             .fg(self.theme.fg)
@@ -116,6 +252,48 @@ impl Widget for Footer<'_> {
         let sep_style = Style::default().fg(self.theme.border);
 
         let mut x = area.x + 1;
+
+        // Transient messages replace the key hints until they expire
+        let message = self
+            .error_message
+            .map(|m| (m, self.theme.red))
+            .or_else(|| self.status_message.map(|m| (m, self.theme.green)));
+        let prompt = match self.mode {
+            AppMode::GoTo => Some((":", self.goto_input)),
+            AppMode::FileFilter => Some(("/", self.file_filter)),
+            AppMode::KeepNewest => Some(("Keep newest: ", self.keep_newest_input)),
+            _ => None,
+        };
+        let hints = if let Some((marker, input)) = prompt {
+            // The prompt takes the left side; key hints follow the cursor
+            let prompt_style = Style::default()
+                .fg(self.theme.blue)
+                .add_modifier(Modifier::BOLD);
+            buf.set_string(x, area.y, marker, prompt_style);
+            x += marker.len() as u16;
+            let max_len = area.width.saturating_sub(30) as usize;
+            let len = input.chars().count();
+            let shown: String = input.chars().skip(len.saturating_sub(max_len)).collect();
+            buf.set_string(x, area.y, &shown, Style::default().fg(self.theme.fg));
+            x += shown.chars().count() as u16;
+            buf.set_string(x, area.y, "▏", prompt_style);
+            x += 3;
+            hints
+        } else if let Some((text, color)) = message {
+            let max_len = area.width.saturating_sub(2) as usize;
+            let text: String = text.chars().take(max_len).collect();
+            buf.set_string(
+                x,
+                area.y,
+                &text,
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            );
+            x += text.chars().count() as u16;
+            Vec::new()
+        } else {
+            hints
+        };
+
         for (i, (key, desc)) in hints.iter().enumerate() {
             // Key
             buf.set_string(x, area.y, *key, key_style);
@@ -138,12 +316,26 @@ impl Widget for Footer<'_> {
 
         // Right side: selection info or freed space
         let right_text = if self.selection_count > 0 {
-            Some((
-                format!(
-                    "{} selected ({})",
+            let mut text = format!(
+                "{} selected ({})",
+                self.selection_count,
+                self.size_format.format(self.selection_size),
+            );
+            if self.tree_total > 0 {
+                let projected = format!(
+                    "{} selected · after delete: {} (−{})",
                     self.selection_count,
-                    dux_core::format_size(self.selection_size),
-                ),
+                    self.size_format
+                        .format(self.tree_total.saturating_sub(self.selection_size)),
+                    self.size_format.format(self.selection_size),
+                );
+                // Fall back to the short form when the hints leave no room
+                if (x as usize + projected.chars().count() + 3) < (area.x + area.width) as usize {
+                    text = projected;
+                }
+            }
+            Some((
+                text,
                 Style::default()
                     .fg(self.theme.purple)
                     .add_modifier(Modifier::BOLD),
@@ -152,7 +344,7 @@ impl Widget for Footer<'_> {
             Some((
                 format!(
                     "Freed: {} ({} item{})",
-                    dux_core::format_size(self.session_stats.bytes_freed),
+                    self.size_format.format(self.session_stats.bytes_freed),
                     self.session_stats.items_deleted,
                     if self.session_stats.items_deleted == 1 {
                         ""
@@ -164,12 +356,17 @@ impl Widget for Footer<'_> {
                     .fg(self.theme.green)
                     .add_modifier(Modifier::BOLD),
             ))
+        } else if !self.indicators.is_empty() {
+            Some((
+                self.indicators.join(" · "),
+                Style::default().fg(self.theme.fg_muted),
+            ))
         } else {
             None
         };
 
         if let Some((text, style)) = right_text {
-            let stats_x = area.x + area.width - text.len() as u16 - 1;
+            let stats_x = (area.x + area.width).saturating_sub(text.chars().count() as u16 + 1);
             if stats_x > x + 2 {
                 buf.set_string(stats_x, area.y, &text, style);
             }
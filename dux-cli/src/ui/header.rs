@@ -8,6 +8,7 @@ use ratatui::{
 use crate::app::{AppState, ViewMode};
 
 use super::progress::progress_indicator;
+use super::text::{display_width, truncate_left};
 use super::theme::Theme;
 
 /// Header widget showing title, path, and status
@@ -42,6 +43,10 @@ impl Widget for Header<'_> {
             ViewMode::Tree => None,
             ViewMode::LargeFiles => Some("Large Files"),
             ViewMode::BuildArtifacts => Some("Build Artifacts"),
+            ViewMode::EmptyDirs => Some("Empty Directories"),
+            ViewMode::Changes => Some("Changes"),
+            ViewMode::Overview => Some("Overview"),
+            ViewMode::Symlinks => Some("Symlinks"),
         };
 
         if let Some(label) = view_label {
@@ -79,45 +84,98 @@ impl Widget for Header<'_> {
                     self.state.root_path.to_string_lossy().to_string()
                 }
             }
-            ViewMode::LargeFiles | ViewMode::BuildArtifacts => {
-                self.state.root_path.to_string_lossy().to_string()
-            }
-        };
-
-        let max_path_len = area.width.saturating_sub(content_x - area.x + 22) as usize;
-        let display_path = if path.len() > max_path_len {
-            format!("...{}", &path[path.len() - max_path_len + 3..])
-        } else {
-            path
+            ViewMode::LargeFiles
+            | ViewMode::BuildArtifacts
+            | ViewMode::EmptyDirs
+            | ViewMode::Changes
+            | ViewMode::Overview
+            | ViewMode::Symlinks => self.state.root_path.to_string_lossy().to_string(),
         };
 
-        buf.set_string(
-            content_x,
-            area.y,
-            &display_path,
-            Style::default().fg(self.theme.fg),
-        );
-
         // Status (right-aligned)
         let status = if self.state.tree.is_none() {
-            progress_indicator(&self.state.progress, self.state.spinner_frame)
+            progress_indicator(
+                &self.state.progress,
+                self.state.spinner_frame,
+                self.state.size_format,
+            )
         } else if let Some(tree) = &self.state.tree {
             let cached_indicator = if self.state.loaded_from_cache {
                 " (cached)"
+            } else if self.state.partial_scan {
+                " (partial)"
             } else {
                 ""
             };
-            format!(
-                "{} files, {}{}",
-                dux_core::format_count(tree.total_files()),
-                dux_core::format_size(tree.total_size()),
-                cached_indicator
-            )
+            let format = self.state.size_format;
+            let files = dux_core::format_count(tree.total_files());
+            // Compact mode has no size bar, so its apparent size moves here
+            let size = match tree.total_apparent_size() {
+                Some(apparent) if self.state.compact => format!(
+                    "{} (apparent {})",
+                    format.format(tree.total_size()),
+                    format.format(apparent)
+                ),
+                _ => format.format(tree.total_size()),
+            };
+            let with_capacity = self.state.disk_capacity.map(|(total, free)| {
+                format!(
+                    "{} files, {} / {} ({:.0}% of disk, {} free){}",
+                    files,
+                    size,
+                    format.format(total),
+                    dux_core::size_percentage(tree.total_size(), total),
+                    format.format(free),
+                    cached_indicator
+                )
+            });
+            // Drop the disk context when it would crowd out the path
+            match with_capacity {
+                Some(status) if status.len() + 40 <= area.width as usize => status,
+                _ => format!("{} files, {}{}", files, size, cached_indicator),
+            }
+        } else {
+            String::new()
+        };
+        let status_width = status.chars().count() as u16;
+
+        let max_path_len =
+            area.width
+                .saturating_sub(content_x - area.x + status_width + 12) as usize;
+        let display_path = if display_width(&path) <= max_path_len {
+            path
+        } else if max_path_len > 3 {
+            truncate_left(&path, max_path_len)
         } else {
             String::new()
         };
 
-        let status_x = area.x + area.width - status.len() as u16 - 2;
+        buf.set_string(
+            content_x,
+            area.y,
+            &display_path,
+            Style::default().fg(self.theme.fg),
+        );
+
+        let status_x = (area.x + area.width).saturating_sub(status_width + 2);
+
+        // Error badge: totals may be incomplete when paths were unreadable
+        if !self.state.scan_errors.is_empty() {
+            let badge = format!(
+                "{} {}",
+                self.theme.icons.warning(),
+                dux_core::format_count(self.state.scan_errors.len() as u64)
+            );
+            let badge_x = status_x.saturating_sub(badge.chars().count() as u16 + 2);
+            buf.set_string(
+                badge_x,
+                area.y,
+                &badge,
+                Style::default()
+                    .fg(self.theme.yellow)
+                    .add_modifier(Modifier::BOLD),
+            );
+        }
         let status_style = if self.state.tree.is_none() {
             Style::default().fg(self.theme.yellow)
         } else {
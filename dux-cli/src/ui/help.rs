@@ -2,27 +2,85 @@ use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Rect},
     style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Padding, Widget},
 };
 
 use super::theme::Theme;
+use crate::tui::keymap::{self, Section};
+
+/// Room between the key column and the descriptions
+const KEY_GAP: usize = 2;
 
 /// Help overlay widget
 pub struct HelpView<'a> {
     theme: &'a Theme,
+    read_only: bool,
 }
 
 impl<'a> HelpView<'a> {
     pub fn new(theme: &'a Theme) -> Self {
-        Self { theme }
+        Self {
+            theme,
+            read_only: false,
+        }
+    }
+
+    /// Leave out the delete keys, which a read-only session refuses
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
     }
 }
 
+/// Help rows: (keys, description), with sections as titled groups. Built
+/// from the keymap so the screen always matches what the keys do.
+fn help_sections(read_only: bool) -> Vec<(&'static str, Vec<(String, &'static str)>)> {
+    Section::ALL
+        .iter()
+        .map(|&section| {
+            let mut rows: Vec<(String, &'static str)> = keymap::BROWSING
+                .iter()
+                .filter(|b| b.section == section && !b.description.is_empty())
+                .filter(|b| !(read_only && b.action.is_delete()))
+                .map(|b| (b.keys_label(), b.description))
+                .collect();
+            if section == Section::Navigation {
+                rows.extend(
+                    keymap::MOUSE_HELP
+                        .iter()
+                        .map(|&(keys, desc)| (keys.to_string(), desc)),
+                );
+            }
+            (section.title(), rows)
+        })
+        .collect()
+}
+
 impl Widget for HelpView<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        // Center the help box
-        let width = 50.min(area.width.saturating_sub(4));
-        let height = 31.min(area.height.saturating_sub(4));
+        let sections = help_sections(self.read_only);
+        let rows = sections.iter().flat_map(|(_, rows)| rows);
+        let key_width = rows
+            .clone()
+            .map(|(keys, _)| keys.chars().count())
+            .max()
+            .unwrap_or(0);
+        let desc_width = rows
+            .map(|(_, desc)| desc.chars().count())
+            .max()
+            .unwrap_or(0);
+        // Each section has a title row and a blank row after it (but the last)
+        let line_count = sections
+            .iter()
+            .map(|(_, rows)| rows.len() + 2)
+            .sum::<usize>()
+            - 1;
+
+        // Center the help box: border and padding take 2 cells per side
+        let width =
+            ((key_width + KEY_GAP + desc_width + 4) as u16).min(area.width.saturating_sub(4));
+        let height = ((line_count + 4) as u16).min(area.height.saturating_sub(4));
         let x = area.x + (area.width - width) / 2;
         let y = area.y + (area.height - height) / 2;
         let help_area = Rect::new(x, y, width, height);
@@ -30,9 +88,23 @@ impl Widget for HelpView<'_> {
         // Clear the area
         Clear.render(help_area, buf);
 
+        // Size color legend sits in the bottom border so it is never cut off
+        let mut legend = vec![Span::styled(
+            " Size: ",
+            Style::default().fg(self.theme.fg_dim),
+        )];
+        for (label, color) in self.theme.size_legend() {
+            legend.push(Span::styled("■ ", Style::default().fg(color)));
+            legend.push(Span::styled(
+                format!("{} ", label),
+                Style::default().fg(self.theme.fg_dim),
+            ));
+        }
+
         // Draw border
         let block = Block::default()
             .title(" Help ")
+            .title_bottom(Line::from(legend))
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(self.theme.blue))
@@ -50,51 +122,30 @@ impl Widget for HelpView<'_> {
             .fg(self.theme.blue)
             .add_modifier(Modifier::BOLD);
 
-        let help_items = [
-            ("", "Views", true),
-            ("Tab", "Next view", false),
-            ("S-Tab", "Previous view", false),
-            ("s", "Cycle stale threshold (Build Artifacts)", false),
-            ("", "", false),
-            ("", "Navigation", true),
-            ("↑ k", "Move up", false),
-            ("↓ j", "Move down", false),
-            ("v", "Enter/exit select mode", false),
-            ("K", "Select up (or ↑ in select mode)", false),
-            ("J", "Select down (or ↓ in select mode)", false),
-            ("PgUp/PgDn", "Page up/down", false),
-            ("Home g", "Go to first", false),
-            ("End G", "Go to last", false),
-            ("Esc", "Clear selection / Go back", false),
-            ("", "", false),
-            ("", "Tree", true),
-            ("→ l", "Expand directory", false),
-            ("← h", "Collapse directory", false),
-            ("Space", "Toggle expand/collapse", false),
-            ("Enter", "Drill down into directory", false),
-            ("Backspace", "Go back", false),
-            ("", "", false),
-            ("", "Actions", true),
-            ("o", "Open in Finder", false),
-            ("d", "Delete selected item(s)", false),
-            ("", "", false),
-            ("", "Other", true),
-            ("?", "Toggle this help", false),
-            ("q Ctrl+C", "Quit", false),
-        ];
-
-        for (i, (key, desc, is_section)) in help_items.iter().enumerate() {
-            if i >= inner.height as usize {
+        let desc_x = inner.x + (key_width + KEY_GAP) as u16;
+        let mut y = inner.y;
+        for (i, (title, rows)) in sections.iter().enumerate() {
+            if i > 0 {
+                y += 1;
+            }
+            if y >= inner.y + inner.height {
                 break;
             }
-
-            let y = inner.y + i as u16;
-
-            if *is_section {
-                buf.set_string(inner.x, y, *desc, section_style);
-            } else if !key.is_empty() {
-                buf.set_string(inner.x, y, format!("{:12}", key), key_style);
-                buf.set_string(inner.x + 12, y, *desc, desc_style);
+            buf.set_string(inner.x, y, *title, section_style);
+            y += 1;
+            for (keys, desc) in rows {
+                if y >= inner.y + inner.height {
+                    break;
+                }
+                buf.set_string(inner.x, y, keys, key_style);
+                buf.set_stringn(
+                    desc_x,
+                    y,
+                    *desc,
+                    (inner.x + inner.width).saturating_sub(desc_x) as usize,
+                    desc_style,
+                );
+                y += 1;
             }
         }
     }
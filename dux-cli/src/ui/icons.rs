@@ -0,0 +1,71 @@
+use dux_core::NodeKind;
+
+/// Terminals known to lack emoji glyphs, matched against the start of `TERM`
+const LIMITED_TERMS: &[&str] = &["dumb", "linux", "vt100", "vt102", "vt220", "cons25", "ansi"];
+
+/// Icon set the list views draw entries with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Icons {
+    /// Emoji icons (📁 📄 🔗), two cells wide
+    #[default]
+    Emoji,
+    /// Plain ASCII tags (`[D]` `[F]` `[L]`) for terminals without emoji
+    Ascii,
+}
+
+impl Icons {
+    /// ASCII when asked for with `--ascii` or when `TERM` names a terminal
+    /// that can't draw emoji, emoji otherwise
+    pub fn detect(ascii: bool, term: Option<&str>) -> Self {
+        let limited = term.is_some_and(|term| {
+            LIMITED_TERMS
+                .iter()
+                .any(|limited| term == *limited || term.starts_with(&format!("{}-", limited)))
+        });
+        if ascii || limited {
+            Icons::Ascii
+        } else {
+            Icons::Emoji
+        }
+    }
+
+    /// Icon for an entry; expanded directories get an open folder
+    pub fn for_kind(self, kind: NodeKind, expanded: bool) -> &'static str {
+        match (self, kind) {
+            (Icons::Emoji, NodeKind::Directory) if expanded => "📂",
+            (Icons::Emoji, _) => kind.icon(),
+            (Icons::Ascii, _) => kind.ascii_icon(),
+        }
+    }
+
+    /// Columns the icon takes, including the gap before the name
+    pub fn width(self) -> u16 {
+        match self {
+            Icons::Emoji => 2,
+            Icons::Ascii => 4,
+        }
+    }
+
+    /// Marker for the scan-error badge in the header
+    pub fn warning(self) -> &'static str {
+        match self {
+            Icons::Emoji => "⚠",
+            Icons::Ascii => "!",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Icons::detect(false, Some("xterm-256color")), Icons::Emoji);
+        assert_eq!(Icons::detect(false, None), Icons::Emoji);
+        assert_eq!(Icons::detect(true, Some("xterm-256color")), Icons::Ascii);
+        assert_eq!(Icons::detect(false, Some("linux")), Icons::Ascii);
+        assert_eq!(Icons::detect(false, Some("vt100-am")), Icons::Ascii);
+        assert_eq!(Icons::detect(false, Some("linuxish")), Icons::Emoji);
+    }
+}
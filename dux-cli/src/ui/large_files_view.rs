@@ -1,25 +1,38 @@
 use std::collections::HashSet;
+use std::path::Path;
 
-use dux_core::{NodeId, format_size};
-use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+use dux_core::{NodeId, NodeKind, SizeFormat};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Widget,
+};
 
-use crate::app::views::LargeFileEntry;
+use crate::app::views::{LargeFileEntry, LargeFileRow};
 
 use super::bar_chart::render_bar;
+use super::layout::SizeColumns;
+use super::scrollbar::{render_scrollbar, split_scrollbar};
+use super::text::truncate_left;
 use super::theme::Theme;
 
-/// Large files flat list view
+/// Large files list view, flat or grouped under parent directory headers
 pub struct LargeFilesView<'a> {
     entries: &'a [LargeFileEntry],
+    rows: &'a [LargeFileRow],
     selected_index: usize,
     scroll_offset: usize,
     selected_nodes: &'a HashSet<NodeId>,
+    size_format: SizeFormat,
+    filter: &'a str,
     theme: &'a Theme,
 }
 
 impl<'a> LargeFilesView<'a> {
     pub fn new(
         entries: &'a [LargeFileEntry],
+        rows: &'a [LargeFileRow],
         selected_index: usize,
         scroll_offset: usize,
         selected_nodes: &'a HashSet<NodeId>,
@@ -27,12 +40,26 @@ impl<'a> LargeFilesView<'a> {
     ) -> Self {
         Self {
             entries,
+            rows,
             selected_index,
             scroll_offset,
             selected_nodes,
+            size_format: SizeFormat::default(),
+            filter: "",
             theme,
         }
     }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    /// Glob the entries were narrowed by, for the empty-list message
+    pub fn with_filter(mut self, pattern: &'a str) -> Self {
+        self.filter = pattern;
+        self
+    }
 }
 
 impl Widget for LargeFilesView<'_> {
@@ -41,22 +68,36 @@ impl Widget for LargeFilesView<'_> {
             return;
         }
 
-        if self.entries.is_empty() {
-            let msg = "No large files found";
+        if self.rows.is_empty() {
+            let msg = match self.filter.trim() {
+                "" => "No large files found".to_string(),
+                pattern => format!("No files match {}", pattern),
+            };
             let x = area.x + (area.width.saturating_sub(msg.len() as u16)) / 2;
             let y = area.y + area.height / 2;
-            buf.set_string(x, y, msg, Style::default().fg(self.theme.fg_dim));
+            buf.set_string(x, y, &msg, Style::default().fg(self.theme.fg_dim));
             return;
         }
 
-        // Column widths (same as TreeView)
-        let bar_width: usize = 24;
-        let pct_width: usize = 6;
-        let size_width: usize = 10;
-        let path_width = area.width as usize - bar_width - pct_width - size_width - 4;
+        let (area, scrollbar_area) = split_scrollbar(area, self.rows.len());
+        if let Some(bar_area) = scrollbar_area {
+            render_scrollbar(
+                bar_area,
+                self.rows.len(),
+                self.scroll_offset,
+                self.theme,
+                buf,
+            );
+        }
 
-        for (i, entry) in self
-            .entries
+        let columns = SizeColumns::new(area);
+        let path_width = columns.name_width;
+        // Grouped files are indented under their header and named without
+        // the directory it already shows
+        let grouped = matches!(self.rows.first(), Some(LargeFileRow::Group { .. }));
+
+        for (i, row) in self
+            .rows
             .iter()
             .skip(self.scroll_offset)
             .take(area.height as usize)
@@ -64,6 +105,41 @@ impl Widget for LargeFilesView<'_> {
         {
             let y = area.y + i as u16;
             let is_cursor = i + self.scroll_offset == self.selected_index;
+
+            let entry = match row {
+                LargeFileRow::File(index) => &self.entries[*index],
+                LargeFileRow::Group {
+                    dir,
+                    size,
+                    percentage,
+                    count,
+                    collapsed,
+                } => {
+                    let style = if is_cursor {
+                        Style::default()
+                            .bg(self.theme.selection_bg)
+                            .fg(self.theme.selection_fg)
+                    } else {
+                        Style::default().fg(self.theme.fg).bg(self.theme.bg_surface)
+                    }
+                    .add_modifier(Modifier::BOLD);
+                    buf.set_style(Rect::new(area.x, y, area.width, 1), style);
+
+                    let arrow = if *collapsed { "▸" } else { "▾" };
+                    let files = if *count == 1 { "file" } else { "files" };
+                    let label = format!(" ({} {})", count, files);
+                    let dir = if dir.is_empty() { "." } else { dir.as_str() };
+                    let max_dir_len = path_width.saturating_sub(label.len() + 3);
+                    let text = format!("{} {}/{}", arrow, truncate_left(dir, max_dir_len), label);
+                    buf.set_string(area.x, y, &text, style);
+
+                    let pct_str = format!("{:>5.1}%", percentage);
+                    buf.set_string(columns.pct_x, y, &pct_str, style);
+                    let size_str = format!("{:>9}", self.size_format.format(*size));
+                    buf.set_string(columns.size_x, y, &size_str, style);
+                    continue;
+                }
+            };
             let is_multi_selected = self.selected_nodes.contains(&entry.node_id);
 
             let row_style = if is_cursor {
@@ -83,7 +159,7 @@ impl Widget for LargeFilesView<'_> {
                 buf.set_string(area.x + x, y, " ", row_style);
             }
 
-            let mut x = area.x;
+            let mut x = area.x + if grouped { 2 } else { 0 };
 
             // Selection marker
             if is_multi_selected {
@@ -112,43 +188,42 @@ impl Widget for LargeFilesView<'_> {
                         self.theme.bg
                     })
             };
-            buf.set_string(x, y, "📄", icon_style);
-            x += 2;
+            let icons = self.theme.icons;
+            buf.set_string(x, y, icons.for_kind(NodeKind::File, false), icon_style);
+            x += icons.width();
 
             // Path (truncated with leading ... if too long)
-            let marker_offset = if is_multi_selected { 2 } else { 0 };
-            let max_path_len = path_width.saturating_sub(3 + marker_offset); // 2 for icon + 1 space
-            let display_path = if entry.relative_path.len() > max_path_len {
-                let start = entry.relative_path.len() - max_path_len + 3;
-                format!("...{}", &entry.relative_path[start..])
-            } else {
-                entry.relative_path.clone()
+            let marker_offset = if is_multi_selected { 2 } else { 0 } + if grouped { 2 } else { 0 };
+            let max_path_len =
+                path_width.saturating_sub(icons.width() as usize + 1 + marker_offset);
+            let shown_path = match Path::new(&entry.relative_path).file_name() {
+                Some(name) if grouped => name.to_string_lossy(),
+                _ => entry.relative_path.as_str().into(),
             };
+            let display_path = truncate_left(&shown_path, max_path_len);
 
             let path_style = if is_cursor {
                 row_style
             } else {
-                Style::default().fg(self.theme.fg).bg(if is_multi_selected {
-                    self.theme.bg_highlight
-                } else {
-                    self.theme.bg
-                })
+                Style::default()
+                    .fg(self.theme.file_color(&entry.relative_path))
+                    .bg(if is_multi_selected {
+                        self.theme.bg_highlight
+                    } else {
+                        self.theme.bg
+                    })
             };
             buf.set_string(x, y, &display_path, path_style);
 
-            // Right-aligned section
-            let right_x =
-                area.x + area.width - bar_width as u16 - pct_width as u16 - size_width as u16 - 2;
-
             // Size bar
             let bar_color = if is_cursor {
                 self.theme.selection_fg
             } else {
                 self.theme.size_color(entry.percentage)
             };
-            let (bar, _) = render_bar(entry.percentage, bar_width.saturating_sub(2), bar_color);
+            let (bar, _) = render_bar(entry.percentage, columns.bar_width, bar_color);
             buf.set_string(
-                right_x,
+                columns.right_x,
                 y,
                 &bar,
                 if is_cursor {
@@ -175,10 +250,10 @@ impl Widget for LargeFilesView<'_> {
                         self.theme.bg
                     })
             };
-            buf.set_string(right_x + bar_width as u16 - 1, y, &pct_str, pct_style);
+            buf.set_string(columns.pct_x, y, &pct_str, pct_style);
 
             // Size
-            let size_str = format!("{:>9}", format_size(entry.size));
+            let size_str = format!("{:>9}", self.size_format.format(entry.size));
             let size_style = if is_cursor {
                 row_style
             } else {
@@ -190,12 +265,7 @@ impl Widget for LargeFilesView<'_> {
                         self.theme.bg
                     })
             };
-            buf.set_string(
-                right_x + bar_width as u16 + pct_width as u16 - 1,
-                y,
-                &size_str,
-                size_style,
-            );
+            buf.set_string(columns.size_x, y, &size_str, size_style);
         }
     }
 }
@@ -1,22 +1,80 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
+/// Smallest terminal the views can draw in: the list views need 40 columns,
+/// and the header, size bar, five list rows and footer need eight lines
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 8;
+
+/// List views narrower than this drop the size bar, keeping the room for
+/// names and sizes
+const MIN_BAR_AREA_WIDTH: u16 = 60;
+
+/// The size bar never grows past this many columns
+const MAX_BAR_WIDTH: u16 = 48;
+
+const PCT_WIDTH: u16 = 6;
+const SIZE_WIDTH: u16 = 10;
+
+/// Right-hand columns the list views share: size bar, percentage and size.
+/// The bar takes a fifth of the width, so it grows on wide terminals and
+/// gives way to the name on narrow ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeColumns {
+    /// Left edge of the size columns; view-specific columns end here
+    pub right_x: u16,
+    /// Cells in the drawn bar, 0 when it is dropped
+    pub bar_width: usize,
+    pub pct_x: u16,
+    pub size_x: u16,
+    /// Columns left for the name or path and any view-specific columns
+    pub name_width: usize,
+}
+
+impl SizeColumns {
+    /// Column positions for a list view drawn in `area`, at least
+    /// [`MIN_WIDTH`] wide
+    pub fn new(area: Rect) -> Self {
+        let bar = if area.width < MIN_BAR_AREA_WIDTH {
+            0
+        } else {
+            (area.width / 5).min(MAX_BAR_WIDTH)
+        };
+        let right_x = area.x + area.width - bar - PCT_WIDTH - SIZE_WIDTH - 2;
+        // The percentage follows the bar after a one-column gap
+        let pct_x = right_x + bar.saturating_sub(1);
+        Self {
+            right_x,
+            bar_width: bar.saturating_sub(2) as usize,
+            pct_x,
+            size_x: pct_x + PCT_WIDTH,
+            name_width: (area.width - bar - PCT_WIDTH - SIZE_WIDTH - 4) as usize,
+        }
+    }
+}
+
 /// Main application layout
 pub struct AppLayout {
     pub header: Rect,
+    /// Zero height in compact mode
     pub size_bar: Rect,
     pub tree: Rect,
+    /// What the active filters hide (zero height when nothing is filtered)
+    pub summary: Rect,
     pub footer: Rect,
 }
 
 impl AppLayout {
-    pub fn new(area: Rect) -> Self {
+    /// Split `area` into rows. The filter summary only takes a row when
+    /// shown; the size bar is dropped in compact mode.
+    pub fn new(area: Rect, show_summary: bool, show_size_bar: bool) -> Self {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(1), // Header
-                Constraint::Length(1), // Size bar
-                Constraint::Min(5),    // Tree view
-                Constraint::Length(1), // Footer
+                Constraint::Length(1),                    // Header
+                Constraint::Length(show_size_bar.into()), // Size bar
+                Constraint::Min(5),                       // Tree view
+                Constraint::Length(show_summary.into()),  // Filter summary
+                Constraint::Length(1),                    // Footer
             ])
             .split(area);
 
@@ -24,9 +82,15 @@ impl AppLayout {
             header: chunks[0],
             size_bar: chunks[1],
             tree: chunks[2],
-            footer: chunks[3],
+            summary: chunks[3],
+            footer: chunks[4],
         }
     }
+
+    /// Whether `area` is large enough for the layout
+    pub fn fits(area: Rect) -> bool {
+        area.width >= MIN_WIDTH && area.height >= MIN_HEIGHT
+    }
 }
 
 /// Calculate centered rectangle for overlays
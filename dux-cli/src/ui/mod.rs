@@ -1,26 +1,55 @@
+pub mod age;
 pub mod bar_chart;
+mod bookmarks;
+mod breadcrumbs;
 mod build_artifacts_view;
+mod changes_view;
 mod confirm;
 mod confirm_multi_delete;
+mod details;
+mod empty_dirs_view;
+mod errors;
+mod excludes;
+mod file_types;
 mod footer;
 mod header;
 mod help;
+mod icons;
 mod large_files_view;
 mod layout;
 mod multi_delete_progress;
+mod overview_view;
 mod progress;
+mod scrollbar;
+mod symlinks_view;
+mod text;
 mod theme;
+mod too_small;
 mod tree_view;
+mod treemap;
 
+pub use bookmarks::BookmarksView;
+pub use breadcrumbs::BreadcrumbsView;
 pub use build_artifacts_view::BuildArtifactsView;
+pub use changes_view::ChangesView;
 pub use confirm::ConfirmDeleteView;
 pub use confirm_multi_delete::ConfirmMultiDeleteView;
+pub use details::DetailsView;
+pub use empty_dirs_view::EmptyDirsView;
+pub use errors::ErrorsView;
+pub use excludes::ExcludesView;
+pub use file_types::FileTypesView;
 pub use footer::Footer;
 pub use header::Header;
 pub use help::HelpView;
+pub use icons::Icons;
 pub use large_files_view::LargeFilesView;
 pub use layout::AppLayout;
 pub use multi_delete_progress::MultiDeleteProgressView;
+pub use overview_view::OverviewView;
 pub use progress::ProgressView;
-pub use theme::Theme;
+pub use symlinks_view::SymlinksView;
+pub use theme::{Theme, ThemeName, ThemeOverrides};
+pub use too_small::TooSmallView;
 pub use tree_view::TreeView;
+pub use treemap::TreemapView;
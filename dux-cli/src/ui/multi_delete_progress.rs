@@ -1,3 +1,4 @@
+use dux_core::{SizeFormat, size_percentage};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Rect},
@@ -13,12 +14,22 @@ use super::theme::Theme;
 /// Progress overlay shown during multi-delete
 pub struct MultiDeleteProgressView<'a> {
     progress: &'a MultiDeleteProgress,
+    size_format: SizeFormat,
     theme: &'a Theme,
 }
 
 impl<'a> MultiDeleteProgressView<'a> {
     pub fn new(progress: &'a MultiDeleteProgress, theme: &'a Theme) -> Self {
-        Self { progress, theme }
+        Self {
+            progress,
+            size_format: SizeFormat::default(),
+            theme,
+        }
+    }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
     }
 }
 
@@ -50,15 +61,26 @@ impl Widget for MultiDeleteProgressView<'_> {
 
         // Progress count
         let count_str = format!(
-            "{} / {} completed",
+            "Items: {} / {}",
             self.progress.completed, self.progress.total
         );
         buf.set_string(inner.x, row, &count_str, text_style);
         row += 1;
 
-        // Progress bar
+        // Freed bytes
+        let freed_str = format!(
+            "Freed: {} / {}",
+            self.size_format.format(self.progress.bytes_freed),
+            self.size_format.format(self.progress.total_bytes)
+        );
+        buf.set_string(inner.x, row, &freed_str, text_style);
+        row += 1;
+
+        // Progress bar by bytes, since one large item can outweigh many small ones
         let bar_width = (inner.width as usize).saturating_sub(2);
-        let pct = if self.progress.total > 0 {
+        let pct = if self.progress.total_bytes > 0 {
+            size_percentage(self.progress.bytes_done, self.progress.total_bytes)
+        } else if self.progress.total > 0 {
             (self.progress.completed as f64 / self.progress.total as f64) * 100.0
         } else {
             0.0
@@ -67,14 +89,6 @@ impl Widget for MultiDeleteProgressView<'_> {
         buf.set_string(inner.x, row, &bar, Style::default().fg(self.theme.green));
         row += 2;
 
-        // Freed bytes
-        let freed_str = format!(
-            "Freed: {}",
-            dux_core::format_size(self.progress.bytes_freed)
-        );
-        buf.set_string(inner.x, row, &freed_str, text_style);
-        row += 1;
-
         // Failures
         if !self.progress.failures.is_empty() {
             let fail_str = format!("{} failed", self.progress.failures.len());
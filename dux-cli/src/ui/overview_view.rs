@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+
+use dux_core::{DiskTree, NodeId, SizeFormat, size_percentage};
+use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+use crate::app::BarMetric;
+
+use super::bar_chart::render_bar;
+use super::scrollbar::{render_scrollbar, split_scrollbar};
+use super::text::{display_width, truncate_right};
+use super::theme::Theme;
+
+/// Widest the name column gets, so the bars keep most of the row
+const MAX_NAME_WIDTH: usize = 40;
+
+/// The scan root's top-level entries, one per row, with a wide size bar
+pub struct OverviewView<'a> {
+    tree: &'a DiskTree,
+    nodes: &'a [NodeId],
+    selected_index: usize,
+    scroll_offset: usize,
+    selected_nodes: &'a HashSet<NodeId>,
+    metric: BarMetric,
+    /// Fixed total for percentages instead of the scan root's size
+    percent_total: Option<u64>,
+    size_format: SizeFormat,
+    theme: &'a Theme,
+}
+
+impl<'a> OverviewView<'a> {
+    pub fn new(
+        tree: &'a DiskTree,
+        nodes: &'a [NodeId],
+        selected_index: usize,
+        scroll_offset: usize,
+        selected_nodes: &'a HashSet<NodeId>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            tree,
+            nodes,
+            selected_index,
+            scroll_offset,
+            selected_nodes,
+            metric: BarMetric::Size,
+            percent_total: None,
+            size_format: SizeFormat::default(),
+            theme,
+        }
+    }
+
+    pub fn with_metric(mut self, metric: BarMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    pub fn with_percent_total(mut self, total: Option<u64>) -> Self {
+        self.percent_total = total;
+        self
+    }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+}
+
+impl Widget for OverviewView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 1 || area.width < 40 {
+            return;
+        }
+
+        if self.nodes.is_empty() {
+            let msg = "Nothing at the top level";
+            let x = area.x + (area.width.saturating_sub(msg.len() as u16)) / 2;
+            let y = area.y + area.height / 2;
+            buf.set_string(x, y, msg, Style::default().fg(self.theme.fg_dim));
+            return;
+        }
+
+        let (area, scrollbar_area) = split_scrollbar(area, self.nodes.len());
+        if let Some(bar_area) = scrollbar_area {
+            render_scrollbar(
+                bar_area,
+                self.nodes.len(),
+                self.scroll_offset,
+                self.theme,
+                buf,
+            );
+        }
+
+        let total = self
+            .percent_total
+            .or_else(|| self.tree.get(NodeId::ROOT).map(|n| self.metric.of(n)))
+            .unwrap_or(0);
+        let icons = self.theme.icons;
+        // Marker, icon, name and trailing slash
+        let lead_width = 2 + icons.width() as usize;
+        let longest = self
+            .nodes
+            .iter()
+            .filter_map(|&id| self.tree.get(id))
+            .map(|n| display_width(&n.name) + 1)
+            .max()
+            .unwrap_or(0);
+        let pct_width: usize = 7;
+        let size_width: usize = 10;
+        let name_width = longest
+            .min(MAX_NAME_WIDTH)
+            .min((area.width as usize).saturating_sub(lead_width + pct_width + size_width) / 2);
+        let bar_x = area.x + (lead_width + name_width + 2) as u16;
+        let pct_x = area.x + area.width - (pct_width + size_width) as u16;
+        let bar_width = pct_x.saturating_sub(bar_x + 1) as usize;
+
+        for (i, &id) in self
+            .nodes
+            .iter()
+            .skip(self.scroll_offset)
+            .take(area.height as usize)
+            .enumerate()
+        {
+            let Some(node) = self.tree.get(id) else {
+                continue;
+            };
+            let y = area.y + i as u16;
+            let is_cursor = i + self.scroll_offset == self.selected_index;
+            let is_multi_selected = self.selected_nodes.contains(&id);
+
+            let row_bg = if is_cursor {
+                self.theme.selection_bg
+            } else if is_multi_selected {
+                self.theme.bg_highlight
+            } else {
+                self.theme.bg
+            };
+            let style_for = |fg| {
+                if is_cursor {
+                    Style::default().bg(row_bg).fg(self.theme.selection_fg)
+                } else {
+                    Style::default().bg(row_bg).fg(fg)
+                }
+            };
+
+            // Clear the row
+            for x in 0..area.width {
+                buf.set_string(area.x + x, y, " ", style_for(self.theme.fg));
+            }
+
+            if is_multi_selected {
+                buf.set_string(
+                    area.x,
+                    y,
+                    "▪ ",
+                    Style::default().bg(row_bg).fg(self.theme.purple),
+                );
+            }
+            let mut x = area.x + 2;
+
+            let is_dir = node.kind.is_directory();
+            buf.set_string(
+                x,
+                y,
+                icons.for_kind(node.kind, false),
+                style_for(self.theme.icon_color(is_dir)),
+            );
+            x += icons.width();
+
+            let (name, name_color) = if is_dir {
+                (format!("{}/", node.name), self.theme.fg)
+            } else {
+                (node.name.clone(), self.theme.file_color(&node.name))
+            };
+            buf.set_string(
+                x,
+                y,
+                truncate_right(&name, name_width),
+                style_for(name_color),
+            );
+
+            let percentage = size_percentage(self.metric.of(node), total);
+            let (bar, bar_color) =
+                render_bar(percentage, bar_width, self.theme.size_color(percentage));
+            buf.set_string(bar_x, y, &bar, style_for(bar_color));
+
+            let pct_str = format!("{:>5.1}%", percentage);
+            buf.set_string(pct_x, y, &pct_str, style_for(self.theme.fg_dim));
+
+            let size_str = format!("{:>9}", self.size_format.format(node.size));
+            buf.set_string(
+                pct_x + pct_width as u16,
+                y,
+                &size_str,
+                style_for(self.theme.fg_muted),
+            );
+        }
+    }
+}
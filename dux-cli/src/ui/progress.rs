@@ -1,4 +1,4 @@
-use dux_core::{ScanProgress, format_count, format_size};
+use dux_core::{ScanProgress, SizeFormat, format_count};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -6,6 +6,8 @@ use ratatui::{
     widgets::{Block, Borders, Padding, Widget},
 };
 
+use super::bar_chart::render_bar;
+use super::text::truncate_left;
 use super::theme::Theme;
 
 /// Braille spinner characters
@@ -16,6 +18,8 @@ pub struct ProgressView<'a> {
     progress: &'a ScanProgress,
     spinner_frame: usize,
     finalizing: bool,
+    finalize_progress: Option<(usize, usize)>,
+    size_format: SizeFormat,
     theme: &'a Theme,
 }
 
@@ -30,9 +34,31 @@ impl<'a> ProgressView<'a> {
             progress,
             spinner_frame,
             finalizing,
+            finalize_progress: None,
+            size_format: SizeFormat::default(),
             theme,
         }
     }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    pub fn with_finalize_progress(mut self, progress: Option<(usize, usize)>) -> Self {
+        self.finalize_progress = progress;
+        self
+    }
+
+    /// Rows the box needs: three lines of text, the percent bar when the
+    /// entries were pre-counted, plus borders
+    pub fn height(&self) -> u16 {
+        if self.progress.expected_entries.is_some() {
+            6
+        } else {
+            5
+        }
+    }
 }
 
 impl Widget for ProgressView<'_> {
@@ -50,8 +76,12 @@ impl Widget for ProgressView<'_> {
             return;
         }
 
-        // Spinner
-        let spinner = SPINNER[self.spinner_frame % SPINNER.len()];
+        // Spinner (stopped while paused)
+        let spinner = if self.progress.paused {
+            '‖'
+        } else {
+            SPINNER[self.spinner_frame % SPINNER.len()]
+        };
         let spinner_style = Style::default()
             .fg(self.theme.blue)
             .add_modifier(Modifier::BOLD);
@@ -61,6 +91,10 @@ impl Widget for ProgressView<'_> {
         // Status text
         let status_text = if self.finalizing {
             " Finalizing... (calculating sizes)"
+        } else if self.progress.paused {
+            " Paused (p to resume)"
+        } else if self.progress.counting {
+            " Counting entries..."
         } else {
             " Scanning..."
         };
@@ -71,17 +105,28 @@ impl Widget for ProgressView<'_> {
             Style::default().fg(self.theme.fg),
         );
 
-        // Current path (truncated) - only show during scanning, not finalizing
-        if !self.finalizing
-            && let Some(path) = &self.progress.current_path
-        {
+        // Current path (truncated) while scanning; how far summing and
+        // sorting have got while finalizing
+        if self.finalizing {
+            if let Some((done, total)) = self.finalize_progress
+                && total > 0
+            {
+                let percent = done as f64 * 100.0 / total as f64;
+                let label = format!(" {:>3.0}%", percent);
+                let bar_width = (inner.width as usize).saturating_sub(label.len());
+                let (bar, color) = render_bar(percent, bar_width, self.theme.blue);
+                buf.set_string(inner.x, inner.y + 1, &bar, Style::default().fg(color));
+                buf.set_string(
+                    inner.x + bar_width as u16,
+                    inner.y + 1,
+                    &label,
+                    Style::default().fg(self.theme.fg),
+                );
+            }
+        } else if let Some(path) = &self.progress.current_path {
             let path_str = path.to_string_lossy();
             let max_len = inner.width.saturating_sub(2) as usize;
-            let display_path = if path_str.len() > max_len {
-                format!("...{}", &path_str[path_str.len() - max_len + 3..])
-            } else {
-                path_str.to_string()
-            };
+            let display_path = truncate_left(&path_str, max_len);
 
             buf.set_string(
                 inner.x,
@@ -97,7 +142,7 @@ impl Widget for ProgressView<'_> {
             format_count(self.progress.files_scanned),
             format_count(self.progress.dirs_scanned),
             format_count(self.progress.errors),
-            format_size(self.progress.bytes_scanned),
+            self.size_format.format(self.progress.bytes_scanned),
         );
 
         buf.set_string(
@@ -106,16 +151,43 @@ impl Widget for ProgressView<'_> {
             &stats,
             Style::default().fg(self.theme.fg_muted),
         );
+
+        // Percent bar, once the pre-count pass has a total
+        if let Some(percent) = self.progress.percent_complete()
+            && inner.height > 3
+        {
+            let label = format!(" {:>3.0}%", percent);
+            let bar_width = (inner.width as usize).saturating_sub(label.len());
+            let (bar, color) = render_bar(percent, bar_width, self.theme.blue);
+            buf.set_string(inner.x, inner.y + 3, &bar, Style::default().fg(color));
+            buf.set_string(
+                inner.x + bar_width as u16,
+                inner.y + 3,
+                &label,
+                Style::default().fg(self.theme.fg),
+            );
+        }
     }
 }
 
 /// Compact progress indicator for header
-pub fn progress_indicator(progress: &ScanProgress, spinner_frame: usize) -> String {
+pub fn progress_indicator(
+    progress: &ScanProgress,
+    spinner_frame: usize,
+    format: SizeFormat,
+) -> String {
+    if progress.paused {
+        return format!(
+            "‖ paused at {} files, {}",
+            format_count(progress.files_scanned + progress.dirs_scanned),
+            format.format(progress.bytes_scanned)
+        );
+    }
     let spinner = SPINNER[spinner_frame % SPINNER.len()];
     format!(
         "{} {} files, {}",
         spinner,
         format_count(progress.files_scanned + progress.dirs_scanned),
-        format_size(progress.bytes_scanned)
+        format.format(progress.bytes_scanned)
     )
 }
@@ -0,0 +1,70 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+
+use super::theme::Theme;
+
+/// Split off a one-column scrollbar on the right edge when content overflows.
+/// Returns the remaining content area and the scrollbar column (if any).
+pub fn split_scrollbar(area: Rect, total: usize) -> (Rect, Option<Rect>) {
+    if total <= area.height as usize || area.width < 2 {
+        return (area, None);
+    }
+    let content = Rect::new(area.x, area.y, area.width - 1, area.height);
+    let bar = Rect::new(area.x + area.width - 1, area.y, 1, area.height);
+    (content, Some(bar))
+}
+
+/// Compute thumb (start, length) within a track of `height` rows
+fn thumb_bounds(total: usize, offset: usize, height: usize) -> (usize, usize) {
+    if height == 0 || total <= height {
+        return (0, height);
+    }
+    let len = (height * height / total).max(1);
+    let max_offset = total - height;
+    let start = offset.min(max_offset) * (height - len) / max_offset;
+    (start, len)
+}
+
+/// Render a vertical scrollbar driven by the scroll offset
+pub fn render_scrollbar(area: Rect, total: usize, offset: usize, theme: &Theme, buf: &mut Buffer) {
+    let height = area.height as usize;
+    let (start, len) = thumb_bounds(total, offset, height);
+
+    for row in 0..height {
+        let (symbol, color) = if row >= start && row < start + len {
+            ("█", theme.fg_muted)
+        } else {
+            ("│", theme.border)
+        };
+        buf.set_string(
+            area.x,
+            area.y + row as u16,
+            symbol,
+            Style::default().fg(color).bg(theme.bg),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumb_bounds() {
+        // Content fits: thumb fills the track
+        assert_eq!(thumb_bounds(5, 0, 10), (0, 10));
+        // Top and bottom of a 100-item list in a 10-row track
+        assert_eq!(thumb_bounds(100, 0, 10), (0, 1));
+        assert_eq!(thumb_bounds(100, 90, 10), (9, 1));
+        // Halfway
+        assert_eq!(thumb_bounds(20, 5, 10), (2, 5));
+    }
+
+    #[test]
+    fn test_split_only_when_overflowing() {
+        let area = Rect::new(0, 0, 80, 10);
+        assert!(split_scrollbar(area, 10).1.is_none());
+        let (content, bar) = split_scrollbar(area, 11);
+        assert_eq!(content.width, 79);
+        assert_eq!(bar.unwrap().x, 79);
+    }
+}
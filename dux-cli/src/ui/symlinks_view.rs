@@ -0,0 +1,146 @@
+use std::collections::HashSet;
+
+use dux_core::{LinkStatus, NodeId, NodeKind};
+use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+use crate::app::views::SymlinkEntry;
+
+use super::scrollbar::{render_scrollbar, split_scrollbar};
+use super::text::{display_width, truncate_left, truncate_right};
+use super::theme::Theme;
+
+/// Flat list of symlinks with their targets, broken ones first and in red
+pub struct SymlinksView<'a> {
+    entries: &'a [SymlinkEntry],
+    selected_index: usize,
+    scroll_offset: usize,
+    selected_nodes: &'a HashSet<NodeId>,
+    theme: &'a Theme,
+}
+
+impl<'a> SymlinksView<'a> {
+    pub fn new(
+        entries: &'a [SymlinkEntry],
+        selected_index: usize,
+        scroll_offset: usize,
+        selected_nodes: &'a HashSet<NodeId>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            entries,
+            selected_index,
+            scroll_offset,
+            selected_nodes,
+            theme,
+        }
+    }
+}
+
+impl Widget for SymlinksView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 1 || area.width < 40 {
+            return;
+        }
+
+        if self.entries.is_empty() {
+            let msg = "No symlinks found";
+            let x = area.x + (area.width.saturating_sub(msg.len() as u16)) / 2;
+            let y = area.y + area.height / 2;
+            buf.set_string(x, y, msg, Style::default().fg(self.theme.fg_dim));
+            return;
+        }
+
+        let (area, scrollbar_area) = split_scrollbar(area, self.entries.len());
+        if let Some(bar_area) = scrollbar_area {
+            render_scrollbar(
+                bar_area,
+                self.entries.len(),
+                self.scroll_offset,
+                self.theme,
+                buf,
+            );
+        }
+
+        let icons = self.theme.icons;
+        let status_width: usize = 9;
+        // Marker and icon, then the link path and its target share the rest
+        let lead_width = 2 + icons.width() as usize;
+        let text_width = (area.width as usize).saturating_sub(lead_width + status_width + 1);
+        let path_width = text_width / 2;
+
+        for (i, entry) in self
+            .entries
+            .iter()
+            .skip(self.scroll_offset)
+            .take(area.height as usize)
+            .enumerate()
+        {
+            let y = area.y + i as u16;
+            let is_cursor = i + self.scroll_offset == self.selected_index;
+            let is_multi_selected = self.selected_nodes.contains(&entry.node_id);
+
+            let row_bg = if is_cursor {
+                self.theme.selection_bg
+            } else if is_multi_selected {
+                self.theme.bg_highlight
+            } else {
+                self.theme.bg
+            };
+            let style_for = |fg| {
+                if is_cursor {
+                    Style::default().bg(row_bg).fg(self.theme.selection_fg)
+                } else {
+                    Style::default().bg(row_bg).fg(fg)
+                }
+            };
+
+            // Clear the row
+            for x in 0..area.width {
+                buf.set_string(area.x + x, y, " ", style_for(self.theme.fg));
+            }
+
+            if is_multi_selected {
+                buf.set_string(
+                    area.x,
+                    y,
+                    "▪ ",
+                    Style::default().bg(row_bg).fg(self.theme.purple),
+                );
+            }
+            let mut x = area.x + 2;
+
+            let (status_color, path_color) = match entry.status {
+                LinkStatus::Broken => (self.theme.red, self.theme.red),
+                LinkStatus::Outside => (self.theme.yellow, self.theme.fg),
+                LinkStatus::Inside => (self.theme.fg_dim, self.theme.fg),
+            };
+            buf.set_string(
+                x,
+                y,
+                icons.for_kind(NodeKind::Symlink, false),
+                style_for(self.theme.fg_dim),
+            );
+            x += icons.width();
+
+            let path = truncate_left(&entry.relative_path, path_width);
+            buf.set_string(x, y, &path, style_for(path_color));
+            x += display_width(&path) as u16;
+
+            let target = entry.target.as_ref().map_or_else(
+                || "(unreadable)".to_string(),
+                |target| target.display().to_string(),
+            );
+            let target_width = text_width.saturating_sub(display_width(&path) + 3);
+            buf.set_string(
+                x,
+                y,
+                format!(" → {}", truncate_right(&target, target_width)),
+                style_for(self.theme.fg_dim),
+            );
+
+            let status_x = area.x + area.width - status_width as u16;
+            let status_str = format!("{:>8}", entry.status.label());
+            buf.set_string(status_x, y, &status_str, style_for(status_color));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Terminal columns `text` takes; wide characters (CJK, emoji) count as two
+pub fn display_width(text: &str) -> usize {
+    text.width()
+}
+
+/// Keep the start of text wider than `max_width` columns, marking the cut
+/// with "…". Cuts fall between characters, never inside one.
+pub fn truncate_right(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let budget = max_width.saturating_sub(1);
+    let mut width = 0;
+    let head: String = text
+        .chars()
+        .take_while(|c| {
+            width += c.width().unwrap_or(0);
+            width <= budget
+        })
+        .collect();
+    if max_width == 0 {
+        head
+    } else {
+        format!("{}…", head)
+    }
+}
+
+/// Keep the tail of a path wider than `max_width` columns, marking the cut
+/// with "...". Cuts fall between characters, never inside one.
+pub fn truncate_left(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let budget = max_width.saturating_sub(3);
+    let mut width = 0;
+    let mut tail: Vec<char> = text
+        .chars()
+        .rev()
+        .take_while(|c| {
+            width += c.width().unwrap_or(0);
+            width <= budget
+        })
+        .collect();
+    tail.reverse();
+    format!("...{}", tail.into_iter().collect::<String>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_left_keeps_tail() {
+        assert_eq!(truncate_left("/a/b", 10), "/a/b");
+        assert_eq!(truncate_left("/private/var/db/secret", 10), ".../secret");
+        assert_eq!(
+            truncate_left("/private/var/db/secret", 10).chars().count(),
+            10
+        );
+    }
+
+    #[test]
+    fn test_truncate_multibyte_names() {
+        assert_eq!(truncate_right("café-crème.txt", 8), "café-cr…");
+        assert_eq!(truncate_right("short", 8), "short");
+        assert_eq!(truncate_left("/photos/été/plage.jpg", 12), "...plage.jpg");
+        assert_eq!(truncate_left("/données/résumé", 10), ".../résumé");
+    }
+
+    #[test]
+    fn test_truncate_wide_characters() {
+        // Each CJK character and emoji takes two columns
+        let name = "写真アルバム🎉.zip";
+        let cut = truncate_right(name, 7);
+        assert_eq!(cut, "写真ア…");
+        assert!(display_width(&cut) <= 7);
+        // A wide character that would straddle the limit is dropped whole
+        assert_eq!(truncate_right(name, 6), "写真…");
+        assert_eq!(truncate_left("/home/写真/🎉🎉", 9), ".../🎉🎉");
+        assert_eq!(truncate_left("/home/写真/🎉🎉", 7), "...🎉🎉");
+        assert_eq!(truncate_left("/home/写真/🎉🎉", 6), "...🎉");
+        for width in 0..20 {
+            assert!(display_width(&truncate_right(name, width)) <= width.max(1));
+            assert!(display_width(&truncate_left(name, width)) <= width.max(3));
+        }
+    }
+}
@@ -1,8 +1,104 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+use color_eyre::{Result, eyre::eyre};
 use ratatui::style::Color;
 
-/// Catppuccin Mocha-inspired dark theme with 24-bit RGB colors
+use super::icons::Icons;
+
+/// Custom colors from `theme.toml`, applied on top of whichever built-in
+/// palette is in use
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThemeOverrides {
+    colors: Vec<(String, Color)>,
+    size_thresholds: Option<(f64, f64, f64)>,
+}
+
+impl ThemeOverrides {
+    /// Load overrides from a TOML file mapping theme field names to hex
+    /// colors, plus an optional `size_thresholds = [10, 30, 50]`
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut entries: toml::Table = toml::from_str(&contents)
+            .map_err(|e| eyre!("Invalid theme file {}: {}", path.display(), e))?;
+
+        let mut overrides = Self::default();
+        if let Some(value) = entries.remove("size_thresholds") {
+            overrides.size_thresholds = Some(parse_thresholds(&value).ok_or_else(|| {
+                eyre!(
+                    "Invalid `size_thresholds`: expected three ascending percentages, got {}",
+                    value
+                )
+            })?);
+        }
+        let mut known = Theme::default();
+        for (key, value) in entries {
+            let color = value
+                .as_str()
+                .and_then(parse_hex_color)
+                .ok_or_else(|| eyre!("Invalid hex color for `{}`: {}", key, value))?;
+            if known.color_mut(&key).is_none() {
+                return Err(eyre!("Unknown theme color `{}`", key));
+            }
+            overrides.colors.push((key, color));
+        }
+        Ok(overrides)
+    }
+}
+
+/// Built-in color palettes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ThemeName {
+    /// Catppuccin Mocha (dark)
+    #[default]
+    Dark,
+    /// Catppuccin Latte (light)
+    Light,
+    SolarizedDark,
+    SolarizedLight,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 4] = [
+        ThemeName::Dark,
+        ThemeName::Light,
+        ThemeName::SolarizedDark,
+        ThemeName::SolarizedLight,
+    ];
+
+    /// Stable identifier used on the command line and in the config file
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemeName::Dark => "dark",
+            ThemeName::Light => "light",
+            ThemeName::SolarizedDark => "solarized-dark",
+            ThemeName::SolarizedLight => "solarized-light",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.as_str() == name)
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ThemeName::Dark => ThemeName::Light,
+            ThemeName::Light => ThemeName::SolarizedDark,
+            ThemeName::SolarizedDark => ThemeName::SolarizedLight,
+            ThemeName::SolarizedLight => ThemeName::Dark,
+        }
+    }
+}
+
+/// Default size gradient breakpoints, in percent
+const DEFAULT_SIZE_THRESHOLDS: (f64, f64, f64) = (10.0, 30.0, 50.0);
+
+/// Color palette with 24-bit RGB colors
 #[allow(dead_code)]
 pub struct Theme {
+    /// Which built-in palette this theme was created from
+    pub name: ThemeName,
+
     // Base colors
     pub bg: Color,
     pub bg_surface: Color,
@@ -28,11 +124,46 @@ pub struct Theme {
     pub size_small: Color,
     pub size_medium: Color,
     pub size_large: Color,
+    /// Percentages where the gradient reaches green's end, yellow, and
+    /// yellow's end; red is reached at 100%
+    pub size_thresholds: (f64, f64, f64),
+
+    /// Entry icons, emoji unless the terminal can't draw them
+    pub icons: Icons,
+    /// Custom colors in effect, kept so the next palette gets them too
+    pub overrides: ThemeOverrides,
+
+    // File name colors by type
+    pub file_image: Color,
+    pub file_video: Color,
+    pub file_audio: Color,
+    pub file_archive: Color,
+    pub file_code: Color,
+    pub file_document: Color,
 }
 
 impl Default for Theme {
     fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Create the theme for a built-in palette
+    pub fn from_name(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Self::dark(),
+            ThemeName::Light => Self::light(),
+            ThemeName::SolarizedDark => Self::solarized_dark(),
+            ThemeName::SolarizedLight => Self::solarized_light(),
+        }
+    }
+
+    /// Catppuccin Mocha-inspired dark theme
+    pub fn dark() -> Self {
         Self {
+            name: ThemeName::Dark,
+
             // Catppuccin Mocha base
             bg: Color::Rgb(30, 30, 46),           // Base
             bg_surface: Color::Rgb(49, 50, 68),   // Surface0
@@ -58,28 +189,247 @@ impl Default for Theme {
             size_small: Color::Rgb(166, 227, 161),  // Green
             size_medium: Color::Rgb(249, 226, 175), // Yellow
             size_large: Color::Rgb(243, 139, 168),  // Red
+            size_thresholds: DEFAULT_SIZE_THRESHOLDS,
+            icons: Icons::default(),
+            overrides: ThemeOverrides::default(),
+
+            // File types
+            file_image: Color::Rgb(203, 166, 247),    // Mauve
+            file_video: Color::Rgb(235, 160, 172),    // Maroon
+            file_audio: Color::Rgb(148, 226, 213),    // Teal
+            file_archive: Color::Rgb(250, 179, 135),  // Peach
+            file_code: Color::Rgb(166, 227, 161),     // Green
+            file_document: Color::Rgb(116, 199, 236), // Sapphire
         }
     }
-}
 
-impl Theme {
+    /// Catppuccin Latte-inspired light theme
+    pub fn light() -> Self {
+        Self {
+            name: ThemeName::Light,
+
+            // Catppuccin Latte base
+            bg: Color::Rgb(239, 241, 245),           // Base
+            bg_surface: Color::Rgb(230, 233, 239),   // Mantle
+            bg_highlight: Color::Rgb(204, 208, 218), // Surface0
+            fg: Color::Rgb(76, 79, 105),             // Text
+            fg_dim: Color::Rgb(92, 95, 119),         // Subtext1
+            fg_muted: Color::Rgb(124, 127, 147),     // Overlay2
+
+            // Accent colors
+            blue: Color::Rgb(30, 102, 245),   // Blue
+            green: Color::Rgb(64, 160, 43),   // Green
+            yellow: Color::Rgb(223, 142, 29), // Yellow
+            red: Color::Rgb(210, 15, 57),     // Red
+            purple: Color::Rgb(136, 57, 239), // Mauve
+            teal: Color::Rgb(23, 146, 153),   // Teal
+
+            // UI
+            border: Color::Rgb(172, 176, 190),       // Surface2
+            selection_bg: Color::Rgb(30, 102, 245),  // Blue
+            selection_fg: Color::Rgb(239, 241, 245), // Base
+
+            // Size gradient
+            size_small: Color::Rgb(64, 160, 43),   // Green
+            size_medium: Color::Rgb(223, 142, 29), // Yellow
+            size_large: Color::Rgb(210, 15, 57),   // Red
+            size_thresholds: DEFAULT_SIZE_THRESHOLDS,
+            icons: Icons::default(),
+            overrides: ThemeOverrides::default(),
+
+            // File types
+            file_image: Color::Rgb(136, 57, 239),    // Mauve
+            file_video: Color::Rgb(230, 69, 83),     // Maroon
+            file_audio: Color::Rgb(23, 146, 153),    // Teal
+            file_archive: Color::Rgb(254, 100, 11),  // Peach
+            file_code: Color::Rgb(64, 160, 43),      // Green
+            file_document: Color::Rgb(32, 159, 181), // Sapphire
+        }
+    }
+
+    /// Solarized dark theme
+    pub fn solarized_dark() -> Self {
+        Self {
+            name: ThemeName::SolarizedDark,
+
+            bg: Color::Rgb(0, 43, 54),            // base03
+            bg_surface: Color::Rgb(7, 54, 66),    // base02
+            bg_highlight: Color::Rgb(23, 77, 91), // base02, lifted
+            fg: Color::Rgb(147, 161, 161),        // base1
+            fg_dim: Color::Rgb(131, 148, 150),    // base0
+            fg_muted: Color::Rgb(101, 123, 131),  // base00
+
+            // Accent colors
+            blue: Color::Rgb(38, 139, 210),    // blue
+            green: Color::Rgb(133, 153, 0),    // green
+            yellow: Color::Rgb(181, 137, 0),   // yellow
+            red: Color::Rgb(220, 50, 47),      // red
+            purple: Color::Rgb(108, 113, 196), // violet
+            teal: Color::Rgb(42, 161, 152),    // cyan
+
+            // UI
+            border: Color::Rgb(88, 110, 117),       // base01
+            selection_bg: Color::Rgb(38, 139, 210), // blue
+            selection_fg: Color::Rgb(0, 43, 54),    // base03
+
+            // Size gradient
+            size_small: Color::Rgb(133, 153, 0),  // green
+            size_medium: Color::Rgb(181, 137, 0), // yellow
+            size_large: Color::Rgb(220, 50, 47),  // red
+            size_thresholds: DEFAULT_SIZE_THRESHOLDS,
+            icons: Icons::default(),
+            overrides: ThemeOverrides::default(),
+
+            // File types
+            file_image: Color::Rgb(108, 113, 196),   // violet
+            file_video: Color::Rgb(211, 54, 130),    // magenta
+            file_audio: Color::Rgb(42, 161, 152),    // cyan
+            file_archive: Color::Rgb(203, 75, 22),   // orange
+            file_code: Color::Rgb(133, 153, 0),      // green
+            file_document: Color::Rgb(38, 139, 210), // blue
+        }
+    }
+
+    /// Solarized light theme
+    pub fn solarized_light() -> Self {
+        Self {
+            name: ThemeName::SolarizedLight,
+
+            bg: Color::Rgb(253, 246, 227),           // base3
+            bg_surface: Color::Rgb(238, 232, 213),   // base2
+            bg_highlight: Color::Rgb(223, 217, 196), // base2, darkened
+            fg: Color::Rgb(88, 110, 117),            // base01
+            fg_dim: Color::Rgb(101, 123, 131),       // base00
+            fg_muted: Color::Rgb(147, 161, 161),     // base1
+
+            // Accent colors
+            blue: Color::Rgb(38, 139, 210),    // blue
+            green: Color::Rgb(133, 153, 0),    // green
+            yellow: Color::Rgb(181, 137, 0),   // yellow
+            red: Color::Rgb(220, 50, 47),      // red
+            purple: Color::Rgb(108, 113, 196), // violet
+            teal: Color::Rgb(42, 161, 152),    // cyan
+
+            // UI
+            border: Color::Rgb(147, 161, 161),       // base1
+            selection_bg: Color::Rgb(38, 139, 210),  // blue
+            selection_fg: Color::Rgb(253, 246, 227), // base3
+
+            // Size gradient
+            size_small: Color::Rgb(133, 153, 0),  // green
+            size_medium: Color::Rgb(181, 137, 0), // yellow
+            size_large: Color::Rgb(220, 50, 47),  // red
+            size_thresholds: DEFAULT_SIZE_THRESHOLDS,
+            icons: Icons::default(),
+            overrides: ThemeOverrides::default(),
+
+            // File types
+            file_image: Color::Rgb(108, 113, 196),   // violet
+            file_video: Color::Rgb(211, 54, 130),    // magenta
+            file_audio: Color::Rgb(42, 161, 152),    // cyan
+            file_archive: Color::Rgb(203, 75, 22),   // orange
+            file_code: Color::Rgb(133, 153, 0),      // green
+            file_document: Color::Rgb(38, 139, 210), // blue
+        }
+    }
+
+    /// This palette with `overrides` laid over it; anything they leave out
+    /// keeps the palette's value
+    pub fn with_overrides(mut self, overrides: &ThemeOverrides) -> Self {
+        self.overrides = overrides.clone();
+        if let Some(thresholds) = overrides.size_thresholds {
+            self.size_thresholds = thresholds;
+        }
+        for (key, color) in &overrides.colors {
+            if let Some(slot) = self.color_mut(key) {
+                *slot = *color;
+            }
+        }
+        self
+    }
+
+    /// Look up a color field by its name
+    fn color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        let slot = match key {
+            "bg" => &mut self.bg,
+            "bg_surface" => &mut self.bg_surface,
+            "bg_highlight" => &mut self.bg_highlight,
+            "fg" => &mut self.fg,
+            "fg_dim" => &mut self.fg_dim,
+            "fg_muted" => &mut self.fg_muted,
+            "blue" => &mut self.blue,
+            "green" => &mut self.green,
+            "yellow" => &mut self.yellow,
+            "red" => &mut self.red,
+            "purple" => &mut self.purple,
+            "teal" => &mut self.teal,
+            "border" => &mut self.border,
+            "selection_bg" => &mut self.selection_bg,
+            "selection_fg" => &mut self.selection_fg,
+            "size_small" => &mut self.size_small,
+            "size_medium" => &mut self.size_medium,
+            "size_large" => &mut self.size_large,
+            "file_image" => &mut self.file_image,
+            "file_video" => &mut self.file_video,
+            "file_audio" => &mut self.file_audio,
+            "file_archive" => &mut self.file_archive,
+            "file_code" => &mut self.file_code,
+            "file_document" => &mut self.file_document,
+            _ => return None,
+        };
+        Some(slot)
+    }
+
     /// Get color for a size percentage (0-100)
     pub fn size_color(&self, percentage: f64) -> Color {
-        if percentage < 10.0 {
+        let (low, mid, high) = self.size_thresholds;
+        if percentage < low {
             self.size_small
-        } else if percentage < 30.0 {
+        } else if percentage < mid {
             // Interpolate between green and yellow
-            let t = (percentage - 10.0) / 20.0;
+            let t = (percentage - low) / (mid - low);
             interpolate_color(self.size_small, self.size_medium, t)
-        } else if percentage < 50.0 {
+        } else if percentage < high {
             self.size_medium
         } else {
             // Interpolate between yellow and red
-            let t = ((percentage - 50.0) / 50.0).min(1.0);
+            let t = ((percentage - high) / (100.0 - high).max(f64::EPSILON)).min(1.0);
             interpolate_color(self.size_medium, self.size_large, t)
         }
     }
 
+    /// Labels and colors explaining the size gradient, smallest first
+    pub fn size_legend(&self) -> [(String, Color); 3] {
+        let (low, _, high) = self.size_thresholds;
+        [
+            (format!("<{}%", low), self.size_small),
+            (format!("{}-{}%", low, high), self.size_medium),
+            (format!("{}%+", high), self.size_large),
+        ]
+    }
+
+    /// Name color for a file with this extension (without the dot). Files
+    /// outside the known groups keep the regular text color.
+    pub fn extension_color(&self, ext: &str) -> Color {
+        match FileGroup::from_extension(ext) {
+            Some(FileGroup::Image) => self.file_image,
+            Some(FileGroup::Video) => self.file_video,
+            Some(FileGroup::Audio) => self.file_audio,
+            Some(FileGroup::Archive) => self.file_archive,
+            Some(FileGroup::Code) => self.file_code,
+            Some(FileGroup::Document) => self.file_document,
+            None => self.fg,
+        }
+    }
+
+    /// Name color for a file, from the extension of its name or path
+    pub fn file_color(&self, name: &str) -> Color {
+        match Path::new(name).extension() {
+            Some(ext) => self.extension_color(&ext.to_string_lossy()),
+            None => self.fg,
+        }
+    }
+
     /// Get icon color based on node kind
     pub fn icon_color(&self, is_directory: bool) -> Color {
         if is_directory {
@@ -90,6 +440,70 @@ impl Theme {
     }
 }
 
+/// Broad file types whose names are colored in the lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileGroup {
+    Image,
+    Video,
+    Audio,
+    Archive,
+    Code,
+    Document,
+}
+
+impl FileGroup {
+    /// Group for a file extension (without the dot, any case)
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        let group = match ext.to_ascii_lowercase().as_str() {
+            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "tif" | "tiff" | "webp" | "heic" | "heif"
+            | "avif" | "svg" | "ico" | "psd" | "raw" | "cr2" | "nef" | "arw" | "dng" => {
+                FileGroup::Image
+            }
+            "mp4" | "m4v" | "mkv" | "mov" | "avi" | "webm" | "wmv" | "flv" | "mpg" | "mpeg"
+            | "3gp" | "vob" => FileGroup::Video,
+            "mp3" | "wav" | "flac" | "aac" | "ogg" | "oga" | "opus" | "m4a" | "wma" | "aif"
+            | "aiff" | "mid" | "midi" => FileGroup::Audio,
+            "zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "zst" | "lz4" | "lzma" | "7z" | "rar"
+            | "cab" | "jar" | "apk" | "deb" | "rpm" | "pkg" | "dmg" | "iso" => FileGroup::Archive,
+            "rs" | "c" | "h" | "cc" | "cpp" | "hpp" | "cs" | "go" | "java" | "kt" | "swift"
+            | "py" | "rb" | "php" | "js" | "mjs" | "ts" | "jsx" | "tsx" | "vue" | "svelte"
+            | "html" | "css" | "scss" | "sh" | "bash" | "zsh" | "fish" | "lua" | "pl" | "scala"
+            | "hs" | "ml" | "ex" | "exs" | "erl" | "clj" | "dart" | "zig" | "sql" | "json"
+            | "toml" | "yaml" | "yml" | "xml" => FileGroup::Code,
+            "pdf" | "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" | "odt" | "ods" | "odp"
+            | "rtf" | "txt" | "md" | "rst" | "tex" | "epub" | "csv" | "pages" | "numbers"
+            | "key" => FileGroup::Document,
+            _ => return None,
+        };
+        Some(group)
+    }
+}
+
+/// Three ascending percentages between 0 and 100 (integers or floats)
+fn parse_thresholds(value: &toml::Value) -> Option<(f64, f64, f64)> {
+    let numbers: Vec<f64> = value
+        .as_array()?
+        .iter()
+        .map(|v| v.as_float().or_else(|| v.as_integer().map(|i| i as f64)))
+        .collect::<Option<_>>()?;
+    let [low, mid, high] = numbers[..] else {
+        return None;
+    };
+    (0.0 <= low && low < mid && mid < high && high <= 100.0).then_some((low, mid, high))
+}
+
+/// Parse `#rrggbb` (leading `#` optional) into an RGB color
+fn parse_hex_color(s: &str) -> Option<Color> {
+    let hex = s.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Color::Rgb(r, g, b))
+}
+
 /// Interpolate between two RGB colors
 fn interpolate_color(from: Color, to: Color, t: f64) -> Color {
     match (from, to) {
@@ -108,3 +522,108 @@ fn lerp(a: u8, b: u8, t: f64) -> u8 {
     let b = b as f64;
     (a + (b - a) * t).round() as u8
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#1e1e2e"), Some(Color::Rgb(30, 30, 46)));
+        assert_eq!(parse_hex_color("FFFFFF"), Some(Color::Rgb(255, 255, 255)));
+        assert_eq!(parse_hex_color("#fff"), None);
+        assert_eq!(parse_hex_color("#gg0000"), None);
+    }
+
+    #[test]
+    fn test_file_group_from_extension() {
+        let cases = [
+            ("png", FileGroup::Image),
+            ("mkv", FileGroup::Video),
+            ("flac", FileGroup::Audio),
+            ("tgz", FileGroup::Archive),
+            ("rs", FileGroup::Code),
+            ("pdf", FileGroup::Document),
+        ];
+        for (ext, group) in cases {
+            assert_eq!(FileGroup::from_extension(ext), Some(group), "{ext}");
+            let upper = ext.to_ascii_uppercase();
+            assert_eq!(FileGroup::from_extension(&upper), Some(group), "{upper}");
+        }
+        assert_eq!(FileGroup::from_extension("bin"), None);
+        assert_eq!(FileGroup::from_extension(""), None);
+        assert_eq!(FileGroup::from_extension(".rs"), None);
+    }
+
+    #[test]
+    fn test_extension_color_groups() {
+        let theme = Theme::default();
+        assert_eq!(theme.extension_color("flac"), theme.file_audio);
+        assert_eq!(theme.extension_color("bin"), theme.fg);
+        assert_eq!(theme.file_color("src/main.rs"), theme.file_code);
+        assert_eq!(theme.file_color("notes.PDF"), theme.file_document);
+        assert_eq!(theme.file_color("Makefile"), theme.fg);
+        assert_eq!(theme.file_color(".bashrc"), theme.fg);
+    }
+
+    #[test]
+    fn test_size_color_respects_custom_thresholds() {
+        let default = Theme::default();
+        assert_eq!(default.size_color(5.0), default.size_small);
+        assert_eq!(default.size_color(40.0), default.size_medium);
+
+        let early_red = Theme {
+            size_thresholds: (1.0, 2.0, 5.0),
+            ..Theme::default()
+        };
+        assert_eq!(early_red.size_color(0.5), early_red.size_small);
+        assert_eq!(early_red.size_color(3.0), early_red.size_medium);
+        assert_eq!(early_red.size_color(100.0), early_red.size_large);
+        assert_eq!(early_red.size_legend()[2].0, "5%+");
+
+        let value: toml::Value =
+            toml::from_str::<toml::Table>("t = [1, 2.5, 5]").unwrap()["t"].clone();
+        assert_eq!(parse_thresholds(&value), Some((1.0, 2.5, 5.0)));
+        let value: toml::Value =
+            toml::from_str::<toml::Table>("t = [30, 10, 50]").unwrap()["t"].clone();
+        assert_eq!(parse_thresholds(&value), None);
+    }
+
+    #[test]
+    fn test_from_file_overrides_and_defaults() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("theme.toml");
+
+        std::fs::write(
+            &path,
+            "bg = \"#000000\"\nred = \"#ff0000\"\nfile_code = \"#00ff00\"\n",
+        )
+        .unwrap();
+        let overrides = ThemeOverrides::from_file(&path).unwrap();
+        // Laid over the chosen palette, not the default one
+        let theme = Theme::light().with_overrides(&overrides);
+        assert_eq!(theme.name, ThemeName::Light);
+        assert_eq!(theme.bg, Color::Rgb(0, 0, 0));
+        assert_eq!(theme.red, Color::Rgb(255, 0, 0));
+        assert_eq!(theme.extension_color("rs"), Color::Rgb(0, 255, 0));
+        assert_eq!(theme.fg, Theme::light().fg);
+
+        // Cycling to the next palette keeps them
+        let next = Theme::from_name(theme.name.next()).with_overrides(&theme.overrides);
+        assert_eq!(next.name, ThemeName::SolarizedDark);
+        assert_eq!(next.bg, Color::Rgb(0, 0, 0));
+        assert_eq!(next.fg, Theme::solarized_dark().fg);
+
+        std::fs::write(&path, "size_thresholds = [2, 5, 20]\n").unwrap();
+        let overrides = ThemeOverrides::from_file(&path).unwrap();
+        assert_eq!(
+            Theme::default().with_overrides(&overrides).size_thresholds,
+            (2.0, 5.0, 20.0)
+        );
+
+        std::fs::write(&path, "bg = \"not-a-color\"\n").unwrap();
+        assert!(ThemeOverrides::from_file(&path).is_err());
+        std::fs::write(&path, "no_such_color = \"#000000\"\n").unwrap();
+        assert!(ThemeOverrides::from_file(&path).is_err());
+    }
+}
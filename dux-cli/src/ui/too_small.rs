@@ -0,0 +1,53 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Widget,
+};
+
+use super::layout::{MIN_HEIGHT, MIN_WIDTH};
+use super::text::{display_width, truncate_right};
+use super::theme::Theme;
+
+/// Shown instead of the UI while the terminal is below the minimum size
+pub struct TooSmallView<'a> {
+    theme: &'a Theme,
+}
+
+impl<'a> TooSmallView<'a> {
+    pub fn new(theme: &'a Theme) -> Self {
+        Self { theme }
+    }
+}
+
+impl Widget for TooSmallView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let lines = [
+            (
+                "Terminal too small".to_string(),
+                Style::default()
+                    .fg(self.theme.yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            (
+                format!("need ≥ {}×{}", MIN_WIDTH, MIN_HEIGHT),
+                Style::default().fg(self.theme.fg),
+            ),
+            (
+                format!("now {}×{}", area.width, area.height),
+                Style::default().fg(self.theme.fg_dim),
+            ),
+        ];
+
+        let shown = lines.len().min(area.height as usize);
+        let top = area.y + (area.height - shown as u16) / 2;
+        for (i, (text, style)) in lines.iter().take(shown).enumerate() {
+            let text = truncate_right(text, area.width as usize);
+            let x = area.x + (area.width.saturating_sub(display_width(&text) as u16)) / 2;
+            buf.set_string(x, top + i as u16, &text, *style);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
+use std::time::SystemTime;
 
-use dux_core::{DiskTree, NodeId, NodeKind, format_size, size_percentage};
+use dux_core::{DiskTree, NodeId, NodeKind, SizeFormat, TreeNode, format_count, size_percentage};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -8,9 +9,27 @@ use ratatui::{
     widgets::Widget,
 };
 
+use crate::app::views::NodeFilter;
+use crate::app::{BarMetric, PercentBase};
+
 use super::bar_chart::render_bar;
+use super::layout::SizeColumns;
+use super::scrollbar::{render_scrollbar, split_scrollbar};
+use super::text::{display_width, truncate_right};
 use super::theme::Theme;
 
+/// Width of the optional file-count column
+pub const FILE_COUNT_WIDTH: usize = 10;
+
+/// Names keep at least this many columns before the file-count column is dropped
+pub const MIN_NAME_WIDTH: usize = 20;
+
+/// Marks an entry with a change inside the recent-activity window
+const RECENT_MARKER: &str = "●";
+
+/// Shown after the name of a directory the scanner could not read
+const NO_ACCESS_NOTE: &str = " (no access)";
+
 /// Tree prefix characters
 struct TreePrefix;
 
@@ -28,6 +47,15 @@ pub struct TreeView<'a> {
     selected_index: usize,
     scroll_offset: usize,
     selected_nodes: &'a HashSet<NodeId>,
+    filter: NodeFilter,
+    percent_base: PercentBase,
+    /// Fixed total for percentages, overriding the percent base
+    percent_total: Option<u64>,
+    metric: BarMetric,
+    show_file_counts: bool,
+    /// Newest mtime at or below each node, and the cutoff for marking it
+    recent: Option<(&'a [Option<SystemTime>], SystemTime)>,
+    size_format: SizeFormat,
     theme: &'a Theme,
 }
 
@@ -46,13 +74,79 @@ impl<'a> TreeView<'a> {
             selected_index,
             scroll_offset,
             selected_nodes,
+            filter: NodeFilter::default(),
+            percent_base: PercentBase::ViewRoot,
+            percent_total: None,
+            metric: BarMetric::Size,
+            show_file_counts: false,
+            recent: None,
+            size_format: SizeFormat::default(),
             theme,
         }
     }
 
-    /// Get visible nodes respecting expansion state
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    pub fn with_file_counts(mut self, show: bool) -> Self {
+        self.show_file_counts = show;
+        self
+    }
+
+    pub fn with_percent_base(mut self, base: PercentBase) -> Self {
+        self.percent_base = base;
+        self
+    }
+
+    /// Measure percentages and bars against `total` (say, free space)
+    /// rather than the view root or parent; None keeps the percent base
+    pub fn with_percent_total(mut self, total: Option<u64>) -> Self {
+        self.percent_total = total;
+        self
+    }
+
+    pub fn with_metric(mut self, metric: BarMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Mark entries changed at or after `cutoff`, judged by `newest_mtimes`
+    /// (see `DiskTree::newest_mtimes`); None marks nothing
+    pub fn with_recent(
+        mut self,
+        newest_mtimes: &'a [Option<SystemTime>],
+        cutoff: Option<SystemTime>,
+    ) -> Self {
+        self.recent = cutoff.map(|cutoff| (newest_mtimes, cutoff));
+        self
+    }
+
+    fn is_recent(&self, id: NodeId) -> bool {
+        self.recent.is_some_and(|(newest, cutoff)| {
+            newest
+                .get(id.index())
+                .copied()
+                .flatten()
+                .is_some_and(|mtime| mtime >= cutoff)
+        })
+    }
+
+    pub fn with_filter(mut self, filter: NodeFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Get visible nodes respecting expansion state and the view filter
     fn visible_nodes(&self) -> Vec<NodeId> {
-        self.tree.visible_nodes(self.view_root)
+        let keep = |n: &TreeNode| self.filter.keep_in(self.tree, n);
+        match self.metric {
+            BarMetric::Size => self.tree.visible_nodes_filtered(self.view_root, &keep),
+            metric => self
+                .tree
+                .visible_nodes_ranked(self.view_root, &keep, &|n| metric.of(n)),
+        }
     }
 
     /// Calculate tree prefixes for each visible node
@@ -132,7 +226,21 @@ impl<'a> TreeView<'a> {
             None => return true,
         };
 
-        parent.children.last() == Some(&node_id)
+        // Last among the siblings that pass the filter, in display order
+        let metric = self.metric;
+        let ranked;
+        let siblings = match metric {
+            BarMetric::Size => &parent.children,
+            _ => {
+                ranked = self.tree.children_ranked(parent_id, &|n| metric.of(n));
+                &ranked
+            }
+        };
+        siblings.iter().rev().find(|&&id| {
+            self.tree
+                .get(id)
+                .is_some_and(|n| self.filter.keep_in(self.tree, n))
+        }) == Some(&node_id)
     }
 }
 
@@ -143,14 +251,29 @@ impl Widget for TreeView<'_> {
         }
 
         let nodes = self.visible_nodes();
+        let (area, scrollbar_area) = split_scrollbar(area, nodes.len());
+        if let Some(bar_area) = scrollbar_area {
+            render_scrollbar(bar_area, nodes.len(), self.scroll_offset, self.theme, buf);
+        }
         let prefixes = self.calculate_prefixes(&nodes);
-        let total_size = self.tree.get(self.view_root).map(|n| n.size).unwrap_or(1);
-
-        // Column widths
-        let bar_width = 24;
-        let pct_width = 6;
-        let size_width = 10;
-        let name_width = area.width as usize - bar_width - pct_width - size_width - 4;
+        let view_root_size = self
+            .tree
+            .get(self.view_root)
+            .map(|n| self.metric.of(n))
+            .unwrap_or(1);
+
+        let columns = SizeColumns::new(area);
+        let base_name_width = columns.name_width;
+        // File-count column only when names still have room next to it
+        let count_width =
+            if self.show_file_counts && base_name_width >= FILE_COUNT_WIDTH + MIN_NAME_WIDTH {
+                FILE_COUNT_WIDTH
+            } else {
+                0
+            };
+        // A column for the recent-activity marker, right of the names
+        let recent_width = if self.recent.is_some() { 2 } else { 0 };
+        let name_width = base_name_width.saturating_sub(count_width + recent_width);
 
         for (i, (node_id, prefix)) in nodes
             .iter()
@@ -227,13 +350,8 @@ impl Widget for TreeView<'_> {
             x += prefix.chars().count() as u16;
 
             // Icon
-            let icon = match node.kind {
-                NodeKind::Directory if node.is_expanded => "📂",
-                NodeKind::Directory => "📁",
-                NodeKind::File => "📄",
-                NodeKind::Symlink => "🔗",
-                NodeKind::Error => "⚠️",
-            };
+            let icons = self.theme.icons;
+            let icon = icons.for_kind(node.kind, node.is_expanded);
             let icon_style = if is_cursor {
                 row_style
             } else {
@@ -246,18 +364,20 @@ impl Widget for TreeView<'_> {
                     })
             };
             buf.set_string(x, y, icon, icon_style);
-            x += 2; // Icon + space
+            x += icons.width();
 
             // Name
             let name = &node.name;
             let marker_offset = if is_multi_selected { 2 } else { 0 };
-            let max_name_len =
-                name_width.saturating_sub(prefix.chars().count() + 3 + marker_offset);
-            let display_name = if name.len() > max_name_len {
-                format!("{}…", &name[..max_name_len.saturating_sub(1)])
+            let note_len = if node.kind == NodeKind::Error {
+                NO_ACCESS_NOTE.len()
             } else {
-                name.clone()
+                0
             };
+            let max_name_len = name_width.saturating_sub(
+                prefix.chars().count() + icons.width() as usize + 1 + marker_offset + note_len,
+            );
+            let display_name = truncate_right(name, max_name_len);
 
             let name_style = if is_cursor {
                 row_style.add_modifier(Modifier::BOLD)
@@ -271,7 +391,11 @@ impl Widget for TreeView<'_> {
                     })
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(self.theme.fg).bg(if is_multi_selected {
+                let fg = match node.kind {
+                    NodeKind::File => self.theme.file_color(name),
+                    _ => self.theme.fg,
+                };
+                Style::default().fg(fg).bg(if is_multi_selected {
                     self.theme.bg_highlight
                 } else {
                     self.theme.bg
@@ -279,10 +403,27 @@ impl Widget for TreeView<'_> {
             };
             buf.set_string(x, y, &display_name, name_style);
 
+            // Unreadable directories say why they look small
+            if node.kind == NodeKind::Error {
+                let note_style = if is_cursor {
+                    row_style
+                } else {
+                    Style::default()
+                        .fg(self.theme.fg_muted)
+                        .bg(if is_multi_selected {
+                            self.theme.bg_highlight
+                        } else {
+                            self.theme.bg
+                        })
+                };
+                let note_x = x + display_width(&display_name) as u16;
+                buf.set_string(note_x, y, NO_ACCESS_NOTE, note_style);
+            }
+
             // Expand indicator for directories
             if node.kind.is_directory() && !node.children.is_empty() {
                 let indicator = if node.is_expanded { " ▼" } else { " ▶" };
-                let indicator_x = x + display_name.chars().count() as u16;
+                let indicator_x = x + display_width(&display_name) as u16;
                 let indicator_style = if is_cursor {
                     row_style
                 } else {
@@ -297,19 +438,67 @@ impl Widget for TreeView<'_> {
                 buf.set_string(indicator_x, y, indicator, indicator_style);
             }
 
-            // Size bar (right-aligned section)
-            let right_x =
-                area.x + area.width - bar_width as u16 - pct_width as u16 - size_width as u16 - 2;
+            if self.is_recent(*node_id) {
+                let marker_style = if is_cursor {
+                    row_style
+                } else {
+                    Style::default()
+                        .fg(self.theme.teal)
+                        .bg(if is_multi_selected {
+                            self.theme.bg_highlight
+                        } else {
+                            self.theme.bg
+                        })
+                };
+                buf.set_string(
+                    columns.right_x - (count_width + recent_width) as u16,
+                    y,
+                    RECENT_MARKER,
+                    marker_style,
+                );
+            }
 
-            let percentage = size_percentage(node.size, total_size);
+            // File count (directories only)
+            if count_width > 0 && node.kind.is_directory() {
+                let count_str = format!("{:>9}", format_count(node.file_count));
+                let count_style = if is_cursor {
+                    row_style
+                } else {
+                    Style::default()
+                        .fg(self.theme.fg_muted)
+                        .bg(if is_multi_selected {
+                            self.theme.bg_highlight
+                        } else {
+                            self.theme.bg
+                        })
+                };
+                buf.set_string(
+                    columns.right_x - count_width as u16,
+                    y,
+                    &count_str,
+                    count_style,
+                );
+            }
+
+            let total_size =
+                self.percent_total
+                    .unwrap_or_else(|| match (self.percent_base, node.parent) {
+                        (PercentBase::Parent, Some(parent)) if *node_id != self.view_root => self
+                            .tree
+                            .get(parent)
+                            .map(|p| self.metric.of(p))
+                            .unwrap_or(view_root_size),
+                        _ => view_root_size,
+                    });
+            let percentage = size_percentage(self.metric.of(node), total_size);
             let bar_color = if is_cursor {
                 self.theme.selection_fg
             } else {
                 self.theme.size_color(percentage)
             };
-            let (bar, _) = render_bar(percentage, bar_width.saturating_sub(2), bar_color);
+            let (bar, _) = render_bar(percentage, columns.bar_width, bar_color);
             buf.set_string(
-                right_x,
+                columns.right_x,
                 y,
                 &bar,
                 if is_cursor {
@@ -336,10 +525,17 @@ impl Widget for TreeView<'_> {
                         self.theme.bg
                     })
             };
-            buf.set_string(right_x + bar_width as u16 - 1, y, &pct_str, pct_style);
-
-            // Size
-            let size_str = format!("{:>9}", format_size(node.size));
+            buf.set_string(columns.pct_x, y, &pct_str, pct_style);
+
+            // Size; totals missing an unreadable directory are lower bounds,
+            // and sampled ones are estimates
+            let size = self.size_format.format(node.size);
+            let size_str = match node.kind {
+                NodeKind::Error => format!("{:>9}", "?"),
+                _ if node.estimated => format!("{:>9}", format!("~{}", size)),
+                _ if node.incomplete => format!("{:>9}", format!("≥{}", size)),
+                _ => format!("{:>9}", size),
+            };
             let size_style = if is_cursor {
                 row_style
             } else {
@@ -351,12 +547,7 @@ impl Widget for TreeView<'_> {
                         self.theme.bg
                     })
             };
-            buf.set_string(
-                right_x + bar_width as u16 + pct_width as u16 - 1,
-                y,
-                &size_str,
-                size_style,
-            );
+            buf.set_string(columns.size_x, y, &size_str, size_style);
         }
     }
 }
@@ -0,0 +1,125 @@
+use dux_core::{DiskTree, NodeId, SizeFormat, size_percentage};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Widget,
+};
+
+use crate::app::views::NodeFilter;
+use crate::app::{squarify, treemap_children};
+
+use super::text::truncate_right;
+use super::theme::Theme;
+
+/// Block visualization of the view root's immediate children
+pub struct TreemapView<'a> {
+    tree: &'a DiskTree,
+    view_root: NodeId,
+    selected_index: usize,
+    filter: NodeFilter,
+    size_format: SizeFormat,
+    theme: &'a Theme,
+}
+
+impl<'a> TreemapView<'a> {
+    pub fn new(
+        tree: &'a DiskTree,
+        view_root: NodeId,
+        selected_index: usize,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            tree,
+            view_root,
+            selected_index,
+            filter: NodeFilter::default(),
+            size_format: SizeFormat::default(),
+            theme,
+        }
+    }
+
+    pub fn with_size_format(mut self, format: SizeFormat) -> Self {
+        self.size_format = format;
+        self
+    }
+
+    pub fn with_filter(mut self, filter: NodeFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+}
+
+impl Widget for TreemapView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 1 || area.width < 10 {
+            return;
+        }
+
+        let children = treemap_children(self.tree, self.view_root, self.filter);
+        if children.is_empty() {
+            let msg = "Nothing to show";
+            let x = area.x + (area.width.saturating_sub(msg.len() as u16)) / 2;
+            let y = area.y + area.height / 2;
+            buf.set_string(x, y, msg, Style::default().fg(self.theme.fg_dim));
+            return;
+        }
+
+        let total_size = self.tree.get(self.view_root).map(|n| n.size).unwrap_or(1);
+        let sizes: Vec<u64> = children
+            .iter()
+            .filter_map(|&id| self.tree.get(id).map(|n| n.size))
+            .collect();
+        let rects = squarify(&sizes, area);
+
+        for (i, (&node_id, rect)) in children.iter().zip(rects.iter()).enumerate() {
+            let node = match self.tree.get(node_id) {
+                Some(n) => n,
+                None => continue,
+            };
+            if rect.width == 0 || rect.height == 0 {
+                continue;
+            }
+
+            let is_cursor = i == self.selected_index;
+            let fill = if is_cursor {
+                self.theme.selection_bg
+            } else {
+                self.theme
+                    .size_color(size_percentage(node.size, total_size))
+            };
+            let text_color = if is_cursor {
+                self.theme.selection_fg
+            } else {
+                self.theme.bg
+            };
+
+            // Leave a one-cell gutter on the right and bottom to separate blocks
+            let inner_w = if rect.width > 1 { rect.width - 1 } else { 1 };
+            let inner_h = if rect.height > 1 { rect.height - 1 } else { 1 };
+            let block_style = Style::default().bg(fill).fg(text_color);
+            for dy in 0..inner_h {
+                for dx in 0..inner_w {
+                    buf.set_string(rect.x + dx, rect.y + dy, " ", block_style);
+                }
+            }
+
+            // Name and size, if they fit
+            let label_width = inner_w as usize;
+            if label_width >= 3 {
+                let name = truncate_right(&node.name, label_width);
+                buf.set_string(
+                    rect.x,
+                    rect.y,
+                    &name,
+                    block_style.add_modifier(Modifier::BOLD),
+                );
+
+                let size_str = self.size_format.format_short(node.size);
+                if inner_h >= 2 && size_str.len() <= label_width {
+                    buf.set_string(rect.x, rect.y + 1, &size_str, block_style);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use dux_core::{DiskTree, ScanConfig, Scanner};
+use notify::event::{MetadataKind, ModifyKind};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Quiet time after the last change before affected directories are
+/// rescanned, so a burst of writes (a build, an unpacked archive) costs one
+/// rescan rather than hundreds
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Filesystem watch on the scan root for `--watch`. Changed directories are
+/// collected, coalesced and rescanned in the background; the fresh
+/// subtrees are handed back to be swapped into the tree.
+pub struct Watch {
+    _watcher: RecommendedWatcher,
+    events: mpsc::Receiver<Event>,
+    config: ScanConfig,
+    /// Directories in the tree with changes not yet rescanned
+    pending: HashSet<PathBuf>,
+    last_event: Option<Instant>,
+    /// Rescan running in the background, if any
+    job: Option<mpsc::Receiver<Vec<(PathBuf, DiskTree)>>>,
+}
+
+impl Watch {
+    /// Start watching everything under `root`. Rescans use `config`, so
+    /// excludes and other scan settings still apply.
+    pub fn start(root: &Path, config: ScanConfig) -> notify::Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                let _ = tx.send(event);
+            }
+        })?;
+        watcher.watch(root, RecursiveMode::Recursive)?;
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            config,
+            pending: HashSet::new(),
+            last_event: None,
+            job: None,
+        })
+    }
+
+    /// Whether changes are waiting to be rescanned or a rescan is running
+    pub fn is_busy(&self) -> bool {
+        !self.pending.is_empty() || self.job.is_some()
+    }
+
+    /// Take in new change events, start a rescan once they have settled,
+    /// and return the subtrees of a finished one, keyed by directory path
+    pub fn poll(&mut self, tree: &DiskTree) -> Option<Vec<(PathBuf, DiskTree)>> {
+        while let Ok(event) = self.events.try_recv() {
+            if !is_change(&event.kind) && !event.need_rescan() {
+                continue;
+            }
+            self.last_event = Some(Instant::now());
+            if event.need_rescan() {
+                self.pending.insert(tree.root_path().to_path_buf());
+            }
+            for path in &event.paths {
+                if let Some(dir) = changed_dir(tree, path) {
+                    self.pending.insert(dir);
+                }
+            }
+        }
+
+        if let Some(job) = &self.job {
+            return match job.try_recv() {
+                Ok(results) => {
+                    self.job = None;
+                    Some(results)
+                }
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.job = None;
+                    None
+                }
+            };
+        }
+
+        let settled = self.last_event.is_some_and(|at| at.elapsed() >= DEBOUNCE);
+        if settled && !self.pending.is_empty() {
+            let dirs = coalesce(self.pending.drain());
+            log::debug!("watch: rescanning {} directories", dirs.len());
+            self.job = Some(rescan(dirs, self.config.clone()));
+        }
+        None
+    }
+}
+
+/// Scan each of `dirs` on a background thread; the fresh subtrees, keyed by
+/// directory path, arrive together once all are done. Directories that no
+/// longer exist are left out.
+pub fn rescan(dirs: Vec<PathBuf>, config: ScanConfig) -> mpsc::Receiver<Vec<(PathBuf, DiskTree)>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let results = dirs
+            .into_iter()
+            .filter(|dir| dir.is_dir())
+            .map(|dir| {
+                let (progress, handle) = Scanner::new(config.clone()).scan(dir.clone());
+                for _ in progress {}
+                (dir, handle.join())
+            })
+            .filter_map(|(dir, tree)| Some((dir, tree.ok()?)))
+            .collect();
+        let _ = tx.send(results);
+    });
+    rx
+}
+
+/// Events that can change sizes; reads and access-time updates (including
+/// the rescans' own) are ignored
+fn is_change(kind: &EventKind) -> bool {
+    !matches!(
+        kind,
+        EventKind::Access(_) | EventKind::Modify(ModifyKind::Metadata(MetadataKind::AccessTime))
+    )
+}
+
+/// The directory to rescan for a change at `path`: its nearest ancestor
+/// that is a directory in the tree. New entries aren't in the tree yet and
+/// removed ones are gone from disk, so the path itself is never used,
+/// except for the root.
+fn changed_dir(tree: &DiskTree, path: &Path) -> Option<PathBuf> {
+    let root = tree.root_path();
+    if path == root {
+        return Some(root.to_path_buf());
+    }
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(root))
+        .find_map(|dir| {
+            let id = tree.find_by_path(dir)?;
+            tree.get(id)?.kind.is_directory().then(|| dir.to_path_buf())
+        })
+}
+
+/// Drop directories that lie inside another one being rescanned
+fn coalesce(dirs: impl IntoIterator<Item = PathBuf>) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = dirs.into_iter().collect();
+    // Sorted, an ancestor comes before everything under it
+    dirs.sort();
+    let mut kept: Vec<PathBuf> = Vec::new();
+    for dir in dirs {
+        if !kept.iter().any(|parent| dir.starts_with(parent)) {
+            kept.push(dir);
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use dux_core::{NodeId, NodeKind};
+
+    use super::*;
+
+    #[test]
+    fn test_changes_map_to_tree_directories_and_coalesce() {
+        let mut tree = DiskTree::new(PathBuf::from("/w"));
+        let build = tree.add_sized("build", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("out", NodeKind::Directory, 0, build);
+
+        // A new file deep in a new directory maps to the closest known one
+        assert_eq!(
+            changed_dir(&tree, Path::new("/w/build/out/new/a.o")),
+            Some(PathBuf::from("/w/build/out"))
+        );
+        assert_eq!(
+            changed_dir(&tree, Path::new("/w/build/out")),
+            Some(PathBuf::from("/w/build"))
+        );
+        assert_eq!(
+            changed_dir(&tree, Path::new("/w")),
+            Some(PathBuf::from("/w"))
+        );
+        assert_eq!(changed_dir(&tree, Path::new("/elsewhere/x")), None);
+
+        let dirs = coalesce([
+            PathBuf::from("/w/build/out"),
+            PathBuf::from("/w/build"),
+            PathBuf::from("/w/docs"),
+            PathBuf::from("/w/build-cache"),
+        ]);
+        assert_eq!(
+            dirs,
+            [
+                PathBuf::from("/w/build"),
+                PathBuf::from("/w/build-cache"),
+                PathBuf::from("/w/docs"),
+            ]
+        );
+    }
+}
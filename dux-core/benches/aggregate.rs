@@ -0,0 +1,62 @@
+//! Size aggregation on a synthetic 5M-node tree.
+//!
+//! Run with `cargo bench -p dux-core --bench aggregate`.
+
+use std::path::PathBuf;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use dux_core::{DiskTree, NodeId, NodeKind};
+
+/// Directories fan out 10 wide; each leaf directory holds 40 files
+fn synthetic_tree(target_nodes: usize) -> DiskTree {
+    let root = PathBuf::from("/bench");
+    let mut tree = DiskTree::new(root.clone());
+    let mut frontier = vec![(NodeId::ROOT, root)];
+
+    while tree.len() < target_nodes {
+        let mut next = Vec::new();
+        for (parent, path) in frontier {
+            for d in 0..10 {
+                let dir_path = path.join(format!("d{}", d));
+                let dir = tree.add_node(
+                    format!("d{}", d),
+                    NodeKind::Directory,
+                    dir_path.clone(),
+                    parent,
+                );
+                for f in 0..40 {
+                    let file = tree.add_node(
+                        format!("f{}", f),
+                        NodeKind::File,
+                        dir_path.join(format!("f{}", f)),
+                        dir,
+                    );
+                    tree.set_size(file, 4096 * (f as u64 + 1));
+                }
+                next.push((dir, dir_path));
+                if tree.len() >= target_nodes {
+                    return tree;
+                }
+            }
+        }
+        frontier = next;
+    }
+    tree
+}
+
+fn bench_aggregate(c: &mut Criterion) {
+    let tree = synthetic_tree(5_000_000);
+    let mut group = c.benchmark_group("aggregate_sizes");
+    group.sample_size(10);
+    group.bench_function("5M nodes", |b| {
+        b.iter_batched(
+            || tree.clone(),
+            |mut t| t.aggregate_sizes(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_aggregate);
+criterion_main!(benches);
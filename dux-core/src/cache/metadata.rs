@@ -4,7 +4,7 @@ use std::time::SystemTime;
 use serde::{Deserialize, Serialize};
 
 /// Current cache format version - increment when format changes
-pub const CACHE_VERSION: u32 = 3;
+pub const CACHE_VERSION: u32 = 16;
 
 /// Magic bytes identifying a DUX cache file
 pub const CACHE_MAGIC: [u8; 4] = *b"DUXC";
@@ -26,6 +26,9 @@ pub struct CacheMetadata {
     pub node_count: usize,
     /// Scan configuration used
     pub config: CachedScanConfig,
+    /// Whether the scan was cancelled or timed out before finishing, so the
+    /// tree holds only what was found up to then
+    pub partial: bool,
 }
 
 /// Scan configuration that affects cache validity
@@ -33,8 +36,20 @@ pub struct CacheMetadata {
 pub struct CachedScanConfig {
     /// Whether symlinks were followed during scan
     pub follow_symlinks: bool,
+    /// Whether symlinks were sized by their targets
+    pub size_symlinks: bool,
     /// Whether scan stayed on same filesystem
     pub same_filesystem: bool,
     /// Maximum depth that was scanned
     pub max_depth: Option<usize>,
+    /// Whether apparent sizes were recorded
+    pub track_apparent: bool,
+    /// Extra filesystems allowed alongside the root's
+    pub extra_filesystems: Vec<PathBuf>,
+    /// Depth below which subdirectories were sampled, if the scan sampled
+    pub sample_depth: Option<usize>,
+    /// Names and paths left out of the scan
+    pub exclude: Vec<PathBuf>,
+    /// Whether directories' own blocks were counted
+    pub count_dir_overhead: bool,
 }
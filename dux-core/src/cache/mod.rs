@@ -67,12 +67,17 @@ pub fn save_cache(path: &Path, tree: &DiskTree, meta: &CacheMetadata) -> Result<
 
     // Write atomically by writing to temp file then renaming
     let temp_path = path.with_extension("tmp");
-    let mut file = File::create(&temp_path)?;
-    file.write_all(&data)?;
-    file.sync_all()?;
-    drop(file);
-
-    fs::rename(&temp_path, path)?;
+    let written = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(&data)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp_path, path));
+    if written.is_err() {
+        // Don't leave a half-written temp file behind
+        let _ = fs::remove_file(&temp_path);
+    }
+    written?;
 
     Ok(())
 }
@@ -152,6 +157,11 @@ pub fn load_cache(path: &Path) -> Result<(CacheMetadata, DiskTree)> {
 
 /// Check if a cache is still valid for the given configuration
 pub fn is_cache_valid(meta: &CacheMetadata, root: &Path, config: &CachedScanConfig) -> bool {
+    // An unfinished scan never stands in for a full one
+    if meta.partial {
+        return false;
+    }
+
     // Config must match
     if meta.config != *config {
         return false;
@@ -188,19 +198,21 @@ pub fn get_mtime(path: &Path) -> Option<SystemTime> {
 ///
 /// Collects all directory nodes with stored mtimes, sorts by size descending
 /// (largest dirs are most impactful if stale), and stats the top `limit` entries.
+/// Directories under any of `skip` (ones rescanned anyway) aren't checked.
 /// Returns `true` if all checked mtimes match (cache is likely valid).
-pub fn spot_check_mtimes(tree: &crate::tree::DiskTree, limit: usize) -> bool {
+pub fn spot_check_mtimes(tree: &crate::tree::DiskTree, limit: usize, skip: &[PathBuf]) -> bool {
     use crate::tree::NodeKind;
 
     // Collect (size, path, stored_mtime) for all directories with mtimes
     let mut dirs: Vec<(u64, &Path, SystemTime)> = tree
         .iter()
         .filter(|n| n.kind == NodeKind::Directory)
+        .filter(|n| !skip.iter().any(|dir| n.path.starts_with(dir)))
         .filter_map(|n| n.mtime.map(|mt| (n.size, n.path.as_path(), mt)))
         .collect();
 
     // Sort by size descending — largest dirs cover the most of the tree
-    dirs.sort_by(|a, b| b.0.cmp(&a.0));
+    dirs.sort_by_key(|d| std::cmp::Reverse(d.0));
 
     for (_, path, stored_mtime) in dirs.into_iter().take(limit) {
         match fs::metadata(path).and_then(|m| m.modified()) {
@@ -247,9 +259,16 @@ mod tests {
             node_count: 1,
             config: CachedScanConfig {
                 follow_symlinks: false,
+                size_symlinks: false,
                 same_filesystem: true,
                 max_depth: None,
+                track_apparent: false,
+                extra_filesystems: Vec::new(),
+                sample_depth: None,
+                exclude: Vec::new(),
+                count_dir_overhead: false,
             },
+            partial: false,
         };
 
         // Save
@@ -259,9 +278,40 @@ mod tests {
         let (loaded_meta, loaded_tree) = load_cache(&cache_path).unwrap();
 
         assert_eq!(loaded_meta.total_size, 1024);
+        assert!(!loaded_meta.partial);
         assert_eq!(loaded_tree.len(), 1);
     }
 
+    #[test]
+    fn test_partial_cache_is_not_valid() {
+        let temp = TempDir::new().unwrap();
+        let config = CachedScanConfig {
+            follow_symlinks: false,
+            size_symlinks: false,
+            same_filesystem: true,
+            max_depth: None,
+            track_apparent: false,
+            extra_filesystems: Vec::new(),
+            sample_depth: None,
+            exclude: Vec::new(),
+            count_dir_overhead: false,
+        };
+        let mut meta = CacheMetadata {
+            version: CACHE_VERSION,
+            root_path: temp.path().to_path_buf(),
+            scan_time: SystemTime::now(),
+            root_mtime: get_mtime(temp.path()).unwrap(),
+            total_size: 0,
+            node_count: 1,
+            config: config.clone(),
+            partial: false,
+        };
+        assert!(is_cache_valid(&meta, temp.path(), &config));
+
+        meta.partial = true;
+        assert!(!is_cache_valid(&meta, temp.path(), &config));
+    }
+
     #[test]
     fn test_paths_reconstructed_after_load() {
         use crate::tree::NodeKind;
@@ -301,9 +351,16 @@ mod tests {
             node_count: 3,
             config: CachedScanConfig {
                 follow_symlinks: false,
+                size_symlinks: false,
                 same_filesystem: true,
                 max_depth: None,
+                track_apparent: false,
+                extra_filesystems: Vec::new(),
+                sample_depth: None,
+                exclude: Vec::new(),
+                count_dir_overhead: false,
             },
+            partial: false,
         };
 
         // Save and reload
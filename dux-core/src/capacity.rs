@@ -0,0 +1,23 @@
+use std::path::Path;
+
+/// Total and available bytes of the filesystem containing `path`, or None
+/// when the filesystem can't be queried. Available space is what an
+/// unprivileged user can still write, which can be less than the raw free
+/// count on filesystems that reserve blocks for root.
+pub fn fs_capacity(path: &Path) -> Option<(u64, u64)> {
+    let stats = fs4::statvfs(path).ok()?;
+    Some((stats.total_space(), stats.available_space()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fs_capacity_of_temp_dir() {
+        let (total, available) = fs_capacity(&std::env::temp_dir()).unwrap();
+        assert!(total > 0);
+        assert!(available <= total);
+        assert!(fs_capacity(Path::new("/definitely/not/a/real/path")).is_none());
+    }
+}
@@ -20,6 +20,9 @@ pub enum DuxError {
 
     #[error("Cache error: {0}")]
     Cache(String),
+
+    #[error("Invalid size '{0}' (expected e.g. 1024, 500K, 1.5M, 2GiB)")]
+    InvalidSize(String),
 }
 
 pub type Result<T> = std::result::Result<T, DuxError>;
@@ -0,0 +1,90 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::tree::{DiskTree, NodeId};
+
+/// One exported line
+#[derive(Serialize)]
+struct Record<'a> {
+    path: &'a str,
+    size: u64,
+    kind: &'static str,
+    depth: u16,
+    /// Only written for sizes a sampled scan extrapolated
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    estimated: bool,
+}
+
+/// Write every node as one JSON object per line (`path`, `size`, `kind`,
+/// `depth`, plus `estimated` on sampled sizes), parents before children and children largest first. Nodes are
+/// serialized one at a time, so memory use doesn't grow with the tree.
+/// Returns the number of lines written; errors such as a closed pipe are
+/// passed through for the caller to handle.
+pub fn write_jsonl<W: Write>(mut out: W, tree: &DiskTree) -> io::Result<u64> {
+    let mut written = 0;
+    let mut stack = vec![NodeId::ROOT];
+    while let Some(id) = stack.pop() {
+        let Some(node) = tree.get(id) else {
+            continue;
+        };
+        let path = node.path.to_string_lossy();
+        let record = Record {
+            path: &path,
+            size: node.size,
+            kind: node.kind.label(),
+            depth: node.depth,
+            estimated: node.estimated,
+        };
+        serde_json::to_writer(&mut out, &record)?;
+        out.write_all(b"\n")?;
+        written += 1;
+        stack.extend(node.children.iter().rev());
+    }
+    out.flush()?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::tree::NodeKind;
+
+    #[test]
+    fn test_write_jsonl() {
+        let mut tree = DiskTree::new(PathBuf::from("/r"));
+        let dir = tree.add_sized("a \"b\"", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("f", NodeKind::File, 4096, dir);
+        tree.aggregate_sizes();
+
+        let mut out = Vec::new();
+        assert_eq!(write_jsonl(&mut out, &tree).unwrap(), 3);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            concat!(
+                "{\"path\":\"/r\",\"size\":4096,\"kind\":\"directory\",\"depth\":0}\n",
+                "{\"path\":\"/r/a \\\"b\\\"\",\"size\":4096,\"kind\":\"directory\",\"depth\":1}\n",
+                "{\"path\":\"/r/a \\\"b\\\"/f\",\"size\":4096,\"kind\":\"file\",\"depth\":2}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_write_jsonl_passes_broken_pipe_through() {
+        struct ClosedPipe;
+        impl Write for ClosedPipe {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let tree = DiskTree::new(PathBuf::from("/r"));
+        let err = write_jsonl(ClosedPipe, &tree).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+}
@@ -1,5 +1,8 @@
 pub mod cache;
+pub mod capacity;
 pub mod error;
+pub mod jsonl;
+pub mod mounts;
 pub mod scanner;
 pub mod size;
 pub mod tree;
@@ -8,7 +11,15 @@ pub use cache::{
     CACHE_MAGIC, CACHE_VERSION, CacheMetadata, CachedScanConfig, cache_path_for, get_mtime,
     is_cache_valid, load_cache, save_cache, spot_check_mtimes,
 };
+pub use capacity::fs_capacity;
 pub use error::{DuxError, Result};
-pub use scanner::{CancellationToken, ScanConfig, ScanMessage, ScanProgress, Scanner};
-pub use size::{format_count, format_size, format_size_short, size_percentage};
-pub use tree::{DiskTree, NodeId, NodeKind, TreeNode};
+pub use jsonl::write_jsonl;
+pub use mounts::fs_type;
+pub use scanner::{
+    CancellationToken, SIZE_STRATEGY, ScanConfig, ScanMessage, ScanProgress, Scanner,
+};
+pub use size::{
+    SizeFormat, SizeUnits, format_count, format_size, format_size_prec, format_size_short,
+    format_size_short_si, format_size_si, format_size_si_prec, parse_human_size, size_percentage,
+};
+pub use tree::{DiskTree, LinkStatus, NodeId, NodeKind, TreeNode};
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Filesystem types that hold no disk usage of their own (kernel views,
+/// memory-backed scratch space) and are never worth descending into
+const PSEUDO_TYPES: &[&str] = &[
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "proc",
+    "pstore",
+    "securityfs",
+    "sysfs",
+    "tmpfs",
+    "tracefs",
+];
+
+/// Network and FUSE types that may hang or take minutes to list
+const REMOTE_TYPES: &[&str] = &[
+    "9p",
+    "afs",
+    "ceph",
+    "cifs",
+    "davfs",
+    "glusterfs",
+    "ncpfs",
+    "nfs",
+    "nfs4",
+    "smb3",
+    "smbfs",
+    "sshfs",
+];
+
+/// Mount points and their filesystem types, as listed in `/proc/mounts`.
+/// Empty on platforms without it, where lookups find nothing.
+#[derive(Debug, Default, Clone)]
+pub struct MountTable {
+    mounts: HashMap<PathBuf, String>,
+}
+
+impl MountTable {
+    /// Mounts of the running system
+    pub fn load() -> Self {
+        if cfg!(target_os = "linux") {
+            std::fs::read_to_string("/proc/mounts")
+                .map(|text| Self::parse(&text))
+                .unwrap_or_default()
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Parse the `/proc/mounts` format: device, mount point, type and
+    /// options per line, with spaces and the like in paths octal-escaped.
+    /// Later mounts on the same point shadow earlier ones.
+    fn parse(text: &str) -> Self {
+        let mounts = text
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let _device = fields.next()?;
+                let point = unescape(fields.next()?);
+                let fs_type = fields.next()?;
+                Some((PathBuf::from(point), fs_type.to_string()))
+            })
+            .collect();
+        Self { mounts }
+    }
+
+    /// Type of the filesystem `path` lives on: that of its closest mount
+    /// point. `path` is taken as is, so it should be absolute and free of
+    /// symlinks.
+    pub fn fs_type(&self, path: &Path) -> Option<&str> {
+        path.ancestors()
+            .find_map(|dir| self.mounts.get(dir))
+            .map(String::as_str)
+    }
+
+    /// Type of the filesystem mounted at `path` when it's one the scan
+    /// should stay out of: a pseudo, network or FUSE filesystem mounted
+    /// below `root_path`. Mounts at or above the root never count, so
+    /// scanning inside one still works. A mount holding one of `allowed`
+    /// (`--also-scan`) is always entered, and with `cross_filesystems`
+    /// only network and FUSE mounts are skipped.
+    pub fn skipped_mount(
+        &self,
+        path: &Path,
+        root_path: &Path,
+        cross_filesystems: bool,
+        allowed: &[PathBuf],
+    ) -> Option<&str> {
+        if path == root_path || !path.starts_with(root_path) {
+            return None;
+        }
+        let fs_type = self.mounts.get(path)?.as_str();
+        if allowed.iter().any(|extra| extra.starts_with(path)) {
+            return None;
+        }
+        let skipped = is_remote_type(fs_type) || (!cross_filesystems && is_pseudo_type(fs_type));
+        skipped.then_some(fs_type)
+    }
+}
+
+/// Whether `fs_type` holds no disk usage of its own
+fn is_pseudo_type(fs_type: &str) -> bool {
+    PSEUDO_TYPES.contains(&fs_type)
+}
+
+/// Whether `fs_type` is a network or FUSE filesystem that may hang
+fn is_remote_type(fs_type: &str) -> bool {
+    // FUSE mounts are listed as `fuse.<program>`, e.g. `fuse.sshfs`
+    let base = fs_type.strip_prefix("fuse.").unwrap_or(fs_type);
+    REMOTE_TYPES.contains(&base)
+}
+
+/// Undo the `\040`-style octal escapes `/proc/mounts` uses for whitespace
+/// and backslashes in paths
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = bytes
+            .get(i + 1..i + 4)
+            .filter(|digits| bytes[i] == b'\\' && digits.iter().all(|d| (b'0'..=b'7').contains(d)));
+        match escape {
+            Some(digits) => {
+                let value = digits
+                    .iter()
+                    .fold(0u32, |acc, d| acc * 8 + (d - b'0') as u32);
+                out.push(value as u8);
+                i += 4;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Type of the filesystem `path` lives on, e.g. `ext4` or `nfs4`, or None
+/// when it can't be told (including on platforms without `/proc/mounts`)
+pub fn fs_type(path: &Path) -> Option<String> {
+    let path = path.canonicalize().ok()?;
+    MountTable::load().fs_type(&path).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTS: &str = "\
+/dev/sda1 / ext4 rw,relatime 0 0
+proc /proc proc rw,nosuid 0 0
+tmpfs /tmp tmpfs rw 0 0
+server:/export /srv/data nfs4 rw 0 0
+proc /srv/data/chroot/proc proc rw 0 0
+sshfs#me@host: /home/me/remote\\040box fuse.sshfs rw 0 0
+";
+
+    #[test]
+    fn test_mount_table_types_and_skips() {
+        let table = MountTable::parse(MOUNTS);
+        assert_eq!(table.fs_type(Path::new("/home/me/src")), Some("ext4"));
+        assert_eq!(table.fs_type(Path::new("/srv/data/a/b")), Some("nfs4"));
+        assert_eq!(
+            table.fs_type(Path::new("/home/me/remote box/x")),
+            Some("fuse.sshfs")
+        );
+
+        let root = Path::new("/");
+        assert_eq!(
+            table.skipped_mount(Path::new("/proc"), root, false, &[]),
+            Some("proc")
+        );
+        assert_eq!(
+            table.skipped_mount(Path::new("/home/me/remote box"), root, false, &[]),
+            Some("fuse.sshfs")
+        );
+        assert_eq!(
+            table.skipped_mount(Path::new("/home/me"), root, false, &[]),
+            None
+        );
+        // Only mount points themselves are reported
+        assert_eq!(
+            table.skipped_mount(Path::new("/srv/data/x"), root, false, &[]),
+            None
+        );
+
+        // Scanning inside a skipped type still descends, but not into
+        // pseudo mounts below the root
+        let root = Path::new("/srv/data");
+        assert_eq!(table.skipped_mount(root, root, false, &[]), None);
+        assert_eq!(
+            table.skipped_mount(Path::new("/tmp"), root, false, &[]),
+            None
+        );
+        assert_eq!(
+            table.skipped_mount(Path::new("/srv/data/chroot/proc"), root, false, &[]),
+            Some("proc")
+        );
+    }
+
+    #[test]
+    fn test_skipped_mount_follows_scan_settings() {
+        let table = MountTable::parse(MOUNTS);
+        let root = Path::new("/");
+        let nas = Path::new("/srv/data");
+        assert_eq!(table.skipped_mount(nas, root, false, &[]), Some("nfs4"));
+
+        // --also-scan on the mount, or on a path inside it, lets it in
+        let allowed = [PathBuf::from("/srv/data")];
+        assert_eq!(table.skipped_mount(nas, root, false, &allowed), None);
+        let allowed = [PathBuf::from("/srv/data/photos")];
+        assert_eq!(table.skipped_mount(nas, root, false, &allowed), None);
+        let allowed = [PathBuf::from("/srv")];
+        assert_eq!(
+            table.skipped_mount(nas, root, false, &allowed),
+            Some("nfs4")
+        );
+
+        // Crossing filesystems enters tmpfs and the like, never remote mounts
+        assert_eq!(
+            table.skipped_mount(Path::new("/tmp"), root, true, &[]),
+            None
+        );
+        assert_eq!(table.skipped_mount(nas, root, true, &[]), Some("nfs4"));
+        assert_eq!(
+            table.skipped_mount(Path::new("/home/me/remote box"), root, true, &[]),
+            Some("fuse.sshfs")
+        );
+    }
+}
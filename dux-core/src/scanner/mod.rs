@@ -2,4 +2,4 @@ mod progress;
 mod walker;
 
 pub use progress::{ScanMessage, ScanProgress};
-pub use walker::{CancellationToken, ScanConfig, Scanner};
+pub use walker::{CancellationToken, SIZE_STRATEGY, ScanConfig, Scanner};
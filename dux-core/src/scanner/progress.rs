@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use crate::tree::DiskTree;
+
 /// Progress update during scanning
 #[derive(Debug, Clone)]
 pub enum ScanMessage {
@@ -7,14 +9,24 @@ pub enum ScanMessage {
     StartedDirectory(PathBuf),
     /// Progress update
     Progress(ScanProgress),
+    /// Snapshot of the root's direct children with running totals, sent
+    /// periodically so the UI can show the tree filling in
+    PartialTree(DiskTree),
     /// Finalizing (aggregating sizes, sorting)
     Finalizing,
+    /// How far finalizing has got, in steps, so a large tree shows movement
+    /// while it is summed and sorted
+    FinalizingProgress { done: usize, total: usize },
     /// Scan completed
     Completed,
-    /// Scan was cancelled
-    Cancelled,
+    /// Scan was cancelled, by the user or (with `timed_out`) by
+    /// `ScanConfig::scan_timeout`. The partial tree is still returned,
+    /// aggregated and sorted
+    Cancelled { timed_out: bool },
     /// Error during scanning
     Error(String),
+    /// A path could not be read; its size is missing from the totals
+    PathError { path: PathBuf, reason: String },
 }
 
 /// Scanning progress statistics
@@ -30,10 +42,22 @@ pub struct ScanProgress {
     pub errors: u64,
     /// Current directory being scanned
     pub current_path: Option<PathBuf>,
+    /// Whether the entry-counting pre-pass is still running
+    pub counting: bool,
+    /// Entries the pre-count pass found, when one was run
+    pub expected_entries: Option<u64>,
+    /// Whether the scan is paused
+    pub paused: bool,
 }
 
 impl ScanProgress {
     pub fn total_entries(&self) -> u64 {
         self.files_scanned + self.dirs_scanned
     }
+
+    /// Percentage of the pre-counted entries scanned so far, capped at 100
+    pub fn percent_complete(&self) -> Option<f64> {
+        let expected = self.expected_entries.filter(|&n| n > 0)?;
+        Some((self.total_entries() as f64 / expected as f64 * 100.0).min(100.0))
+    }
 }
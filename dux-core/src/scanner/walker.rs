@@ -1,56 +1,124 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::fs::Metadata;
-use std::path::{Path, PathBuf};
+use std::hash::{BuildHasher, BuildHasherDefault, DefaultHasher};
+use std::path::{Component, Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[cfg(unix)]
 use std::os::unix::fs::MetadataExt;
 
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use jwalk::WalkDir;
+use rayon::prelude::*;
 
 use super::progress::{ScanMessage, ScanProgress};
-use crate::tree::{DiskTree, NodeId, NodeKind};
+use crate::mounts::MountTable;
+use crate::tree::{DiskTree, LinkStatus, NodeId, NodeKind, TreeNode};
 
 /// Scanner configuration
 #[derive(Debug, Clone)]
 pub struct ScanConfig {
-    /// Follow symbolic links
+    /// Follow symbolic links, descending into linked directories as if they
+    /// were part of the tree
     pub follow_symlinks: bool,
+    /// Give each symlink the disk usage of the file it points to, without
+    /// descending. Targets inside the scan root that the scan counted keep the
+    /// symlink at zero; ones it left out (excluded, past the depth limit) are
+    /// counted. A target reached through several links counts once.
+    /// Has no effect with `follow_symlinks`, where links are walked instead.
+    pub size_symlinks: bool,
     /// Maximum depth to scan (None = unlimited)
     pub max_depth: Option<usize>,
     /// Stay on same filesystem (don't cross mount points)
     pub same_filesystem: bool,
+    /// Paths whose filesystems may also be entered when `same_filesystem`
+    /// is set. A path that can't be stat'd adds nothing and is reported as
+    /// a scan error.
+    pub extra_filesystems: Vec<PathBuf>,
     /// Number of parallel threads (0 = auto)
     pub num_threads: usize,
+    /// Record each file's logical length alongside its disk usage
+    pub track_apparent: bool,
+    /// Stop the scan after this long and keep what was found so far
+    pub scan_timeout: Option<Duration>,
+    /// Sample instead of walking everything: a directory at this depth or
+    /// deeper descends into at most `SAMPLE_DIRS` of its subdirectories. The
+    /// rest are not read; each is given the mean size of its walked siblings
+    /// and marked `estimated`. Files are always counted exactly.
+    ///
+    /// The sample is picked by hashing names, so it is the same on every run
+    /// but unrelated to size. That makes the estimate unbiased on average,
+    /// yet a single huge directory dominates: left out, its parent is
+    /// underestimated; sampled, its size is credited to every skipped
+    /// sibling. Treat sampled totals as a first look, not an answer.
+    pub sample_depth: Option<usize>,
+    /// Entries skipped along with everything below them. A bare name
+    /// (`.git`) matches entries of that name anywhere; a longer path matches
+    /// only that path.
+    pub exclude: Vec<PathBuf>,
+    /// Count each directory's own blocks (its entry table) in its size, as
+    /// `du` does. Off by default, so directory sizes are the sum of their
+    /// contents.
+    pub count_dir_overhead: bool,
 }
 
 impl Default for ScanConfig {
     fn default() -> Self {
         Self {
             follow_symlinks: false,
+            size_symlinks: false,
             max_depth: None,
             same_filesystem: true,
+            extra_filesystems: Vec::new(),
             num_threads: 0, // auto
+            track_apparent: false,
+            scan_timeout: None,
+            sample_depth: None,
+            exclude: Vec::new(),
+            count_dir_overhead: false,
         }
     }
 }
 
-/// Cancellation token for stopping scans
+impl ScanConfig {
+    /// Threads the walk will actually use: `num_threads`, or Rayon's
+    /// default pool size when it is 0
+    pub fn effective_threads(&self) -> usize {
+        if self.num_threads > 0 {
+            self.num_threads
+        } else {
+            rayon::current_num_threads()
+        }
+    }
+}
+
+/// Cancellation token for stopping scans. It also pauses them: the walk
+/// waits in place while paused and resumes where it stopped.
 #[derive(Debug, Clone)]
 pub struct CancellationToken {
     cancelled: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
 }
 
 impl CancellationToken {
     pub fn new() -> Self {
         Self {
             cancelled: Arc::new(AtomicBool::new(false)),
+            paused: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
     pub fn cancel(&self) {
         self.cancelled.store(true, Ordering::SeqCst);
     }
@@ -66,6 +134,15 @@ impl Default for CancellationToken {
     }
 }
 
+/// Subdirectories a sampled directory descends into; see `sample_depth`
+const SAMPLE_DIRS: usize = 8;
+
+/// Directories a sampled walk left unread, shared with `process_read_dir`
+type Unsampled = Arc<Mutex<HashSet<PathBuf>>>;
+
+/// How often a partial tree snapshot is sent while scanning
+const PARTIAL_TREE_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Shared progress state for heartbeat updates
 struct SharedProgress {
     files_scanned: AtomicU64,
@@ -73,6 +150,10 @@ struct SharedProgress {
     bytes_scanned: AtomicU64,
     errors: AtomicU64,
     current_path: Mutex<Option<PathBuf>>,
+    counting: AtomicBool,
+    timed_out: AtomicBool,
+    /// Entries found by the pre-count pass (0 = not counted)
+    expected_entries: AtomicU64,
     done: AtomicBool,
 }
 
@@ -84,6 +165,9 @@ impl SharedProgress {
             bytes_scanned: AtomicU64::new(0),
             errors: AtomicU64::new(0),
             current_path: Mutex::new(None),
+            counting: AtomicBool::new(false),
+            timed_out: AtomicBool::new(false),
+            expected_entries: AtomicU64::new(0),
             done: AtomicBool::new(false),
         }
     }
@@ -95,6 +179,12 @@ impl SharedProgress {
             bytes_scanned: self.bytes_scanned.load(Ordering::Relaxed),
             errors: self.errors.load(Ordering::Relaxed),
             current_path: self.current_path.lock().ok().and_then(|g| g.clone()),
+            counting: self.counting.load(Ordering::Relaxed),
+            expected_entries: match self.expected_entries.load(Ordering::Relaxed) {
+                0 => None,
+                n => Some(n),
+            },
+            paused: false,
         }
     }
 }
@@ -119,6 +209,18 @@ const SLOW_PATTERNS: &[&str] = &[
     "/Library/Mobile Documents", // iCloud Drive documents
 ];
 
+/// Whether `path` matches an `exclude` entry: by name for a bare name, or
+/// exactly for anything longer
+fn is_excluded(path: &Path, exclude: &[PathBuf]) -> bool {
+    exclude.iter().any(|pattern| {
+        let mut components = pattern.components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(name)), None) => path.file_name() == Some(name),
+            _ => path == pattern,
+        }
+    })
+}
+
 /// Check if a path looks like a virtual/problematic filesystem path
 /// Only returns true if the path contains a slow pattern AND is not under the root path
 fn is_virtual_or_slow_path(path: &std::path::Path, root_path: &std::path::Path) -> bool {
@@ -159,10 +261,318 @@ fn metadata_with_timeout(path: &Path) -> Option<Metadata> {
     }
 }
 
+/// Walker over `root_path` that applies the scan's skip, depth, filesystem
+/// and parallelism rules. Shared by the scan and the counting pre-pass so
+/// both see the same entries. Directories left out by sampling are recorded
+/// in `unsampled`. While `cancel` is paused no further directory is read.
+fn build_walker(
+    root_path: &Path,
+    config: &ScanConfig,
+    allowed_devs: Vec<u64>,
+    unsampled: Unsampled,
+    cancel: CancellationToken,
+) -> WalkDir {
+    // Configure walker with process_read_dir to skip problematic directories
+    let same_fs = config.same_filesystem;
+    let sample_depth = config.sample_depth;
+    let exclude = config.exclude.clone();
+    let root_for_filter = root_path.to_path_buf();
+    let mounts = MountTable::load();
+    let extra_filesystems = config.extra_filesystems.clone();
+    let walker = WalkDir::new(root_path)
+        .skip_hidden(false)
+        .follow_links(config.follow_symlinks)
+        .sort(false) // We'll sort by size later
+        .process_read_dir(move |depth, path, _read_dir_state, children| {
+            // Hold the walker's threads here, so a pause stops the disk IO
+            // and not just the consumer
+            while cancel.is_paused() && !cancel.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            let started = Instant::now();
+            // Skip children in virtual/slow directories
+            if is_virtual_or_slow_path(path, &root_for_filter) {
+                log::debug!("skip contents of {}: virtual or slow path", path.display());
+                children.clear();
+                return;
+            }
+
+            children.retain(|entry| {
+                if let Ok(e) = entry {
+                    // Check if child path is virtual/slow
+                    if is_virtual_or_slow_path(&e.path(), &root_for_filter) {
+                        log::debug!("skip {}: virtual or slow path", e.path().display());
+                        return false;
+                    }
+                    if let Some(fs_type) = mounts.skipped_mount(
+                        &e.path(),
+                        &root_for_filter,
+                        !same_fs,
+                        &extra_filesystems,
+                    ) {
+                        log::debug!("skip {}: {} filesystem", e.path().display(), fs_type);
+                        return false;
+                    }
+                    if is_excluded(&e.path(), &exclude) {
+                        log::debug!("skip {}: excluded", e.path().display());
+                        return false;
+                    }
+
+                    // For directories, probe metadata with a timeout to detect
+                    // slow FUSE/network mounts before jwalk descends into them
+                    if e.file_type().is_dir() {
+                        match metadata_with_timeout(&e.path()) {
+                            Some(meta) if same_fs => {
+                                let allowed = allowed_devs.contains(&get_device_id(&meta));
+                                if !allowed {
+                                    log::debug!(
+                                        "skip {}: on another filesystem",
+                                        e.path().display()
+                                    );
+                                }
+                                return allowed;
+                            }
+                            None => {
+                                // Timed out — skip this subtree
+                                log::warn!(
+                                    "skip {}: metadata took longer than {:?}",
+                                    e.path().display(),
+                                    METADATA_TIMEOUT
+                                );
+                                return false;
+                            }
+                            _ => {}
+                        }
+                    } else if same_fs {
+                        // For files, use jwalk's cached metadata (already fetched)
+                        if let Ok(meta) = e.metadata()
+                            && !allowed_devs.contains(&get_device_id(&meta))
+                        {
+                            log::debug!("skip {}: on another filesystem", e.path().display());
+                            return false;
+                        }
+                    }
+                }
+                true
+            });
+
+            if sample_depth.is_some_and(|sample| depth.is_some_and(|d| d >= sample)) {
+                let skipped = sample_children(children);
+                if !skipped.is_empty() {
+                    log::debug!(
+                        "sampled {}: skipped {} subdirectories",
+                        path.display(),
+                        skipped.len()
+                    );
+                    if let Ok(mut unsampled) = unsampled.lock() {
+                        unsampled.extend(skipped);
+                    }
+                }
+            }
+            log::trace!(
+                "{}: {} entries kept in {:?}",
+                path.display(),
+                children.len(),
+                started.elapsed()
+            );
+        });
+
+    let walker = if let Some(depth) = config.max_depth {
+        walker.max_depth(depth)
+    } else {
+        walker
+    };
+
+    if config.num_threads > 0 {
+        walker.parallelism(jwalk::Parallelism::RayonNewPool(config.num_threads))
+    } else {
+        walker
+    }
+}
+
+/// Keep `SAMPLE_DIRS` of a directory's subdirectories, chosen by name hash,
+/// and stop the walk from descending into the others. Returns their paths.
+fn sample_children(children: &mut [jwalk::Result<jwalk::DirEntry<((), ())>>]) -> Vec<PathBuf> {
+    let mut dirs: Vec<(u64, &mut jwalk::DirEntry<((), ())>)> = children
+        .iter_mut()
+        .flatten()
+        .filter(|e| e.read_children_path.is_some())
+        .map(|e| {
+            (
+                BuildHasherDefault::<DefaultHasher>::default().hash_one(e.file_name()),
+                e,
+            )
+        })
+        .collect();
+    if dirs.len() <= SAMPLE_DIRS {
+        return Vec::new();
+    }
+    dirs.sort_unstable_by_key(|(hash, _)| *hash);
+    dirs.into_iter()
+        .skip(SAMPLE_DIRS)
+        .map(|(_, entry)| {
+            entry.read_children_path = None;
+            entry.path()
+        })
+        .collect()
+}
+
+/// Whether directory `dir` of a cached tree still matches the disk; see
+/// [`Scanner::verify_tree`]
+fn verify_dir(
+    tree: &DiskTree,
+    dir: &TreeNode,
+    config: &ScanConfig,
+    allowed_devs: &[u64],
+    mounts: &MountTable,
+) -> bool {
+    let mtime = std::fs::metadata(&dir.path).and_then(|meta| meta.modified());
+    if dir
+        .mtime
+        .is_none_or(|stored| !mtime.is_ok_and(|current| current == stored))
+    {
+        return false;
+    }
+    // Directories at the depth limit or left out by sampling were never listed
+    let at_depth_limit = config
+        .max_depth
+        .is_some_and(|max| dir.depth as usize >= max);
+    if at_depth_limit || (dir.estimated && dir.children.is_empty()) {
+        return true;
+    }
+    let Ok(entries) = std::fs::read_dir(&dir.path) else {
+        return false;
+    };
+
+    let mut expected: HashMap<&str, &TreeNode> = dir
+        .children
+        .iter()
+        .filter_map(|&child| tree.get(child))
+        .map(|child| (child.name.as_str(), child))
+        .collect();
+    let root = tree.root_path();
+    for entry in entries {
+        let Ok(entry) = entry else {
+            return false;
+        };
+        if let Some(child) = expected.remove(entry.file_name().to_string_lossy().as_ref()) {
+            // A file rewritten in place leaves its directory's mtime alone
+            if child.kind == NodeKind::File
+                && !entry.metadata().is_ok_and(|meta| {
+                    get_disk_usage(&meta) == child.size && meta.modified().ok() == child.mtime
+                })
+            {
+                log::debug!("cache verify: {} changed", child.path.display());
+                return false;
+            }
+            continue;
+        }
+        // Not in the tree: only fine if the scan would have skipped it too
+        let path = entry.path();
+        let skipped = is_excluded(&path, &config.exclude)
+            || is_virtual_or_slow_path(&path, root)
+            || mounts
+                .skipped_mount(
+                    &path,
+                    root,
+                    !config.same_filesystem,
+                    &config.extra_filesystems,
+                )
+                .is_some()
+            || (config.same_filesystem
+                && entry
+                    .metadata()
+                    .is_ok_and(|meta| !allowed_devs.contains(&get_device_id(&meta))));
+        if !skipped {
+            log::debug!("cache verify: {} is new", path.display());
+            return false;
+        }
+    }
+    if let Some(missing) = expected.keys().next() {
+        log::debug!("cache verify: {} is gone", dir.path.join(missing).display());
+    }
+    expected.is_empty()
+}
+
+/// Device IDs of the root and of each `extra_filesystems` path, plus the
+/// extra paths that could not be stat'd and why
+fn allowed_devices(root: &Path, config: &ScanConfig) -> (Vec<u64>, Vec<(PathBuf, String)>) {
+    let root_dev = std::fs::metadata(root)
+        .map(|m| get_device_id(&m))
+        .unwrap_or(0);
+    let mut devices = vec![root_dev];
+    let mut unreadable = Vec::new();
+    for path in &config.extra_filesystems {
+        match std::fs::metadata(path) {
+            Ok(meta) => devices.push(get_device_id(&meta)),
+            Err(e) => unreadable.push((path.clone(), e.to_string())),
+        }
+    }
+    (devices, unreadable)
+}
+
+/// Run a walker on its own thread and yield its entries until it finishes or
+/// `cancel` fires. A `read_dir` that never returns (e.g. on a hung network
+/// mount) then can't block cancellation; the walk thread is abandoned.
+fn walk_entries(
+    walker: WalkDir,
+    cancel: CancellationToken,
+) -> impl Iterator<Item = jwalk::Result<jwalk::DirEntry<((), ())>>> {
+    let (entry_tx, entry_rx) = crossbeam_channel::bounded(4096);
+    std::thread::spawn(move || {
+        for entry in walker {
+            if entry_tx.send(entry).is_err() {
+                break;
+            }
+        }
+    });
+
+    std::iter::from_fn(move || {
+        loop {
+            if cancel.is_cancelled() {
+                return None;
+            }
+            // Hold back while paused; the walker fills the channel and blocks
+            if cancel.is_paused() {
+                std::thread::sleep(Duration::from_millis(50));
+                continue;
+            }
+            match entry_rx.recv_timeout(Duration::from_millis(100)) {
+                Ok(entry) => return Some(entry),
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    })
+}
+
+/// Count the entries `build_walker` yields under `root`, stopping early if
+/// the scan is cancelled
+fn count_walk(root: &Path, config: &ScanConfig, cancel: &CancellationToken) -> u64 {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let (allowed_devs, _) = allowed_devices(&root, config);
+
+    let mut count = 0;
+    let walker = build_walker(
+        &root,
+        config,
+        allowed_devs,
+        Unsampled::default(),
+        cancel.clone(),
+    );
+    for entry in walk_entries(walker, cancel.clone()).flatten() {
+        if entry.depth > 0 {
+            count += 1;
+        }
+    }
+    count
+}
+
 /// Filesystem scanner
 pub struct Scanner {
     config: ScanConfig,
     cancel_token: CancellationToken,
+    pre_count: bool,
 }
 
 impl Scanner {
@@ -170,6 +580,7 @@ impl Scanner {
         Self {
             config,
             cancel_token: CancellationToken::new(),
+            pre_count: false,
         }
     }
 
@@ -178,6 +589,81 @@ impl Scanner {
         self
     }
 
+    /// Count entries before scanning so progress can report a percentage.
+    /// This walks the directory tree twice, roughly doubling traversal time.
+    pub fn with_pre_count(mut self, pre_count: bool) -> Self {
+        self.pre_count = pre_count;
+        self
+    }
+
+    /// Count the entries a scan of `root` with `config` would visit, without
+    /// reading sizes. The root itself is not counted.
+    pub fn count_entries(root: &Path, config: &ScanConfig) -> u64 {
+        count_walk(root, config, &CancellationToken::new())
+    }
+
+    /// The `n` largest files under `root` by disk usage, largest first. The
+    /// walk follows a scan's skip, depth and filesystem rules but builds no
+    /// tree, so memory stays at `n` entries however many files there are.
+    pub fn top_files(root: &Path, n: usize, config: &ScanConfig) -> Vec<(PathBuf, u64)> {
+        if n == 0 {
+            return Vec::new();
+        }
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let (allowed_devs, _) = allowed_devices(&root, config);
+        let walker = build_walker(
+            &root,
+            config,
+            allowed_devs,
+            Unsampled::default(),
+            CancellationToken::new(),
+        );
+
+        // Min-heap of the largest files so far; the smallest is evicted first
+        let mut heap: BinaryHeap<Reverse<(u64, PathBuf)>> = BinaryHeap::with_capacity(n + 1);
+        for entry in walk_entries(walker, CancellationToken::new()).flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let size = get_disk_usage(&metadata);
+            if heap.len() == n && heap.peek().is_some_and(|Reverse((min, _))| size <= *min) {
+                continue;
+            }
+            heap.push(Reverse((size, entry.path())));
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+
+        let mut files: Vec<_> = heap.into_iter().map(|Reverse(file)| file).collect();
+        files.sort_by(|(a_size, a_path), (b_size, b_path)| {
+            b_size.cmp(a_size).then_with(|| a_path.cmp(b_path))
+        });
+        files.into_iter().map(|(size, path)| (path, size)).collect()
+    }
+
+    /// Thorough check of a cached tree, for filesystems whose directory
+    /// mtimes can't be trusted (some network mounts): every directory must
+    /// keep its mtime and list exactly the entries the tree holds for it, and
+    /// every file keep its size and mtime. Entries a scan with `config` would
+    /// leave out don't count, nor do directories under any of `skip`. Each
+    /// directory is listed once, and each entry stat'd once.
+    pub fn verify_tree(tree: &DiskTree, config: &ScanConfig, skip: &[PathBuf]) -> bool {
+        let root = tree.root_path();
+        let (allowed_devs, _) = allowed_devices(root, config);
+        let mounts = MountTable::load();
+        let dirs: Vec<&TreeNode> = tree
+            .iter()
+            .filter(|node| node.kind == NodeKind::Directory)
+            .filter(|node| !skip.iter().any(|dir| node.path.starts_with(dir)))
+            .collect();
+        dirs.par_iter()
+            .all(|dir| verify_dir(tree, dir, config, &allowed_devs, &mounts))
+    }
+
     /// Scan a directory and build a tree
     /// Returns a receiver for progress updates and spawns scanning in background
     pub fn scan(
@@ -195,112 +681,104 @@ impl Scanner {
     fn scan_sync(self, root_path: PathBuf, tx: Sender<ScanMessage>) -> DiskTree {
         let root_path = root_path.canonicalize().unwrap_or(root_path);
         let mut tree = DiskTree::new(root_path.clone());
+        let started = Instant::now();
+        log::info!("scan {} with {:?}", root_path.display(), self.config);
 
-        // Set root mtime for cache invalidation
+        // Set root mtime for cache invalidation, plus ownership
         if let Ok(root_meta) = std::fs::metadata(&root_path)
-            && let Ok(mtime) = root_meta.modified()
             && let Some(root_node) = tree.get_mut(NodeId::ROOT)
         {
-            root_node.mtime = Some(mtime);
+            root_node.mtime = root_meta.modified().ok();
+            set_ownership(root_node, &root_meta);
+            if self.config.count_dir_overhead {
+                root_node.own_size = get_disk_usage(&root_meta);
+            }
         }
 
-        // Map from path to node ID for parent lookups
-        let mut path_to_id: HashMap<PathBuf, NodeId> = HashMap::new();
-        path_to_id.insert(root_path.clone(), NodeId::ROOT);
-
-        // Get root device for same-filesystem check
-        let root_dev = std::fs::metadata(&root_path)
-            .map(|m| get_device_id(&m))
-            .unwrap_or(0);
+        // Devices the same-filesystem check lets through
+        let (allowed_devs, unreadable) = allowed_devices(&root_path, &self.config);
+        for (path, reason) in unreadable {
+            log::warn!("cannot add filesystem of {}: {}", path.display(), reason);
+            let _ = tx.send(ScanMessage::PathError { path, reason });
+        }
 
         // Shared progress state
         let shared_progress = Arc::new(SharedProgress::new());
         let progress_for_heartbeat = Arc::clone(&shared_progress);
         let tx_for_heartbeat = tx.clone();
         let cancel_for_heartbeat = self.cancel_token.clone();
+        let scan_timeout = self.config.scan_timeout;
 
-        // Spawn heartbeat thread that sends progress every 100ms
+        // Spawn heartbeat thread that sends progress every 100ms and
+        // enforces the scan timeout
         let heartbeat_handle = std::thread::spawn(move || {
+            let mut active = Duration::ZERO;
+            let mut last_tick = Instant::now();
             while !progress_for_heartbeat.done.load(Ordering::Relaxed)
                 && !cancel_for_heartbeat.is_cancelled()
             {
                 std::thread::sleep(std::time::Duration::from_millis(100));
-                let progress = progress_for_heartbeat.to_scan_progress();
+                let mut progress = progress_for_heartbeat.to_scan_progress();
+                progress.paused = cancel_for_heartbeat.is_paused();
                 let _ = tx_for_heartbeat.send(ScanMessage::Progress(progress));
-            }
-        });
-
-        let _ = tx.send(ScanMessage::StartedDirectory(root_path.clone()));
 
-        // Configure walker with process_read_dir to skip problematic directories
-        let same_fs = self.config.same_filesystem;
-        let root_for_filter = root_path.clone();
-        let walker = WalkDir::new(&root_path)
-            .skip_hidden(false)
-            .follow_links(self.config.follow_symlinks)
-            .sort(false) // We'll sort by size later
-            .process_read_dir(move |_depth, path, _read_dir_state, children| {
-                // Skip children in virtual/slow directories
-                if is_virtual_or_slow_path(path, &root_for_filter) {
-                    children.clear();
-                    return;
+                // Time spent paused doesn't count toward the timeout
+                let now = Instant::now();
+                if !cancel_for_heartbeat.is_paused() {
+                    active += now - last_tick;
                 }
+                last_tick = now;
+                if scan_timeout.is_some_and(|limit| active >= limit) {
+                    progress_for_heartbeat
+                        .timed_out
+                        .store(true, Ordering::Relaxed);
+                    cancel_for_heartbeat.cancel();
+                }
+            }
+        });
 
-                children.retain(|entry| {
-                    if let Ok(e) = entry {
-                        // Check if child path is virtual/slow
-                        if is_virtual_or_slow_path(&e.path(), &root_for_filter) {
-                            return false;
-                        }
-
-                        // For directories, probe metadata with a timeout to detect
-                        // slow FUSE/network mounts before jwalk descends into them
-                        if e.file_type().is_dir() {
-                            match metadata_with_timeout(&e.path()) {
-                                Some(meta) if same_fs => {
-                                    return get_device_id(&meta) == root_dev;
-                                }
-                                None => return false, // Timed out — skip this subtree
-                                _ => {}
-                            }
-                        } else if same_fs {
-                            // For files, use jwalk's cached metadata (already fetched)
-                            if let Ok(meta) = e.metadata()
-                                && get_device_id(&meta) != root_dev
-                            {
-                                return false;
-                            }
-                        }
-                    }
-                    true
-                });
-            });
-
-        let walker = if let Some(depth) = self.config.max_depth {
-            walker.max_depth(depth)
-        } else {
-            walker
-        };
-
-        let walker = if self.config.num_threads > 0 {
-            walker.parallelism(jwalk::Parallelism::RayonNewPool(self.config.num_threads))
-        } else {
-            walker
-        };
+        if self.pre_count {
+            shared_progress.counting.store(true, Ordering::Relaxed);
+            let total = count_walk(&root_path, &self.config, &self.cancel_token);
+            shared_progress
+                .expected_entries
+                .store(total, Ordering::Relaxed);
+            shared_progress.counting.store(false, Ordering::Relaxed);
+        }
 
-        for entry_result in walker {
-            // Check for cancellation
-            if self.cancel_token.is_cancelled() {
-                shared_progress.done.store(true, Ordering::Relaxed);
-                let _ = heartbeat_handle.join();
-                let _ = tx.send(ScanMessage::Cancelled);
-                return tree;
-            }
+        let _ = tx.send(ScanMessage::StartedDirectory(root_path.clone()));
 
+        let unsampled = Unsampled::default();
+        let walker = build_walker(
+            &root_path,
+            &self.config,
+            allowed_devs.clone(),
+            Arc::clone(&unsampled),
+            self.cancel_token.clone(),
+        );
+
+        // Running size/file totals per top-level entry, for partial snapshots
+        let mut top_level_totals: HashMap<NodeId, (u64, u64)> = HashMap::new();
+        // The top-level entry each directory sits under, so an entry finds
+        // its own from its parent
+        let mut top_level_of: HashMap<NodeId, NodeId> = HashMap::new();
+        let mut last_snapshot = Instant::now();
+        // Symlink targets already sized, so each counts once
+        let mut sized_targets: HashSet<TargetId> = HashSet::new();
+        let mut links_inside: Vec<(NodeId, PathBuf)> = Vec::new();
+
+        for entry_result in walk_entries(walker, self.cancel_token.clone()) {
             let entry = match entry_result {
                 Ok(e) => e,
-                Err(_e) => {
+                Err(e) => {
                     shared_progress.errors.fetch_add(1, Ordering::Relaxed);
+                    let path = e.path().map(Path::to_path_buf).unwrap_or_default();
+                    let reason = e
+                        .io_error()
+                        .map(|io| io.to_string())
+                        .unwrap_or_else(|| e.to_string());
+                    log::warn!("error at {}: {}", path.display(), reason);
+                    let _ = tx.send(ScanMessage::PathError { path, reason });
                     continue;
                 }
             };
@@ -315,20 +793,26 @@ impl Scanner {
             // Get metadata
             let metadata = match entry.metadata() {
                 Ok(m) => m,
-                Err(_) => {
+                Err(e) => {
                     shared_progress.errors.fetch_add(1, Ordering::Relaxed);
+                    log::warn!("no metadata for {}: {}", path.display(), e);
+                    let _ = tx.send(ScanMessage::PathError {
+                        path: path.clone(),
+                        reason: e.to_string(),
+                    });
                     continue;
                 }
             };
 
             // Check filesystem boundary
-            if self.config.same_filesystem && get_device_id(&metadata) != root_dev {
+            if self.config.same_filesystem && !allowed_devs.contains(&get_device_id(&metadata)) {
+                log::debug!("skip {}: on another filesystem", path.display());
                 continue;
             }
 
             // Determine node kind
             let file_type = entry.file_type();
-            let kind = if file_type.is_dir() {
+            let mut kind = if file_type.is_dir() {
                 NodeKind::Directory
             } else if file_type.is_symlink() {
                 NodeKind::Symlink
@@ -336,15 +820,37 @@ impl Scanner {
                 NodeKind::File
             };
 
+            // A directory that can't be listed stays in the tree as an error
+            // node, so its ancestors are known to be undercounted
+            if kind == NodeKind::Directory
+                && let Some(e) = &entry.read_children_error
+            {
+                shared_progress.errors.fetch_add(1, Ordering::Relaxed);
+                let reason = e
+                    .io_error()
+                    .map(|io| io.to_string())
+                    .unwrap_or_else(|| e.to_string());
+                log::warn!("cannot list {}: {}", path.display(), reason);
+                let _ = tx.send(ScanMessage::PathError {
+                    path: path.clone(),
+                    reason,
+                });
+                kind = NodeKind::Error;
+            }
+
             // Get parent path and node ID
             let parent_path = match path.parent() {
                 Some(p) => p.to_path_buf(),
                 None => continue,
             };
 
-            let parent_id = match path_to_id.get(&parent_path) {
-                Some(&id) => id,
-                None => continue, // Parent not in tree (skipped?)
+            // Parents are looked up through the tree's path index
+            let parent_id = match tree.find_by_path(&parent_path) {
+                Some(id) => id,
+                None => {
+                    log::debug!("skip {}: parent not in tree", path.display());
+                    continue;
+                }
             };
 
             // Get name
@@ -355,15 +861,35 @@ impl Scanner {
 
             // Add node
             let node_id = tree.add_node(name, kind, path.clone(), parent_id);
+            // Followed once here, so nothing later has to touch the filesystem
+            let resolved = if kind == NodeKind::Symlink {
+                std::fs::canonicalize(&path).ok()
+            } else {
+                None
+            };
 
-            // Track path and mtime for directories
-            if kind == NodeKind::Directory {
-                path_to_id.insert(path.clone(), node_id);
-                if let Ok(mtime) = metadata.modified()
-                    && let Some(node) = tree.get_mut(node_id)
-                {
-                    node.mtime = Some(mtime);
+            if let Some(node) = tree.get_mut(node_id) {
+                set_ownership(node, &metadata);
+                if self.config.track_apparent && kind != NodeKind::Directory {
+                    node.apparent_size = Some(metadata.len());
+                }
+                if kind == NodeKind::Symlink {
+                    node.link_target = std::fs::read_link(&path).ok();
+                    node.link_status = Some(match &resolved {
+                        _ if node.link_target.is_none() => LinkStatus::Broken,
+                        Some(target) if target.starts_with(&root_path) => LinkStatus::Inside,
+                        Some(_) => LinkStatus::Outside,
+                        None => LinkStatus::Broken,
+                    });
                 }
+            }
+
+            if let Ok(mtime) = metadata.modified()
+                && let Some(node) = tree.get_mut(node_id)
+            {
+                node.mtime = Some(mtime);
+            }
+            if matches!(kind, NodeKind::Directory | NodeKind::Error) {
                 shared_progress.dirs_scanned.fetch_add(1, Ordering::Relaxed);
             } else {
                 shared_progress
@@ -371,41 +897,176 @@ impl Scanner {
                     .fetch_add(1, Ordering::Relaxed);
             }
 
-            // Set size for files
-            let size = get_disk_usage(&metadata);
+            // Set size for files; sized symlinks take their target's usage
+            let size = match kind {
+                NodeKind::Symlink if self.config.size_symlinks => match &resolved {
+                    // Whether the scan counts it is known once the walk is done
+                    Some(target) if target.starts_with(&root_path) => {
+                        links_inside.push((node_id, target.clone()));
+                        get_disk_usage(&metadata)
+                    }
+                    Some(target) => match symlink_target_usage(target, &mut sized_targets) {
+                        Some(target) => {
+                            if self.config.track_apparent
+                                && let Some(node) = tree.get_mut(node_id)
+                            {
+                                node.apparent_size = Some(target.len());
+                            }
+                            get_disk_usage(&target)
+                        }
+                        None => get_disk_usage(&metadata),
+                    },
+                    None => get_disk_usage(&metadata),
+                },
+                _ => get_disk_usage(&metadata),
+            };
             tree.set_size(node_id, size);
+            // Aggregation replaces a directory's size with its contents',
+            // plus this when overhead is counted
+            if kind == NodeKind::Directory
+                && self.config.count_dir_overhead
+                && let Some(node) = tree.get_mut(node_id)
+            {
+                node.own_size = size;
+            }
             shared_progress
                 .bytes_scanned
                 .fetch_add(size, Ordering::Relaxed);
 
+            // Attribute file sizes to their top-level ancestor
+            let top = if parent_id == NodeId::ROOT {
+                Some(node_id)
+            } else {
+                top_level_of.get(&parent_id).copied()
+            };
+            if kind == NodeKind::Directory
+                && let Some(top) = top
+            {
+                top_level_of.insert(node_id, top);
+            }
+            if kind != NodeKind::Directory
+                && let Some(top) = top
+            {
+                let totals = top_level_totals.entry(top).or_default();
+                totals.0 += size;
+                totals.1 += u64::from(kind == NodeKind::File);
+            }
+            if last_snapshot.elapsed() >= PARTIAL_TREE_INTERVAL {
+                let _ = tx.send(ScanMessage::PartialTree(top_level_snapshot(
+                    &tree,
+                    &top_level_totals,
+                )));
+                last_snapshot = Instant::now();
+            }
+
             // Update current path
             if let Ok(mut guard) = shared_progress.current_path.lock() {
                 *guard = Some(path.clone());
             }
         }
 
+        // A link into the tree counts its target only if the walk left the
+        // target out; a skipped sampled directory's size is extrapolated instead
+        let unsampled_dirs = unsampled
+            .lock()
+            .map(|dirs| dirs.clone())
+            .unwrap_or_default();
+        for (node_id, target) in links_inside {
+            if tree.find_by_path(&target).is_some()
+                || unsampled_dirs.iter().any(|dir| target.starts_with(dir))
+            {
+                continue;
+            }
+            if let Some(metadata) = symlink_target_usage(&target, &mut sized_targets) {
+                if self.config.track_apparent
+                    && let Some(node) = tree.get_mut(node_id)
+                {
+                    node.apparent_size = Some(metadata.len());
+                }
+                tree.set_size(node_id, get_disk_usage(&metadata));
+            }
+        }
+
         // Stop heartbeat thread
         shared_progress.done.store(true, Ordering::Relaxed);
         let _ = heartbeat_handle.join();
 
+        let progress = shared_progress.to_scan_progress();
+        log::info!(
+            "walked {} directories and {} files in {:?} ({} errors)",
+            progress.dirs_scanned,
+            progress.files_scanned,
+            started.elapsed(),
+            progress.errors
+        );
+
+        // A user cancel skips the final progress, but the tree found so far
+        // is still aggregated so the caller can cache it as partial
+        let timed_out = shared_progress.timed_out.load(Ordering::Relaxed);
+        if timed_out {
+            log::info!("scan timed out; keeping what was found");
+        }
+        if self.cancel_token.is_cancelled() && !timed_out {
+            log::info!("scan cancelled");
+            let _ = tx.send(ScanMessage::Cancelled { timed_out: false });
+            aggregate_and_sort(&mut tree, &unsampled, |_, _| {});
+            return tree;
+        }
+
         // Send finalizing message (aggregation can take time on large trees)
         let _ = tx.send(ScanMessage::Finalizing);
 
-        // Aggregate sizes from children to parents
-        tree.aggregate_sizes();
-
-        // Sort all children by size
-        tree.sort_by_size();
+        aggregate_and_sort(&mut tree, &unsampled, |done, total| {
+            let _ = tx.send(ScanMessage::FinalizingProgress { done, total });
+        });
 
         // Send final progress
         let progress = shared_progress.to_scan_progress();
         let _ = tx.send(ScanMessage::Progress(progress));
-        let _ = tx.send(ScanMessage::Completed);
+        let _ = tx.send(if timed_out {
+            ScanMessage::Cancelled { timed_out: true }
+        } else {
+            ScanMessage::Completed
+        });
 
         tree
     }
 }
 
+/// Aggregate sizes from children to parents, extrapolate any directories
+/// sampling skipped, then sort all children by size. `progress` gets the
+/// (done, total) steps across both passes.
+fn aggregate_and_sort(
+    tree: &mut DiskTree,
+    unsampled: &Unsampled,
+    mut progress: impl FnMut(usize, usize),
+) {
+    let started = Instant::now();
+    let len = tree.len();
+    tree.aggregate_sizes_with_progress(|done, _| progress(done, 2 * len));
+    let unsampled: HashSet<NodeId> = unsampled
+        .lock()
+        .map(|paths| paths.iter().filter_map(|p| tree.find_by_path(p)).collect())
+        .unwrap_or_default();
+    if !unsampled.is_empty() {
+        tree.extrapolate_unsampled(&unsampled);
+    }
+    tree.sort_by_size_with_progress(|done, _| progress(len + done, 2 * len));
+    log::info!(
+        "aggregated and sorted {} nodes in {:?}",
+        tree.live_count(),
+        started.elapsed()
+    );
+}
+
+/// How `get_disk_usage` measures sizes on this platform
+#[cfg(unix)]
+pub const SIZE_STRATEGY: &str = "allocated blocks (st_blocks × 512)";
+
+/// How `get_disk_usage` measures sizes on this platform
+#[cfg(not(unix))]
+pub const SIZE_STRATEGY: &str = "file length (allocation not available)";
+
 /// Get actual disk usage for a file (accounts for sparse files and block size)
 #[cfg(unix)]
 fn get_disk_usage(metadata: &Metadata) -> u64 {
@@ -419,6 +1080,81 @@ fn get_disk_usage(metadata: &Metadata) -> u64 {
     metadata.len()
 }
 
+/// Small tree holding the root and its direct children with running totals
+fn top_level_snapshot(tree: &DiskTree, totals: &HashMap<NodeId, (u64, u64)>) -> DiskTree {
+    let mut snapshot = DiskTree::new(tree.root_path().to_path_buf());
+    let mut root_size = 0;
+    let mut root_files = 0;
+    for &child_id in &tree.root().children {
+        let Some(child) = tree.get(child_id) else {
+            continue;
+        };
+        let (size, files) = totals.get(&child_id).copied().unwrap_or_default();
+        let id = snapshot.add_node(
+            child.name.clone(),
+            child.kind,
+            child.path.clone(),
+            NodeId::ROOT,
+        );
+        snapshot.set_size(id, size);
+        if let Some(node) = snapshot.get_mut(id) {
+            node.file_count = files;
+        }
+        root_size += size;
+        root_files += files;
+    }
+    // Children have no descendants here, so totals are set directly rather
+    // than aggregated
+    snapshot.set_size(NodeId::ROOT, root_size);
+    if let Some(root) = snapshot.get_mut(NodeId::ROOT) {
+        root.file_count = root_files;
+    }
+    snapshot.sort_by_size();
+    snapshot
+}
+
+/// Metadata of a symlink's resolved target when its size should be counted:
+/// the target must be a file no earlier link claimed
+fn symlink_target_usage(target: &Path, seen: &mut HashSet<TargetId>) -> Option<Metadata> {
+    let metadata = std::fs::metadata(target).ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    seen.insert(target_id(target, &metadata))
+        .then_some(metadata)
+}
+
+/// Device and inode, so hard-linked targets are recognised as one file
+#[cfg(unix)]
+type TargetId = (u64, u64);
+
+#[cfg(unix)]
+fn target_id(_target: &Path, metadata: &Metadata) -> TargetId {
+    (metadata.dev(), metadata.ino())
+}
+
+/// Canonical path (no portable inode number on Windows)
+#[cfg(not(unix))]
+type TargetId = PathBuf;
+
+#[cfg(not(unix))]
+fn target_id(target: &Path, _metadata: &Metadata) -> TargetId {
+    target.to_path_buf()
+}
+
+/// Record owner, group, permission bits and hard link count on a node
+#[cfg(unix)]
+fn set_ownership(node: &mut TreeNode, metadata: &Metadata) {
+    node.uid = Some(metadata.uid());
+    node.gid = Some(metadata.gid());
+    node.mode = Some(metadata.mode());
+    node.nlink = Some(metadata.nlink());
+}
+
+/// Ownership and link counts are not captured on Windows
+#[cfg(not(unix))]
+fn set_ownership(_node: &mut TreeNode, _metadata: &Metadata) {}
+
 /// Get device ID for same-filesystem checks
 #[cfg(unix)]
 fn get_device_id(metadata: &Metadata) -> u64 {
@@ -466,4 +1202,435 @@ mod tests {
         let tree = handle.join().unwrap();
         assert!(tree.len() >= 4); // root + 2 files + subdir + 1 file
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_size_symlinks_counts_outside_targets_once() {
+        let outside = TempDir::new().unwrap();
+        let target = outside.path().join("big.bin");
+        fs::write(&target, vec![1u8; 64 * 1024]).unwrap();
+
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("inside.bin"), vec![1u8; 8 * 1024]).unwrap();
+        std::os::unix::fs::symlink(&target, temp.path().join("a")).unwrap();
+        std::os::unix::fs::symlink(&target, temp.path().join("b")).unwrap();
+        std::os::unix::fs::symlink(temp.path().join("inside.bin"), temp.path().join("c")).unwrap();
+
+        let scan = |size_symlinks| {
+            let config = ScanConfig {
+                size_symlinks,
+                ..ScanConfig::default()
+            };
+            let (rx, handle) = Scanner::new(config).scan(temp.path().to_path_buf());
+            for _ in rx {}
+            handle.join().unwrap().total_size()
+        };
+
+        let target_usage = fs::metadata(&target).unwrap().blocks() * 512;
+        assert_eq!(scan(true), scan(false) + target_usage);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_size_symlinks_counts_excluded_inside_targets() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("vendor")).unwrap();
+        let target = temp.path().join("vendor/big.bin");
+        fs::write(&target, vec![1u8; 64 * 1024]).unwrap();
+        std::os::unix::fs::symlink(&target, temp.path().join("link")).unwrap();
+
+        let scan = |size_symlinks, exclude: Vec<PathBuf>| {
+            let config = ScanConfig {
+                size_symlinks,
+                exclude,
+                ..ScanConfig::default()
+            };
+            let (rx, handle) = Scanner::new(config).scan(temp.path().to_path_buf());
+            for _ in rx {}
+            handle.join().unwrap().total_size()
+        };
+
+        // Counted through its own node, so the link adds nothing
+        assert_eq!(scan(true, Vec::new()), scan(false, Vec::new()));
+        // Left out of the tree, so the link carries it
+        let vendor = vec![PathBuf::from("vendor")];
+        let target_usage = fs::metadata(&target).unwrap().blocks() * 512;
+        assert_eq!(
+            scan(true, vendor.clone()),
+            scan(false, vendor) + target_usage
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unreadable_directory_becomes_error_node() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let locked = temp.path().join("locked");
+        fs::create_dir(&locked).unwrap();
+        fs::write(locked.join("secret"), "x").unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        // Root can list it anyway, so there is nothing to test
+        if fs::read_dir(&locked).is_ok() {
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+            return;
+        }
+
+        let (rx, handle) = Scanner::new(ScanConfig::default()).scan(temp.path().to_path_buf());
+        let errors: Vec<PathBuf> = rx
+            .iter()
+            .filter_map(|m| match m {
+                ScanMessage::PathError { path, .. } => Some(path),
+                _ => None,
+            })
+            .collect();
+        let tree = handle.join().unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let id = tree.find_by_path(&locked.canonicalize().unwrap()).unwrap();
+        let node = tree.get(id).unwrap();
+        assert_eq!(node.kind, NodeKind::Error);
+        assert!(node.incomplete);
+        assert!(tree.root().incomplete);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_also_scan_paths_add_their_devices() {
+        let temp = TempDir::new().unwrap();
+        let extra = TempDir::new().unwrap();
+        let missing = temp.path().join("not-mounted");
+        let config = ScanConfig {
+            extra_filesystems: vec![extra.path().to_path_buf(), missing.clone()],
+            ..Default::default()
+        };
+
+        let (devices, unreadable) = allowed_devices(temp.path(), &config);
+        let device_of = |path: &Path| get_device_id(&fs::metadata(path).unwrap());
+        assert_eq!(
+            devices,
+            vec![device_of(temp.path()), device_of(extra.path())]
+        );
+        assert_eq!(unreadable.len(), 1);
+        assert_eq!(unreadable[0].0, missing);
+
+        // A path that can't be stat'd is reported and the scan goes on
+        fs::write(temp.path().join("file"), "x").unwrap();
+        let (rx, handle) = Scanner::new(config).scan(temp.path().to_path_buf());
+        let errors: Vec<PathBuf> = rx
+            .iter()
+            .filter_map(|m| match m {
+                ScanMessage::PathError { path, .. } => Some(path),
+                _ => None,
+            })
+            .collect();
+        let tree = handle.join().unwrap();
+        assert_eq!(errors, vec![missing]);
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn test_pause_holds_scan_until_resumed() {
+        let temp = TempDir::new().unwrap();
+        for i in 0..10 {
+            fs::write(temp.path().join(format!("f{}", i)), "x").unwrap();
+        }
+
+        let token = CancellationToken::new();
+        token.set_paused(true);
+        let (rx, handle) = Scanner::new(ScanConfig::default())
+            .with_cancellation(token.clone())
+            .scan(temp.path().to_path_buf());
+
+        std::thread::sleep(Duration::from_millis(350));
+        let paused: Vec<ScanProgress> = rx
+            .try_iter()
+            .filter_map(|m| match m {
+                ScanMessage::Progress(p) => Some(p),
+                _ => None,
+            })
+            .collect();
+        let last = paused.last().expect("heartbeat while paused");
+        assert!(last.paused);
+        assert_eq!(last.total_entries(), 0);
+
+        token.set_paused(false);
+        for _ in rx {}
+        assert_eq!(handle.join().unwrap().total_files(), 10);
+    }
+
+    #[test]
+    fn test_pause_stops_directory_reads() {
+        let temp = TempDir::new().unwrap();
+        for dir in ["a", "b", "c"] {
+            fs::create_dir(temp.path().join(dir)).unwrap();
+            fs::write(temp.path().join(dir).join("f"), "x").unwrap();
+        }
+
+        let token = CancellationToken::new();
+        token.set_paused(true);
+        let config = ScanConfig::default();
+        let walker = build_walker(
+            temp.path(),
+            &config,
+            allowed_devices(temp.path(), &config).0,
+            Unsampled::default(),
+            token.clone(),
+        );
+        // Read the walker directly, with nothing in between holding it back
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for entry in walker {
+                let _ = tx.send(entry);
+            }
+        });
+
+        std::thread::sleep(Duration::from_millis(300));
+        let while_paused = rx.try_iter().count();
+        std::thread::sleep(Duration::from_millis(300));
+        // At most the root itself, whose listing is held
+        assert!(while_paused <= 1);
+        assert_eq!(rx.try_iter().count(), 0);
+
+        token.set_paused(false);
+        assert_eq!(while_paused + rx.iter().count(), 7);
+    }
+
+    #[test]
+    fn test_exclude_skips_names_and_paths() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("a/.git")).unwrap();
+        fs::create_dir_all(temp.path().join("b/.git")).unwrap();
+        fs::create_dir_all(temp.path().join("backup/nested")).unwrap();
+        fs::write(temp.path().join("a/.git/HEAD"), "ref").unwrap();
+        fs::write(temp.path().join("backup/nested/big.bin"), vec![1u8; 8192]).unwrap();
+        fs::write(temp.path().join("keep.txt"), "kept").unwrap();
+
+        let config = ScanConfig {
+            exclude: vec![PathBuf::from(".git"), temp.path().join("backup")],
+            ..ScanConfig::default()
+        };
+        let (rx, handle) = Scanner::new(config).scan(temp.path().to_path_buf());
+        for _ in rx {}
+        let tree = handle.join().unwrap();
+
+        assert!(tree.find_by_path(&temp.path().join("a")).is_some());
+        assert!(tree.find_by_path(&temp.path().join("a/.git")).is_none());
+        assert!(tree.find_by_path(&temp.path().join("b/.git")).is_none());
+        assert!(tree.find_by_path(&temp.path().join("backup")).is_none());
+        assert!(tree.find_by_path(&temp.path().join("keep.txt")).is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_target_recorded() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("a"), "data").unwrap();
+        std::os::unix::fs::symlink("a", temp.path().join("to_a")).unwrap();
+
+        let (rx, handle) = Scanner::new(ScanConfig::default()).scan(temp.path().to_path_buf());
+        for _ in rx {}
+        let tree = handle.join().unwrap();
+        let target = |name: &str| {
+            let path = temp.path().canonicalize().unwrap().join(name);
+            tree.get(tree.find_by_path(&path).unwrap())
+                .unwrap()
+                .link_target
+                .clone()
+        };
+        assert_eq!(target("to_a"), Some(PathBuf::from("a")));
+        assert_eq!(target("a"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_status_resolved_during_scan() {
+        let temp = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        fs::write(temp.path().join("a"), "data").unwrap();
+        std::os::unix::fs::symlink("a", temp.path().join("to_a")).unwrap();
+        std::os::unix::fs::symlink(outside.path(), temp.path().join("away")).unwrap();
+        std::os::unix::fs::symlink("missing", temp.path().join("gone")).unwrap();
+
+        let (rx, handle) = Scanner::new(ScanConfig::default()).scan(temp.path().to_path_buf());
+        for _ in rx {}
+        let tree = handle.join().unwrap();
+        let status = |name: &str| {
+            let path = temp.path().canonicalize().unwrap().join(name);
+            tree.get(tree.find_by_path(&path).unwrap())
+                .unwrap()
+                .link_status
+        };
+        assert_eq!(status("to_a"), Some(LinkStatus::Inside));
+        assert_eq!(status("away"), Some(LinkStatus::Outside));
+        assert_eq!(status("gone"), Some(LinkStatus::Broken));
+        assert_eq!(status("a"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hard_link_count_recorded() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("a"), "data").unwrap();
+        fs::hard_link(temp.path().join("a"), temp.path().join("b")).unwrap();
+        fs::write(temp.path().join("c"), "data").unwrap();
+
+        let (rx, handle) = Scanner::new(ScanConfig::default()).scan(temp.path().to_path_buf());
+        for _ in rx {}
+        let tree = handle.join().unwrap();
+        let nlink = |name: &str| {
+            let path = temp.path().canonicalize().unwrap().join(name);
+            tree.get(tree.find_by_path(&path).unwrap()).unwrap().nlink
+        };
+        assert_eq!(nlink("a"), Some(2));
+        assert_eq!(nlink("b"), Some(2));
+        assert_eq!(nlink("c"), Some(1));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_count_dir_overhead_adds_directory_blocks() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("a/b")).unwrap();
+        fs::write(temp.path().join("a/b/file"), vec![1u8; 8192]).unwrap();
+
+        let scan = |count_dir_overhead| {
+            let config = ScanConfig {
+                count_dir_overhead,
+                ..ScanConfig::default()
+            };
+            let (rx, handle) = Scanner::new(config).scan(temp.path().to_path_buf());
+            for _ in rx {}
+            handle.join().unwrap()
+        };
+        let plain = scan(false);
+        let counted = scan(true);
+
+        let blocks = |path: &Path| fs::metadata(path).unwrap().blocks() * 512;
+        let a = temp.path().join("a");
+        let b = a.join("b");
+        let overhead = blocks(temp.path()) + blocks(&a) + blocks(&b);
+        assert_eq!(counted.total_size(), plain.total_size() + overhead);
+        let a_id = counted.find_by_path(&a.canonicalize().unwrap()).unwrap();
+        let a_plain = plain.find_by_path(&a.canonicalize().unwrap()).unwrap();
+        assert_eq!(
+            counted.get(a_id).unwrap().size,
+            plain.get(a_plain).unwrap().size + blocks(&a) + blocks(&b)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_verify_tree_catches_changes_hidden_by_mtime() {
+        let temp = TempDir::new().unwrap();
+        let sub = temp.path().join("sub");
+        fs::create_dir_all(&sub).unwrap();
+        fs::create_dir_all(temp.path().join(".git")).unwrap();
+        fs::write(sub.join("kept"), "x").unwrap();
+
+        let config = ScanConfig {
+            exclude: vec![PathBuf::from(".git")],
+            ..ScanConfig::default()
+        };
+        let (rx, handle) = Scanner::new(config.clone()).scan(temp.path().to_path_buf());
+        for _ in rx {}
+        let tree = handle.join().unwrap();
+        assert!(Scanner::verify_tree(&tree, &config, &[]));
+
+        // A new file, with the directory's mtime put back as a lying
+        // filesystem would leave it
+        let mtime = fs::metadata(&sub).unwrap().modified().unwrap();
+        fs::write(sub.join("added"), "y").unwrap();
+        fs::File::open(&sub).unwrap().set_modified(mtime).unwrap();
+        assert!(crate::cache::spot_check_mtimes(&tree, 32, &[]));
+        assert!(!Scanner::verify_tree(&tree, &config, &[]));
+        // Unless that directory is one rescanned anyway
+        assert!(Scanner::verify_tree(
+            &tree,
+            &config,
+            &[sub.canonicalize().unwrap()]
+        ));
+
+        fs::remove_file(sub.join("added")).unwrap();
+        fs::remove_file(sub.join("kept")).unwrap();
+        fs::File::open(&sub).unwrap().set_modified(mtime).unwrap();
+        assert!(!Scanner::verify_tree(&tree, &config, &[]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_verify_tree_catches_rewritten_files() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("data");
+        fs::write(&file, "x").unwrap();
+
+        let config = ScanConfig::default();
+        let (rx, handle) = Scanner::new(config.clone()).scan(temp.path().to_path_buf());
+        for _ in rx {}
+        let tree = handle.join().unwrap();
+        assert!(Scanner::verify_tree(&tree, &config, &[]));
+
+        // Grown in place, directory mtime put back
+        let dir_mtime = fs::metadata(temp.path()).unwrap().modified().unwrap();
+        fs::write(&file, vec![b'x'; 64 * 1024]).unwrap();
+        fs::File::open(temp.path())
+            .unwrap()
+            .set_modified(dir_mtime)
+            .unwrap();
+        assert!(!Scanner::verify_tree(&tree, &config, &[]));
+    }
+
+    #[test]
+    fn test_top_files_matches_scan() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("a/b")).unwrap();
+        for (name, kib) in [
+            ("small", 4),
+            ("a/big", 64),
+            ("a/b/medium", 16),
+            ("a/tiny", 1),
+        ] {
+            fs::write(temp.path().join(name), vec![1u8; kib * 1024]).unwrap();
+        }
+
+        let config = ScanConfig::default();
+        let top = Scanner::top_files(temp.path(), 2, &config);
+        let names: Vec<_> = top
+            .iter()
+            .map(|(path, _)| path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, ["big", "medium"]);
+
+        let (rx, handle) = Scanner::new(config).scan(temp.path().to_path_buf());
+        for _ in rx {}
+        let tree = handle.join().unwrap();
+        for (path, size) in &top {
+            let id = tree.find_by_path(path).unwrap();
+            assert_eq!(tree.get(id).unwrap().size, *size);
+        }
+        assert!(Scanner::top_files(temp.path(), 0, &ScanConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_count_entries_matches_scan() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("file1.txt"), "hello").unwrap();
+        fs::create_dir_all(temp.path().join("a/b")).unwrap();
+        fs::write(temp.path().join("a/b/file2.txt"), "world").unwrap();
+
+        let config = ScanConfig {
+            max_depth: Some(2),
+            ..ScanConfig::default()
+        };
+        let count = Scanner::count_entries(temp.path(), &config);
+
+        let (rx, handle) = Scanner::new(config).scan(temp.path().to_path_buf());
+        for _ in rx {}
+        let tree = handle.join().unwrap();
+
+        // Depth limit excludes file2.txt from both
+        assert_eq!(count, 3);
+        assert_eq!(count, tree.live_count() as u64 - 1);
+    }
 }
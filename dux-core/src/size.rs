@@ -1,43 +1,137 @@
-/// Format bytes into human-readable string
-pub fn format_size(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-    const TB: u64 = GB * 1024;
-
-    if bytes >= TB {
-        format!("{:.1} TB", bytes as f64 / TB as f64)
-    } else if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
+use crate::error::{DuxError, Result};
+
+/// Unit base used when rendering sizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SizeUnits {
+    /// Powers of 1024, labelled KiB/MiB/GiB/TiB
+    #[default]
+    Binary,
+    /// Powers of 1000, labelled kB/MB/GB/TB
+    Si,
+}
+
+impl SizeUnits {
+    /// Format bytes in this unit base
+    pub fn format(self, bytes: u64) -> String {
+        self.format_prec(bytes, 1)
+    }
+
+    /// Format bytes in this unit base with `digits` decimal places
+    pub fn format_prec(self, bytes: u64, digits: usize) -> String {
+        match self {
+            Self::Binary => format_size_prec(bytes, digits),
+            Self::Si => format_size_si_prec(bytes, digits),
+        }
+    }
+
+    /// Format bytes in this unit base, short form
+    pub fn format_short(self, bytes: u64) -> String {
+        match self {
+            Self::Binary => format_size_short(bytes),
+            Self::Si => format_size_short_si(bytes),
+        }
+    }
+}
+
+/// How sizes are rendered: unit base plus decimal places
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeFormat {
+    pub units: SizeUnits,
+    pub precision: usize,
+}
+
+impl Default for SizeFormat {
+    fn default() -> Self {
+        Self {
+            units: SizeUnits::default(),
+            precision: 1,
+        }
+    }
+}
+
+impl SizeFormat {
+    /// Format bytes using this unit base and precision
+    pub fn format(self, bytes: u64) -> String {
+        self.units.format_prec(bytes, self.precision)
+    }
+
+    /// Short form for tight spaces (precision does not apply)
+    pub fn format_short(self, bytes: u64) -> String {
+        self.units.format_short(bytes)
+    }
+}
+
+/// Scale `bytes` by `base` and label with the matching unit (K, M, G, T).
+/// Plain bytes never show decimals.
+fn format_scaled(bytes: u64, base: u64, labels: [&str; 4], digits: usize) -> String {
+    let kb = base;
+    let mb = kb * base;
+    let gb = mb * base;
+    let tb = gb * base;
+
+    if bytes >= tb {
+        format!("{:.*} {}", digits, bytes as f64 / tb as f64, labels[3])
+    } else if bytes >= gb {
+        format!("{:.*} {}", digits, bytes as f64 / gb as f64, labels[2])
+    } else if bytes >= mb {
+        format!("{:.*} {}", digits, bytes as f64 / mb as f64, labels[1])
+    } else if bytes >= kb {
+        format!("{:.*} {}", digits, bytes as f64 / kb as f64, labels[0])
     } else {
         format!("{} B", bytes)
     }
 }
 
-/// Format bytes into short human-readable string (for tight spaces)
-pub fn format_size_short(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-    const TB: u64 = GB * 1024;
-
-    if bytes >= TB {
-        format!("{:.0}T", bytes as f64 / TB as f64)
-    } else if bytes >= GB {
-        format!("{:.1}G", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.0}M", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.0}K", bytes as f64 / KB as f64)
+/// Format bytes into human-readable string (binary units: KiB, MiB, ...)
+pub fn format_size(bytes: u64) -> String {
+    format_size_prec(bytes, 1)
+}
+
+/// [`format_size`] with `digits` decimal places (e.g. `1.23 GiB`)
+pub fn format_size_prec(bytes: u64, digits: usize) -> String {
+    format_scaled(bytes, 1024, ["KiB", "MiB", "GiB", "TiB"], digits)
+}
+
+/// Format bytes into human-readable string (SI units: kB, MB, ...)
+pub fn format_size_si(bytes: u64) -> String {
+    format_size_si_prec(bytes, 1)
+}
+
+/// [`format_size_si`] with `digits` decimal places (e.g. `1.23 GB`)
+pub fn format_size_si_prec(bytes: u64, digits: usize) -> String {
+    format_scaled(bytes, 1000, ["kB", "MB", "GB", "TB"], digits)
+}
+
+/// Scale `bytes` by `base` into the short single-letter form
+fn format_scaled_short(bytes: u64, base: u64) -> String {
+    let kb = base;
+    let mb = kb * base;
+    let gb = mb * base;
+    let tb = gb * base;
+
+    if bytes >= tb {
+        format!("{:.0}T", bytes as f64 / tb as f64)
+    } else if bytes >= gb {
+        format!("{:.1}G", bytes as f64 / gb as f64)
+    } else if bytes >= mb {
+        format!("{:.0}M", bytes as f64 / mb as f64)
+    } else if bytes >= kb {
+        format!("{:.0}K", bytes as f64 / kb as f64)
     } else {
         format!("{}B", bytes)
     }
 }
 
+/// Format bytes into short human-readable string (for tight spaces)
+pub fn format_size_short(bytes: u64) -> String {
+    format_scaled_short(bytes, 1024)
+}
+
+/// Short form of [`format_size_si`] (for tight spaces)
+pub fn format_size_short_si(bytes: u64) -> String {
+    format_scaled_short(bytes, 1000)
+}
+
 /// Calculate percentage of size relative to total
 pub fn size_percentage(size: u64, total: u64) -> f64 {
     if total == 0 {
@@ -47,6 +141,39 @@ pub fn size_percentage(size: u64, total: u64) -> f64 {
     }
 }
 
+/// Parse a human-readable size such as `1024`, `500K`, `1.5M` or `2GiB`.
+/// K/M/G/T are powers of 1024 whether written `K`, `KB` or `KiB` (case
+/// doesn't matter), matching how sizes are shown by default. Fractions are
+/// rounded down to whole bytes.
+pub fn parse_human_size(input: &str) -> Result<u64> {
+    let invalid = || DuxError::InvalidSize(input.to_string());
+    let s = input.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+
+    let shift = match unit.trim_start().to_ascii_lowercase().as_str() {
+        "" | "b" => 0,
+        "k" | "kb" | "kib" => 10,
+        "m" | "mb" | "mib" => 20,
+        "g" | "gb" | "gib" => 30,
+        "t" | "tb" | "tib" => 40,
+        _ => return Err(invalid()),
+    };
+    let multiplier = 1u64 << shift;
+
+    if let Ok(whole) = number.parse::<u64>() {
+        return whole.checked_mul(multiplier).ok_or_else(invalid);
+    }
+    let value: f64 = number.parse().map_err(|_| invalid())?;
+    let bytes = value * multiplier as f64;
+    if !bytes.is_finite() || bytes < 0.0 || bytes >= u64::MAX as f64 {
+        return Err(invalid());
+    }
+    Ok(bytes as u64)
+}
+
 /// Format a number with thousand separators (e.g., 1,234,567)
 pub fn format_count(n: u64) -> String {
     if n < 1000 {
@@ -74,11 +201,60 @@ mod tests {
     fn test_format_size() {
         assert_eq!(format_size(0), "0 B");
         assert_eq!(format_size(512), "512 B");
-        assert_eq!(format_size(1024), "1.0 KB");
-        assert_eq!(format_size(1536), "1.5 KB");
-        assert_eq!(format_size(1024 * 1024), "1.0 MB");
-        assert_eq!(format_size(1024 * 1024 * 1024), "1.0 GB");
-        assert_eq!(format_size(1024 * 1024 * 1024 * 1024), "1.0 TB");
+        assert_eq!(format_size(1024), "1.0 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(1024 * 1024), "1.0 MiB");
+        assert_eq!(format_size(1024 * 1024 * 1024), "1.0 GiB");
+        assert_eq!(format_size(1024 * 1024 * 1024 * 1024), "1.0 TiB");
+    }
+
+    #[test]
+    fn test_format_size_si() {
+        assert_eq!(format_size_si(0), "0 B");
+        assert_eq!(format_size_si(999), "999 B");
+        assert_eq!(format_size_si(1000), "1.0 kB");
+        assert_eq!(format_size_si(1500), "1.5 kB");
+        assert_eq!(format_size_si(1024), "1.0 kB");
+        assert_eq!(format_size_si(1000 * 1000), "1.0 MB");
+        assert_eq!(format_size_si(1000 * 1000 * 1000), "1.0 GB");
+        assert_eq!(format_size_si(1000 * 1000 * 1000 * 1000), "1.0 TB");
+    }
+
+    #[test]
+    fn test_format_size_prec() {
+        let gib = 1024 * 1024 * 1024;
+        // 0 decimals
+        assert_eq!(format_size_prec(1536, 0), "2 KiB");
+        assert_eq!(format_size_prec(gib, 0), "1 GiB");
+        // 1 decimal matches format_size
+        assert_eq!(format_size_prec(1536, 1), format_size(1536));
+        assert_eq!(format_size_prec(gib + gib / 4, 1), "1.2 GiB");
+        // 2 decimals
+        assert_eq!(format_size_prec(gib + gib / 4, 2), "1.25 GiB");
+        assert_eq!(format_size_si_prec(1_234_000_000, 2), "1.23 GB");
+        // Plain bytes never show decimals
+        assert_eq!(format_size_prec(512, 0), "512 B");
+        assert_eq!(format_size_prec(512, 2), "512 B");
+        assert_eq!(format_size_si_prec(999, 2), "999 B");
+    }
+
+    #[test]
+    fn test_size_format() {
+        let format = SizeFormat {
+            units: SizeUnits::Si,
+            precision: 2,
+        };
+        assert_eq!(format.format(1_500_000), "1.50 MB");
+        assert_eq!(SizeFormat::default().format(1536), "1.5 KiB");
+    }
+
+    #[test]
+    fn test_size_units_dispatch() {
+        assert_eq!(SizeUnits::default(), SizeUnits::Binary);
+        assert_eq!(SizeUnits::Binary.format(1024), "1.0 KiB");
+        assert_eq!(SizeUnits::Si.format(1000), "1.0 kB");
+        assert_eq!(SizeUnits::Binary.format_short(1024 * 1024), "1M");
+        assert_eq!(SizeUnits::Si.format_short(1000 * 1000), "1M");
     }
 
     #[test]
@@ -88,6 +264,22 @@ mod tests {
         assert_eq!(size_percentage(100, 0), 0.0);
     }
 
+    #[test]
+    fn test_parse_human_size() {
+        assert_eq!(parse_human_size("1024").unwrap(), 1024);
+        assert_eq!(parse_human_size("1K").unwrap(), 1024);
+        assert_eq!(parse_human_size("1KiB").unwrap(), 1024);
+        assert_eq!(parse_human_size("1kb").unwrap(), 1024);
+        assert_eq!(parse_human_size("1.5M").unwrap(), 1536 * 1024);
+        assert_eq!(parse_human_size(" 2 GiB ").unwrap(), 2 << 30);
+        assert_eq!(parse_human_size("0").unwrap(), 0);
+        assert_eq!(parse_human_size("10B").unwrap(), 10);
+
+        for bad in ["", "M", "1.2.3K", "-1K", "5X", "1 K B", "99999999999T"] {
+            assert!(parse_human_size(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+
     #[test]
     fn test_format_count() {
         assert_eq!(format_count(0), "0");
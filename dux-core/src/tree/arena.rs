@@ -1,14 +1,33 @@
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use super::node::{NodeId, NodeKind, TreeNode};
+use super::path_index::PathIndex;
+
+/// Compact once tombstones make up more than this fraction of the arena
+const COMPACT_THRESHOLD: f64 = 0.25;
+
+/// Trees with at least this many slots aggregate sizes in parallel;
+/// smaller ones stay single-threaded to avoid thread-pool overhead
+const PARALLEL_AGGREGATE_THRESHOLD: usize = 200_000;
+
+/// Nodes handled between progress reports while aggregating or sorting
+const PROGRESS_STEP: usize = 1 << 16;
 
 /// Arena-allocated directory tree
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiskTree {
+    /// Slots indexed by `NodeId`. A child always sits after its parent, so
+    /// one backwards pass sees every node before its parent.
     nodes: Vec<Option<TreeNode>>,
     root_path: PathBuf,
+    /// Path → node lookup (rebuilt by `rebuild_paths` after cache load)
+    #[serde(skip)]
+    path_index: PathIndex,
 }
 
 impl DiskTree {
@@ -27,8 +46,10 @@ impl DiskTree {
             0,
         );
 
+        let nodes = vec![Some(root_node)];
         Self {
-            nodes: vec![Some(root_node)],
+            path_index: PathIndex::build(&nodes),
+            nodes,
             root_path,
         }
     }
@@ -45,6 +66,7 @@ impl DiskTree {
         let id = NodeId(self.nodes.len());
 
         let node = TreeNode::new(id, name, kind, path, Some(parent), parent_depth + 1);
+        self.path_index.insert(&self.nodes, &node.path, id);
 
         self.nodes.push(Some(node));
         if let Some(parent_node) = self.get_mut(parent) {
@@ -100,6 +122,13 @@ impl DiskTree {
                 }
             }
         }
+
+        self.rebuild_path_index();
+    }
+
+    /// Recreate the path → node index from the nodes' paths
+    fn rebuild_path_index(&mut self) {
+        self.path_index = PathIndex::build(&self.nodes);
     }
 
     /// Get total number of nodes (including tombstones)
@@ -112,6 +141,41 @@ impl DiskTree {
         self.nodes.iter().filter(|n| n.is_some()).count()
     }
 
+    /// Whether enough nodes have been removed that [`compact`](Self::compact) is worthwhile
+    pub fn needs_compaction(&self) -> bool {
+        let tombstones = self.nodes.len() - self.live_count();
+        tombstones as f64 > self.nodes.len() as f64 * COMPACT_THRESHOLD
+    }
+
+    /// Rebuild the arena without tombstones, releasing their memory.
+    /// Live nodes keep their relative order, so parents still precede children.
+    /// Returns the old → new `NodeId` mapping for every live node.
+    pub fn compact(&mut self) -> HashMap<NodeId, NodeId> {
+        let mut remap = HashMap::with_capacity(self.live_count());
+        for (i, node) in self.nodes.iter().enumerate() {
+            if node.is_some() {
+                remap.insert(NodeId(i), NodeId(remap.len()));
+            }
+        }
+
+        let old_nodes = std::mem::take(&mut self.nodes);
+        let mut nodes = Vec::with_capacity(remap.len());
+        for mut node in old_nodes.into_iter().flatten() {
+            node.id = remap[&node.id];
+            node.parent = node.parent.and_then(|p| remap.get(&p).copied());
+            node.children = node
+                .children
+                .iter()
+                .filter_map(|c| remap.get(c).copied())
+                .collect();
+            nodes.push(Some(node));
+        }
+        self.nodes = nodes;
+        self.rebuild_path_index();
+
+        remap
+    }
+
     /// Check if tree is empty (only has root)
     pub fn is_empty(&self) -> bool {
         self.live_count() <= 1
@@ -126,48 +190,247 @@ impl DiskTree {
 
     /// Propagate sizes from children to parents (bottom-up)
     pub fn aggregate_sizes(&mut self) {
+        self.aggregate_sizes_with_progress(|_, _| {});
+    }
+
+    /// `aggregate_sizes`, calling `progress` with the (done, total) nodes
+    /// now and then, for a progress display on large trees
+    pub fn aggregate_sizes_with_progress(&mut self, progress: impl FnMut(usize, usize)) {
+        if self.nodes.len() >= PARALLEL_AGGREGATE_THRESHOLD {
+            self.aggregate_sizes_parallel(progress);
+        } else {
+            self.aggregate_sizes_sequential(progress);
+        }
+    }
+
+    /// Level-by-level reduction: every directory at one depth only reads its
+    /// children one level deeper, so each level is summed in parallel
+    fn aggregate_sizes_parallel(&mut self, mut progress: impl FnMut(usize, usize)) {
+        let mut levels: Vec<Vec<usize>> = Vec::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if let Some(node) = node
+                && node.kind.is_directory()
+            {
+                let depth = node.depth as usize;
+                if levels.len() <= depth {
+                    levels.resize_with(depth + 1, Vec::new);
+                }
+                levels[depth].push(i);
+            }
+        }
+
+        // Files need no work, so they count as done up front
+        let total = self.nodes.len();
+        let mut done = total - levels.iter().map(Vec::len).sum::<usize>();
+        for level in levels.iter().rev() {
+            progress(done, total);
+            done += level.len();
+            let nodes = &self.nodes;
+            let totals: Vec<(u64, u64, Option<u64>, bool)> = level
+                .par_iter()
+                .map(|&i| {
+                    let node = nodes[i].as_ref();
+                    let own_size = node.map_or(0, |n| n.own_size);
+                    node.map(|n| n.children.as_slice())
+                        .unwrap_or_default()
+                        .iter()
+                        .filter_map(|c| nodes.get(c.index()).and_then(|o| o.as_ref()))
+                        .fold(
+                            (own_size, 0, None, false),
+                            |(size, files, apparent, incomplete), child| {
+                                (
+                                    size + child.size,
+                                    files + child.file_count,
+                                    add_apparent(apparent, child.apparent_size),
+                                    incomplete || child.incomplete,
+                                )
+                            },
+                        )
+                })
+                .collect();
+
+            for (&i, (size, files, apparent, incomplete)) in level.iter().zip(totals) {
+                if let Some(node) = self.nodes[i].as_mut() {
+                    node.size = size;
+                    node.file_count = files;
+                    node.apparent_size = apparent;
+                    node.incomplete = incomplete;
+                }
+            }
+        }
+    }
+
+    fn aggregate_sizes_sequential(&mut self, mut progress: impl FnMut(usize, usize)) {
+        let total = self.nodes.len();
         // Process nodes in reverse order (children before parents)
-        for i in (0..self.nodes.len()).rev() {
+        for i in (0..total).rev() {
+            if (total - i).is_multiple_of(PROGRESS_STEP) {
+                progress(total - i, total);
+            }
             let node = match &self.nodes[i] {
                 Some(n) => n,
                 None => continue, // Skip tombstones
             };
             if node.kind.is_directory() {
                 let children = node.children.clone();
-                let mut total_size = 0u64;
+                let mut total_size = node.own_size;
                 let mut total_files = 0u64;
+                let mut total_apparent = None;
+                let mut incomplete = false;
 
                 for child_id in &children {
                     if let Some(child) = self.get(*child_id) {
                         total_size += child.size;
                         total_files += child.file_count;
+                        total_apparent = add_apparent(total_apparent, child.apparent_size);
+                        incomplete |= child.incomplete;
                     }
                 }
 
                 if let Some(node) = self.get_mut(NodeId(i)) {
                     node.size = total_size;
                     node.file_count = total_files;
+                    node.apparent_size = total_apparent;
+                    node.incomplete = incomplete;
                 }
             }
         }
     }
 
-    /// Sort all children by size descending
+    /// Sort all children by size descending. Ties are broken by name, then
+    /// by NodeId, so equal-sized siblings always come out in the same order.
     pub fn sort_by_size(&mut self) {
-        // First collect all the size information
-        let sizes: Vec<u64> = self
+        self.sort_by_size_with_progress(|_, _| {});
+    }
+
+    /// `sort_by_size`, calling `progress` with the (done, total) nodes now
+    /// and then
+    pub fn sort_by_size_with_progress(&mut self, mut progress: impl FnMut(usize, usize)) {
+        let total = self.nodes.len();
+        for index in 0..total {
+            if index.is_multiple_of(PROGRESS_STEP) {
+                progress(index, total);
+            }
+            self.sort_children(index);
+        }
+    }
+
+    /// Sort one node's children the way [`sort_by_size`](Self::sort_by_size) does
+    fn sort_children(&mut self, index: usize) {
+        // Take the list out so siblings can be looked up while sorting
+        let Some(mut children) = self
             .nodes
+            .get_mut(index)
+            .and_then(|slot| slot.as_mut())
+            .map(|node| std::mem::take(&mut node.children))
+        else {
+            return;
+        };
+        children.sort_by(|&a, &b| {
+            let (node_a, node_b) = (self.get(a), self.get(b));
+            let size_a = node_a.map_or(0, |n| n.size);
+            let size_b = node_b.map_or(0, |n| n.size);
+            size_b
+                .cmp(&size_a)
+                .then_with(|| node_a.map(|n| &n.name).cmp(&node_b.map(|n| &n.name)))
+                .then_with(|| a.index().cmp(&b.index()))
+        });
+        if let Some(node) = self.nodes[index].as_mut() {
+            node.children = children;
+        }
+    }
+
+    /// Swap everything below directory `id` for `fresh`, a new scan rooted
+    /// at the same path. The difference in size and file count is carried
+    /// up to the root, and directories that were expanded stay expanded.
+    pub fn replace_subtree(&mut self, id: NodeId, fresh: &DiskTree) {
+        let Some(node) = self.get(id) else {
+            return;
+        };
+        let children = node.children.clone();
+        let mut descendants = Vec::new();
+        self.collect_descendants(id, &mut descendants);
+        let expanded: HashSet<PathBuf> = descendants
             .iter()
-            .map(|n| n.as_ref().map(|n| n.size).unwrap_or(0))
+            .filter_map(|&d| self.get(d))
+            .filter(|node| node.is_expanded)
+            .map(|node| node.path.clone())
             .collect();
 
-        // Then sort each node's children
-        for node in self.nodes.iter_mut().flatten() {
-            node.children.sort_by(|a, b| {
-                let size_a = sizes.get(a.index()).copied().unwrap_or(0);
-                let size_b = sizes.get(b.index()).copied().unwrap_or(0);
-                size_b.cmp(&size_a)
-            });
+        // Removal already takes the old children's totals off every ancestor
+        for child in children {
+            self.remove_node(child);
+        }
+
+        // Copy depth-first in sorted order, so parents still precede their
+        // children and each child list comes out sorted
+        let mut stack: Vec<(NodeId, NodeId)> = fresh
+            .root()
+            .children
+            .iter()
+            .rev()
+            .map(|&child| (child, id))
+            .collect();
+        while let Some((source_id, parent)) = stack.pop() {
+            let Some(source) = fresh.get(source_id) else {
+                continue;
+            };
+            let new_id = self.add_node(
+                source.name.clone(),
+                source.kind,
+                source.path.clone(),
+                parent,
+            );
+            if let Some(node) = self.get_mut(new_id) {
+                node.size = source.size;
+                node.own_size = source.own_size;
+                node.apparent_size = source.apparent_size;
+                node.file_count = source.file_count;
+                node.incomplete = source.incomplete;
+                node.estimated = source.estimated;
+                node.mtime = source.mtime;
+                node.uid = source.uid;
+                node.gid = source.gid;
+                node.mode = source.mode;
+                node.nlink = source.nlink;
+                node.link_target = source.link_target.clone();
+                node.link_status = source.link_status;
+                node.is_expanded = expanded.contains(&source.path);
+            }
+            stack.extend(source.children.iter().rev().map(|&child| (child, new_id)));
+        }
+
+        // What is left on `id` is its own overhead; the fresh root's totals
+        // replace it and the difference goes up the chain
+        let root = fresh.root();
+        let Some(node) = self.get_mut(id) else {
+            return;
+        };
+        let size_delta = root.size as i64 - node.size as i64;
+        let files_delta = root.file_count as i64 - node.file_count as i64;
+        let apparent_delta = root
+            .apparent_size
+            .map(|a| a as i64 - node.apparent_size.unwrap_or(0) as i64);
+        node.own_size = root.own_size;
+        node.mtime = root.mtime;
+        let mut current = Some(id);
+        while let Some(nid) = current {
+            let Some(node) = self.get_mut(nid) else {
+                break;
+            };
+            node.size = node.size.saturating_add_signed(size_delta);
+            node.file_count = node.file_count.saturating_add_signed(files_delta);
+            if let Some(delta) = apparent_delta {
+                node.apparent_size =
+                    Some(node.apparent_size.unwrap_or(0).saturating_add_signed(delta));
+            }
+            node.incomplete |= root.incomplete;
+            node.estimated |= root.estimated;
+            current = node.parent;
+            // Sizes below changed, so siblings may need to swap places
+            if let Some(parent) = current {
+                self.sort_children(parent.index());
+            }
         }
     }
 
@@ -189,25 +452,114 @@ impl DiskTree {
         }
     }
 
+    /// Set expanded state for a node and every directory below it
+    pub fn set_expanded_recursive(&mut self, id: NodeId, expanded: bool) {
+        let mut stack = vec![id];
+        while let Some(node_id) = stack.pop() {
+            self.set_expanded(node_id, expanded);
+            if let Some(node) = self.get(node_id) {
+                stack.extend(node.children.iter().copied());
+            }
+        }
+    }
+
+    /// Collapse every directory except the root and `keep` (typically the
+    /// view root), so each shows its direct children only
+    pub fn collapse_all(&mut self, keep: NodeId) {
+        for node in self.nodes.iter_mut().flatten() {
+            node.is_expanded = false;
+        }
+        self.set_expanded(NodeId::ROOT, true);
+        self.set_expanded(keep, true);
+    }
+
     /// Get visible nodes in tree order (respecting expansion state)
     pub fn visible_nodes(&self, root: NodeId) -> Vec<NodeId> {
+        self.visible_nodes_filtered(root, &|_| true)
+    }
+
+    /// Get visible nodes in tree order, skipping nodes rejected by `keep`
+    /// together with their subtrees. The root itself is always included.
+    pub fn visible_nodes_filtered<F>(&self, root: NodeId, keep: &F) -> Vec<NodeId>
+    where
+        F: Fn(&TreeNode) -> bool,
+    {
         let mut result = Vec::new();
-        self.collect_visible(root, &mut result);
+        self.collect_visible(root, keep, None::<&fn(&TreeNode) -> u64>, &mut result);
         result
     }
 
-    fn collect_visible(&self, id: NodeId, result: &mut Vec<NodeId>) {
+    /// Like [`visible_nodes_filtered`](Self::visible_nodes_filtered), with
+    /// siblings ordered by `rank`, highest first, instead of by size
+    pub fn visible_nodes_ranked<F, R>(&self, root: NodeId, keep: &F, rank: &R) -> Vec<NodeId>
+    where
+        F: Fn(&TreeNode) -> bool,
+        R: Fn(&TreeNode) -> u64,
+    {
+        let mut result = Vec::new();
+        self.collect_visible(root, keep, Some(rank), &mut result);
+        result
+    }
+
+    /// Children of `id` ordered by `rank`, highest first. The sort is
+    /// stable, so equal ranks stay in size order.
+    pub fn children_ranked<R>(&self, id: NodeId, rank: &R) -> Vec<NodeId>
+    where
+        R: Fn(&TreeNode) -> u64,
+    {
+        let mut children = self.get(id).map(|n| n.children.clone()).unwrap_or_default();
+        children.sort_by_key(|&c| std::cmp::Reverse(self.get(c).map_or(0, rank)));
+        children
+    }
+
+    fn collect_visible<F, R>(
+        &self,
+        id: NodeId,
+        keep: &F,
+        rank: Option<&R>,
+        result: &mut Vec<NodeId>,
+    ) where
+        F: Fn(&TreeNode) -> bool,
+        R: Fn(&TreeNode) -> u64,
+    {
         result.push(id);
 
         if let Some(node) = self.get(id)
             && node.is_expanded
         {
-            for &child_id in &node.children {
-                self.collect_visible(child_id, result);
+            let ranked;
+            let children = match rank {
+                Some(rank) => {
+                    ranked = self.children_ranked(id, rank);
+                    &ranked
+                }
+                None => &node.children,
+            };
+            for &child_id in children {
+                if self.get(child_id).is_some_and(keep) {
+                    self.collect_visible(child_id, keep, rank, result);
+                }
             }
         }
     }
 
+    /// Mark every node rejected by `keep` or lying under a rejected ancestor.
+    /// Indexed by `NodeId`; the root is never excluded.
+    pub fn excluded_mask<F>(&self, keep: F) -> Vec<bool>
+    where
+        F: Fn(&TreeNode) -> bool,
+    {
+        let mut mask = vec![false; self.nodes.len()];
+        // Parents always precede children in the arena
+        for i in 1..self.nodes.len() {
+            if let Some(node) = &self.nodes[i] {
+                let parent_excluded = node.parent.is_some_and(|p| mask[p.index()]);
+                mask[i] = parent_excluded || !keep(node);
+            }
+        }
+        mask
+    }
+
     /// Get the path from root to a node
     pub fn path_to_node(&self, id: NodeId) -> Vec<NodeId> {
         let mut path = Vec::new();
@@ -249,6 +601,100 @@ impl DiskTree {
         self.root().file_count
     }
 
+    /// Mean disk usage per file under a directory, or None when it holds no
+    /// files. Symlinks add to the size but not to the file count.
+    pub fn avg_file_size(&self, id: NodeId) -> Option<u64> {
+        let node = self.get(id)?;
+        (node.file_count > 0).then(|| node.size / node.file_count)
+    }
+
+    /// The largest file anywhere under `id`. A directory can hold no file
+    /// larger than itself, so subtrees no bigger than the best file found
+    /// so far are skipped; with children sorted by size that is most of them.
+    pub fn largest_descendant_file(&self, id: NodeId) -> Option<NodeId> {
+        let mut best: Option<(u64, NodeId)> = None;
+        let mut stack = vec![id];
+        while let Some(current) = stack.pop() {
+            let Some(node) = self.get(current) else {
+                continue;
+            };
+            if best.is_some_and(|(size, _)| node.size <= size) {
+                continue;
+            }
+            match node.kind {
+                NodeKind::File if current != id => best = Some((node.size, current)),
+                NodeKind::Directory => stack.extend(node.children.iter().rev()),
+                _ => {}
+            }
+        }
+        best.map(|(_, file)| file)
+    }
+
+    /// Get total apparent size, if the scan tracked it
+    pub fn total_apparent_size(&self) -> Option<u64> {
+        self.root().apparent_size
+    }
+
+    /// Space lost to block rounding under each node, indexed by slot: every
+    /// file's allocated size minus its apparent size, summed up the tree.
+    /// Sparse files, where the apparent size is larger, count as zero rather
+    /// than cancelling out other files' slack. All zero unless the scan
+    /// tracked apparent sizes.
+    pub fn slack_sizes(&self) -> Vec<u64> {
+        let mut slack = vec![0u64; self.nodes.len()];
+        for i in (0..self.nodes.len()).rev() {
+            let Some(node) = &self.nodes[i] else {
+                continue;
+            };
+            if let Some(apparent) = node.apparent_size
+                && !node.kind.is_directory()
+            {
+                slack[i] = node.size.saturating_sub(apparent);
+            }
+            if let Some(parent) = node.parent {
+                slack[parent.index()] += slack[i];
+            }
+        }
+        slack
+    }
+
+    /// Slack of the files at or below `id` whose apparent size is under
+    /// `cutoff`: what packing those small files into one archive could win
+    /// back. Zero unless the scan tracked apparent sizes.
+    pub fn small_file_slack(&self, id: NodeId, cutoff: u64) -> u64 {
+        let mut total = 0;
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let Some(node) = self.get(id) else {
+                continue;
+            };
+            if let Some(apparent) = node.apparent_size
+                && !node.kind.is_directory()
+                && apparent < cutoff
+            {
+                total += node.size.saturating_sub(apparent);
+            }
+            stack.extend(&node.children);
+        }
+        total
+    }
+
+    /// Newest modification time anywhere at or below each node, indexed by
+    /// `NodeId::index`. None where nothing below has an mtime.
+    pub fn newest_mtimes(&self) -> Vec<Option<SystemTime>> {
+        let mut newest: Vec<Option<SystemTime>> = vec![None; self.nodes.len()];
+        for i in (0..self.nodes.len()).rev() {
+            let Some(node) = &self.nodes[i] else {
+                continue;
+            };
+            newest[i] = newest[i].max(node.mtime);
+            if let Some(parent) = node.parent {
+                newest[parent.index()] = newest[parent.index()].max(newest[i]);
+            }
+        }
+        newest
+    }
+
     /// Iterator over all live nodes (skips tombstones)
     pub fn iter(&self) -> impl Iterator<Item = &TreeNode> {
         self.nodes.iter().filter_map(|opt| opt.as_ref())
@@ -256,14 +702,101 @@ impl DiskTree {
 
     /// Find a node by its path
     pub fn find_by_path(&self, path: &Path) -> Option<NodeId> {
-        for (i, node_opt) in self.nodes.iter().enumerate() {
-            if let Some(node) = node_opt
-                && node.path == path
-            {
-                return Some(NodeId(i));
+        self.path_index.get(&self.nodes, path)
+    }
+
+    /// Size change of every path between `old` and this tree, matched by
+    /// path. Paths present in only one tree are reported once, at the
+    /// outermost added or removed node, as the full size with a + or - sign.
+    /// Unchanged paths are left out.
+    pub fn diff(&self, old: &DiskTree) -> Vec<(PathBuf, i64)> {
+        let mut changes = Vec::new();
+        for node in self.iter() {
+            match old.find_by_path(&node.path).and_then(|id| old.get(id)) {
+                Some(before) if before.size != node.size => {
+                    changes.push((node.path.clone(), node.size as i64 - before.size as i64));
+                }
+                Some(_) => {}
+                None => {
+                    let parent_existed = node
+                        .parent
+                        .and_then(|p| self.get(p))
+                        .is_none_or(|p| old.find_by_path(&p.path).is_some());
+                    if parent_existed {
+                        changes.push((node.path.clone(), node.size as i64));
+                    }
+                }
+            }
+        }
+        for node in old.iter() {
+            if self.find_by_path(&node.path).is_some() {
+                continue;
+            }
+            let parent_remains = node
+                .parent
+                .and_then(|p| old.get(p))
+                .is_none_or(|p| self.find_by_path(&p.path).is_some());
+            if parent_remains {
+                changes.push((node.path.clone(), -(node.size as i64)));
+            }
+        }
+        changes
+    }
+
+    /// Fill in directories a sampled scan did not descend into. Each gets the
+    /// mean size and file count of its walked sibling directories, and it and
+    /// every ancestor are marked `estimated`. Call after `aggregate_sizes`.
+    pub fn extrapolate_unsampled(&mut self, unsampled: &HashSet<NodeId>) {
+        let mut parents: Vec<NodeId> = unsampled
+            .iter()
+            .filter_map(|&id| self.get(id)?.parent)
+            .collect();
+        // Children always sit after their parent in the arena, so walking
+        // parents from the back settles deeper estimates before their
+        // ancestors average over them
+        parents.sort_by_key(|id| std::cmp::Reverse(id.index()));
+        parents.dedup();
+
+        for parent in parents {
+            let Some(children) = self.get(parent).map(|node| node.children.clone()) else {
+                continue;
+            };
+            let (mut walked, mut size, mut files, mut apparent) = (0u64, 0, 0, None);
+            for child in children.iter().filter_map(|&c| self.get(c)) {
+                if child.kind.is_directory() && !unsampled.contains(&child.id) {
+                    walked += 1;
+                    size += child.size;
+                    files += child.file_count;
+                    apparent = add_apparent(apparent, child.apparent_size);
+                }
+            }
+            let mean = |total: u64| total.checked_div(walked).unwrap_or(0);
+
+            let (mut added_size, mut added_files, mut added_apparent) = (0, 0, None);
+            for &id in children.iter().filter(|id| unsampled.contains(id)) {
+                if let Some(node) = self.get_mut(id) {
+                    node.size = mean(size);
+                    node.file_count = mean(files);
+                    node.apparent_size = apparent.map(mean);
+                    node.estimated = true;
+                    added_size += node.size;
+                    added_files += node.file_count;
+                    added_apparent = add_apparent(added_apparent, node.apparent_size);
+                }
+            }
+
+            let mut current = Some(parent);
+            while let Some(id) = current {
+                let Some(node) = self.get_mut(id) else { break };
+                node.size += added_size;
+                node.file_count += added_files;
+                if added_apparent.is_some() {
+                    node.apparent_size = add_apparent(node.apparent_size, added_apparent);
+                }
+                node.estimated = true;
+                current = node.parent;
             }
         }
-        None
     }
 
     /// Collect all descendant node IDs
@@ -285,8 +818,8 @@ impl DiskTree {
         }
 
         // Get node info before removal
-        let (size, file_count, parent_id) = match self.get(id) {
-            Some(node) => (node.size, node.file_count, node.parent),
+        let (size, file_count, apparent, parent_id) = match self.get(id) {
+            Some(node) => (node.size, node.file_count, node.apparent_size, node.parent),
             None => return 0, // Already removed
         };
 
@@ -303,17 +836,32 @@ impl DiskTree {
 
         // Tombstone node and all descendants
         for nid in to_remove {
-            if let Some(slot) = self.nodes.get_mut(nid.index()) {
-                *slot = None;
+            if let Some(slot) = self.nodes.get_mut(nid.index())
+                && let Some(node) = slot.take()
+            {
+                self.path_index.remove(&node.path, nid);
             }
         }
 
-        // Propagate size decrease up to root
+        // Propagate size decrease up to root; an ancestor stays incomplete
+        // (or estimated) only while another such node remains below it
         let mut current = parent_id;
         while let Some(nid) = current {
+            let any_child = |flag: fn(&TreeNode) -> bool| {
+                self.get(nid).is_some_and(|node| {
+                    node.children.iter().any(|&c| self.get(c).is_some_and(flag))
+                })
+            };
+            let incomplete = any_child(|child| child.incomplete);
+            let estimated = any_child(|child| child.estimated);
             if let Some(node) = self.get_mut(nid) {
+                node.incomplete = incomplete;
+                node.estimated = estimated;
                 node.size = node.size.saturating_sub(size);
                 node.file_count = node.file_count.saturating_sub(file_count);
+                if let (Some(total), Some(removed)) = (node.apparent_size, apparent) {
+                    node.apparent_size = Some(total.saturating_sub(removed));
+                }
                 current = node.parent;
             } else {
                 break;
@@ -324,6 +872,14 @@ impl DiskTree {
     }
 }
 
+/// Sum apparent sizes, staying `None` only while nothing has been tracked
+fn add_apparent(total: Option<u64>, child: Option<u64>) -> Option<u64> {
+    match (total, child) {
+        (None, None) => None,
+        (total, child) => Some(total.unwrap_or(0) + child.unwrap_or(0)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -438,6 +994,418 @@ mod tests {
         assert_eq!(tree.root().size, 0);
     }
 
+    #[test]
+    fn test_parallel_aggregation_matches_sequential() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let mut dirs = vec![NodeId::ROOT];
+        for d in 0..50 {
+            let parent = dirs[(d * 7) % dirs.len()];
+            let dir = tree.add_sized(&format!("d{}", d), NodeKind::Directory, 0, parent);
+            dirs.push(dir);
+            for f in 0..(d % 5) {
+                let size = (d * 100 + f) as u64 + 1;
+                let file = tree.add_sized(&format!("f{}", f), NodeKind::File, size, dir);
+                if f % 2 == 0 {
+                    tree.get_mut(file).unwrap().apparent_size = Some(f as u64 * 3);
+                }
+            }
+        }
+        tree.remove_node(dirs[10]);
+
+        let mut sequential = tree.clone();
+        sequential.aggregate_sizes_sequential(|_, _| {});
+        let mut parallel = tree;
+        let mut reports = Vec::new();
+        parallel.aggregate_sizes_parallel(|done, total| reports.push((done, total)));
+        // One report per depth level, rising but never claiming to be done
+        assert!(reports.len() > 1);
+        assert!(reports.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(reports.iter().all(|&(done, total)| done < total));
+
+        assert!(sequential.total_size() > 0);
+        for (a, b) in sequential.iter().zip(parallel.iter()) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.size, b.size, "size of {}", a.name);
+            assert_eq!(a.file_count, b.file_count, "files of {}", a.name);
+            assert_eq!(a.apparent_size, b.apparent_size, "apparent of {}", a.name);
+        }
+    }
+
+    #[test]
+    fn test_parallel_aggregation_on_small_tree() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let src = tree.add_sized("src", NodeKind::Directory, 0, NodeId::ROOT);
+        let lib = tree.add_sized("lib", NodeKind::Directory, 0, src);
+        let main = tree.add_sized("main.rs", NodeKind::File, 4096, src);
+        tree.get_mut(main).unwrap().apparent_size = Some(1200);
+        tree.add_sized("mod.rs", NodeKind::File, 8192, lib);
+        tree.add_sized("locked", NodeKind::Error, 0, lib);
+        let docs = tree.add_sized("docs", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.get_mut(docs).unwrap().own_size = 512;
+        tree.add_sized("readme.md", NodeKind::File, 100, docs);
+
+        let mut sequential = tree.clone();
+        sequential.aggregate_sizes_sequential(|_, _| {});
+        let mut parallel = tree;
+        let mut reports = Vec::new();
+        parallel.aggregate_sizes_parallel(|done, total| reports.push((done, total)));
+        // Root, then src and docs, then lib
+        assert_eq!(reports.len(), 3);
+
+        assert_eq!(parallel.total_size(), 4096 + 8192 + 512 + 100);
+        assert!(parallel.root().incomplete);
+        assert!(!parallel.get(docs).unwrap().incomplete);
+        for (a, b) in sequential.iter().zip(parallel.iter()) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.size, b.size, "size of {}", a.name);
+            assert_eq!(a.file_count, b.file_count, "files of {}", a.name);
+            assert_eq!(a.apparent_size, b.apparent_size, "apparent of {}", a.name);
+            assert_eq!(a.incomplete, b.incomplete, "incomplete of {}", a.name);
+        }
+    }
+
+    #[test]
+    fn test_replace_subtree_carries_difference_to_root() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let build = tree.add_sized("build", NodeKind::Directory, 0, NodeId::ROOT);
+        let out = tree.add_sized("out", NodeKind::Directory, 0, build);
+        tree.add_sized("old.o", NodeKind::File, 100, out);
+        tree.add_sized("notes.txt", NodeKind::File, 300, NodeId::ROOT);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+        tree.set_expanded(out, true);
+        assert_eq!(
+            tree.root().children[0],
+            tree.find_by_path(Path::new("/test/notes.txt")).unwrap()
+        );
+
+        let mut fresh = DiskTree::new(PathBuf::from("/test/build"));
+        let fresh_out = fresh.add_sized("out", NodeKind::Directory, 0, NodeId::ROOT);
+        fresh.add_sized("a.o", NodeKind::File, 400, fresh_out);
+        fresh.add_sized("b.o", NodeKind::File, 500, fresh_out);
+        fresh.add_sized("log", NodeKind::File, 50, NodeId::ROOT);
+        fresh.aggregate_sizes();
+        fresh.sort_by_size();
+
+        tree.replace_subtree(build, &fresh);
+
+        assert_eq!(tree.get(build).unwrap().size, 950);
+        assert_eq!(tree.get(build).unwrap().file_count, 3);
+        assert_eq!(tree.total_size(), 1250);
+        assert_eq!(tree.root().file_count, 4);
+        assert!(
+            tree.find_by_path(Path::new("/test/build/out/old.o"))
+                .is_none()
+        );
+        let new_out = tree.find_by_path(Path::new("/test/build/out")).unwrap();
+        assert!(tree.get(new_out).unwrap().is_expanded);
+        let names: Vec<_> = tree
+            .get(new_out)
+            .unwrap()
+            .children
+            .iter()
+            .map(|&c| tree.get(c).unwrap().name.as_str())
+            .collect();
+        assert_eq!(names, ["b.o", "a.o"]);
+        // build now outweighs notes.txt
+        assert_eq!(tree.root().children[0], build);
+    }
+
+    #[test]
+    fn test_avg_and_largest_descendant_file() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let small = tree.add_sized("small", NodeKind::Directory, 0, NodeId::ROOT);
+        for i in 0..4 {
+            tree.add_sized(&format!("f{}", i), NodeKind::File, 100, small);
+        }
+        let videos = tree.add_sized("videos", NodeKind::Directory, 0, NodeId::ROOT);
+        let nested = tree.add_sized("2024", NodeKind::Directory, 0, videos);
+        let movie = tree.add_sized("trip.mov", NodeKind::File, 8000, nested);
+        tree.add_sized("clip.mov", NodeKind::File, 900, videos);
+        tree.add_sized("link", NodeKind::Symlink, 9000, NodeId::ROOT);
+        let empty = tree.add_sized("empty", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+
+        assert_eq!(tree.avg_file_size(small), Some(100));
+        assert_eq!(tree.avg_file_size(videos), Some(4450));
+        assert_eq!(tree.avg_file_size(empty), None);
+        assert_eq!(tree.largest_descendant_file(NodeId::ROOT), Some(movie));
+        assert_eq!(tree.largest_descendant_file(videos), Some(movie));
+        assert_eq!(tree.largest_descendant_file(empty), None);
+        assert_eq!(tree.largest_descendant_file(movie), None);
+    }
+
+    #[test]
+    fn test_newest_mtimes_carry_up_to_ancestors() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let at = |secs| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+        let mut add = |name: &str, kind, mtime, parent| {
+            let id = tree.add_sized(name, kind, 0, parent);
+            tree.get_mut(id).unwrap().mtime = mtime;
+            id
+        };
+        let src = add("src", NodeKind::Directory, Some(at(10)), NodeId::ROOT);
+        let main = add("main.rs", NodeKind::File, Some(at(50)), src);
+        let docs = add("docs", NodeKind::Directory, Some(at(20)), NodeId::ROOT);
+        let old = add("old", NodeKind::Directory, None, NodeId::ROOT);
+
+        let newest = tree.newest_mtimes();
+        assert_eq!(newest[main.index()], Some(at(50)));
+        assert_eq!(newest[src.index()], Some(at(50)));
+        assert_eq!(newest[docs.index()], Some(at(20)));
+        assert_eq!(newest[old.index()], None);
+        assert_eq!(newest[NodeId::ROOT.index()], Some(at(50)));
+    }
+
+    #[test]
+    fn test_apparent_size_aggregates_and_propagates_removal() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let dir = tree.add_sized("dir", NodeKind::Directory, 0, NodeId::ROOT);
+        let untracked = tree.add_sized("empty", NodeKind::Directory, 0, NodeId::ROOT);
+        for (i, apparent) in [100u64, 5000].iter().enumerate() {
+            let file = tree.add_sized(
+                &format!("f{}", i),
+                NodeKind::File,
+                4096 * (i as u64 + 1),
+                dir,
+            );
+            tree.get_mut(file).unwrap().apparent_size = Some(*apparent);
+        }
+        tree.aggregate_sizes();
+
+        assert_eq!(tree.total_size(), 4096 * 3);
+        assert_eq!(tree.total_apparent_size(), Some(5100));
+        assert_eq!(tree.get(untracked).unwrap().apparent_size, None);
+
+        let slack = tree.slack_sizes();
+        assert_eq!(slack[dir.index()], 4096 - 100 + 8192 - 5000);
+        assert_eq!(slack[NodeId::ROOT.index()], slack[dir.index()]);
+        assert_eq!(slack[untracked.index()], 0);
+        assert_eq!(tree.small_file_slack(NodeId::ROOT, 1000), 4096 - 100);
+        assert_eq!(tree.small_file_slack(dir, u64::MAX), slack[dir.index()]);
+
+        let f0 = tree.get(dir).unwrap().children[0];
+        tree.remove_node(f0);
+        assert_eq!(tree.get(dir).unwrap().apparent_size, Some(5000));
+        assert_eq!(tree.total_apparent_size(), Some(5000));
+    }
+
+    #[test]
+    fn test_incomplete_propagates_until_error_nodes_are_removed() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let dir = tree.add_sized("dir", NodeKind::Directory, 0, NodeId::ROOT);
+        let locked = tree.add_sized("locked", NodeKind::Error, 0, dir);
+        let other = tree.add_sized("other", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.aggregate_sizes();
+
+        assert!(tree.get(locked).unwrap().incomplete);
+        assert!(tree.get(dir).unwrap().incomplete);
+        assert!(tree.root().incomplete);
+        assert!(!tree.get(other).unwrap().incomplete);
+
+        tree.remove_node(locked);
+        assert!(!tree.get(dir).unwrap().incomplete);
+        assert!(!tree.root().incomplete);
+    }
+
+    #[test]
+    fn test_extrapolate_unsampled() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let top = tree.add_sized("top", NodeKind::Directory, 0, NodeId::ROOT);
+        let a = tree.add_sized("a", NodeKind::Directory, 0, top);
+        tree.add_sized("big", NodeKind::File, 300, a);
+        let b = tree.add_sized("b", NodeKind::Directory, 0, top);
+        tree.add_sized("small", NodeKind::File, 100, b);
+        let skipped = tree.add_sized("c", NodeKind::Directory, 0, top);
+        tree.add_sized("loose", NodeKind::File, 50, NodeId::ROOT);
+        tree.aggregate_sizes();
+
+        tree.extrapolate_unsampled(&HashSet::from([skipped]));
+
+        let c = tree.get(skipped).unwrap();
+        assert_eq!((c.size, c.file_count), (200, 1));
+        assert!(c.estimated);
+        assert_eq!(tree.get(top).unwrap().size, 600);
+        assert!(tree.get(top).unwrap().estimated);
+        assert!(!tree.get(a).unwrap().estimated);
+        assert_eq!(tree.root().size, 650);
+        assert!(tree.root().estimated);
+
+        tree.remove_node(skipped);
+        assert!(!tree.get(top).unwrap().estimated);
+        assert!(!tree.root().estimated);
+    }
+
+    #[test]
+    fn test_filtered_visibility_hides_subtrees() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let hidden = tree.add_sized(".git", NodeKind::Directory, 0, NodeId::ROOT);
+        let inner = tree.add_sized("config", NodeKind::File, 0, hidden);
+        let visible = tree.add_sized("src", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.set_expanded(hidden, true);
+
+        let keep = |n: &TreeNode| !n.name.starts_with('.');
+        assert_eq!(
+            tree.visible_nodes_filtered(NodeId::ROOT, &keep),
+            vec![NodeId::ROOT, visible]
+        );
+
+        let mask = tree.excluded_mask(keep);
+        assert!(!mask[NodeId::ROOT.index()]);
+        assert!(mask[hidden.index()]);
+        assert!(mask[inner.index()]);
+        assert!(!mask[visible.index()]);
+    }
+
+    #[test]
+    fn test_ranked_visibility_orders_siblings_by_rank() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let video = tree.add_sized("video.mp4", NodeKind::File, 1 << 30, NodeId::ROOT);
+        let src = tree.add_sized("src", NodeKind::Directory, 0, NodeId::ROOT);
+        for name in ["a.rs", "b.rs", "c.rs"] {
+            tree.add_sized(name, NodeKind::File, 4096, src);
+        }
+        let docs = tree.add_sized("docs", NodeKind::Directory, 0, NodeId::ROOT);
+        tree.add_sized("x.md", NodeKind::File, 4096, docs);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+
+        let keep = |_: &TreeNode| true;
+        assert_eq!(
+            tree.visible_nodes_filtered(NodeId::ROOT, &keep),
+            vec![NodeId::ROOT, video, src, docs]
+        );
+        // video.mp4 and docs both hold one file; size keeps the video first
+        let files = |n: &TreeNode| n.file_count;
+        assert_eq!(
+            tree.visible_nodes_ranked(NodeId::ROOT, &keep, &files),
+            vec![NodeId::ROOT, src, video, docs]
+        );
+        assert_eq!(
+            tree.children_ranked(NodeId::ROOT, &files),
+            vec![src, video, docs]
+        );
+    }
+
+    #[test]
+    fn test_compact_preserves_structure_and_sizes() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let a = tree.add_sized("a", NodeKind::Directory, 0, NodeId::ROOT);
+        let a1 = tree.add_sized("a1", NodeKind::File, 100, a);
+        let b = tree.add_sized("b", NodeKind::Directory, 0, NodeId::ROOT);
+        let b1 = tree.add_sized("b1", NodeKind::File, 300, b);
+        let b2 = tree.add_sized("b2", NodeKind::File, 50, b);
+        tree.aggregate_sizes();
+        tree.set_expanded(b, true);
+
+        assert!(!tree.needs_compaction());
+        tree.remove_node(a);
+        assert!(tree.needs_compaction());
+
+        let remap = tree.compact();
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.live_count(), 4);
+        assert!(!tree.needs_compaction());
+        assert!(!remap.contains_key(&a));
+        assert!(!remap.contains_key(&a1));
+
+        let new_b = remap[&b];
+        let new_b1 = remap[&b1];
+        let new_b2 = remap[&b2];
+        assert_eq!(remap[&NodeId::ROOT], NodeId::ROOT);
+        assert_eq!(tree.root().children, vec![new_b]);
+        assert_eq!(tree.root().size, 350);
+        assert_eq!(tree.root().file_count, 2);
+
+        let b_node = tree.get(new_b).unwrap();
+        assert_eq!(b_node.id, new_b);
+        assert_eq!(b_node.size, 350);
+        assert!(b_node.is_expanded);
+        assert_eq!(b_node.children, vec![new_b1, new_b2]);
+        assert_eq!(tree.get(new_b1).unwrap().parent, Some(new_b));
+        assert_eq!(tree.get(new_b2).unwrap().size, 50);
+        assert_eq!(tree.find_by_path(Path::new("/test/b/b2")), Some(new_b2));
+    }
+
+    #[test]
+    fn test_find_by_path_matches_linear_scan() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let mut dirs = vec![NodeId::ROOT];
+        for d in 0..20 {
+            let parent = dirs[d % dirs.len()];
+            let dir = tree.add_sized(&format!("dir{}", d), NodeKind::Directory, 0, parent);
+            dirs.push(dir);
+            for f in 0..10 {
+                tree.add_sized(&format!("f{}", f), NodeKind::File, 0, dir);
+            }
+        }
+        tree.remove_node(dirs[3]);
+        tree.remove_node(dirs[7]);
+
+        let brute_force =
+            |tree: &DiskTree, path: &Path| tree.iter().find(|n| n.path == path).map(|n| n.id);
+
+        let mut all_paths: Vec<PathBuf> = (0..tree.len())
+            .filter_map(|i| tree.get(NodeId(i)).map(|n| n.path.clone()))
+            .collect();
+        all_paths.push(PathBuf::from("/test/dir3/f0")); // removed
+        all_paths.push(PathBuf::from("/test/missing"));
+
+        for path in &all_paths {
+            assert_eq!(
+                tree.find_by_path(path),
+                brute_force(&tree, path),
+                "{:?}",
+                path
+            );
+        }
+
+        // Index survives a cache-style reload of paths
+        tree.rebuild_paths();
+        for path in &all_paths {
+            assert_eq!(
+                tree.find_by_path(path),
+                brute_force(&tree, path),
+                "{:?}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_recursive_and_collapse_all() {
+        let mut tree = DiskTree::new(PathBuf::from("/test"));
+        let a = tree.add_sized("a", NodeKind::Directory, 0, NodeId::ROOT);
+        let b = tree.add_sized("b", NodeKind::Directory, 0, a);
+        let f = tree.add_sized("f", NodeKind::File, 0, b);
+
+        tree.set_expanded_recursive(a, true);
+        assert_eq!(
+            tree.visible_nodes(NodeId::ROOT),
+            vec![NodeId::ROOT, a, b, f]
+        );
+
+        tree.collapse_all(NodeId::ROOT);
+        assert_eq!(tree.visible_nodes(NodeId::ROOT), vec![NodeId::ROOT, a]);
+        assert!(!tree.get(b).unwrap().is_expanded);
+    }
+
+    #[test]
+    fn test_sort_by_size_breaks_ties_by_name_then_id() {
+        let mut tree = DiskTree::new(PathBuf::from("/t"));
+        let mut add = |name, size| tree.add_sized(name, NodeKind::File, size, NodeId::ROOT);
+        let c = add("c", 10);
+        let b_first = add("b", 10);
+        let big = add("z", 20);
+        let a = add("a", 10);
+        let b_second = add("b", 10);
+        tree.aggregate_sizes();
+        tree.sort_by_size();
+
+        assert_eq!(tree.root().children, vec![big, a, b_first, b_second, c]);
+    }
+
     #[test]
     fn test_find_by_path() {
         let mut tree = DiskTree::new(PathBuf::from("/test"));
@@ -461,4 +1429,43 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn test_diff_matches_by_path() {
+        let build = |files: &[(&str, u64)]| {
+            let mut tree = DiskTree::new(PathBuf::from("/r"));
+            for &(rel, size) in files {
+                let path = PathBuf::from("/r").join(rel);
+                let mut parent = NodeId::ROOT;
+                for dir in path.parent().unwrap().strip_prefix("/r").unwrap().iter() {
+                    let dir_path = tree.get(parent).unwrap().path.join(dir);
+                    parent = tree.find_by_path(&dir_path).unwrap_or_else(|| {
+                        let name = dir.to_string_lossy();
+                        tree.add_sized(&name, NodeKind::Directory, 0, parent)
+                    });
+                }
+                let name = path.file_name().unwrap().to_string_lossy();
+                tree.add_sized(&name, NodeKind::File, size, parent);
+            }
+            tree.aggregate_sizes();
+            tree
+        };
+
+        let old = build(&[("a/x", 100), ("a/y", 50), ("gone/z", 30), ("same", 10)]);
+        let new = build(&[("a/x", 160), ("a/y", 50), ("new/w/v", 40), ("same", 10)]);
+
+        let mut changes = new.diff(&old);
+        changes.sort();
+        let p = |s: &str| PathBuf::from(s);
+        assert_eq!(
+            changes,
+            vec![
+                (p("/r"), 70),
+                (p("/r/a"), 60),
+                (p("/r/a/x"), 60),
+                (p("/r/gone"), -30),
+                (p("/r/new"), 40),
+            ]
+        );
+    }
 }
@@ -1,5 +1,8 @@
 mod arena;
 mod node;
+mod path_index;
+#[cfg(any(test, feature = "test-util"))]
+mod test_util;
 
 pub use arena::DiskTree;
-pub use node::{NodeId, NodeKind, TreeNode};
+pub use node::{LinkStatus, NodeId, NodeKind, TreeNode};
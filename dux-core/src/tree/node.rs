@@ -34,11 +34,52 @@ impl NodeKind {
         }
     }
 
+    /// Plain-text stand-in for `icon`, for terminals without emoji
+    pub fn ascii_icon(&self) -> &'static str {
+        match self {
+            NodeKind::Directory => "[D]",
+            NodeKind::File => "[F]",
+            NodeKind::Symlink => "[L]",
+            NodeKind::Error => "[!]",
+        }
+    }
+
+    /// Lowercase name used in exports
+    pub fn label(&self) -> &'static str {
+        match self {
+            NodeKind::Directory => "directory",
+            NodeKind::File => "file",
+            NodeKind::Symlink => "symlink",
+            NodeKind::Error => "error",
+        }
+    }
+
     pub fn is_directory(&self) -> bool {
         matches!(self, NodeKind::Directory)
     }
 }
 
+/// Where a symlink leads, in the order the Symlinks view lists them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LinkStatus {
+    /// The target doesn't exist (or the link couldn't be read)
+    Broken,
+    /// The target is outside the scanned tree
+    Outside,
+    /// The target is part of the scanned tree
+    Inside,
+}
+
+impl LinkStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            LinkStatus::Broken => "broken",
+            LinkStatus::Outside => "outside",
+            LinkStatus::Inside => "inside",
+        }
+    }
+}
+
 /// A node in the disk tree
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TreeNode {
@@ -47,16 +88,39 @@ pub struct TreeNode {
     pub kind: NodeKind,
     /// Actual disk usage in bytes
     pub size: u64,
+    /// Disk usage of a directory's own entry table, counted in `size` on
+    /// top of its children. Zero unless the scan counted directory overhead.
+    pub own_size: u64,
+    /// Logical file length in bytes, when the scan tracked it
+    pub apparent_size: Option<u64>,
     /// Number of files (including self if file)
     pub file_count: u64,
+    /// Whether this node, or something below it, could not be read, making
+    /// `size` a lower bound
+    pub incomplete: bool,
+    /// Whether `size` was extrapolated by a sampled scan rather than
+    /// measured, here or somewhere below
+    pub estimated: bool,
     /// Parent node (None for root)
     pub parent: Option<NodeId>,
     /// Children sorted by size descending
     pub children: Vec<NodeId>,
     /// Depth in tree (0 for root)
     pub depth: u16,
-    /// Modification time (directories only, for cache invalidation)
+    /// Modification time; a directory's also tells if a cache is stale
     pub mtime: Option<SystemTime>,
+    /// Owner user ID (Unix only)
+    pub uid: Option<u32>,
+    /// Owner group ID (Unix only)
+    pub gid: Option<u32>,
+    /// Permission bits from `st_mode` (Unix only)
+    pub mode: Option<u32>,
+    /// Number of hard links to the entry (Unix only)
+    pub nlink: Option<u64>,
+    /// Where a symlink points, as written in the link (symlinks only)
+    pub link_target: Option<PathBuf>,
+    /// Where a symlink leads, resolved when it was scanned (symlinks only)
+    pub link_status: Option<LinkStatus>,
     /// Whether directory is expanded in UI
     #[serde(skip)]
     pub is_expanded: bool,
@@ -79,11 +143,21 @@ impl TreeNode {
             name,
             kind,
             size: 0,
+            own_size: 0,
+            apparent_size: None,
             file_count: if kind == NodeKind::File { 1 } else { 0 },
+            incomplete: kind == NodeKind::Error,
+            estimated: false,
             parent,
             children: Vec::new(),
             depth,
             mtime: None,
+            uid: None,
+            gid: None,
+            mode: None,
+            nlink: None,
+            link_target: None,
+            link_status: None,
             is_expanded: depth == 0, // Root starts expanded
             path,
         }
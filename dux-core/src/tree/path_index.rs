@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::path::Path;
+
+use super::node::{NodeId, TreeNode};
+
+/// Path → node lookup keyed by a hash of the path, so the index doesn't hold
+/// a second copy of every node's path. Lookups confirm the match against the
+/// node's own path; the rare paths whose hash another path already took are
+/// kept in `collisions`.
+#[derive(Debug, Clone, Default)]
+pub(super) struct PathIndex {
+    hasher: RandomState,
+    first: HashMap<u64, NodeId>,
+    collisions: HashMap<u64, Vec<NodeId>>,
+}
+
+impl PathIndex {
+    /// Index every live node in `nodes`
+    pub(super) fn build(nodes: &[Option<TreeNode>]) -> Self {
+        let mut index = Self::default();
+        for node in nodes.iter().flatten() {
+            index.insert(nodes, &node.path, node.id);
+        }
+        index
+    }
+
+    /// Point `path` at `id`, replacing whatever node it pointed at before
+    pub(super) fn insert(&mut self, nodes: &[Option<TreeNode>], path: &Path, id: NodeId) {
+        let hash = self.hasher.hash_one(path);
+        let holds = |other: NodeId| other == id || path_of(nodes, other) == Some(path);
+        match self.first.get(&hash) {
+            Some(&other) if other != id && path_of(nodes, other).is_some_and(|p| p != path) => {
+                let ids = self.collisions.entry(hash).or_default();
+                ids.retain(|&other| !holds(other));
+                ids.push(id);
+            }
+            _ => {
+                self.first.insert(hash, id);
+            }
+        }
+    }
+
+    /// The live node at `path`
+    pub(super) fn get(&self, nodes: &[Option<TreeNode>], path: &Path) -> Option<NodeId> {
+        let hash = self.hasher.hash_one(path);
+        let at_path = |id: &NodeId| path_of(nodes, *id) == Some(path);
+        self.first
+            .get(&hash)
+            .filter(|id| at_path(id))
+            .copied()
+            .or_else(|| {
+                self.collisions
+                    .get(&hash)?
+                    .iter()
+                    .rev()
+                    .find(|id| at_path(id))
+                    .copied()
+            })
+    }
+
+    /// Forget `id`, which was indexed under `path`
+    pub(super) fn remove(&mut self, path: &Path, id: NodeId) {
+        let hash = self.hasher.hash_one(path);
+        if self.first.get(&hash) == Some(&id) {
+            self.first.remove(&hash);
+            if let Some(ids) = self.collisions.get_mut(&hash)
+                && let Some(next) = ids.pop()
+            {
+                self.first.insert(hash, next);
+                if ids.is_empty() {
+                    self.collisions.remove(&hash);
+                }
+            }
+        } else if let Some(ids) = self.collisions.get_mut(&hash) {
+            ids.retain(|&other| other != id);
+            if ids.is_empty() {
+                self.collisions.remove(&hash);
+            }
+        }
+    }
+}
+
+fn path_of(nodes: &[Option<TreeNode>], id: NodeId) -> Option<&Path> {
+    nodes
+        .get(id.index())?
+        .as_ref()
+        .map(|node| node.path.as_path())
+}
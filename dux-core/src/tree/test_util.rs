@@ -0,0 +1,13 @@
+use super::arena::DiskTree;
+use super::node::{NodeId, NodeKind};
+
+impl DiskTree {
+    /// Add `name` under `parent`, at the parent's path joined with the name,
+    /// with `size` bytes. Shorthand for building trees in tests.
+    pub fn add_sized(&mut self, name: &str, kind: NodeKind, size: u64, parent: NodeId) -> NodeId {
+        let path = self.get(parent).expect("parent must exist").path.join(name);
+        let id = self.add_node(name.to_string(), kind, path, parent);
+        self.set_size(id, size);
+        id
+    }
+}